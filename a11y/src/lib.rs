@@ -0,0 +1,160 @@
+//! Linux accessibility for mctk apps: the Component tree as an AT-SPI2 accessible tree.
+//!
+//! With the `a11y` feature, the tree is served on the AT-SPI2 accessibility bus from a
+//! background thread, so that screen readers can read it. Without it, the tree is only kept in
+//! memory.
+//!
+//! The backend is registered once, when the app starts. The `UI` then updates it each time
+//! that the Node tree is viewed, and tells it when the focus moves:
+//! ```ignore
+//! register_accessibility_backend(&root_node);
+//! ```
+
+mod role;
+#[cfg(feature = "a11y")]
+mod service;
+mod state;
+mod tree;
+
+#[cfg(feature = "a11y")]
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, RwLock, RwLockReadGuard};
+
+use mctk_core::accessibility::{
+    set_accessibility_listener, AccessibilityListener, AccessibilityProvider,
+};
+
+pub use role::Role;
+pub use state::{State, StateSet};
+pub use tree::{object_path, AtspiObject, AtspiTree, ROOT_PATH};
+
+/// The accessible tree of an app, kept up to date by the app and read by the AT-SPI2 service.
+#[derive(Debug, Clone, Default)]
+pub struct AccessibilityBackend {
+    tree: Arc<RwLock<AtspiTree>>,
+    /// Passes changes on to the AT-SPI2 service.
+    #[cfg(feature = "a11y")]
+    events: Option<Sender<service::Event>>,
+}
+
+impl AccessibilityBackend {
+    /// Rebuild the tree from `root`, keeping the focused object if it still exists.
+    pub fn update(&self, root: &dyn AccessibilityProvider) {
+        let mut tree = self.tree.write().unwrap();
+        let focus = tree.active_descendant();
+        *tree = AtspiTree::new(root);
+        if let Some(id) = focus {
+            tree.set_focus(id);
+        }
+        #[cfg(feature = "a11y")]
+        self.send(service::Event::Update);
+    }
+
+    /// Called when the Node with `id` gains focus, to make it the active descendant.
+    pub fn focus_changed(&self, id: u64) {
+        self.tree.write().unwrap().set_focus(id);
+        #[cfg(feature = "a11y")]
+        self.send(service::Event::Focus(id));
+    }
+
+    #[cfg(feature = "a11y")]
+    fn send(&self, event: service::Event) {
+        if let Some(events) = &self.events {
+            // The service has stopped if there is no accessibility bus, which is not an error
+            let _ = events.send(event);
+        }
+    }
+
+    pub fn tree(&self) -> RwLockReadGuard<'_, AtspiTree> {
+        self.tree.read().unwrap()
+    }
+}
+
+impl AccessibilityListener for AccessibilityBackend {
+    fn update(&self, root: &dyn AccessibilityProvider) {
+        AccessibilityBackend::update(self, root);
+    }
+
+    fn focus_changed(&self, id: u64) {
+        AccessibilityBackend::focus_changed(self, id);
+    }
+}
+
+/// Build the accessible tree of `root`, the root Node of an app, and set the backend as the
+/// `UI`'s [`AccessibilityListener`], which keeps it up to date. With the `a11y` feature, this
+/// also starts the AT-SPI2 service, which runs until another listener replaces the backend and
+/// the backend's clones are dropped.
+pub fn register_accessibility_backend(root: &dyn AccessibilityProvider) -> AccessibilityBackend {
+    let backend = AccessibilityBackend::default();
+    backend.update(root);
+    #[cfg(feature = "a11y")]
+    let backend = AccessibilityBackend {
+        events: Some(service::spawn(backend.tree.clone())),
+        ..backend
+    };
+    set_accessibility_listener(Arc::new(backend.clone()));
+    backend
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mctk_core::accessibility::AccessibleNode;
+    use mctk_core::component::AriaRole;
+
+    struct App;
+
+    impl AccessibilityProvider for App {
+        fn accessible_children(&self) -> Vec<AccessibleNode> {
+            vec![
+                AccessibleNode {
+                    id: 7,
+                    role: AriaRole::Button,
+                    label: Some("Ok".to_string()),
+                    pressed: None,
+                    children: vec![],
+                },
+                AccessibleNode {
+                    id: 8,
+                    role: AriaRole::Button,
+                    label: Some("Bold".to_string()),
+                    pressed: Some(true),
+                    children: vec![],
+                },
+            ]
+        }
+    }
+
+    #[test]
+    fn test_register() {
+        let backend = register_accessibility_backend(&App);
+        let tree = backend.tree();
+        let root = tree.get(ROOT_PATH).unwrap();
+        assert_eq!(root.role, Role::Application);
+        assert_eq!(root.children, vec![object_path(7), object_path(8)]);
+        drop(tree);
+
+        backend.focus_changed(7);
+        backend.update(&App);
+        let tree = backend.tree();
+        assert_eq!(tree.active_descendant(), Some(7));
+        let button = tree.get(&object_path(7)).unwrap();
+        assert_eq!(button.role, Role::PushButton);
+        assert!(button.states.contains(State::Focused));
+        assert!(!button.states.contains(State::Pressed));
+        assert_eq!(tree.index_in_parent(&object_path(7)), Some(0));
+
+        let toggle = tree.get(&object_path(8)).unwrap();
+        assert_eq!(toggle.role, Role::ToggleButton);
+        assert!(toggle.states.contains(State::Pressed));
+        assert_eq!(tree.index_in_parent(&object_path(8)), Some(1));
+        drop(tree);
+
+        // The root Node has the focus when nothing else does
+        backend.focus_changed(0);
+        let tree = backend.tree();
+        assert_eq!(tree.active_descendant(), None);
+        let button = tree.get(&object_path(7)).unwrap();
+        assert!(!button.states.contains(State::Focused));
+    }
+}
@@ -0,0 +1,33 @@
+use mctk_core::component::AriaRole;
+
+/// The AT-SPI2 roles that Components are exposed as, with their `AtspiRole` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum Role {
+    PushButton = 43,
+    /// A button with a pressed state, i.e. a `toggle_mode` Button.
+    ToggleButton = 62,
+    Application = 75,
+    Link = 88,
+}
+
+impl Role {
+    /// The name of the role, as reported by `GetRoleName`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Role::PushButton => "push button",
+            Role::ToggleButton => "toggle button",
+            Role::Application => "application",
+            Role::Link => "link",
+        }
+    }
+}
+
+impl From<AriaRole> for Role {
+    fn from(role: AriaRole) -> Self {
+        match role {
+            AriaRole::Button => Role::PushButton,
+            AriaRole::Link => Role::Link,
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! The AT-SPI2 D-Bus service: serves the objects of an [`AtspiTree`] on the accessibility bus,
+//! and embeds the app in the desktop of the AT-SPI2 registry, where screen readers find it.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+use zbus::blocking::{connection, Connection};
+use zbus::interface;
+use zbus::zvariant::{OwnedObjectPath, Value};
+
+use crate::tree::{object_path, AtspiObject, AtspiTree, ROOT_PATH};
+
+/// A change to the tree, that the service passes on to screen readers.
+#[derive(Debug)]
+pub(crate) enum Event {
+    /// The tree was rebuilt.
+    Update,
+    /// The Node with this id gained focus.
+    Focus(u64),
+}
+
+/// An object as it is sent over D-Bus: the bus name of its app, and its object path.
+type ObjectRef = (String, OwnedObjectPath);
+
+/// The path of the object that stands for no object.
+const NULL_PATH: &str = "/org/a11y/atspi/null";
+
+fn object_ref(bus: &str, path: &str) -> ObjectRef {
+    (
+        bus.to_string(),
+        OwnedObjectPath::try_from(path.to_string()).unwrap(),
+    )
+}
+
+/// Start the service on a background thread, and return the sender of its events. The thread
+/// stops when the sender is dropped, or when there is no accessibility bus to connect to.
+pub(crate) fn spawn(tree: Arc<RwLock<AtspiTree>>) -> Sender<Event> {
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        .name("mctk-a11y".to_string())
+        .spawn(move || {
+            if let Err(e) = run(tree, receiver) {
+                tracing::warn!("The AT-SPI2 service stopped: {}", e);
+            }
+        })
+        .expect("Failed to spawn the AT-SPI2 service thread");
+    sender
+}
+
+fn run(tree: Arc<RwLock<AtspiTree>>, events: Receiver<Event>) -> zbus::Result<()> {
+    let connection = connect()?;
+    let bus = connection
+        .unique_name()
+        .map(|name| name.to_string())
+        .unwrap_or_default();
+    let desktop = Arc::new(RwLock::new(object_ref("", NULL_PATH)));
+    let mut served = HashSet::new();
+    serve(&connection, &tree, &bus, &desktop, &mut served)?;
+
+    let reply = connection.call_method(
+        Some("org.a11y.atspi.Registry"),
+        ROOT_PATH,
+        Some("org.a11y.atspi.Socket"),
+        "Embed",
+        &(object_ref(&bus, ROOT_PATH),),
+    )?;
+    *desktop.write().unwrap() = reply.body().deserialize()?;
+
+    for event in events {
+        match event {
+            Event::Update => serve(&connection, &tree, &bus, &desktop, &mut served)?,
+            Event::Focus(id) => {
+                let path = object_path(id);
+                if tree.read().unwrap().get(&path).is_some() {
+                    focus(&connection, &path)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Connect to the accessibility bus, whose address is given by the session bus.
+fn connect() -> zbus::Result<Connection> {
+    let session = Connection::session()?;
+    let reply = session.call_method(
+        Some("org.a11y.Bus"),
+        "/org/a11y/bus",
+        Some("org.a11y.Bus"),
+        "GetAddress",
+        &(),
+    )?;
+    let address: String = reply.body().deserialize()?;
+    connection::Builder::address(address.as_str())?.build()
+}
+
+/// Serve the objects of the tree that are not `served` yet, and stop serving the ones that are
+/// not in the tree anymore.
+fn serve(
+    connection: &Connection,
+    tree: &Arc<RwLock<AtspiTree>>,
+    bus: &str,
+    desktop: &Arc<RwLock<ObjectRef>>,
+    served: &mut HashSet<String>,
+) -> zbus::Result<()> {
+    let paths: HashSet<String> = tree.read().unwrap().paths().map(String::from).collect();
+    let server = connection.object_server();
+    for path in served.difference(&paths) {
+        server.remove::<Accessible, _>(path.as_str())?;
+    }
+    for path in paths.difference(served) {
+        let accessible = Accessible {
+            path: path.clone(),
+            bus: bus.to_string(),
+            tree: tree.clone(),
+            desktop: desktop.clone(),
+        };
+        server.at(path.as_str(), accessible)?;
+        if path == ROOT_PATH {
+            server.at(ROOT_PATH, Application { id: 0 })?;
+        }
+    }
+    *served = paths;
+    Ok(())
+}
+
+/// Tell screen readers that the object at `path` gained focus.
+fn focus(connection: &Connection, path: &str) -> zbus::Result<()> {
+    connection.emit_signal(
+        None::<&str>,
+        path,
+        "org.a11y.atspi.Event.Object",
+        "StateChanged",
+        &(
+            "focused",
+            1i32,
+            0i32,
+            Value::from(0i32),
+            HashMap::<String, Value>::new(),
+        ),
+    )
+}
+
+/// The `Accessible` interface of an object, which reads the object from the tree on each call.
+struct Accessible {
+    path: String,
+    bus: String,
+    tree: Arc<RwLock<AtspiTree>>,
+    /// The registry's desktop, that is the parent of the root.
+    desktop: Arc<RwLock<ObjectRef>>,
+}
+
+impl Accessible {
+    /// Read the object, or get the default when it is not in the tree anymore.
+    fn with<T: Default>(&self, f: impl FnOnce(&AtspiObject) -> T) -> T {
+        self.tree
+            .read()
+            .unwrap()
+            .get(&self.path)
+            .map(f)
+            .unwrap_or_default()
+    }
+
+    fn object_ref(&self, path: &str) -> ObjectRef {
+        object_ref(&self.bus, path)
+    }
+}
+
+#[interface(name = "org.a11y.atspi.Accessible")]
+impl Accessible {
+    #[zbus(property)]
+    fn name(&self) -> String {
+        self.with(|o| o.name.clone())
+    }
+
+    #[zbus(property)]
+    fn description(&self) -> String {
+        String::new()
+    }
+
+    #[zbus(property)]
+    fn parent(&self) -> ObjectRef {
+        match self.with(|o| o.parent.clone()) {
+            Some(parent) => self.object_ref(&parent),
+            None => self.desktop.read().unwrap().clone(),
+        }
+    }
+
+    #[zbus(property)]
+    fn child_count(&self) -> i32 {
+        self.with(|o| o.children.len() as i32)
+    }
+
+    #[zbus(property)]
+    fn locale(&self) -> String {
+        String::new()
+    }
+
+    fn get_child_at_index(&self, index: i32) -> ObjectRef {
+        let child = self.with(|o| o.children.get(index as usize).cloned());
+        child.map_or_else(|| object_ref("", NULL_PATH), |c| self.object_ref(&c))
+    }
+
+    fn get_children(&self) -> Vec<ObjectRef> {
+        let children = self.with(|o| o.children.clone());
+        children.iter().map(|c| self.object_ref(c)).collect()
+    }
+
+    fn get_index_in_parent(&self) -> i32 {
+        let tree = self.tree.read().unwrap();
+        tree.index_in_parent(&self.path).map_or(-1, |i| i as i32)
+    }
+
+    fn get_relation_set(&self) -> Vec<(u32, Vec<ObjectRef>)> {
+        vec![]
+    }
+
+    fn get_role(&self) -> u32 {
+        self.with(|o| o.role as u32)
+    }
+
+    fn get_role_name(&self) -> String {
+        self.with(|o| o.role.name().to_string())
+    }
+
+    fn get_localized_role_name(&self) -> String {
+        self.get_role_name()
+    }
+
+    fn get_state(&self) -> Vec<u32> {
+        self.with(|o| o.states.to_dbus().to_vec())
+    }
+
+    fn get_attributes(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    fn get_application(&self) -> ObjectRef {
+        self.object_ref(ROOT_PATH)
+    }
+
+    fn get_interfaces(&self) -> Vec<String> {
+        let mut interfaces = vec!["org.a11y.atspi.Accessible".to_string()];
+        if self.path == ROOT_PATH {
+            interfaces.push("org.a11y.atspi.Application".to_string());
+        }
+        interfaces
+    }
+}
+
+/// The `Application` interface of the root object.
+struct Application {
+    /// Set by the registry when the app is embedded.
+    id: i32,
+}
+
+#[interface(name = "org.a11y.atspi.Application")]
+impl Application {
+    #[zbus(property)]
+    fn toolkit_name(&self) -> String {
+        "mctk".to_string()
+    }
+
+    #[zbus(property)]
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    #[zbus(property)]
+    fn atspi_version(&self) -> String {
+        "2.1".to_string()
+    }
+
+    #[zbus(property)]
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    #[zbus(property)]
+    fn set_id(&mut self, id: i32) {
+        self.id = id;
+    }
+}
@@ -0,0 +1,60 @@
+/// The AT-SPI2 states of an accessible object, with their `AtspiStateType` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum State {
+    Active = 1,
+    Enabled = 8,
+    Focusable = 11,
+    Focused = 12,
+    /// A toggle button that is on.
+    Pressed = 20,
+    Sensitive = 24,
+    Showing = 25,
+    Visible = 30,
+}
+
+/// A set of [`State`]s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct StateSet(u64);
+
+impl StateSet {
+    pub fn new(states: &[State]) -> Self {
+        let mut set = Self::default();
+        for state in states {
+            set.insert(*state);
+        }
+        set
+    }
+
+    pub fn insert(&mut self, state: State) {
+        self.0 |= 1 << state as u32;
+    }
+
+    pub fn remove(&mut self, state: State) {
+        self.0 &= !(1 << state as u32);
+    }
+
+    pub fn contains(&self, state: State) -> bool {
+        self.0 & (1 << state as u32) != 0
+    }
+
+    /// The set as sent over dbus by `GetState`: the low 32 states, then the high 32.
+    pub fn to_dbus(self) -> [u32; 2] {
+        [self.0 as u32, (self.0 >> 32) as u32]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_set() {
+        let mut set = StateSet::new(&[State::Enabled, State::Visible]);
+        assert!(set.contains(State::Visible));
+        assert!(!set.contains(State::Focused));
+        assert_eq!(set.to_dbus(), [1 << 8 | 1 << 30, 0]);
+        set.remove(State::Visible);
+        assert_eq!(set.to_dbus(), [1 << 8, 0]);
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use mctk_core::accessibility::{AccessibilityProvider, AccessibleNode};
+use mctk_core::component::AriaRole;
+
+use crate::role::Role;
+use crate::state::{State, StateSet};
+
+/// The dbus object path of the app's root object.
+pub const ROOT_PATH: &str = "/org/a11y/atspi/accessible/root";
+
+/// The dbus object path of the object for the Node with `id`.
+pub fn object_path(id: u64) -> String {
+    format!("/org/a11y/atspi/accessible/{id}")
+}
+
+/// An object of the accessible tree, with what AT-SPI2's `Accessible` interface reports of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtspiObject {
+    pub path: String,
+    pub role: Role,
+    pub name: String,
+    pub parent: Option<String>,
+    pub children: Vec<String>,
+    pub states: StateSet,
+}
+
+/// The objects of an app's accessible tree, by their object path.
+#[derive(Debug, Clone, Default)]
+pub struct AtspiTree {
+    objects: HashMap<String, AtspiObject>,
+    active_descendant: Option<u64>,
+}
+
+impl AtspiTree {
+    pub fn new(root: &dyn AccessibilityProvider) -> Self {
+        let mut tree = Self::default();
+        let children = root.accessible_children();
+        tree.objects.insert(
+            ROOT_PATH.to_string(),
+            AtspiObject {
+                path: ROOT_PATH.to_string(),
+                role: Role::Application,
+                name: String::new(),
+                parent: None,
+                children: children.iter().map(|c| object_path(c.id)).collect(),
+                states: StateSet::new(&[
+                    State::Active,
+                    State::Enabled,
+                    State::Sensitive,
+                    State::Showing,
+                    State::Visible,
+                ]),
+            },
+        );
+        for child in children.iter() {
+            tree.add(child, ROOT_PATH);
+        }
+        tree
+    }
+
+    fn add(&mut self, node: &AccessibleNode, parent: &str) {
+        let path = object_path(node.id);
+        for child in node.children.iter() {
+            self.add(child, &path);
+        }
+        let role = match (node.role, node.pressed) {
+            (AriaRole::Button, Some(_)) => Role::ToggleButton,
+            (role, _) => role.into(),
+        };
+        let mut states = StateSet::new(&[
+            State::Enabled,
+            State::Focusable,
+            State::Sensitive,
+            State::Showing,
+            State::Visible,
+        ]);
+        if node.pressed == Some(true) {
+            states.insert(State::Pressed);
+        }
+        self.objects.insert(
+            path.clone(),
+            AtspiObject {
+                path,
+                role,
+                name: node.label.clone().unwrap_or_default(),
+                parent: Some(parent.to_string()),
+                children: node.children.iter().map(|c| object_path(c.id)).collect(),
+                states,
+            },
+        );
+    }
+
+    pub fn get(&self, path: &str) -> Option<&AtspiObject> {
+        self.objects.get(path)
+    }
+
+    /// The object paths of all objects, in no particular order.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.objects.keys().map(|path| path.as_str())
+    }
+
+    /// The index of the object at `path` among the children of its parent.
+    pub fn index_in_parent(&self, path: &str) -> Option<usize> {
+        let parent = self.get(self.get(path)?.parent.as_deref()?)?;
+        parent.children.iter().position(|c| c == path)
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// The id of the Node whose object is focused.
+    pub fn active_descendant(&self) -> Option<u64> {
+        self.active_descendant
+    }
+
+    /// Move the focus to the object of the Node with `id`. When there is no such object, e.g.
+    /// for the root Node, which has the focus when nothing else does, no object is focused.
+    pub fn set_focus(&mut self, id: u64) {
+        if let Some(previous) = self.active_descendant.take() {
+            if let Some(object) = self.objects.get_mut(&object_path(previous)) {
+                object.states.remove(State::Focused);
+            }
+        }
+        if let Some(object) = self.objects.get_mut(&object_path(id)) {
+            object.states.insert(State::Focused);
+            self.active_descendant = Some(id);
+        }
+    }
+}
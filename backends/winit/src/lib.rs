@@ -13,7 +13,7 @@ use raw_window_handle::{
 };
 use winit::{
     dpi::LogicalSize,
-    event::{Event, WindowEvent},
+    event::{Event, Ime, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
     window::WindowBuilder,
 };
@@ -46,6 +46,8 @@ impl Window {
             .with_inner_size(LogicalSize::new(width as f32, height as f32))
             .build(&event_loop)
             .unwrap();
+        // Otherwise input methods aren't used, and composed text never arrives
+        window.set_ime_allowed(true);
         let mut ui: UI<Window, A, B> = UI::new(
             Window {
                 winit_window: window,
@@ -110,6 +112,16 @@ impl Window {
                         };
                         ui.handle_input(&Input::Motion(scroll));
                     }
+                    WindowEvent::Ime(Ime::Preedit(text, _)) => {
+                        ui.handle_input(&Input::ImeCompose(text));
+                    }
+                    WindowEvent::Ime(Ime::Commit(text)) => {
+                        ui.handle_input(&Input::ImeCommit(text));
+                    }
+                    WindowEvent::Ime(Ime::Disabled) => {
+                        // Cancels any composition that is underway
+                        ui.handle_input(&Input::ImeCompose(String::new()));
+                    }
                     _ => (),
                 },
                 _ => (),
@@ -144,6 +156,38 @@ impl mctk_core::window::Window for Window {
         self.winit_window.request_redraw();
     }
 
+    fn set_cursor(&self, cursor_type: &str) {
+        use winit::window::CursorIcon;
+        let icon = match cursor_type {
+            "None" | "Hidden" => {
+                self.winit_window.set_cursor_visible(false);
+                return;
+            }
+            "Ibeam" | "Text" => CursorIcon::Text,
+            "PointingHand" => CursorIcon::Hand,
+            "Hand" => CursorIcon::Grab,
+            "HandGrabbing" => CursorIcon::Grabbing,
+            "NoEntry" => CursorIcon::NotAllowed,
+            "Cross" => CursorIcon::Crosshair,
+            "Size" | "Move" => CursorIcon::Move,
+            "SizeNWSE" => CursorIcon::NwseResize,
+            "SizeNS" => CursorIcon::NsResize,
+            "SizeNESW" => CursorIcon::NeswResize,
+            "SizeWE" => CursorIcon::EwResize,
+            "Progress" => CursorIcon::Progress,
+            "Wait" => CursorIcon::Wait,
+            _ => CursorIcon::Default,
+        };
+        self.winit_window.set_cursor_visible(true);
+        self.winit_window.set_cursor_icon(icon);
+    }
+
+    fn unset_cursor(&self) {
+        self.winit_window.set_cursor_visible(true);
+        self.winit_window
+            .set_cursor_icon(winit::window::CursorIcon::Default);
+    }
+
     fn exit(&mut self) {}
 
     fn fonts(&self) -> cosmic_text::fontdb::Database {
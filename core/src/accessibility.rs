@@ -0,0 +1,97 @@
+//! A description of the Component tree for assistive technologies, such as screen readers.
+
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::component::AriaRole;
+use crate::node::Node;
+
+/// A Component with an [`AriaRole`], as seen by assistive technologies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessibleNode {
+    /// The id of the Component's Node, as used by focus events.
+    pub id: u64,
+    pub role: AriaRole,
+    /// The text that names the Component, e.g. the label of a button.
+    pub label: Option<String>,
+    /// Whether a toggle button is on. `None` for Components that are not toggle buttons.
+    pub pressed: Option<bool>,
+    pub children: Vec<AccessibleNode>,
+}
+
+/// Something that can describe its Components to assistive technologies.
+pub trait AccessibilityProvider {
+    /// The Components that have a role, each with the Components with a role below it as its
+    /// children. Components without a role are left out, and the Components below them are
+    /// attached to the nearest ancestor that has one.
+    fn accessible_children(&self) -> Vec<AccessibleNode>;
+}
+
+/// Told by the [`UI`](crate::ui::UI) about what assistive technologies should see, e.g. to pass
+/// it on to the platform's accessibility service.
+pub trait AccessibilityListener: Send + Sync {
+    /// Called with the root Node each time that the Node tree is viewed.
+    fn update(&self, root: &dyn AccessibilityProvider);
+
+    /// Called with the id of the Node that gains focus. When focus is lost, this is the id of
+    /// the root Node, which gets the focus then.
+    fn focus_changed(&self, id: u64);
+}
+
+fn _listener() -> &'static Mutex<Option<Arc<dyn AccessibilityListener>>> {
+    static LISTENER: OnceLock<Mutex<Option<Arc<dyn AccessibilityListener>>>> = OnceLock::new();
+    LISTENER.get_or_init(|| Mutex::new(None))
+}
+
+/// Set the listener that the `UI` tells about changes to the Node tree and the focus, in place
+/// of the one that was set before.
+pub fn set_accessibility_listener(listener: Arc<dyn AccessibilityListener>) {
+    *_listener().lock().unwrap() = Some(listener);
+}
+
+/// The listener that was set with [`set_accessibility_listener`], if any.
+pub(crate) fn accessibility_listener() -> Option<Arc<dyn AccessibilityListener>> {
+    _listener().lock().unwrap().clone()
+}
+
+impl AccessibilityProvider for Node {
+    fn accessible_children(&self) -> Vec<AccessibleNode> {
+        let mut nodes = vec![];
+        for child in self.children.iter() {
+            match child.component.aria_role() {
+                Some(role) => nodes.push(AccessibleNode {
+                    id: child.id,
+                    role,
+                    label: child.component.aria_label(),
+                    pressed: child.component.aria_pressed(),
+                    children: child.accessible_children(),
+                }),
+                None => nodes.append(&mut child.accessible_children()),
+            }
+        }
+        nodes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::{Button, Div};
+    use crate::{node, txt};
+
+    #[test]
+    fn test_accessible_children() {
+        let mut root = node!(Div::new())
+            .push(node!(Div::new()).push(node!(Button::new(txt!("Ok")).toggle_mode(true))))
+            .push(node!(Button::new(txt!("Docs")).href("/docs")));
+        root.view(None, &mut vec![]);
+
+        let children = root.accessible_children();
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].role, AriaRole::Button);
+        assert_eq!(children[0].label.as_deref(), Some("Ok"));
+        assert_eq!(children[0].pressed, Some(true));
+        assert_eq!(children[1].role, AriaRole::Link);
+        assert_eq!(children[1].pressed, None);
+        assert!(children[1].children.is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+//! Time based animation helpers, stepped from
+//! [`Component::on_tick`][crate::component::Component::on_tick].
+
+/// The longest time that [`SpringAnimator::step`] integrates at once, in seconds.
+/// Longer frames are split up to keep the spring stable.
+const MAX_STEP: f32 = 1.0 / 240.0;
+/// How close to its target, in pixels, a spring has to be to settle.
+const SETTLE_DISTANCE: f32 = 0.5;
+/// How slow, in pixels per second, a spring has to be to settle.
+const SETTLE_VELOCITY: f32 = 5.0;
+
+/// A damped spring that moves a value towards a target.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SpringAnimator {
+    pub stiffness: f32,
+    pub damping: f32,
+    pub mass: f32,
+    pub value: f32,
+    pub velocity: f32,
+    pub target: f32,
+}
+
+impl SpringAnimator {
+    pub fn new(stiffness: f32, damping: f32, mass: f32) -> Self {
+        Self {
+            stiffness,
+            damping,
+            mass,
+            ..Default::default()
+        }
+    }
+
+    /// A quick spring with a slight overshoot, as used to spring back from an over-scroll.
+    pub fn preset_bounce() -> Self {
+        Self::new(400.0, 30.0, 1.0)
+    }
+
+    /// A stiff, nearly critically damped spring that settles in about 150ms.
+    pub fn preset_snappy() -> Self {
+        Self::new(1500.0, 77.0, 1.0)
+    }
+
+    /// Start animating from `from` to `to`, at rest.
+    pub fn start(mut self, from: f32, to: f32) -> Self {
+        self.value = from;
+        self.target = to;
+        self.velocity = 0.0;
+        self
+    }
+
+    /// Advance the spring by `dt` seconds, returning the new value.
+    /// Once it is close enough to the target and slow enough, it snaps to the target.
+    pub fn step(&mut self, dt: f32) -> f32 {
+        let mut remaining = dt;
+        while remaining > 0.0 && !self.is_settled() {
+            let dt = remaining.min(MAX_STEP);
+            let force = -self.stiffness * (self.value - self.target) - self.damping * self.velocity;
+            self.velocity += force / self.mass * dt;
+            self.value += self.velocity * dt;
+            remaining -= dt;
+
+            if (self.value - self.target).abs() < SETTLE_DISTANCE
+                && self.velocity.abs() < SETTLE_VELOCITY
+            {
+                self.value = self.target;
+                self.velocity = 0.0;
+            }
+        }
+        self.value
+    }
+
+    pub fn is_settled(&self) -> bool {
+        self.value == self.target && self.velocity == 0.0
+    }
+}
+
+/// Moves a value towards a target over a fixed duration, decelerating with an ease-out curve.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct EaseOutAnimator {
+    pub from: f32,
+    pub to: f32,
+    /// In seconds.
+    pub duration: f32,
+    pub elapsed: f32,
+}
+
+impl EaseOutAnimator {
+    pub fn new(from: f32, to: f32, duration: f32) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            elapsed: 0.0,
+        }
+    }
+
+    pub fn value(&self) -> f32 {
+        if self.is_finished() {
+            return self.to;
+        }
+        let t = 1.0 - (1.0 - self.elapsed / self.duration).powi(3);
+        self.from + (self.to - self.from) * t
+    }
+
+    /// Advance the animation by `dt` seconds, returning the new value.
+    pub fn step(&mut self, dt: f32) -> f32 {
+        self.elapsed = (self.elapsed + dt).min(self.duration);
+        self.value()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spring_settles() {
+        let mut spring = SpringAnimator::preset_bounce().start(130.0, 100.0);
+        assert!(!spring.is_settled());
+        let first = spring.step(0.016);
+        assert!(first < 130.0 && first > 100.0);
+
+        for _ in 0..30 {
+            spring.step(0.016);
+        }
+        assert!(spring.is_settled());
+        assert_eq!(spring.value, 100.0);
+    }
+
+    #[test]
+    fn test_ease_out() {
+        let mut ease = EaseOutAnimator::new(500.0, 0.0, 0.25);
+        assert_eq!(ease.value(), 500.0);
+        // Most of the distance is covered in the first half
+        assert!(ease.step(0.125) < 125.0);
+        assert!(!ease.is_finished());
+        assert_eq!(ease.step(0.2), 0.0);
+        assert!(ease.is_finished());
+    }
+}
@@ -8,6 +8,7 @@ use crate::node::Node;
 use crate::renderables::types::Canvas;
 use crate::renderables::Renderable;
 use crate::renderer::Caches;
+use crate::style::CursorStyle;
 use crate::types::*;
 use crate::window::Window;
 use ahash::AHasher;
@@ -31,6 +32,13 @@ macro_rules! msg {
     };
 }
 
+/// The role of a [`Component`] for assistive technologies, as with the ARIA `role` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AriaRole {
+    Button,
+    Link,
+}
+
 /// Passed to [`Component#render`][Component#method.render], with context required for rendering.
 #[derive(Clone)]
 pub struct RenderContext {
@@ -124,6 +132,21 @@ pub trait Component: fmt::Debug {
         vec![]
     }
 
+    /// Whether the Tab key can move the focus to this Component, while it is inside a focus trap.
+    fn focusable(&self) -> bool {
+        false
+    }
+
+    /// Return `true` to trap the focus inside this Component, e.g. while a dialog is open. Tab and
+    /// Shift+Tab then cycle the focus through its [focusable][Component#method.focusable]
+    /// descendants, and key events go to this Component when no descendant is focused, or when
+    /// the focused one doesn't stop their bubbling.
+    ///
+    /// If more than one Component traps the focus, the last one in the tree wins.
+    fn traps_focus(&self) -> bool {
+        false
+    }
+
     /// Is the `mouse_position` over this Component? Implement if the Component has
     /// non-rectangular geometry. Otherwise will default to `aabb.is_under(mouse_position)`.
     fn is_mouse_over(&self, mouse_position: Point, aabb: AABB) -> bool {
@@ -190,6 +213,11 @@ pub trait Component: fmt::Debug {
         None
     }
 
+    /// Whether the scroll position is currently past the end of the content on purpose, e.g. while it is dragged past it and springs back. Otherwise the children are kept from scrolling past the end.
+    fn over_scrolled(&self) -> bool {
+        false
+    }
+
     /// Should only be overridden by scrollable containers. Used to limit the bounds of the scrollable area.
     /// Should return an [`AABB`] that is inside the bounds of the input `aabb` which belongs to the current Node. `inner_scale` is the size of its child Nodes.
     ///
@@ -203,6 +231,37 @@ pub trait Component: fmt::Debug {
         Scale::new(0.0, 0.0)
     }
 
+    /// The mouse cursor to show while the mouse is over this Component. Usually its `cursor` style.
+    ///
+    /// When `None`, the cursor of the nearest ancestor that has one is shown, or else the default pointer.
+    fn cursor(&self) -> Option<CursorStyle> {
+        None
+    }
+
+    /// The role of this Component for assistive technologies. `None` for Components that are
+    /// only presentational, or that are described by their children.
+    fn aria_role(&self) -> Option<AriaRole> {
+        None
+    }
+
+    /// The text that names this Component for assistive technologies, e.g. the label of a button.
+    fn aria_label(&self) -> Option<String> {
+        None
+    }
+
+    /// Whether this Component is pressed, as with the ARIA `aria-pressed` attribute. `None` for
+    /// Components that are not toggle buttons.
+    fn aria_pressed(&self) -> Option<bool> {
+        None
+    }
+
+    #[cfg(feature = "debug-inspector")]
+    #[doc(hidden)]
+    // Used to name the Component under the cursor in the style inspector.
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
     // Event handlers
     /// Handle mouse click events. These events will only be sent if the mouse is over the Component.
     fn on_click(&mut self, _event: &mut Event<event::Click>) {}
@@ -255,6 +314,10 @@ pub trait Component: fmt::Debug {
     fn on_key_press(&mut self, _event: &mut Event<event::KeyPress>) {}
     /// Handle text entry events. These events will only be sent if this component is focused.
     fn on_text_entry(&mut self, _event: &mut Event<event::TextEntry>) {}
+    /// Handle an update to the text that an input method is composing. These events will only be sent if this component is focused.
+    fn on_ime_compose(&mut self, _event: &mut Event<event::ImeCompose>) {}
+    /// Handle text committed by an input method. These events will only be sent if this component is focused.
+    fn on_ime_commit(&mut self, _event: &mut Event<event::ImeCommit>) {}
     /// Handle a drag and drop event moving over the component.
     fn on_drag_target(&mut self, _event: &mut Event<event::DragTarget>) {}
     /// Handle a drag and drop event the first it moves over this component.
@@ -271,3 +334,52 @@ pub trait RootComponent<A> {
     // Called when a root node is first instantiated, this method will only be called for root components. This is called after state is init()
     fn root(&mut self, window: &dyn Any, app_params: &dyn Any) {}
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::style::Styled;
+    use mctk_macros::component;
+
+    #[derive(Debug, Default)]
+    struct CounterState;
+
+    #[component(State = "CounterState", Internal, New)]
+    #[styled(name = "Counter")]
+    #[derive(Debug)]
+    struct Counter {
+        #[prop]
+        label: String,
+        #[prop]
+        max: Option<u32>,
+        step: u32,
+    }
+
+    #[component(Internal, New)]
+    #[derive(Debug)]
+    struct Spacer {
+        #[prop]
+        width: Option<f32>,
+    }
+
+    #[test]
+    fn test_component_macro() {
+        let mut c = Counter::new("a".to_string(), 1)
+            .max(Some(3))
+            .label("b".to_string());
+        assert_eq!(c.label, "b");
+        assert_eq!(c.max, Some(3));
+        assert_eq!(c.step, 1);
+        // So that state_ref works without an init
+        assert!(c.state.is_some());
+        assert!(!c.dirty);
+        c.state_mut();
+        assert!(c.dirty);
+
+        assert_eq!(Counter::name(), "Counter");
+        let c = c.with_class("big");
+        assert_eq!(c.class(), Some("big"));
+
+        assert_eq!(Spacer::default().width, None);
+        assert_eq!(Spacer::new().width(Some(2.0)).width, Some(2.0));
+    }
+}
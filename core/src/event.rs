@@ -38,6 +38,7 @@ pub struct Event<T: EventInput> {
     pub(crate) target: Option<u64>,
     pub(crate) focus: Option<u64>,
     pub(crate) scale_factor: f32,
+    pub(crate) click_count: u32,
     pub(crate) messages: Vec<Message>,
     pub(crate) registrations: Vec<crate::node::Registration>,
 }
@@ -59,6 +60,7 @@ impl<T: EventInput> std::fmt::Debug for Event<T> {
             .field("target", &self.target)
             .field("focus", &self.focus)
             .field("scale_factor", &self.scale_factor)
+            .field("click_count", &self.click_count)
             .finish()
     }
 }
@@ -257,6 +259,22 @@ pub struct TextEntry(
 );
 impl EventInput for TextEntry {}
 
+/// [`EventInput`] type for the text that an input method is composing, before it is committed.
+#[derive(Debug)]
+pub struct ImeCompose(
+    /// The text composed so far. Empty when the composition has been cancelled.
+    pub String,
+);
+impl EventInput for ImeCompose {}
+
+/// [`EventInput`] type for the text that an input method commits, ending any composition.
+#[derive(Debug)]
+pub struct ImeCommit(
+    /// The text committed.
+    pub String,
+);
+impl EventInput for ImeCommit {}
+
 /// [`EventInput`] type for scroll events.
 #[derive(Debug, Copy, Clone)]
 pub struct Scroll {
@@ -380,13 +398,14 @@ impl<T: EventInput> Event<T> {
             over_child_n_aabb: None,
             over_subchild_n: None,
             scale_factor: event_cache.scale_factor,
+            click_count: event_cache.click_count,
             messages: vec![],
             registrations: vec![],
         }
     }
 
     /// Set the current Node to be "focused".
-    /// This will cause it to receive [`Blur`], [`KeyDown`], [`KeyUp`], [`KeyPress`], [`TextEntry`], [`Drag`], and [`DragEnd`] events.
+    /// This will cause it to receive [`Blur`], [`KeyDown`], [`KeyUp`], [`KeyPress`], [`TextEntry`], [`ImeCompose`], [`ImeCommit`], [`Drag`], and [`DragEnd`] events.
     ///
     /// Note that any other Nodes may also request focus.
     pub fn focus(&mut self) {
@@ -471,6 +490,11 @@ impl<T: EventInput> Event<T> {
         (self.touch_position - Point { x: pos.x, y: pos.y }).unscale(self.scale_factor)
     }
 
+    /// How many clicks (or taps) in quick succession led to this event: `1` for a single click, `2` for a double click, `3` for a triple click, and so on.
+    pub fn click_count(&self) -> u32 {
+        self.click_count
+    }
+
     /// Returns which child of this Node the mouse is over, if any.
     pub fn over_child_n(&self) -> Option<usize> {
         self.over_child_n
@@ -624,6 +648,8 @@ pub(crate) struct EventCache {
     // Used to detect double clicks
     pub last_mouse_click: Instant,
     pub last_mouse_click_position: Point,
+    // Number of clicks in the current run of double clicks
+    pub click_count: u32,
     // This is used as the start of the drag position, even if we haven't decided to start dragging
     pub drag_started: Option<Point>,
     // This is used as the start of the touch drag position, even if we haven't decided to start dragging
@@ -665,6 +691,7 @@ impl EventCache {
             mouse_position: Default::default(),
             last_mouse_click: Instant::now(),
             last_mouse_click_position: Default::default(),
+            click_count: 0,
             touch_held: false,
             last_touch_position: Default::default(),
             last_touch_down: Instant::now(),
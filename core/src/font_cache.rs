@@ -6,7 +6,7 @@ use std::ops::Deref;
 
 use crate::renderables::text::{self, InstanceBuilder};
 use crate::renderer::text::TextRenderer;
-use crate::style::HorizontalPosition;
+use crate::style::{HorizontalPosition, OverflowWrap, WordBreak};
 use crate::{Pos, Scale};
 
 /// Value by which fonts are scaled. 12 px fonts render at scale 18 px for some reason. Useful if you need to compute the line height: it will be `<font_size> * SIZE_SCALE` in logical size, and `<font_size> * SIZE_SCALE * <scale_factor>` in physical pixels.
@@ -37,6 +37,36 @@ impl FontCache {
         line_height: f32,
         h_alignment: HorizontalPosition,
         bounds: (f32, f32),
+        letter_spacing: f32,
+    ) -> (Option<f32>, Option<f32>, Vec<LayoutGlyph>) {
+        self.measure_wrapped_text(
+            text,
+            font,
+            size,
+            scale_factor,
+            line_height,
+            h_alignment,
+            bounds,
+            letter_spacing,
+            WordBreak::Normal,
+            OverflowWrap::Normal,
+        )
+    }
+
+    /// Like [`#measure_text`][FontCache#method.measure_text], but wraps lines that are wider than
+    /// `bounds` where `word_break` and `overflow_wrap` allow.
+    pub fn measure_wrapped_text(
+        &mut self,
+        text: String,
+        font: Option<String>,
+        size: f32,
+        scale_factor: f32,
+        line_height: f32,
+        h_alignment: HorizontalPosition,
+        bounds: (f32, f32),
+        letter_spacing: f32,
+        word_break: WordBreak,
+        overflow_wrap: OverflowWrap,
     ) -> (Option<f32>, Option<f32>, Vec<LayoutGlyph>) {
         let font_size = size * scale_factor;
         let text_renderer = &mut self.text_renderer;
@@ -60,6 +90,9 @@ impl FontCache {
             .font(font)
             .line_height(line_height)
             .font_size(font_size)
+            .letter_spacing(letter_spacing * scale_factor)
+            .word_break(word_break)
+            .overflow_wrap(overflow_wrap)
             .build()
             .unwrap();
 
@@ -78,6 +111,45 @@ pub struct TextSegment {
     pub size: Option<f32>,
     /// An optional font name. A default will be selected if `None`.
     pub font: Option<String>,
+    /// How far, in logical pixels, to raise the baseline of this segment. Negative values lower it.
+    pub vertical_offset: Option<f32>,
+    /// A multiplier of the font size. `1.0` if `None`.
+    pub size_factor: Option<f32>,
+}
+
+/// The approximate ascent of a font, as a fraction of its size.
+const ASCENT: f32 = 0.8;
+
+impl TextSegment {
+    /// A segment raised by `0.4` of `font_size`, and shrunk to `0.75` of it.
+    pub fn superscript<S: Into<String>>(text: S, font_size: f32) -> Self {
+        let text: String = text.into();
+        Self {
+            vertical_offset: Some(font_size * 0.4),
+            size_factor: Some(0.75),
+            ..text.into()
+        }
+    }
+
+    /// A segment lowered by `0.4` of `font_size`, and shrunk to `0.75` of it.
+    pub fn subscript<S: Into<String>>(text: S, font_size: f32) -> Self {
+        Self {
+            vertical_offset: Some(-font_size * 0.4),
+            ..Self::superscript(text, font_size)
+        }
+    }
+
+    /// The font size of this segment, in text whose size is `base_size`.
+    pub fn font_size(&self, base_size: f32) -> f32 {
+        self.size.unwrap_or(base_size) * self.size_factor.unwrap_or(1.0)
+    }
+
+    /// Where the top of this segment is drawn, in a line of text with a top of `top` and a size of `base_size`.
+    ///
+    /// Segments without a `vertical_offset` share a baseline with the line, regardless of their size.
+    pub fn top(&self, top: f32, base_size: f32) -> f32 {
+        top + (base_size - self.font_size(base_size)) * ASCENT - self.vertical_offset.unwrap_or(0.0)
+    }
 }
 
 impl From<&str> for TextSegment {
@@ -92,6 +164,8 @@ impl From<String> for TextSegment {
             text,
             size: None,
             font: None,
+            vertical_offset: None,
+            size_factor: None,
         }
     }
 }
@@ -167,18 +241,24 @@ macro_rules! txt {
         text: $text.into(),
         size: Some($size),
         font: None,
+        vertical_offset: None,
+        size_factor: None,
     } };
 
     (@as_txt_seg  ($text:expr, $font:expr, $size:expr)) => { $crate::font_cache::TextSegment {
         text: $text.into(),
         size: Some($size),
         font: Some($font.into()),
+        vertical_offset: None,
+        size_factor: None,
     } };
 
     (@as_txt_seg  ($text:expr, $font:expr)) => { $crate::font_cache::TextSegment {
         text: $text.into(),
         size: None,
         font: Some($font.into()),
+        vertical_offset: None,
+        size_factor: None,
     } };
 
     (@as_txt_seg  $e:expr) => {
@@ -199,5 +279,36 @@ impl Hash for TextSegment {
         self.size.map(|s| (s * 100.0) as u32).hash(state);
         self.font.hash(state);
         self.text.hash(state);
+        self.vertical_offset.map(|o| (o * 100.0) as i32).hash(state);
+        self.size_factor.map(|f| (f * 100.0) as u32).hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_super_and_subscript() {
+        let base: TextSegment = "x".into();
+        let sup = TextSegment::superscript("2", 20.0);
+        let sub = TextSegment::subscript("i", 20.0);
+        assert_eq!(sup.vertical_offset, Some(8.0));
+        assert_eq!(sub.vertical_offset, Some(-8.0));
+        assert_eq!(sup.font_size(20.0), 15.0);
+
+        // Superscripts sit above the line, subscripts below, and the base doesn't move
+        assert_eq!(base.top(100.0, 20.0), 100.0);
+        assert!(sup.top(100.0, 20.0) < 100.0);
+        assert!(sub.top(100.0, 20.0) > 100.0);
+        assert!(sub.top(100.0, 20.0) + sub.font_size(20.0) > 100.0 + 20.0);
+
+        // A smaller segment without an offset shares the baseline of the line
+        let small = TextSegment {
+            size_factor: Some(0.5),
+            ..base
+        };
+        let baseline = |s: &TextSegment| s.top(100.0, 20.0) + s.font_size(20.0) * ASCENT;
+        assert_eq!(baseline(&small), 100.0 + 20.0 * ASCENT);
     }
 }
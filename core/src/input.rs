@@ -278,6 +278,10 @@ pub enum Input {
     Resize,
     Motion(Motion),
     Text(String),
+    /// The text an input method is composing. Empty when the composition is cancelled.
+    ImeCompose(String),
+    /// The text an input method commits.
+    ImeCommit(String),
     Focus(bool),
     Menu(i32),
     MouseLeaveWindow,
@@ -0,0 +1,46 @@
+//! Hooks for the `mctk_inspector` style inspector. Only built with the `debug-inspector` feature.
+
+use std::sync::Mutex;
+
+use crate::types::AABB;
+
+/// The Component under the mouse cursor.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Inspected {
+    /// The name of the Component's type, e.g. `"Button"`.
+    pub name: &'static str,
+    /// Where the Component was laid out, in physical pixels.
+    pub aabb: AABB,
+}
+
+static HOVERED: Mutex<Option<Inspected>> = Mutex::new(None);
+
+/// The Component that the mouse cursor is over, if any.
+pub fn hovered() -> Option<Inspected> {
+    *HOVERED.lock().unwrap()
+}
+
+pub(crate) fn set_hovered(inspected: Option<Inspected>) {
+    *HOVERED.lock().unwrap() = inspected;
+}
+
+/// `type_name` without its module path or generic parameters.
+pub fn short_name(type_name: &'static str) -> &'static str {
+    let name = type_name.split('<').next().unwrap_or(type_name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_name() {
+        assert_eq!(short_name("mctk_core::widgets::button::Button"), "Button");
+        assert_eq!(
+            short_name("mctk_core::widgets::select::Select<alloc::string::String>"),
+            "Select"
+        );
+        assert_eq!(short_name("Div"), "Div");
+    }
+}
@@ -22,6 +22,7 @@ impl Div<f32> for ScrollPosition {
 }
 
 #[derive(Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Dimension {
     Auto,
     Px(f64),
@@ -178,6 +179,7 @@ impl From<Dimension> for f64 {
 }
 
 #[derive(Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Size {
     pub width: Dimension,
     pub height: Dimension,
@@ -272,6 +274,7 @@ impl From<ScrollPosition> for Size {
 }
 
 #[derive(Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rect {
     pub left: Dimension,
     pub right: Dimension,
@@ -467,6 +470,7 @@ impl From<crate::types::Point> for Rect {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     Row,
     Column,
@@ -520,6 +524,7 @@ impl Direction {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PositionType {
     Absolute,
     Relative,
@@ -532,6 +537,7 @@ impl Default for PositionType {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Alignment {
     Start,
     End,
@@ -545,7 +551,81 @@ impl Default for Alignment {
     }
 }
 
+/// The CSS `align-items` of a [`Layout`]: how children are aligned on the cross axis.
+///
+/// These CSS flexbox enums describe a `Layout` with the names used on the web, e.g.
+/// ```
+/// use mctk_core::layout::{AlignItems, FlexDirection, FlexWrap, JustifyContent, Layout};
+///
+/// let layout = Layout::centered();
+/// assert_eq!(layout.flex_direction(), FlexDirection::Row);
+/// assert_eq!(layout.flex_wrap(), FlexWrap::NoWrap);
+/// assert_eq!(layout.justify_content(), JustifyContent::Center);
+/// assert_eq!(layout.align_items(), AlignItems::Center);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AlignItems {
+    Start,
+    Center,
+    End,
+    Stretch,
+    Baseline,
+}
+
+impl Default for AlignItems {
+    fn default() -> Self {
+        Self::Stretch
+    }
+}
+
+/// The CSS `justify-content` of a [`Layout`]: how children are distributed on the main axis.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum JustifyContent {
+    Start,
+    Center,
+    End,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+impl Default for JustifyContent {
+    fn default() -> Self {
+        Self::Start
+    }
+}
+
+/// The CSS `flex-direction` of a [`Layout`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FlexDirection {
+    Row,
+    Column,
+    RowReverse,
+    ColumnReverse,
+}
+
+impl Default for FlexDirection {
+    fn default() -> Self {
+        Self::Row
+    }
+}
+
+/// The CSS `flex-wrap` of a [`Layout`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FlexWrap {
+    NoWrap,
+    Wrap,
+    WrapReverse,
+}
+
+impl Default for FlexWrap {
+    fn default() -> Self {
+        Self::NoWrap
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Layout {
     pub direction: Direction,
     pub wrap: bool,
@@ -555,15 +635,105 @@ pub struct Layout {
     pub cross_alignment: Alignment,
     pub margin: Rect,
     pub padding: Rect,
+    /// Space between adjacent relatively-positioned children, along the main axis.
+    pub gap: f64,
     pub size: Size,
     // TODO employ this more consistently
     pub max_size: Size,
     pub min_size: Size,
     pub z_index: Option<f64>,
     pub z_index_increment: f64,
+    /// Within a vertically scrolling parent, stay at the top of the parent's frame once scrolled
+    /// past, until pushed out by the next sticky sibling. E.g. for table headers.
+    pub sticky: bool,
     pub debug: Option<String>,
 }
 
+impl Layout {
+    /// Children laid out left to right.
+    pub fn row() -> Self {
+        Self {
+            direction: Direction::Row,
+            ..Default::default()
+        }
+    }
+
+    /// Children laid out top to bottom.
+    pub fn column() -> Self {
+        Self {
+            direction: Direction::Column,
+            ..Default::default()
+        }
+    }
+
+    /// Children centered on both axes.
+    pub fn centered() -> Self {
+        Self {
+            axis_alignment: Alignment::Center,
+            cross_alignment: Alignment::Center,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_gap(mut self, gap: f32) -> Self {
+        self.gap = gap.into();
+        self
+    }
+
+    pub fn with_padding(mut self, padding: crate::types::Padding) -> Self {
+        self.padding = Rect {
+            top: Dimension::Px(padding.top.into()),
+            right: Dimension::Px(padding.right.into()),
+            bottom: Dimension::Px(padding.bottom.into()),
+            left: Dimension::Px(padding.left.into()),
+        };
+        self
+    }
+
+    /// Set the alignment of children on the cross axis.
+    pub fn with_align_items(mut self, align: Alignment) -> Self {
+        self.cross_alignment = align;
+        self
+    }
+
+    /// The CSS `flex-direction` equivalent of `direction`.
+    pub fn flex_direction(&self) -> FlexDirection {
+        match self.direction {
+            Direction::Row => FlexDirection::Row,
+            Direction::Column => FlexDirection::Column,
+        }
+    }
+
+    /// The CSS `flex-wrap` equivalent of `wrap`.
+    pub fn flex_wrap(&self) -> FlexWrap {
+        if self.wrap {
+            FlexWrap::Wrap
+        } else {
+            FlexWrap::NoWrap
+        }
+    }
+
+    /// The CSS `justify-content` equivalent of `axis_alignment`. Children stretched along the
+    /// main axis (which CSS does with `flex-grow`) are `Start`.
+    pub fn justify_content(&self) -> JustifyContent {
+        match self.axis_alignment {
+            Alignment::Start | Alignment::Stretch => JustifyContent::Start,
+            Alignment::Center => JustifyContent::Center,
+            Alignment::End => JustifyContent::End,
+        }
+    }
+
+    /// The CSS `align-items` equivalent of `cross_alignment`.
+    pub fn align_items(&self) -> AlignItems {
+        match self.cross_alignment {
+            Alignment::Start => AlignItems::Start,
+            Alignment::Center => AlignItems::Center,
+            Alignment::End => AlignItems::End,
+            Alignment::Stretch => AlignItems::Stretch,
+        }
+    }
+}
+
 impl Default for Layout {
     fn default() -> Self {
         Self {
@@ -575,6 +745,7 @@ impl Default for Layout {
             cross_alignment: Default::default(),
             margin: Rect::ZERO,
             padding: Rect::ZERO,
+            gap: 0.0,
             size: Default::default(),
             max_size: Default::default(),
             min_size: Size {
@@ -583,6 +754,7 @@ impl Default for Layout {
             },
             z_index: None,
             z_index_increment: 0.0,
+            sticky: false,
             debug: None,
         }
     }
@@ -594,6 +766,17 @@ pub struct LayoutResult {
     pub position: Rect,
 }
 
+/// The inputs and outputs of one pass of [`resolve_layout`][super::node::Node] over a Node, so
+/// that unchanged subtrees can skip being laid out again.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct LayoutCache {
+    bounds_size: Size,
+    entry_size: Size,
+    scale_factor: f32,
+    result: LayoutResult,
+    inner_scale: Option<crate::types::Scale>,
+}
+
 impl From<LayoutResult> for crate::types::AABB {
     fn from(p: LayoutResult) -> Self {
         Self::new(
@@ -604,13 +787,15 @@ impl From<LayoutResult> for crate::types::AABB {
 }
 
 impl super::node::Node {
+    /// Returns the number of nodes that were laid out.
     fn resolve_child_sizes(
         &mut self,
         inner_size: Size,
         font_cache: &mut crate::font_cache::FontCache,
         scale_factor: f32,
         final_pass: bool,
-    ) {
+    ) -> usize {
+        let mut laid_out = 0;
         let dir = self.layout.direction;
         let mut main_remaining = f64::from(inner_size.main(dir));
         let mut max_cross_size = 0.0;
@@ -656,20 +841,20 @@ impl super::node::Node {
             if !child.layout_result.size.resolved() {
                 let inner_size =
                     inner_size.minus_rect(&child.layout.margin.maybe_resolve(&inner_size));
-                let (w, h) = child.component.fill_bounds(
-                    child.layout_result.size.width.maybe_px(),
-                    child.layout_result.size.height.maybe_px(),
-                    inner_size
-                        .width
-                        .maybe_px()
-                        .or(self.layout.max_size.width.maybe_px()),
-                    inner_size
-                        .height
-                        .maybe_px()
-                        .or(self.layout.max_size.height.maybe_px()),
-                    font_cache,
-                    scale_factor,
-                );
+                let max_size = &self.layout.max_size;
+                let width = child.layout_result.size.width.maybe_px();
+                let max_width = inner_size.width.maybe_px().or(max_size.width.maybe_px());
+                let max_height = inner_size.height.maybe_px().or(max_size.height.maybe_px());
+                let (w, h) = crate::style::with_container_width(width.or(max_width), || {
+                    child.component.fill_bounds(
+                        width,
+                        child.layout_result.size.height.maybe_px(),
+                        max_width,
+                        max_height,
+                        font_cache,
+                        scale_factor,
+                    )
+                });
                 if let Some(w) = w {
                     child.layout_result.size.width = Dimension::Px(w.into());
                 }
@@ -688,6 +873,12 @@ impl super::node::Node {
                 unresolved += 1;
             }
         }
+        let relative_children = self
+            .children
+            .iter()
+            .filter(|c| c.layout.position_type == PositionType::Relative)
+            .count();
+        main_remaining -= self.layout.gap * relative_children.saturating_sub(1) as f64;
         main_remaining = main_remaining.max(0.0);
 
         for child in self.children.iter_mut() {
@@ -719,8 +910,9 @@ impl super::node::Node {
                     .minus_rect(&child.layout.margin.maybe_resolve(&inner_size));
             }
 
-            child.resolve_layout(inner_size, font_cache, scale_factor, final_pass);
+            laid_out += child.resolve_layout(inner_size, font_cache, scale_factor, final_pass);
         }
+        laid_out
     }
 
     fn resolve_position(&mut self, bounds: Size) {
@@ -789,15 +981,20 @@ impl super::node::Node {
             self.children.iter_mut().collect()
         };
 
+        let gap = self.layout.gap;
         for child in children.iter_mut() {
             let margin = child.layout.margin.maybe_resolve(&size);
             let child_outer_size = child.layout_result.size.plus_rect(&margin);
+            let leading_gap = if row_elements_count > 0 { gap } else { 0.0 };
 
             // Perform a wrap?
             if self.layout.wrap
                 && size.main(dir).resolved()
                 && child.layout.position_type != PositionType::Absolute
-                && (main_pos + main_end_padding + f64::from(child_outer_size.main(dir)))
+                && (main_pos
+                    + leading_gap
+                    + main_end_padding
+                    + f64::from(child_outer_size.main(dir)))
                     > f64::from(size.main(dir))
                 && main_pos > main_start_padding
             {
@@ -809,6 +1006,9 @@ impl super::node::Node {
             }
 
             if child.layout.position_type == PositionType::Relative {
+                if row_elements_count > 0 {
+                    main_pos += gap;
+                }
                 child.layout_result.position = dir.rect(
                     Dimension::Px(main_pos),
                     Dimension::Px(cross_pos),
@@ -998,13 +1198,30 @@ impl super::node::Node {
     /// Wrapping cannot be performed on an axis that isn't resolved.
     ///
     /// A node that it scrollable on an axis must have a resolved size on that axis.
+    ///
+    /// Nodes whose subtree is unchanged since the last layout, and which are given the same
+    /// bounds, reuse their previous result. Returns the number of nodes that were laid out.
     fn resolve_layout(
         &mut self,
         bounds_size: Size,
         font_cache: &mut crate::font_cache::FontCache,
         scale_factor: f32,
         final_pass: bool,
-    ) {
+    ) -> usize {
+        let pass = final_pass as usize;
+        if self.layout_version == self.computed_version {
+            if let Some(cache) = self.layout_cache[pass] {
+                if cache.bounds_size == bounds_size
+                    && cache.entry_size == self.layout_result.size
+                    && cache.scale_factor == scale_factor
+                {
+                    self.restore_layout_cache(pass);
+                    return 0;
+                }
+            }
+        }
+        let entry_size = self.layout_result.size;
+
         let size = self.layout.size.most_specific(&self.layout_result.size);
 
         let mut inner_size = size.minus_rect(&self.layout.padding.maybe_resolve(&bounds_size));
@@ -1029,7 +1246,7 @@ impl super::node::Node {
             );
         }
 
-        self.resolve_child_sizes(inner_size, font_cache, scale_factor, final_pass);
+        let laid_out = self.resolve_child_sizes(inner_size, font_cache, scale_factor, final_pass);
         let children_size = self.set_children_position(size);
         self.resolve_size(size, children_size);
         self.set_inner_scale(children_size);
@@ -1046,6 +1263,32 @@ impl super::node::Node {
                 &self.layout_result
             );
         }
+
+        self.layout_cache[pass] = Some(LayoutCache {
+            bounds_size,
+            entry_size,
+            scale_factor,
+            result: self.layout_result,
+            inner_scale: self.inner_scale,
+        });
+        if final_pass {
+            self.computed_version = self.layout_version;
+        }
+        laid_out + 1
+    }
+
+    /// Restore the results of the given pass over this subtree. The position of this node is set by its parent.
+    fn restore_layout_cache(&mut self, pass: usize) {
+        if let Some(cache) = self.layout_cache[pass] {
+            self.layout_result.size = cache.result.size;
+            self.inner_scale = cache.inner_scale;
+        }
+        for child in self.children.iter_mut() {
+            child.restore_layout_cache(pass);
+            if let Some(cache) = child.layout_cache[pass] {
+                child.layout_result.position = cache.result.position;
+            }
+        }
     }
 
     pub(crate) fn calculate_layout(
@@ -1061,7 +1304,7 @@ impl super::node::Node {
         };
         self.resolve_layout(self.layout.size, font_cache, scale_factor, false);
         // Layout is resolved twice, the second time to resolve percentages that couldn't have been known without better knowledge of the children
-        self.resolve_layout(self.layout.size, font_cache, scale_factor, true);
+        self.nodes_laid_out = self.resolve_layout(self.layout.size, font_cache, scale_factor, true);
     }
 }
 
@@ -2045,3 +2288,83 @@ macro_rules! rect_pct {
 //         assert_eq!(nodes.children[3].layout_result.position.top, px!(190.0));
 //     }
 // }
+
+#[cfg(test)]
+mod builder_tests {
+    use super::*;
+    use crate::font_cache::FontCache;
+    use crate::widgets::Div;
+    use crate::{node, Node};
+    use cosmic_text::fontdb::Database;
+
+    #[test]
+    fn test_layout_builders() {
+        let layout = Layout::row().with_gap(8.0);
+        assert_eq!(layout.direction, Direction::Row);
+        assert_eq!(layout.gap, 8.0);
+
+        let layout = Layout::column()
+            .with_padding(crate::types::Padding::uniform(4.0))
+            .with_align_items(Alignment::End);
+        assert_eq!(layout.direction, Direction::Column);
+        assert_eq!(layout.padding, rect!(4.0));
+        assert_eq!(layout.cross_alignment, Alignment::End);
+
+        let layout = Layout::centered();
+        assert_eq!(layout.axis_alignment, Alignment::Center);
+        assert_eq!(layout.cross_alignment, Alignment::Center);
+    }
+
+    #[test]
+    fn test_flex_enums() {
+        assert_eq!(AlignItems::default(), AlignItems::Stretch);
+        assert_eq!(JustifyContent::default(), JustifyContent::Start);
+        assert_eq!(FlexDirection::default(), FlexDirection::Row);
+        assert_eq!(FlexWrap::default(), FlexWrap::NoWrap);
+
+        let layout = Layout {
+            wrap: true,
+            axis_alignment: Alignment::End,
+            cross_alignment: Alignment::Stretch,
+            ..Layout::column()
+        };
+        assert_eq!(layout.flex_direction(), FlexDirection::Column);
+        assert_eq!(layout.flex_wrap(), FlexWrap::Wrap);
+        assert_eq!(layout.justify_content(), JustifyContent::End);
+        assert_eq!(layout.align_items(), AlignItems::Stretch);
+    }
+
+    #[test]
+    fn test_gap() {
+        let child = || {
+            node!(
+                Div::new(),
+                Layout {
+                    size: size!(50.0),
+                    ..Default::default()
+                }
+            )
+        };
+        let mut row: Node = node!(
+            Div::new(),
+            Layout {
+                size: size!(Auto),
+                ..Layout::row().with_gap(8.0)
+            }
+        )
+        .push(child())
+        .push(child())
+        .push(child());
+        row.calculate_layout(&mut FontCache::new(Database::new()), 1.0);
+
+        assert_eq!(
+            row.children[1].layout_result.position.left,
+            Dimension::Px(58.0)
+        );
+        assert_eq!(
+            row.children[2].layout_result.position.left,
+            Dimension::Px(116.0)
+        );
+        assert_eq!(row.layout_result.size.width, Dimension::Px(166.0));
+    }
+}
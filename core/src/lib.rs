@@ -1,7 +1,14 @@
+// Allows the derive macros of `mctk_macros`, which refer to `mctk_core`, to be used within this crate.
+extern crate self as mctk_core;
+
+pub mod accessibility;
+pub mod animation;
 pub mod component;
 pub mod context;
 pub mod event;
 pub mod font_cache;
+#[cfg(feature = "debug-inspector")]
+pub mod inspector;
 pub mod instrumenting;
 pub mod pointer;
 pub mod raw_handle;
@@ -49,8 +56,9 @@ pub mod prelude {
     pub use crate::reexports::*;
     pub use crate::style::*;
     pub use crate::widgets::{
-        Button, Carousel, Div, IconButton, IconType, Image, RoundedRect, Slider, Svg, TextBox,
-        TextBoxAction, TextBoxVariant, TransitionPositions,
+        Button, Carousel, Div, IconButton, IconType, Image, Orientation, RoundedRect,
+        ScrollController, Slider, Svg, TabBehavior, TextBox, TextBoxAction, TextBoxController,
+        TextBoxVariant, TransitionPositions,
     };
     pub use crate::*;
 }
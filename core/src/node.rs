@@ -6,6 +6,7 @@ use crate::event::{self, Event, EventInput};
 use crate::font_cache::FontCache;
 use crate::renderables::Renderable;
 use crate::renderer::Caches;
+use crate::style::{self, CursorStyle};
 use crate::types::*;
 use crate::{component::*, widgets};
 // use crate::font_cache::FontCache;
@@ -98,6 +99,15 @@ pub struct Node {
     pub(crate) props_hash: u64,
     pub(crate) render_hash: u64,
     pub(crate) key: u64,
+    /// Incremented when this node, or any of its descendants, changed in a way that can affect layout.
+    pub(crate) layout_version: u64,
+    /// The `layout_version` that the current layout was computed for.
+    pub(crate) computed_version: u64,
+    layout_hash: u64,
+    /// The result of each of the two layout passes, for reuse by clean nodes.
+    pub(crate) layout_cache: [Option<LayoutCache>; 2],
+    /// The number of nodes laid out by the last final layout pass. Only set on the root.
+    nodes_laid_out: usize,
 }
 
 impl fmt::Debug for Node {
@@ -150,9 +160,19 @@ impl Node {
             props_hash: u64::max_value(),
             render_hash: u64::max_value(),
             clip: None,
+            layout_version: 0,
+            computed_version: 0,
+            layout_hash: u64::max_value(),
+            layout_cache: [None, None],
+            nodes_laid_out: 0,
         }
     }
 
+    /// How many nodes were laid out by the last layout of this (root) Node. Nodes whose subtrees haven't changed since the previous layout are skipped.
+    pub fn num_nodes_laid_out_last_pass(&self) -> usize {
+        self.nodes_laid_out
+    }
+
     /// Add a Node to the children of the current one, returns itself. Can be chained.
     pub fn push(mut self, node: Self) -> Self {
         self.children.push(node);
@@ -237,6 +257,8 @@ impl Node {
             }
         }
 
+        self.mark_layout_dirty(prev.as_deref());
+
         // Children's registrations come first, so they can prevent bubbling
         registrations.append(
             &mut self
@@ -248,6 +270,38 @@ impl Node {
         );
     }
 
+    /// Bump the `layout_version` of this node if it, or any of its children, changed since `prev` in a way that could affect its layout. Otherwise carry over the previous layout.
+    fn mark_layout_dirty(&mut self, prev: Option<&Self>) {
+        let mut hasher = ComponentHasher::new_with_keys(0, 0);
+        self.props_hash.hash(&mut hasher);
+        self.component.render_hash(&mut hasher);
+        self.layout_hash = hasher.finish();
+
+        let prev = match prev {
+            Some(prev) => prev,
+            None => {
+                self.layout_version = 1;
+                return;
+            }
+        };
+        let changed = self.layout_hash != prev.layout_hash
+            || self.layout != prev.layout
+            || self.children.len() != prev.children.len()
+            || self
+                .children
+                .iter()
+                .zip(prev.children.iter())
+                .any(|(c, p)| c.key != p.key || c.layout_version != c.computed_version);
+
+        self.computed_version = prev.computed_version;
+        if changed {
+            self.layout_version = prev.layout_version + 1;
+        } else {
+            self.layout_version = prev.layout_version;
+            self.layout_cache = prev.layout_cache;
+        }
+    }
+
     fn set_aabb(
         &mut self,
         parent_pos: Pos,
@@ -298,12 +352,19 @@ impl Node {
             self.inclusive_aabb.set_scale_mut(scale.width, scale.height);
         }
 
-        let mut child_base_pos = self.aabb.pos;
+        // Children of scrollable nodes are placed within their frame, e.g. past any scroll bars or padding
+        let content_aabb = if self.scrollable() {
+            self.component.frame_bounds(self.aabb, self.inner_scale)
+        } else {
+            self.aabb
+        };
+        let mut child_base_pos = content_aabb.pos;
+        let over_scrolled = self.component.over_scrolled();
 
         if let Some(mut x) = self.scroll_x() {
-            let width = self.aabb.width();
+            let width = content_aabb.width();
             let inner_width = self.inner_scale.unwrap().width;
-            if x + width > inner_width {
+            if x + width > inner_width && !over_scrolled {
                 x = inner_width - width;
             }
 
@@ -312,9 +373,9 @@ impl Node {
         }
 
         if let Some(mut y) = self.scroll_y() {
-            let height = self.aabb.height();
+            let height = content_aabb.height();
             let inner_height = self.inner_scale.unwrap().height;
-            if y + height > inner_height {
+            if y + height > inner_height && !over_scrolled {
                 y = inner_height - height;
             }
 
@@ -348,6 +409,39 @@ impl Node {
                 expand_aabb(&mut self.inclusive_aabb, child.inclusive_aabb);
             }
         }
+
+        if self.scroll_y().is_some() {
+            self.stick_children(content_aabb.pos.y);
+        }
+    }
+
+    /// Move [`sticky`][Layout#structfield.sticky] children that were scrolled past `top` back to
+    /// it, unless the next sticky child is pushing them out.
+    fn stick_children(&mut self, top: f32) {
+        let sticky: Vec<usize> = (0..self.children.len())
+            .filter(|i| self.children[*i].layout.sticky)
+            .collect();
+        for (n, i) in sticky.iter().enumerate() {
+            let aabb = self.children[*i].aabb;
+            let mut y = aabb.pos.y.max(top);
+            if let Some(next) = sticky.get(n + 1) {
+                y = y.min(self.children[*next].aabb.pos.y - aabb.height());
+            }
+            if y != aabb.pos.y {
+                self.children[*i].translate_y(y - aabb.pos.y);
+            }
+        }
+    }
+
+    /// Move this node, and all of its descendants, down by `dy`.
+    fn translate_y(&mut self, dy: f32) {
+        self.aabb.pos.y += dy;
+        self.aabb.bottom_right.y += dy;
+        self.inclusive_aabb.pos.y += dy;
+        self.inclusive_aabb.bottom_right.y += dy;
+        for child in self.children.iter_mut() {
+            child.translate_y(dy);
+        }
     }
 
     pub(crate) fn layout(&mut self, _prev: &Self, font_cache: &mut FontCache, scale_factor: f32) {
@@ -370,9 +464,12 @@ impl Node {
     ) -> bool {
         // TODO: skip non-visible nodes
         let mut hasher = ComponentHasher::new_with_keys(0, 0);
+        let container_width = Some(self.aabb.width() / scale_factor);
         if let Some(prev) = prev {
             let mut ret = false;
-            self.component.render_hash(&mut hasher);
+            style::with_container_width(container_width, || {
+                self.component.render_hash(&mut hasher)
+            });
             self.aabb.size().hash(&mut hasher);
             self.inner_scale.hash(&mut hasher);
             self.render_hash = hasher.finish();
@@ -385,7 +482,9 @@ impl Node {
                     prev_state: prev.render_cache.take(),
                     scale_factor,
                 };
-                self.render_cache = self.component.render(context.clone());
+                self.render_cache = style::with_container_width(container_width, || {
+                    self.component.render(context.clone())
+                });
 
                 // println!("render::aabb - {:?}", self.aabb);
                 // if self.scrollable() {
@@ -442,8 +541,10 @@ impl Node {
                 prev_state: None,
                 scale_factor,
             };
-            self.render_cache = self.component.render(context);
-            self.component.render_hash(&mut hasher);
+            style::with_container_width(container_width, || {
+                self.render_cache = self.component.render(context);
+                self.component.render_hash(&mut hasher);
+            });
             self.render_hash = hasher.finish();
 
             for child in self.children.iter_mut() {
@@ -466,6 +567,54 @@ impl Node {
         self.scroll_x().is_some() || self.scroll_y().is_some()
     }
 
+    /// The Node with the id `id`, if it is this one or one of its descendants.
+    pub(crate) fn find(&self, id: u64) -> Option<&Node> {
+        if self.id == id {
+            return Some(self);
+        }
+        self.children.iter().find_map(|c| c.find(id))
+    }
+
+    /// The id of the Node that traps the focus, if any. The last one in the tree wins.
+    pub(crate) fn focus_trap(&self) -> Option<u64> {
+        self.children
+            .iter()
+            .rev()
+            .find_map(|c| c.focus_trap())
+            .or_else(|| self.component.traps_focus().then_some(self.id))
+    }
+
+    /// The ids of the focusable Nodes in this tree, in order.
+    pub(crate) fn focusable(&self) -> Vec<u64> {
+        let mut ids = vec![];
+        self._focusable(&mut ids);
+        ids
+    }
+
+    /// The focusable Node that Tab moves to from the Node `current` in this tree, or Shift+Tab
+    /// when `backwards`. Wraps around at either end, and starts from the first (or last) when
+    /// `current` isn't focusable here.
+    pub(crate) fn next_focus(&self, current: u64, backwards: bool) -> Option<u64> {
+        let ids = self.focusable();
+        let n = ids.len();
+        let next = match ids.iter().position(|id| *id == current) {
+            Some(i) if backwards => (i + n - 1) % n,
+            Some(i) => (i + 1) % n,
+            None if backwards => n.checked_sub(1)?,
+            None => 0,
+        };
+        ids.get(next).copied()
+    }
+
+    fn _focusable(&self, ids: &mut Vec<u64>) {
+        if self.component.focusable() {
+            ids.push(self.id);
+        }
+        for child in self.children.iter() {
+            child._focusable(ids);
+        }
+    }
+
     pub(crate) fn iter_renderables(&self) -> NodeRenderableIterator<'_> {
         NodeRenderableIterator {
             queue: vec![self],
@@ -684,6 +833,29 @@ impl Node {
         }
     }
 
+    /// The cursor to show over the Node `target`: its own, or that of its nearest ancestor that
+    /// has one.
+    pub(crate) fn cursor_at(&self, target: u64) -> Option<CursorStyle> {
+        let stack = self.get_target_stack(target)?;
+        let mut current = self;
+        let mut cursor = current.component.cursor();
+        for i in stack {
+            current = &current.children[i];
+            cursor = current.component.cursor().or(cursor);
+        }
+        cursor
+    }
+
+    #[cfg(feature = "debug-inspector")]
+    pub(crate) fn inspect_at(&self, target: u64) -> Option<crate::inspector::Inspected> {
+        let stack = self.get_target_stack(target)?;
+        let node = stack.into_iter().fold(self, |node, i| &node.children[i]);
+        Some(crate::inspector::Inspected {
+            name: crate::inspector::short_name(node.component.type_name()),
+            aabb: node.aabb,
+        })
+    }
+
     fn handle_targeted_event<E: EventInput>(
         &mut self,
         event: &mut Event<E>,
@@ -862,6 +1034,14 @@ impl Node {
         self.handle_targeted_event(event, |node, e| node.component.on_text_entry(e));
     }
 
+    pub(crate) fn ime_compose(&mut self, event: &mut Event<event::ImeCompose>) {
+        self.handle_targeted_event(event, |node, e| node.component.on_ime_compose(e));
+    }
+
+    pub(crate) fn ime_commit(&mut self, event: &mut Event<event::ImeCommit>) {
+        self.handle_targeted_event(event, |node, e| node.component.on_ime_commit(e));
+    }
+
     pub(crate) fn drag(&mut self, event: &mut Event<event::Drag>) {
         self.handle_targeted_event(event, |node, e| node.component.on_drag(e));
     }
@@ -1552,3 +1732,204 @@ impl<'a> Iterator for NodeRenderableIterator<'a> {
 //         assert_eq!(registrations[2].0, event::Register::KeyDown);
 //     }
 // }
+
+#[cfg(test)]
+mod layout_cache_tests {
+    use super::*;
+    use cosmic_text::fontdb::Database;
+
+    #[derive(Debug)]
+    struct Group;
+    impl Component for Group {}
+
+    #[derive(Debug)]
+    struct Label(String);
+    impl Component for Label {
+        fn props_hash(&self, hasher: &mut ComponentHasher) {
+            self.0.hash(hasher);
+        }
+
+        fn fill_bounds(
+            &mut self,
+            _width: Option<f32>,
+            _height: Option<f32>,
+            _max_width: Option<f32>,
+            _max_height: Option<f32>,
+            _font_cache: &mut FontCache,
+            _scale_factor: f32,
+        ) -> (Option<f32>, Option<f32>) {
+            (Some(self.0.len() as f32 * 10.0), Some(20.0))
+        }
+    }
+
+    /// A tree of 100 nodes: a root, with 9 rows of 10 labels.
+    fn tree(first_label: &str) -> Node {
+        let mut root = node!(Group, [direction: Direction::Column]);
+        for i in 0..9 {
+            let mut row = node!(Group, [direction: Direction::Row], i);
+            for j in 0..10 {
+                let label = if i == 0 && j == 0 { first_label } else { "abc" };
+                row = row.push(node!(Label(label.into()), Layout::default(), j));
+            }
+            root = root.push(row);
+        }
+        root
+    }
+
+    #[test]
+    fn test_unchanged_subtrees_are_not_laid_out() {
+        let mut font_cache = FontCache::new(Database::new());
+        let mut registrations = vec![];
+
+        let mut first = tree("abc");
+        first.view(None, &mut registrations);
+        first.calculate_layout(&mut font_cache, 1.0);
+        assert_eq!(first.num_nodes_laid_out_last_pass(), 100);
+
+        let mut unchanged = tree("abc");
+        unchanged.view(Some(&mut first), &mut registrations);
+        unchanged.calculate_layout(&mut font_cache, 1.0);
+        assert_eq!(unchanged.num_nodes_laid_out_last_pass(), 0);
+        assert_eq!(
+            unchanged.children[8].children[9].layout_result.position,
+            first.children[8].children[9].layout_result.position
+        );
+
+        let mut changed = tree("xyz");
+        changed.view(Some(&mut unchanged), &mut registrations);
+        changed.calculate_layout(&mut font_cache, 1.0);
+        let laid_out = changed.num_nodes_laid_out_last_pass();
+        assert!(laid_out > 0 && laid_out < 20, "laid out {} nodes", laid_out);
+    }
+}
+
+#[cfg(test)]
+mod sticky_tests {
+    use super::*;
+    use cosmic_text::fontdb::Database;
+
+    #[derive(Debug)]
+    struct Scroller(f32);
+    impl Component for Scroller {
+        fn scroll_position(&self) -> Option<ScrollPosition> {
+            Some(ScrollPosition {
+                x: None,
+                y: Some(self.0),
+            })
+        }
+    }
+
+    #[derive(Debug)]
+    struct Group;
+    impl Component for Group {}
+
+    /// A 100px tall scroller with two sections, each a 20px sticky header over 100px of items.
+    fn laid_out(scroll_y: f32) -> Node {
+        let mut root = node!(
+            Scroller(scroll_y),
+            [size: [100, 100], direction: Direction::Column]
+        );
+        for i in 0..2 {
+            root = root
+                .push(node!(Group, [size: [100, 20], sticky: true], 2 * i))
+                .push(node!(Group, [size: [100, 100]], 2 * i + 1));
+        }
+
+        let mut font_cache = FontCache::new(Database::new());
+        root.view(None, &mut vec![]);
+        root.calculate_layout(&mut font_cache, 1.0);
+        let aabb = root.aabb;
+        root.set_aabb(
+            Pos::default(),
+            aabb,
+            ScrollPosition::default(),
+            false,
+            AABB::from(root.layout_result),
+            1.0,
+        );
+        root
+    }
+
+    #[test]
+    fn test_sticky_children() {
+        let root = laid_out(0.0);
+        let ys = |root: &Node| {
+            root.children
+                .iter()
+                .map(|c| c.aabb.pos.y)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ys(&root), vec![0.0, 20.0, 120.0, 140.0]);
+
+        // The first header sticks, while its items scroll up
+        let root = laid_out(50.0);
+        assert_eq!(ys(&root), vec![0.0, -30.0, 70.0, 90.0]);
+
+        // The second header pushes the first out, then sticks in its place
+        let root = laid_out(110.0);
+        assert_eq!(ys(&root)[..3], [-10.0, -90.0, 10.0]);
+        let root = laid_out(130.0);
+        assert_eq!(ys(&root)[..3], [-30.0, -110.0, 0.0]);
+    }
+}
+
+#[cfg(test)]
+mod focus_tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Group {
+        traps_focus: bool,
+    }
+    impl Component for Group {
+        fn traps_focus(&self) -> bool {
+            self.traps_focus
+        }
+    }
+
+    #[derive(Debug)]
+    struct Field;
+    impl Component for Field {
+        fn focusable(&self) -> bool {
+            true
+        }
+    }
+
+    fn group(traps_focus: bool, key: u64) -> Node {
+        node!(Group { traps_focus }, Layout::default(), key)
+    }
+
+    /// A root with a field, and a trap with two fields, one of them nested in a group.
+    fn tree() -> Node {
+        let mut root = group(false, 0)
+            .push(node!(Field, Layout::default(), 0))
+            .push(
+                group(true, 1)
+                    .push(node!(Field, Layout::default(), 0))
+                    .push(group(false, 1).push(node!(Field, Layout::default(), 0))),
+            );
+        root.view(None, &mut vec![]);
+        root
+    }
+
+    #[test]
+    fn test_focus_trap() {
+        let root = tree();
+        let trap = &root.children[1];
+        assert_eq!(root.focus_trap(), Some(trap.id));
+        assert!(root.find(trap.children[1].children[0].id).is_some());
+
+        let fields = [trap.children[0].id, trap.children[1].children[0].id];
+        assert_eq!(trap.focusable(), fields.to_vec());
+        assert_eq!(trap.next_focus(fields[0], false), Some(fields[1]));
+        // Wraps around at both ends
+        assert_eq!(trap.next_focus(fields[1], false), Some(fields[0]));
+        assert_eq!(trap.next_focus(fields[0], true), Some(fields[1]));
+
+        // From outside the trap, the focus moves to its first or last field
+        let outside = root.children[0].id;
+        assert_eq!(trap.next_focus(outside, false), Some(fields[0]));
+        assert_eq!(trap.next_focus(outside, true), Some(fields[1]));
+        assert_eq!(group(false, 0).next_focus(outside, true), None);
+    }
+}
@@ -0,0 +1,288 @@
+use crate::{Color, Point, Pos, Scale, AABB};
+
+use super::types::{Canvas, FillStyle, StrokeStyle};
+use derive_builder::Builder;
+use femtovg::{Path, Solidity};
+
+#[derive(Clone, Default, Debug, PartialEq, Builder)]
+pub struct Instance {
+    pub center: Point,
+    pub radius: f32,
+    /// Where the arc starts, in degrees clockwise from the right of the center.
+    pub start_angle_deg: f32,
+    /// How far the arc goes around, in degrees. Positive values go clockwise, and `360.0` or
+    /// more is a full circle.
+    pub sweep_deg: f32,
+    #[builder(default = "1.")]
+    pub stroke_width: f32,
+    /// The color of the stroke.
+    #[builder(default = "Color::BLACK")]
+    pub color: Color,
+    /// When set, the arc is closed back to the center and filled, making a pie sector.
+    #[builder(default = "None")]
+    pub fill: Option<Color>,
+}
+
+/// A part of the outline of a circle, or a pie sector when filled.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Arc {
+    pub instance_data: Instance,
+}
+
+impl Arc {
+    /// An unfilled arc, stroked 1px wide with `color`.
+    pub fn new(
+        center: Point,
+        radius: f32,
+        start_angle_deg: f32,
+        sweep_deg: f32,
+        color: Color,
+    ) -> Self {
+        Self {
+            instance_data: Instance {
+                center,
+                radius,
+                start_angle_deg,
+                sweep_deg,
+                stroke_width: 1.,
+                color,
+                fill: None,
+            },
+        }
+    }
+
+    /// A pie sector filled with `fill`, without a stroke.
+    pub fn sector(
+        center: Point,
+        radius: f32,
+        start_angle_deg: f32,
+        sweep_deg: f32,
+        fill: Color,
+    ) -> Self {
+        let mut arc = Self::new(
+            center,
+            radius,
+            start_angle_deg,
+            sweep_deg,
+            Color::TRANSPARENT,
+        );
+        arc.instance_data.stroke_width = 0.;
+        arc.instance_data.fill = Some(fill);
+        arc
+    }
+
+    pub fn from_instance_data(instance_data: Instance) -> Self {
+        Self { instance_data }
+    }
+
+    /// Whether the arc goes all the way around, drawing the same as a [`Circle`](super::Circle).
+    pub fn is_full(&self) -> bool {
+        self.instance_data.sweep_deg.abs() >= 360.0
+    }
+
+    /// The point on the circle at `angle_deg`.
+    pub fn point_at(&self, angle_deg: f32) -> Point {
+        let Instance { center, radius, .. } = self.instance_data;
+        let (sin, cos) = angle_deg.to_radians().sin_cos();
+        Point::new(center.x + radius * cos, center.y + radius * sin)
+    }
+
+    /// The start and end angles, with the end at or after the start.
+    fn angle_range(&self) -> (f32, f32) {
+        let Instance {
+            start_angle_deg,
+            sweep_deg,
+            ..
+        } = self.instance_data;
+        if sweep_deg < 0.0 {
+            (start_angle_deg + sweep_deg, start_angle_deg)
+        } else {
+            (start_angle_deg, start_angle_deg + sweep_deg)
+        }
+    }
+
+    /// The bounds of everything that is drawn, including the stroke.
+    pub fn bounding_box(&self) -> AABB {
+        let Instance {
+            center,
+            radius,
+            stroke_width,
+            fill,
+            ..
+        } = self.instance_data;
+        let mut points = vec![];
+        if self.is_full() {
+            points.push(Point::new(center.x - radius, center.y - radius));
+            points.push(Point::new(center.x + radius, center.y + radius));
+        } else {
+            let (start, end) = self.angle_range();
+            points.push(self.point_at(start));
+            points.push(self.point_at(end));
+            // The arc reaches as far out as the circle wherever it passes one of the axes
+            for axis in [0.0, 90.0, 180.0, 270.0] {
+                if (axis - start).rem_euclid(360.0) <= end - start {
+                    points.push(self.point_at(axis));
+                }
+            }
+            if fill.is_some() {
+                points.push(center);
+            }
+        }
+
+        let half_stroke = if self.stroke_style().is_some() {
+            stroke_width / 2.0
+        } else {
+            0.0
+        };
+        let mut min = Point::new(f32::INFINITY, f32::INFINITY);
+        let mut max = Point::new(f32::NEG_INFINITY, f32::NEG_INFINITY);
+        for p in points {
+            min = Point::new(min.x.min(p.x), min.y.min(p.y));
+            max = Point::new(max.x.max(p.x), max.y.max(p.y));
+        }
+        AABB::new(
+            Pos::new(min.x - half_stroke, min.y - half_stroke, 0.0),
+            Scale::new(
+                max.x - min.x + half_stroke * 2.0,
+                max.y - min.y + half_stroke * 2.0,
+            ),
+        )
+    }
+
+    /// The fill of the sector, if any.
+    pub fn fill_style(&self) -> Option<FillStyle> {
+        self.instance_data.fill.map(FillStyle::new)
+    }
+
+    /// The stroke of the arc, if any.
+    pub fn stroke_style(&self) -> Option<StrokeStyle> {
+        let Instance {
+            color,
+            stroke_width,
+            ..
+        } = self.instance_data;
+        (stroke_width > 0.0 && color.a > 0.0).then(|| StrokeStyle::new(color, stroke_width))
+    }
+
+    fn path(&self) -> Path {
+        let Instance {
+            center,
+            radius,
+            fill,
+            ..
+        } = self.instance_data;
+        let mut path = Path::new();
+        if self.is_full() {
+            path.circle(center.x, center.y, radius);
+            return path;
+        }
+
+        let (start, end) = self.angle_range();
+        let from = self.point_at(start);
+        if fill.is_some() {
+            path.move_to(center.x, center.y);
+            path.line_to(from.x, from.y);
+        } else {
+            path.move_to(from.x, from.y);
+        }
+        // `Hole` draws with increasing angles, which are clockwise on screen
+        path.arc(
+            center.x,
+            center.y,
+            radius,
+            start.to_radians(),
+            end.to_radians(),
+            Solidity::Hole,
+        );
+        if fill.is_some() {
+            path.close();
+        }
+        path
+    }
+
+    pub fn render(&self, canvas: &mut Canvas) {
+        if self.instance_data.radius <= 0.0 || self.instance_data.sweep_deg == 0.0 {
+            return;
+        }
+        let path = self.path();
+        if let Some(fill) = self.fill_style() {
+            canvas.fill_path(&path, &fill.paint());
+        }
+        if let Some(stroke) = self.stroke_style() {
+            canvas.stroke_path(&path, &stroke.paint());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderables::Circle;
+
+    fn assert_bounds(arc: &Arc, pos: (f32, f32), size: (f32, f32)) {
+        let bounds = arc.bounding_box();
+        let near = |a: f32, b: f32| (a - b).abs() < 0.001;
+        assert!(
+            near(bounds.pos.x, pos.0)
+                && near(bounds.pos.y, pos.1)
+                && near(bounds.width(), size.0)
+                && near(bounds.height(), size.1),
+            "{bounds:?} != {pos:?} {size:?}"
+        );
+    }
+
+    #[test]
+    fn test_quarter() {
+        let center = Point::new(50.0, 50.0);
+        // From the right of the center, clockwise down to the bottom
+        let mut arc = Arc::new(center, 10.0, 0.0, 90.0, Color::BLACK);
+        arc.instance_data.stroke_width = 0.0;
+        assert_bounds(&arc, (50.0, 50.0), (10.0, 10.0));
+
+        // The same quarter, swept counter-clockwise from the bottom
+        arc.instance_data.start_angle_deg = 90.0;
+        arc.instance_data.sweep_deg = -90.0;
+        assert_bounds(&arc, (50.0, 50.0), (10.0, 10.0));
+
+        // Centered on an axis, the arc bulges out to the full radius
+        let arc = Arc::new(center, 10.0, -45.0, 90.0, Color::BLACK);
+        let inner = 10.0 * std::f32::consts::FRAC_1_SQRT_2;
+        assert_bounds(
+            &arc,
+            (50.0 + inner - 0.5, 50.0 - inner - 0.5),
+            (10.0 - inner + 1.0, inner * 2.0 + 1.0),
+        );
+
+        // A sector also covers the center
+        let sector = Arc::sector(center, 10.0, -45.0, 90.0, Color::RED);
+        assert_bounds(&sector, (50.0, 50.0 - inner), (10.0, inner * 2.0));
+    }
+
+    #[test]
+    fn test_half() {
+        let center = Point::new(0.0, 0.0);
+        let mut arc = Arc::new(center, 20.0, 180.0, 180.0, Color::BLACK);
+        arc.instance_data.stroke_width = 2.0;
+        // The top half
+        assert_bounds(&arc, (-21.0, -21.0), (42.0, 22.0));
+
+        // Wrapping past 360 degrees
+        let arc = Arc::sector(center, 20.0, 270.0, 180.0, Color::RED);
+        assert_bounds(&arc, (0.0, -20.0), (20.0, 40.0));
+    }
+
+    #[test]
+    fn test_full() {
+        let center = Point::new(30.0, 40.0);
+        let arc = Arc::sector(center, 10.0, 33.0, 360.0, Color::RED);
+        assert!(arc.is_full());
+        assert_bounds(&arc, (20.0, 30.0), (20.0, 20.0));
+        // The same as a circle
+        let circle = Circle::new(Pos::new(30.0, 40.0, 0.0), 10.0);
+        assert_eq!(arc.bounding_box(), circle.bounding_box());
+
+        let arc = Arc::new(center, 10.0, 0.0, -720.0, Color::BLACK);
+        assert!(arc.is_full());
+        assert_bounds(&arc, (19.5, 29.5), (21.0, 21.0));
+    }
+}
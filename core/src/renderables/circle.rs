@@ -1,10 +1,23 @@
-use crate::{Color, Pos};
+use crate::style::BoxShadow;
+use crate::{Color, Pos, Scale, AABB};
 
 use super::types;
-use super::types::Canvas;
+use super::types::{Canvas, FillStyle, StrokeStyle};
 use derive_builder::Builder;
 use femtovg::{ImageId, Paint, Path};
 
+/// Where the border of a [`Circle`] is drawn, relative to its radius.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub enum BorderPosition {
+    /// Centered on the radius.
+    #[default]
+    Center,
+    /// Within the radius.
+    Inside,
+    /// Outside of the radius, expanding the bounds of the circle.
+    Outside,
+}
+
 #[derive(Clone, Copy, Default, Debug, PartialEq, Builder)]
 pub struct Instance {
     pub origin: Pos,
@@ -15,8 +28,13 @@ pub struct Instance {
     pub border_color: Option<Color>,
     #[builder(default = "1.")]
     pub border_width: f32,
+    #[builder(default)]
+    pub border_position: BorderPosition,
     #[builder(default = "None")]
     pub bg_image: Option<ImageId>,
+    /// Drawn behind the circle.
+    #[builder(default = "None")]
+    pub shadow: Option<BoxShadow>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -34,23 +52,134 @@ impl Circle {
                 bg_image: None,
                 border_color: None,
                 border_width: 1.,
+                border_position: BorderPosition::Center,
+                shadow: None,
             },
         }
     }
 
+    /// A circle with a border and no fill.
+    pub fn outlined(origin: Pos, radius: f32, border_width: f32, border_color: Color) -> Self {
+        let mut circle = Self::new(origin, radius);
+        circle.instance_data.border_width = border_width;
+        circle.instance_data.border_color = Some(border_color);
+        circle
+    }
+
+    /// A filled circle with a border.
+    pub fn filled_with_border(
+        origin: Pos,
+        radius: f32,
+        color: Color,
+        border_width: f32,
+        border_color: Color,
+    ) -> Self {
+        let mut circle = Self::outlined(origin, radius, border_width, border_color);
+        circle.instance_data.color = Some(color);
+        circle
+    }
+
+    fn has_border(&self) -> bool {
+        self.stroke_style().is_some()
+    }
+
+    /// The fill inside the radius, if any.
+    pub fn fill_style(&self) -> Option<FillStyle> {
+        self.instance_data.color.map(FillStyle::new)
+    }
+
+    /// The stroke of the border, if any.
+    pub fn stroke_style(&self) -> Option<StrokeStyle> {
+        let Instance {
+            border_color,
+            border_width,
+            ..
+        } = self.instance_data;
+        border_color
+            .filter(|_| border_width > 0.0)
+            .map(|color| StrokeStyle::new(color, border_width))
+    }
+
+    /// The radius that the border is stroked along.
+    fn stroke_radius(&self) -> f32 {
+        let Instance {
+            radius,
+            border_width,
+            border_position,
+            ..
+        } = self.instance_data;
+        match border_position {
+            BorderPosition::Center => radius,
+            BorderPosition::Inside => (radius - border_width / 2.0).max(0.0),
+            BorderPosition::Outside => radius + border_width / 2.0,
+        }
+    }
+
+    /// The bounds of everything that is drawn, including the border.
+    pub fn bounding_box(&self) -> AABB {
+        let Instance { origin, radius, .. } = self.instance_data;
+        let extent = if self.has_border() {
+            radius.max(self.stroke_radius() + self.instance_data.border_width / 2.0)
+        } else {
+            radius
+        };
+        AABB::new(
+            Pos::new(origin.x - extent, origin.y - extent, origin.z),
+            Scale::new(extent * 2.0, extent * 2.0),
+        )
+    }
+
     pub fn from_instance_data(instance_data: Instance) -> Self {
         Self { instance_data }
     }
 
-    pub fn render(&self, canvas: &mut Canvas) {
+    /// The center and radius of the circle that casts the shadow: moved by the shadow's offset
+    /// and grown by its spread. `None` without a visible shadow.
+    pub fn shadow_circle(&self) -> Option<(Pos, f32)> {
         let Instance {
             origin,
             radius,
-            color,
-            bg_image,
-            border_color,
-            border_width,
+            shadow,
+            ..
         } = self.instance_data;
+        let shadow = shadow.filter(|s| s.is_visible())?;
+        Some((
+            Pos::new(
+                origin.x + shadow.offset_x,
+                origin.y + shadow.offset_y,
+                origin.z,
+            ),
+            (radius + shadow.spread).max(0.0),
+        ))
+    }
+
+    fn render_shadow(&self, canvas: &mut Canvas) {
+        let (center, radius, shadow) = match (self.shadow_circle(), self.instance_data.shadow) {
+            (Some((center, radius)), Some(shadow)) => (center, radius, shadow),
+            _ => return,
+        };
+        let blur = shadow.blur_radius.max(0.0);
+        let mut path = Path::new();
+        path.circle(center.x, center.y, radius + blur);
+        let paint = if blur == 0.0 {
+            Paint::color(shadow.color.into())
+        } else {
+            // Fades out from `blur` inside the edge of the circle to `blur` outside of it
+            Paint::radial_gradient(
+                center.x,
+                center.y,
+                (radius - blur).max(0.0),
+                radius + blur,
+                shadow.color.into(),
+                Color::TRANSPARENT.into(),
+            )
+        };
+        canvas.fill_path(&path, &paint);
+    }
+
+    pub fn render(&self, canvas: &mut Canvas) {
+        let Instance { origin, radius, .. } = self.instance_data;
+        self.render_shadow(canvas);
         let mut path = Path::new();
         path.circle(origin.x, origin.y, radius);
         // //Add background image
@@ -58,14 +187,89 @@ impl Circle {
         //     Some(image_id) => Paint::image(image_id, origin.x, origin.y, radius, radius, 0.0, 1.0),
         //     None => Paint::color(color.into()),
         // };
-        if let Some(color) = color {
-            canvas.fill_path(&path, &Paint::color(color.into()));
+        if let Some(fill) = self.fill_style() {
+            canvas.fill_path(&path, &fill.paint());
         }
 
-        if let Some(color) = border_color {
-            let mut stroke = Paint::color(color.into());
-            stroke.set_line_width(border_width);
-            canvas.stroke_path(&path, &stroke);
+        if let Some(stroke) = self.stroke_style() {
+            let mut path = Path::new();
+            path.circle(origin.x, origin.y, self.stroke_radius());
+            canvas.stroke_path(&path, &stroke.paint());
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point;
+
+    /// The inner and outer radius covered by the border.
+    fn border_band(circle: &Circle) -> (f32, f32) {
+        let half = circle.instance_data.border_width / 2.0;
+        (circle.stroke_radius() - half, circle.stroke_radius() + half)
+    }
+
+    #[test]
+    fn test_border_position() {
+        let origin = Pos::new(50.0, 50.0, 0.0);
+        let mut circle = Circle::filled_with_border(origin, 20.0, Color::RED, 5.0, Color::BLACK);
+        assert_eq!(border_band(&circle), (17.5, 22.5));
+
+        circle.instance_data.border_position = BorderPosition::Inside;
+        assert_eq!(border_band(&circle), (15.0, 20.0));
+        assert_eq!(circle.bounding_box().width(), 40.0);
+
+        circle.instance_data.border_position = BorderPosition::Outside;
+        // The fill reaches the radius, and the border covers from there to radius + border_width
+        assert_eq!(border_band(&circle), (20.0, 25.0));
+        let bounds = circle.bounding_box();
+        assert_eq!(bounds.pos, Pos::new(25.0, 25.0, 0.0));
+        assert_eq!(bounds.bottom_right, Point::new(75.0, 75.0));
+    }
+
+    #[test]
+    fn test_outlined() {
+        let circle = Circle::outlined(Pos::default(), 10.0, 2.0, Color::BLACK);
+        assert_eq!(circle.instance_data.color, None);
+        assert_eq!(circle.bounding_box().width(), 22.0);
+        assert_eq!(
+            Circle::new(Pos::default(), 10.0).bounding_box().width(),
+            20.0
+        );
+    }
+
+    #[test]
+    fn test_shadow_circle() {
+        let mut circle = Circle::new(Pos::new(50.0, 50.0, 0.0), 20.0);
+        assert_eq!(circle.shadow_circle(), None);
+
+        circle.instance_data.shadow = Some(BoxShadow::new(2.0, 4.0, 6.0, 3.0, Color::BLACK));
+        assert_eq!(
+            circle.shadow_circle(),
+            Some((Pos::new(52.0, 54.0, 0.0), 23.0))
+        );
+
+        circle.instance_data.shadow = Some(BoxShadow::new(0.0, 0.0, 6.0, -30.0, Color::BLACK));
+        assert_eq!(circle.shadow_circle().unwrap().1, 0.0);
+        circle.instance_data.shadow = Some(BoxShadow::NONE);
+        assert_eq!(circle.shadow_circle(), None);
+    }
+
+    #[test]
+    fn test_styles() {
+        let circle = Circle::filled_with_border(Pos::default(), 10.0, Color::RED, 2.0, Color::BLUE);
+        assert_eq!(circle.fill_style(), Some(FillStyle::new(Color::RED)));
+        assert_eq!(
+            circle.stroke_style(),
+            Some(StrokeStyle::new(Color::BLUE, 2.0))
+        );
+
+        let mut circle = Circle::new(Pos::default(), 10.0);
+        assert_eq!(circle.fill_style(), None);
+        assert_eq!(circle.stroke_style(), None);
+        circle.instance_data.border_color = Some(Color::BLUE);
+        circle.instance_data.border_width = 0.0;
+        assert_eq!(circle.stroke_style(), None);
+    }
+}
@@ -1,9 +1,11 @@
+use std::fmt;
+
 use crate::{Color, Point, Pos};
 
 use super::types;
-use super::types::Canvas;
+use super::types::{Canvas, FillStyle, StrokeStyle};
 use derive_builder::Builder;
-use femtovg::{LineCap, LineJoin, Paint, Path};
+use femtovg::Path;
 
 #[derive(Clone, Default, Debug, PartialEq, Builder)]
 pub struct Instance {
@@ -18,6 +20,36 @@ pub struct Instance {
     pub anchor_color: Color,
 }
 
+/// Why a string could not be parsed by [`Curve::from_svg_path_string`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SvgPathParseError {
+    /// The path did not start with a `M` command.
+    MissingMoveTo,
+    /// A command outside of the supported absolute `M`, `L`, `C`, `Q`, `Z` subset.
+    UnsupportedCommand(char),
+    /// A token that was expected to be a number.
+    InvalidNumber(String),
+    /// The given command was not followed by a whole number of coordinate groups.
+    MissingCoordinates(char),
+}
+
+impl fmt::Display for SvgPathParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SvgPathParseError::MissingMoveTo => write!(f, "path data must start with `M`"),
+            SvgPathParseError::UnsupportedCommand(c) => {
+                write!(f, "unsupported path command `{}`", c)
+            }
+            SvgPathParseError::InvalidNumber(t) => write!(f, "invalid number `{}`", t),
+            SvgPathParseError::MissingCoordinates(c) => {
+                write!(f, "missing coordinates for path command `{}`", c)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SvgPathParseError {}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Curve {
     pub instance_data: Instance,
@@ -40,42 +72,191 @@ impl Curve {
         Self { instance_data }
     }
 
+    /// SVG path data (a `d` attribute) that draws the same curve as [`#render`][Curve#method.render].
+    ///
+    /// Coordinates are rounded to 3 decimal places.
+    pub fn to_svg_path_string(&self) -> String {
+        let mut d = String::new();
+        for (i, p) in self.instance_data.anchors.iter().enumerate() {
+            let (x, y) = (svg_coord(p.x), svg_coord(p.y));
+            if i == 0 {
+                d.push_str(&format!("M {} {}", x, y));
+            } else {
+                d.push_str(&format!(" C {x} {y} {x} {y} {x} {y}", x = x, y = y));
+            }
+        }
+        d
+    }
+
+    /// Parse the absolute `M`, `L`, `C`, `Q` and `Z` commands of SVG path data, as produced by [`#to_svg_path_string`][Curve#method.to_svg_path_string].
+    ///
+    /// The end point of each command becomes an anchor of the curve. `Z` closes the curve by returning to its first anchor.
+    pub fn from_svg_path_string(s: &str) -> Result<Curve, SvgPathParseError> {
+        let mut spaced = String::with_capacity(s.len());
+        for c in s.chars() {
+            if c.is_ascii_alphabetic() && c != 'e' && c != 'E' {
+                spaced.push(' ');
+                spaced.push(c);
+                spaced.push(' ');
+            } else if c == ',' {
+                spaced.push(' ');
+            } else {
+                spaced.push(c);
+            }
+        }
+
+        let mut anchors: Vec<Point> = vec![];
+        let mut command: Option<char> = None;
+        let mut numbers: Vec<f32> = vec![];
+        let mut tokens = spaced.split_whitespace();
+        loop {
+            let token = tokens.next();
+            let is_command = token.map_or(true, |t| t.chars().all(|c| c.is_ascii_alphabetic()));
+            if !is_command {
+                let t = token.unwrap();
+                numbers.push(
+                    t.parse::<f32>()
+                        .map_err(|_| SvgPathParseError::InvalidNumber(t.to_string()))?,
+                );
+                continue;
+            }
+
+            // Finish the previous command
+            if let Some(c) = command {
+                let group = match c {
+                    'M' | 'L' => 2,
+                    'Q' => 4,
+                    'C' => 6,
+                    _ => 0,
+                };
+                if group > 0 {
+                    if numbers.is_empty() || numbers.len() % group != 0 {
+                        return Err(SvgPathParseError::MissingCoordinates(c));
+                    }
+                    for g in numbers.chunks(group) {
+                        anchors.push(Point::new(g[group - 2], g[group - 1]));
+                    }
+                } else if !numbers.is_empty() {
+                    return Err(SvgPathParseError::InvalidNumber(format!("{}", numbers[0])));
+                } else if let Some(first) = anchors.first() {
+                    anchors.push(*first);
+                }
+                numbers.clear();
+            } else if !numbers.is_empty() {
+                return Err(SvgPathParseError::MissingMoveTo);
+            }
+
+            match token {
+                None => break,
+                Some(t) => {
+                    let mut chars = t.chars();
+                    let c = chars.next().unwrap();
+                    if chars.next().is_some() || !matches!(c, 'M' | 'L' | 'C' | 'Q' | 'Z') {
+                        return Err(SvgPathParseError::UnsupportedCommand(c));
+                    }
+                    if command.is_none() && c != 'M' {
+                        return Err(SvgPathParseError::MissingMoveTo);
+                    }
+                    command = Some(c);
+                }
+            }
+        }
+
+        if command.is_none() {
+            return Err(SvgPathParseError::MissingMoveTo);
+        }
+        Ok(Curve::new(anchors))
+    }
+
+    /// The stroke of the curve itself.
+    pub fn stroke_style(&self) -> StrokeStyle {
+        StrokeStyle::new(self.instance_data.color, self.instance_data.width)
+    }
+
+    /// The fill of the dot drawn at each anchor.
+    pub fn anchor_fill_style(&self) -> FillStyle {
+        FillStyle::new(self.instance_data.anchor_color)
+    }
+
     pub fn render(&self, canvas: &mut Canvas) {
-        let anchors = self.instance_data.anchors.clone();
-        let Instance {
-            color,
-            width,
-            anchor_width,
-            anchor_color,
-            ..
-        } = self.instance_data;
+        let anchors = &self.instance_data.anchors;
+        let anchor_width = self.instance_data.anchor_width;
 
         if anchors.len() <= 1 {
             return;
         }
 
         //draw anchors
-        for anchor in anchors.clone() {
+        let anchor_paint = self.anchor_fill_style().paint();
+        for anchor in anchors {
             let mut path = Path::new();
             path.circle(anchor.x, anchor.y, anchor_width);
-            canvas.fill_path(&path, &Paint::color(anchor_color.into()));
+            canvas.fill_path(&path, &anchor_paint);
         }
 
         //draw curve
         let mut path = Path::new();
         path.move_to(anchors[0].x, anchors[0].y);
-        let mut line = Paint::color(color.into());
-        for i in 1..anchors.len() {
-            line.set_line_width(width);
-            path.bezier_to(
-                anchors[i].x,
-                anchors[i].y,
-                anchors[i].x,
-                anchors[i].y,
-                anchors[i].x,
-                anchors[i].y,
-            );
+        for anchor in anchors.iter().skip(1) {
+            path.bezier_to(anchor.x, anchor.y, anchor.x, anchor.y, anchor.x, anchor.y);
         }
-        canvas.stroke_path(&path, &line);
+        canvas.stroke_path(&path, &self.stroke_style().paint());
+    }
+}
+
+fn svg_coord(v: f32) -> f32 {
+    (v * 1000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_svg_path_round_trip() {
+        let curve = Curve::new(vec![
+            Point::new(0.0, 0.0),
+            Point::new(10.5, -20.25),
+            Point::new(1.0 / 3.0, 100.0),
+        ]);
+        let d = curve.to_svg_path_string();
+        assert_eq!(
+            d,
+            "M 0 0 C 10.5 -20.25 10.5 -20.25 10.5 -20.25 C 0.333 100 0.333 100 0.333 100"
+        );
+
+        let parsed = Curve::from_svg_path_string(&d).unwrap();
+        let anchors = &parsed.instance_data.anchors;
+        assert_eq!(anchors.len(), 3);
+        for (a, b) in anchors.iter().zip(curve.instance_data.anchors.iter()) {
+            assert!((a.x - b.x).abs() < 0.001 && (a.y - b.y).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn test_svg_path_parse() {
+        let curve = Curve::from_svg_path_string("M0,0 L10,0 Q 15 5 10 10 Z").unwrap();
+        assert_eq!(
+            curve.instance_data.anchors,
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(10.0, 0.0),
+                Point::new(10.0, 10.0),
+                Point::new(0.0, 0.0),
+            ]
+        );
+
+        assert_eq!(
+            Curve::from_svg_path_string("L 1 1"),
+            Err(SvgPathParseError::MissingMoveTo)
+        );
+        assert_eq!(
+            Curve::from_svg_path_string("M 1 1 a 1 1 0 0 0 2 2"),
+            Err(SvgPathParseError::UnsupportedCommand('a'))
+        );
+        assert_eq!(
+            Curve::from_svg_path_string("M 1 1 L 2"),
+            Err(SvgPathParseError::MissingCoordinates('L'))
+        );
     }
 }
@@ -1,15 +1,216 @@
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::sync::{Arc, Mutex, OnceLock};
 
-use crate::{Pos, Scale};
+use crate::{Color, Pos, Scale};
 
 use super::types;
 use super::types::Canvas;
 use derive_builder::Builder;
-use femtovg::{CompositeOperation, ImageFlags, ImageId, Paint, Path};
+use femtovg::{CompositeOperation, ImageFlags, ImageId, ImageSource, Paint, Path};
+use image::{DynamicImage, ImageFormat};
 
 type Point = types::Point<f32>;
 type Size = types::Size<f32>;
 
+/// Encoded image data (e.g. a PNG file) that is only decoded when it is first drawn.
+///
+/// Clones share the decoded image, so it is decoded at most once.
+#[derive(Clone)]
+pub struct CompressedImage {
+    pub data: Arc<Vec<u8>>,
+    pub format: ImageFormat,
+    decoded: Arc<OnceLock<Option<DynamicImage>>>,
+}
+
+impl CompressedImage {
+    pub fn new(data: Arc<Vec<u8>>, format: ImageFormat) -> Self {
+        Self {
+            data,
+            format,
+            decoded: Default::default(),
+        }
+    }
+
+    /// Decode the image, if that has not happened yet. `None` if the data could not be decoded.
+    pub fn decode(&self) -> Option<&DynamicImage> {
+        self.decoded
+            .get_or_init(|| {
+                match image::load_from_memory_with_format(&self.data, self.format) {
+                    // femtovg can only create images from these formats
+                    Ok(image @ (DynamicImage::ImageRgb8(_) | DynamicImage::ImageRgba8(_))) => {
+                        Some(image)
+                    }
+                    Ok(image) => Some(DynamicImage::ImageRgba8(image.to_rgba8())),
+                    Err(e) => {
+                        println!("Error while decoding image error: {:?}", e);
+                        None
+                    }
+                }
+            })
+            .as_ref()
+    }
+
+    pub fn is_decoded(&self) -> bool {
+        matches!(self.decoded.get(), Some(Some(_)))
+    }
+}
+
+impl std::fmt::Debug for CompressedImage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("CompressedImage")
+            .field("len", &self.data.len())
+            .field("format", &self.format)
+            .field("decoded", &self.is_decoded())
+            .finish()
+    }
+}
+
+impl PartialEq for CompressedImage {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.data, &other.data) && self.format == other.format
+    }
+}
+
+/// How many [`ImageHold`]s there are on each image, by the name it is loaded as, and the names
+/// that have stopped being held since the last render.
+#[derive(Default)]
+struct ImageHolds {
+    counts: HashMap<String, usize>,
+    released: Vec<String>,
+}
+
+fn image_holds() -> &'static Mutex<ImageHolds> {
+    static HOLDS: OnceLock<Mutex<ImageHolds>> = OnceLock::new();
+    HOLDS.get_or_init(Default::default)
+}
+
+/// Keeps an image that is loaded on first use, from a file or [`CompressedImage`] data, from being
+/// evicted. Once every hold on an image has been dropped, it is evicted on the next render.
+#[derive(Debug)]
+pub(crate) struct ImageHold(String);
+
+impl ImageHold {
+    /// Hold the image loaded as `name`, which is the name of the image for unfiltered images.
+    /// See [`asset_name`].
+    pub(crate) fn new(name: String) -> Self {
+        *image_holds()
+            .lock()
+            .unwrap()
+            .counts
+            .entry(name.clone())
+            .or_default() += 1;
+        Self(name)
+    }
+}
+
+impl Drop for ImageHold {
+    fn drop(&mut self) {
+        let mut holds = image_holds().lock().unwrap();
+        if let Some(count) = holds.counts.get_mut(&self.0) {
+            *count -= 1;
+            if *count == 0 {
+                holds.counts.remove(&self.0);
+                holds.released.push(self.0.clone());
+            }
+        }
+    }
+}
+
+/// The names of the loaded images that are no longer held, and can be evicted.
+pub(crate) fn take_released_images() -> Vec<String> {
+    let mut holds = image_holds().lock().unwrap();
+    let released = mem::take(&mut holds.released);
+    released
+        .into_iter()
+        // Held again since
+        .filter(|name| !holds.counts.contains_key(name))
+        .collect()
+}
+
+/// The name that an image called `name` is loaded into the canvas assets as, once `filters` have
+/// been applied to it.
+pub(crate) fn asset_name(name: &str, filters: &[ImageFilter]) -> String {
+    if filters.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}{:?}", name, filters)
+    }
+}
+
+/// A color transformation of the pixels of an [`Image`], applied before it is drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImageFilter {
+    /// Grey, with the luminance of the original color: `0.21 R + 0.72 G + 0.07 B`.
+    Grayscale,
+    /// Multiply each channel, including alpha, by the channel of the color.
+    Tint(Color),
+    /// Invert each color channel. Alpha is kept.
+    Invert,
+    /// A Gaussian blur with the given radius (sigma), in pixels.
+    Blur(f32),
+    /// Multiply each color channel by the factor. `1.0` leaves the image unchanged.
+    Brightness(f32),
+    /// Scale the distance of each color channel from mid grey by the factor. `1.0` leaves the
+    /// image unchanged.
+    Contrast(f32),
+}
+
+impl Hash for ImageFilter {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            ImageFilter::Tint(color) => color.hash(state),
+            ImageFilter::Blur(f) | ImageFilter::Brightness(f) | ImageFilter::Contrast(f) => {
+                f.to_bits().hash(state)
+            }
+            ImageFilter::Grayscale | ImageFilter::Invert => (),
+        }
+    }
+}
+
+impl ImageFilter {
+    /// Apply this filter to one RGBA pixel. [`ImageFilter::Blur`] leaves it unchanged.
+    pub fn apply_to_pixel(&self, [r, g, b, a]: [u8; 4]) -> [u8; 4] {
+        let map = |f: &dyn Fn(f32) -> f32| {
+            let c = |v: u8| f(v as f32).round().clamp(0.0, 255.0) as u8;
+            [c(r), c(g), c(b), a]
+        };
+        match *self {
+            ImageFilter::Grayscale => {
+                let l = (0.21 * r as f32 + 0.72 * g as f32 + 0.07 * b as f32).round() as u8;
+                [l, l, l, a]
+            }
+            ImageFilter::Tint(color) => {
+                let tint = |v: u8, t: f32| (v as f32 * t).round().clamp(0.0, 255.0) as u8;
+                [
+                    tint(r, color.r / 255.0),
+                    tint(g, color.g / 255.0),
+                    tint(b, color.b / 255.0),
+                    tint(a, color.a),
+                ]
+            }
+            ImageFilter::Invert => [255 - r, 255 - g, 255 - b, a],
+            ImageFilter::Blur(_) => [r, g, b, a],
+            ImageFilter::Brightness(factor) => map(&|v| v * factor),
+            ImageFilter::Contrast(factor) => map(&|v| (v - 127.5) * factor + 127.5),
+        }
+    }
+
+    /// Apply this filter to every pixel of `image`.
+    pub fn apply(&self, image: DynamicImage) -> DynamicImage {
+        if let ImageFilter::Blur(sigma) = self {
+            return image.blur(*sigma);
+        }
+        let mut pixels = image.into_rgba8();
+        for pixel in pixels.pixels_mut() {
+            pixel.0 = self.apply_to_pixel(pixel.0);
+        }
+        DynamicImage::ImageRgba8(pixels)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Builder)]
 pub struct Instance {
     pub name: String,
@@ -19,8 +220,37 @@ pub struct Instance {
     pub composite_operation: CompositeOperation,
     #[builder(default = "0.0")]
     pub radius: f32,
+    /// `(top_left, top_right, bottom_right, bottom_left)` radii, used instead of `radius` when set.
+    #[builder(default = "None")]
+    pub corner_radius: Option<(f32, f32, f32, f32)>,
     #[builder(default = "None")]
     pub dynamic_load_from: Option<String>,
+    /// Decoded and loaded into the canvas on first render, when `name` is not loaded yet.
+    #[builder(default = "None")]
+    pub compressed: Option<CompressedImage>,
+    /// Applied in order to the image data, which must come from `compressed` or
+    /// `dynamic_load_from`. Images that are only loaded by name are drawn unfiltered.
+    #[builder(default)]
+    pub filters: Vec<ImageFilter>,
+    #[builder(default = "1.0")]
+    pub opacity: f32,
+}
+
+impl Instance {
+    /// The radius of each corner that the image is clipped to, from `corner_radius` or else
+    /// `radius`, at most half of the shorter side.
+    pub fn corner_radii(&self) -> (f32, f32, f32, f32) {
+        let (top_left, top_right, bottom_right, bottom_left) =
+            self.corner_radius
+                .unwrap_or((self.radius, self.radius, self.radius, self.radius));
+        let max = self.scale.width.min(self.scale.height) / 2.0;
+        (
+            top_left.min(max),
+            top_right.min(max),
+            bottom_right.min(max),
+            bottom_left.min(max),
+        )
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -37,7 +267,11 @@ impl Image {
                 name: name.into(),
                 composite_operation: CompositeOperation::SourceOver,
                 radius: Default::default(),
+                corner_radius: Default::default(),
                 dynamic_load_from: Default::default(),
+                compressed: Default::default(),
+                filters: Default::default(),
+                opacity: 1.0,
             },
         }
     }
@@ -47,20 +281,54 @@ impl Image {
         self
     }
 
+    /// Add `filter` after the filters that are already applied.
+    pub fn with_filter(mut self, filter: ImageFilter) -> Self {
+        self.instance_data.filters.push(filter);
+        self
+    }
+
+    /// The name that the filtered image is loaded into the canvas assets as.
+    fn filtered_name(&self) -> String {
+        asset_name(&self.instance_data.name, &self.instance_data.filters)
+    }
+
     pub fn render(&self, canvas: &mut Canvas, assets: &mut HashMap<String, ImageId>) {
         let Instance {
             pos,
             scale,
             composite_operation,
-            radius,
             dynamic_load_from,
+            compressed,
+            filters,
+            opacity,
             ..
         } = self.instance_data.clone();
 
         canvas.global_composite_operation(composite_operation);
 
+        //Filter the image data on first use
+        let filtered_name = self.filtered_name();
+        if !filters.is_empty() && assets.get(&filtered_name).is_none() {
+            let image = match (&compressed, &dynamic_load_from) {
+                (Some(compressed), _) => compressed.decode().cloned(),
+                (None, Some(path)) => image::open(path).ok(),
+                (None, None) => None,
+            };
+            let image = image.map(|image| filters.iter().fold(image, |i, f| f.apply(i)));
+            let image_id = image.and_then(|image| {
+                let src = ImageSource::try_from(&image).ok()?;
+                canvas.create_image(src, ImageFlags::empty()).ok()
+            });
+            if let Some(image_id) = image_id {
+                assets.insert(filtered_name.clone(), image_id);
+            }
+        }
+
         //Load image dynamically
-        if assets.get(&self.instance_data.name).is_none() && dynamic_load_from.is_some() {
+        if filters.is_empty()
+            && assets.get(&self.instance_data.name).is_none()
+            && dynamic_load_from.is_some()
+        {
             let path = dynamic_load_from.unwrap();
             let image_load_r = canvas.load_image_file(path, ImageFlags::empty());
             if let Ok(image_id) = image_load_r {
@@ -68,13 +336,40 @@ impl Image {
             }
         }
 
-        if let Some(image_id) = assets.get(&self.instance_data.name) {
+        //Decode compressed image on first use
+        let unfiltered = compressed.filter(|_| filters.is_empty());
+        if let (None, Some(compressed)) = (assets.get(&self.instance_data.name), unfiltered) {
+            let image_id = compressed
+                .decode()
+                .and_then(|image| ImageSource::try_from(image).ok())
+                .and_then(|src| canvas.create_image(src, ImageFlags::empty()).ok());
+            if let Some(image_id) = image_id {
+                assets.insert(self.instance_data.name.clone(), image_id);
+            }
+        }
+
+        let image_id = assets
+            .get(&filtered_name)
+            .filter(|_| !filters.is_empty())
+            .or_else(|| assets.get(&self.instance_data.name));
+        if let Some(image_id) = image_id {
             let Pos { x, y, z } = pos;
             let Scale { width, height } = scale;
 
-            let paint = Paint::image(*image_id, x, y, width, height, 0.0, 1.0);
+            let paint = Paint::image(*image_id, x, y, width, height, 0.0, opacity);
+            let (top_left, top_right, bottom_right, bottom_left) =
+                self.instance_data.corner_radii();
             let mut path = Path::new();
-            path.rounded_rect(x, y, width, height, radius);
+            path.rounded_rect_varying(
+                x,
+                y,
+                width,
+                height,
+                top_left,
+                top_right,
+                bottom_right,
+                bottom_left,
+            );
             canvas.fill_path(&path, &paint);
         }
 
@@ -85,3 +380,68 @@ impl Image {
         Self { instance_data }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_holds() {
+        let first = ImageHold::new("held".to_string());
+        let second = ImageHold::new("held".to_string());
+        drop(first);
+        assert!(!take_released_images().contains(&"held".to_string()));
+        drop(second);
+        let filtered = ImageHold::new(asset_name("held", &[ImageFilter::Invert]));
+        assert!(take_released_images().contains(&"held".to_string()));
+
+        // Released, then held again before it could be evicted
+        drop(filtered);
+        let _held = ImageHold::new("held[Invert]".to_string());
+        assert!(!take_released_images().contains(&"held[Invert]".to_string()));
+    }
+
+    #[test]
+    fn test_grayscale() {
+        let [r, g, b, a] = ImageFilter::Grayscale.apply_to_pixel([255, 0, 0, 255]);
+        assert_eq!((r, g, b, a), (r, r, r, 255));
+        assert!((r as f32 - 0.21 * 255.0).abs() <= 0.5);
+    }
+
+    #[test]
+    fn test_filters() {
+        let tint = ImageFilter::Tint(Color::rgba(255.0, 0.0, 127.5, 0.5));
+        assert_eq!(
+            tint.apply_to_pixel([200, 200, 200, 255]),
+            [200, 0, 100, 128]
+        );
+        assert_eq!(
+            ImageFilter::Invert.apply_to_pixel([0, 55, 255, 9]),
+            [255, 200, 0, 9]
+        );
+        let brightness = ImageFilter::Brightness(2.0);
+        assert_eq!(
+            brightness.apply_to_pixel([10, 100, 200, 255]),
+            [20, 200, 255, 255]
+        );
+        let contrast = ImageFilter::Contrast(0.0);
+        assert_eq!(
+            contrast.apply_to_pixel([0, 100, 255, 255]),
+            [128, 128, 128, 255]
+        );
+
+        // Filters are applied in the order they were added
+        let renderable = Image::new(Pos::default(), Scale::new(1.0, 1.0), "red")
+            .with_filter(ImageFilter::Grayscale)
+            .with_filter(ImageFilter::Tint(Color::BLUE));
+        let filters = &renderable.instance_data.filters;
+        assert_eq!(
+            filters,
+            &[ImageFilter::Grayscale, ImageFilter::Tint(Color::BLUE)]
+        );
+        let red =
+            DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(1, 1, [255, 0, 0, 255].into()));
+        let filtered = filters.iter().fold(red, |i, f| f.apply(i)).into_rgba8();
+        assert_eq!(filtered.get_pixel(0, 0).0, [0, 0, 54, 255]);
+    }
+}
@@ -1,11 +1,12 @@
-use crate::{Color, Pos};
+use crate::{Color, Point, Pos};
 
 use super::types;
-use super::types::Canvas;
+pub use super::types::LineCap;
+use super::types::{Canvas, LineJoin, StrokeStyle};
 use derive_builder::Builder;
-use femtovg::{LineCap, LineJoin, Paint, Path};
+use femtovg::Path;
 
-#[derive(Clone, Copy, Default, Debug, PartialEq, Builder)]
+#[derive(Clone, Default, Debug, PartialEq, Builder)]
 pub struct Instance {
     pub from: Pos,
     pub to: Pos,
@@ -13,6 +14,17 @@ pub struct Instance {
     pub color: Color,
     #[builder(default = "2.0")]
     pub width: f32,
+    #[builder(default = "LineCap::Round")]
+    pub cap_style: LineCap,
+    /// Alternating dash and gap lengths in pixels, repeating along the line, as with SVG's
+    /// `stroke-dasharray`. A dash of `0.0` with a [`LineCap::Round`] cap draws a dotted line.
+    /// `None` is solid.
+    #[builder(default)]
+    pub dash_pattern: Option<Vec<f32>>,
+    /// How far into the pattern the line starts, which moves the dashes backward along the
+    /// line. Animating it makes the dashes march.
+    #[builder(default)]
+    pub dash_offset: f32,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -28,6 +40,9 @@ impl Line {
                 to,
                 color,
                 width: 10.0,
+                cap_style: LineCap::Round,
+                dash_pattern: None,
+                dash_offset: 0.0,
             },
         }
     }
@@ -36,22 +51,200 @@ impl Line {
         Self { instance_data }
     }
 
+    /// The stroke that this line is drawn with.
+    pub fn stroke_style(&self) -> StrokeStyle {
+        let Instance {
+            color,
+            width,
+            cap_style,
+            ref dash_pattern,
+            ..
+        } = self.instance_data;
+        StrokeStyle {
+            color,
+            width,
+            cap: cap_style,
+            join: LineJoin::Miter,
+            dash: dash_pattern.clone(),
+        }
+    }
+
     pub fn render(&self, canvas: &mut Canvas) {
         let Instance {
             from,
             to,
-            color,
-            width,
+            dash_offset,
+            ..
         } = self.instance_data;
+        let from = Point::new(from.x, from.y);
+        let to = Point::new(to.x, to.y);
+        let stroke = self.stroke_style();
+
         let mut path = Path::new();
-        path.move_to(from.x, from.y);
-        path.line_to(to.x, to.y);
-
-        let mut paint = Paint::default();
-        paint.set_color(color.into());
-        paint.set_line_cap(LineCap::Round);
-        paint.set_line_join(LineJoin::Miter);
-        paint.set_line_width(width);
-        canvas.stroke_path(&path, &paint);
+        let segments = match &stroke.dash {
+            Some(dash) => dash_array_segments(from, to, dash, dash_offset),
+            None => vec![(from, to)],
+        };
+        for (a, b) in segments.iter() {
+            path.move_to(a.x, a.y);
+            path.line_to(b.x, b.y);
+        }
+
+        canvas.stroke_path(&path, &stroke.paint());
+    }
+}
+
+/// Split the line from `from` to `to` into `(start, end)` dashes, with alternating dash and gap
+/// lengths that repeat along the line. An odd number of lengths is repeated to make it even, as
+/// with SVG's `stroke-dasharray`. The pattern starts `offset` into it, as with
+/// `stroke-dashoffset`.
+///
+/// Zero-length dashes are kept, so that they can be drawn as dots by a round or square cap.
+pub(crate) fn dash_array_segments(
+    from: Point,
+    to: Point,
+    dash_array: &[f32],
+    offset: f32,
+) -> Vec<(Point, Point)> {
+    let dx = to.x - from.x;
+    let dy = to.y - from.y;
+    let length = (dx * dx + dy * dy).sqrt();
+    let period: f32 = dash_array.iter().sum();
+    if length == 0.0 || period <= 0.0 || dash_array.iter().any(|l| *l < 0.0) {
+        return vec![(from, to)];
+    }
+    let dash_array = if dash_array.len() % 2 == 1 {
+        [dash_array, dash_array].concat()
+    } else {
+        dash_array.to_vec()
+    };
+
+    let at = |d: f32| Point::new(from.x + dx * d / length, from.y + dy * d / length);
+    let mut segments = vec![];
+    let cycle: f32 = dash_array.iter().sum();
+    let mut d = -offset.rem_euclid(cycle);
+    for (i, l) in dash_array.iter().cycle().enumerate() {
+        if d > length {
+            break;
+        }
+        // Dashes that start before the line are cut off, unless they end before it too
+        if i % 2 == 0 && (d >= 0.0 || d + l > 0.0) {
+            segments.push((at(d.max(0.0)), at((d + l).min(length))));
+        }
+        d += l;
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dash_segments(from: Point, to: Point, dash: f32, gap: f32) -> Vec<(Point, Point)> {
+        dash_array_segments(from, to, &[dash, gap], 0.0)
+    }
+
+    #[test]
+    fn test_dotted_line() {
+        let from = Point::new(0.0, 5.0);
+        let to = Point::new(50.0, 5.0);
+        let dots = dash_segments(from, to, 0.0, 10.0);
+
+        // Zero-length dashes: one dot every 10px, including both ends
+        assert_eq!(dots.len(), 6);
+        for (i, (a, b)) in dots.iter().enumerate() {
+            assert_eq!(a, b);
+            assert_eq!(*a, Point::new(i as f32 * 10.0, 5.0));
+        }
+        let gaps = dots.windows(2).map(|w| w[1].0.x - w[0].1.x).sum::<f32>();
+        assert_eq!(gaps, 50.0);
+    }
+
+    #[test]
+    fn test_dashed_line() {
+        let dashes = dash_segments(Point::new(0.0, 0.0), Point::new(0.0, 25.0), 5.0, 5.0);
+        assert_eq!(
+            dashes,
+            vec![
+                (Point::new(0.0, 0.0), Point::new(0.0, 5.0)),
+                (Point::new(0.0, 10.0), Point::new(0.0, 15.0)),
+                (Point::new(0.0, 20.0), Point::new(0.0, 25.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dash_array() {
+        let from = Point::new(0.0, 0.0);
+        let dashes = dash_array_segments(from, Point::new(20.0, 0.0), &[6.0, 2.0, 1.0, 2.0], 0.0);
+        assert_eq!(
+            dashes,
+            vec![
+                (Point::new(0.0, 0.0), Point::new(6.0, 0.0)),
+                (Point::new(8.0, 0.0), Point::new(9.0, 0.0)),
+                (Point::new(11.0, 0.0), Point::new(17.0, 0.0)),
+                (Point::new(19.0, 0.0), Point::new(20.0, 0.0)),
+            ]
+        );
+
+        // An odd number of lengths alternates between dash and gap on each repetition
+        let dashes = dash_array_segments(from, Point::new(12.0, 0.0), &[4.0], 0.0);
+        assert_eq!(
+            dashes,
+            vec![
+                (Point::new(0.0, 0.0), Point::new(4.0, 0.0)),
+                (Point::new(8.0, 0.0), Point::new(12.0, 0.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dash_offset() {
+        let from = Point::new(0.0, 0.0);
+        let to = Point::new(20.0, 0.0);
+        // The first dash is cut short, and the pattern carries on from there
+        assert_eq!(
+            dash_array_segments(from, to, &[8.0, 4.0], 3.0),
+            vec![
+                (Point::new(0.0, 0.0), Point::new(5.0, 0.0)),
+                (Point::new(9.0, 0.0), Point::new(17.0, 0.0)),
+            ]
+        );
+        // Starting in a gap, and offsets wrap around the pattern
+        let dashes = dash_array_segments(from, to, &[8.0, 4.0], 10.0);
+        assert_eq!(dashes[0], (Point::new(2.0, 0.0), Point::new(10.0, 0.0)));
+        assert_eq!(dash_array_segments(from, to, &[8.0, 4.0], 22.0), dashes);
+        assert_eq!(dash_array_segments(from, to, &[8.0, 4.0], -2.0), dashes);
+    }
+
+    #[test]
+    fn test_dash_coverage() {
+        let from = Point::new(0.0, 0.0);
+        let period = 12.0;
+        for length in [5.0, 12.0, 50.0, 99.5] {
+            for offset in [0.0, 3.0, 9.5] {
+                let to = Point::new(length, 0.0);
+                let dashes = dash_array_segments(from, to, &[8.0, 4.0], offset);
+                let drawn: f32 = dashes.iter().map(|(a, b)| b.x - a.x).sum();
+                let gaps: f32 = dashes.windows(2).map(|w| w[1].0.x - w[0].1.x).sum();
+                assert!(
+                    (drawn + gaps - length).abs() <= period,
+                    "{length} {offset}: {drawn} + {gaps}"
+                );
+                assert!(dashes.iter().all(|(a, b)| a.x >= 0.0 && b.x <= length));
+            }
+        }
+    }
+
+    #[test]
+    fn test_stroke_style() {
+        let mut line = Line::new(Pos::default(), Pos::new(10.0, 0.0, 0.0), Color::RED);
+        assert_eq!(
+            line.stroke_style(),
+            StrokeStyle::new(Color::RED, 10.0).cap(LineCap::Round)
+        );
+
+        line.instance_data.dash_pattern = Some(vec![4.0, 2.0]);
+        assert_eq!(line.stroke_style().dash, Some(vec![4.0, 2.0]));
     }
 }
@@ -0,0 +1,142 @@
+use crate::{Color, Point, Pos, Scale};
+
+use super::rect::Gradient;
+use super::types::Canvas;
+use derive_builder::Builder;
+use femtovg::Path;
+
+#[derive(Clone, Default, Debug, PartialEq, Builder)]
+pub struct Instance {
+    pub pos: Pos,
+    pub scale: Scale,
+    /// Where the first stop is, relative to `pos`.
+    pub start: Point,
+    /// Where the last stop is, relative to `pos`.
+    pub end: Point,
+    /// `(offset, color)` pairs, where offsets run from `0.0` at `start` to `1.0` at `end`.
+    pub stops: Vec<(f32, Color)>,
+}
+
+/// A rectangle filled with colors that change along the line from `start` to `end`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LinearGradient {
+    pub instance_data: Instance,
+}
+
+impl LinearGradient {
+    pub fn new(pos: Pos, scale: Scale, start: Point, end: Point, stops: Vec<(f32, Color)>) -> Self {
+        Self {
+            instance_data: Instance {
+                pos,
+                scale,
+                start,
+                end,
+                stops,
+            },
+        }
+    }
+
+    pub fn from_instance_data(instance_data: Instance) -> Self {
+        Self { instance_data }
+    }
+
+    /// The gradient that the rectangle is filled with, relative to `pos`.
+    pub fn gradient(&self) -> Gradient {
+        let Instance {
+            start,
+            end,
+            ref stops,
+            ..
+        } = self.instance_data;
+        Gradient::Linear {
+            start,
+            end,
+            stops: stops.clone(),
+        }
+    }
+
+    /// The stops in order, with offsets clamped to `0.0`--`1.0`.
+    pub fn stops(&self) -> Vec<(f32, Color)> {
+        self.gradient().stops()
+    }
+
+    /// The color at `offset` along the gradient, blending each sRGB channel linearly between
+    /// the neighboring stops. Before the first stop and after the last, their colors continue.
+    pub fn color_at(&self, offset: f32) -> Option<Color> {
+        let stops = self.stops();
+        let (first, last) = (stops.first()?, stops.last()?);
+        if offset <= first.0 {
+            return Some(first.1);
+        }
+        if offset >= last.0 {
+            return Some(last.1);
+        }
+        stops.windows(2).find_map(|w| {
+            let ((a, from), (b, to)) = (w[0], w[1]);
+            if offset > b {
+                return None;
+            }
+            let t = if b > a { (offset - a) / (b - a) } else { 1.0 };
+            Some(Color::rgba(
+                from.r + (to.r - from.r) * t,
+                from.g + (to.g - from.g) * t,
+                from.b + (to.b - from.b) * t,
+                from.a + (to.a - from.a) * t,
+            ))
+        })
+    }
+
+    pub fn render(&self, canvas: &mut Canvas) {
+        let Instance {
+            pos,
+            scale,
+            ref stops,
+            ..
+        } = self.instance_data;
+        if stops.is_empty() {
+            return;
+        }
+        let mut path = Path::new();
+        path.rect(pos.x, pos.y, scale.width, scale.height);
+        canvas.fill_path(&path, &self.gradient().paint(pos));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gradient(stops: Vec<(f32, Color)>) -> LinearGradient {
+        LinearGradient::new(
+            Pos::default(),
+            Scale::new(100.0, 10.0),
+            Point::new(0.0, 0.0),
+            Point::new(100.0, 0.0),
+            stops,
+        )
+    }
+
+    #[test]
+    fn test_color_at() {
+        let g = gradient(vec![(1.5, Color::BLUE), (-1.0, Color::RED)]);
+        // Out of range offsets are clamped, and the stops are sorted
+        assert_eq!(g.stops(), vec![(0.0, Color::RED), (1.0, Color::BLUE)]);
+        assert_eq!(g.color_at(0.0), Some(Color::RED));
+        assert_eq!(g.color_at(1.0), Some(Color::BLUE));
+        let mid = g.color_at(0.5).unwrap();
+        assert_eq!((mid.r, mid.g, mid.b), (127.5, 0.0, 127.5));
+
+        let g = gradient(vec![
+            (0.0, Color::BLACK),
+            (0.5, Color::WHITE),
+            (1.0, Color::BLACK),
+        ]);
+        assert_eq!(g.color_at(0.5), Some(Color::WHITE));
+        assert_eq!(g.color_at(0.75).unwrap().r, 127.5);
+
+        let g = gradient(vec![(0.3, Color::GREEN)]);
+        assert_eq!(g.color_at(0.0), Some(Color::GREEN));
+        assert_eq!(g.color_at(1.0), Some(Color::GREEN));
+        assert_eq!(gradient(vec![]).color_at(0.5), None);
+    }
+}
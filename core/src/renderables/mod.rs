@@ -1,21 +1,34 @@
+pub mod arc;
 pub mod circle;
 pub mod curve;
 pub mod image;
 pub mod line;
+pub mod linear_gradient;
+pub mod path;
+pub mod polygon;
+pub mod polyline;
 pub mod radial_gradient;
 pub mod rect;
 pub mod svg;
 pub mod text;
+pub mod transform;
 pub mod types;
 
+pub use arc::Arc;
 pub use circle::Circle;
 pub use curve::Curve;
 pub use image::Image;
 pub use line::Line;
+pub use linear_gradient::LinearGradient;
+pub use path::{FillRule, Path, PathBuilder, PathCommand};
+pub use polygon::Polygon;
+pub use polyline::Polyline;
 pub use radial_gradient::RadialGradient;
 pub use rect::Rect;
 pub use svg::Svg;
 pub use text::Text;
+pub use transform::Transform;
+pub use types::{FillStyle, LineCap, LineJoin, StrokeStyle};
 
 #[derive(Debug, Clone)]
 pub enum Renderable {
@@ -26,5 +39,111 @@ pub enum Renderable {
     Text(Text),
     Svg(Svg),
     RadialGradient(RadialGradient),
+    LinearGradient(LinearGradient),
     Curve(Curve),
+    Polygon(Polygon),
+    Arc(Arc),
+    Path(Path),
+    Polyline(Polyline),
+    /// `inner`, drawn with `transform`. See [`Renderable::with_transform`].
+    Transformed {
+        inner: Box<Renderable>,
+        transform: Transform,
+    },
+    /// `inner`, with every alpha multiplied by `opacity`, from `0.0` to `1.0`. See
+    /// [`Renderable::with_opacity`].
+    WithOpacity {
+        inner: Box<Renderable>,
+        opacity: f32,
+    },
+}
+
+impl Renderable {
+    /// This renderable, drawn with `transform`. The identity transform returns it unchanged,
+    /// so that drawing it costs nothing extra.
+    pub fn with_transform(self, transform: Transform) -> Renderable {
+        if transform.is_identity() {
+            self
+        } else {
+            Renderable::Transformed {
+                inner: Box::new(self),
+                transform,
+            }
+        }
+    }
+
+    /// This renderable, with every alpha multiplied by `opacity`, which is clamped to
+    /// `0.0`--`1.0`. An opacity of `1.0` returns it unchanged, and wrapping a renderable that
+    /// already has an opacity multiplies the two.
+    pub fn with_opacity(self, opacity: f32) -> Renderable {
+        let opacity = opacity.clamp(0.0, 1.0);
+        if opacity == 1.0 {
+            return self;
+        }
+        match self {
+            Renderable::WithOpacity {
+                inner,
+                opacity: inner_opacity,
+            } => Renderable::WithOpacity {
+                inner,
+                opacity: inner_opacity.clamp(0.0, 1.0) * opacity,
+            },
+            _ => Renderable::WithOpacity {
+                inner: Box::new(self),
+                opacity,
+            },
+        }
+    }
+
+    /// The product of the opacities of this renderable and the renderables that it wraps.
+    pub fn opacity(&self) -> f32 {
+        match self {
+            Renderable::WithOpacity { inner, opacity } => opacity.clamp(0.0, 1.0) * inner.opacity(),
+            Renderable::Transformed { inner, .. } => inner.opacity(),
+            _ => 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, Pos, Scale};
+
+    fn rect() -> Renderable {
+        Renderable::Rect(Rect::new(
+            Pos::default(),
+            Scale::new(10.0, 10.0),
+            Color::RED,
+        ))
+    }
+
+    #[test]
+    fn test_with_opacity() {
+        // A full opacity is drawn exactly like no wrapper at all
+        assert!(matches!(rect().with_opacity(1.0), Renderable::Rect(_)));
+        assert!(matches!(rect().with_opacity(3.0), Renderable::Rect(_)));
+        assert_eq!(rect().opacity(), 1.0);
+
+        let faded = rect().with_opacity(0.5);
+        assert!(matches!(&faded, Renderable::WithOpacity { opacity, .. } if *opacity == 0.5));
+        assert_eq!(faded.opacity(), 0.5);
+        assert_eq!(rect().with_opacity(-1.0).opacity(), 0.0);
+
+        // Nested opacities multiply
+        let nested = faded.with_opacity(0.5);
+        assert_eq!(nested.opacity(), 0.25);
+        match &nested {
+            Renderable::WithOpacity { inner, .. } => {
+                assert!(matches!(**inner, Renderable::Rect(_)))
+            }
+            r => panic!("Expected a rect with an opacity, got {r:?}"),
+        }
+
+        let transformed = rect()
+            .with_opacity(0.5)
+            .with_transform(Transform::rotate(45.0))
+            .with_opacity(0.5);
+        assert_eq!(transformed.opacity(), 0.25);
+    }
 }
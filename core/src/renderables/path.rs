@@ -0,0 +1,685 @@
+use crate::{Color, Point, Pos, Scale, AABB};
+
+#[cfg(feature = "svg-path")]
+use super::curve::SvgPathParseError;
+use super::types::{Canvas, FillStyle, StrokeStyle};
+use femtovg::Path as CanvasPath;
+
+/// How many line segments each curve is split into for [`Path::contains`].
+const CURVE_SEGMENTS: usize = 16;
+
+/// One step of drawing a [`Path`], in the same coordinates as the points of the path.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PathCommand {
+    /// Start a new subpath at the point.
+    MoveTo(Point),
+    LineTo(Point),
+    CubicBezierTo {
+        cp1: Point,
+        cp2: Point,
+        end: Point,
+    },
+    QuadraticBezierTo {
+        cp: Point,
+        end: Point,
+    },
+    /// Draw a line back to the start of the current subpath.
+    ClosePath,
+}
+
+/// Which parts of a path that crosses itself are inside of it, as with SVG's `fill-rule`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FillRule {
+    /// Inside wherever the outline winds around a point a non-zero number of times.
+    #[default]
+    NonZero,
+    /// Inside wherever a ray from a point crosses the outline an odd number of times.
+    EvenOdd,
+}
+
+impl From<FillRule> for femtovg::FillRule {
+    fn from(rule: FillRule) -> Self {
+        match rule {
+            FillRule::NonZero => femtovg::FillRule::NonZero,
+            FillRule::EvenOdd => femtovg::FillRule::EvenOdd,
+        }
+    }
+}
+
+/// An arbitrary shape made of lines and Bézier curves, which can be filled, stroked, or both.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Path {
+    pub commands: Vec<PathCommand>,
+    /// The color and width of the outline, if it is drawn.
+    pub stroke: Option<(Color, f32)>,
+    pub fill: Option<Color>,
+    pub fill_rule: FillRule,
+}
+
+impl Path {
+    pub fn new(commands: Vec<PathCommand>) -> Self {
+        Self {
+            commands,
+            ..Default::default()
+        }
+    }
+
+    pub fn builder() -> PathBuilder {
+        PathBuilder::default()
+    }
+
+    /// Parse SVG path data, as found in the `d` attribute of a `<path>`. The `M`, `L`, `H`,
+    /// `V`, `C`, `Q` and `Z` commands are supported, in both their absolute and relative forms.
+    ///
+    /// The path has neither a fill nor a stroke.
+    #[cfg(feature = "svg-path")]
+    pub fn from_svg_path_data(d: &str) -> Result<Path, SvgPathParseError> {
+        let mut spaced = String::with_capacity(d.len());
+        for c in d.chars() {
+            if c.is_ascii_alphabetic() && c != 'e' && c != 'E' {
+                spaced.push(' ');
+                spaced.push(c);
+                spaced.push(' ');
+            } else if c == ',' {
+                spaced.push(' ');
+            } else {
+                spaced.push(c);
+            }
+        }
+
+        let mut builder = PathBuilder::default();
+        let mut current = Point::new(0.0, 0.0);
+        let mut start = current;
+        let mut command: Option<char> = None;
+        let mut numbers: Vec<f32> = vec![];
+        let mut tokens = spaced.split_whitespace();
+        loop {
+            let token = tokens.next();
+            let is_command = token.map_or(true, |t| t.chars().all(|c| c.is_ascii_alphabetic()));
+            if !is_command {
+                let t = token.unwrap();
+                numbers.push(
+                    t.parse::<f32>()
+                        .map_err(|_| SvgPathParseError::InvalidNumber(t.to_string()))?,
+                );
+                continue;
+            }
+
+            // Finish the previous command
+            if let Some(c) = command {
+                let group = match c.to_ascii_uppercase() {
+                    'H' | 'V' => 1,
+                    'M' | 'L' => 2,
+                    'Q' => 4,
+                    'C' => 6,
+                    _ => 0,
+                };
+                if group > 0 {
+                    if numbers.is_empty() || numbers.len() % group != 0 {
+                        return Err(SvgPathParseError::MissingCoordinates(c));
+                    }
+                } else if !numbers.is_empty() {
+                    return Err(SvgPathParseError::InvalidNumber(format!("{}", numbers[0])));
+                }
+
+                let relative = c.is_ascii_lowercase();
+                let at = |current: Point, x: f32, y: f32| {
+                    if relative {
+                        Point::new(current.x + x, current.y + y)
+                    } else {
+                        Point::new(x, y)
+                    }
+                };
+                for (i, g) in numbers.chunks(group.max(1)).enumerate() {
+                    match c.to_ascii_uppercase() {
+                        // Pairs after the first of a move are lines
+                        'M' if i == 0 => {
+                            current = at(current, g[0], g[1]);
+                            start = current;
+                            builder = builder.move_to(current);
+                        }
+                        'M' | 'L' => {
+                            current = at(current, g[0], g[1]);
+                            builder = builder.line_to(current);
+                        }
+                        'H' => {
+                            let x = if relative { current.x + g[0] } else { g[0] };
+                            current = Point::new(x, current.y);
+                            builder = builder.line_to(current);
+                        }
+                        'V' => {
+                            let y = if relative { current.y + g[0] } else { g[0] };
+                            current = Point::new(current.x, y);
+                            builder = builder.line_to(current);
+                        }
+                        'Q' => {
+                            let cp = at(current, g[0], g[1]);
+                            current = at(current, g[2], g[3]);
+                            builder = builder.quad_to(cp, current);
+                        }
+                        'C' => {
+                            let cp1 = at(current, g[0], g[1]);
+                            let cp2 = at(current, g[2], g[3]);
+                            current = at(current, g[4], g[5]);
+                            builder = builder.cubic_to(cp1, cp2, current);
+                        }
+                        _ => {}
+                    }
+                }
+                if group == 0 {
+                    current = start;
+                    builder = builder.close();
+                }
+                numbers.clear();
+            } else if !numbers.is_empty() {
+                return Err(SvgPathParseError::MissingMoveTo);
+            }
+
+            match token {
+                None => break,
+                Some(t) => {
+                    let mut chars = t.chars();
+                    let c = chars.next().unwrap();
+                    if chars.next().is_some()
+                        || !matches!(
+                            c.to_ascii_uppercase(),
+                            'M' | 'L' | 'H' | 'V' | 'C' | 'Q' | 'Z'
+                        )
+                    {
+                        return Err(SvgPathParseError::UnsupportedCommand(c));
+                    }
+                    if command.is_none() && !matches!(c, 'M' | 'm') {
+                        return Err(SvgPathParseError::MissingMoveTo);
+                    }
+                    command = Some(c);
+                }
+            }
+        }
+
+        if command.is_none() {
+            return Err(SvgPathParseError::MissingMoveTo);
+        }
+        Ok(builder.build())
+    }
+
+    /// The fill of the path, if any.
+    pub fn fill_style(&self) -> Option<FillStyle> {
+        self.fill.map(FillStyle::new)
+    }
+
+    /// The stroke of the path, if any.
+    pub fn stroke_style(&self) -> Option<StrokeStyle> {
+        self.stroke
+            .filter(|(color, width)| *width > 0.0 && color.a > 0.0)
+            .map(|(color, width)| StrokeStyle::new(color, width))
+    }
+
+    /// The subpaths as lists of points, with curves split into line segments, and whether each
+    /// one was closed with [`PathCommand::ClosePath`].
+    fn flatten(&self) -> Vec<(Vec<Point>, bool)> {
+        let mut subpaths: Vec<(Vec<Point>, bool)> = vec![];
+        let mut points: Vec<Point> = vec![];
+        let mut current = Point::new(0.0, 0.0);
+        let mut start = current;
+        let finish = |points: &mut Vec<Point>, closed, subpaths: &mut Vec<(Vec<Point>, bool)>| {
+            if !points.is_empty() {
+                subpaths.push((std::mem::take(points), closed));
+            }
+        };
+        for command in &self.commands {
+            if points.is_empty() && !matches!(command, PathCommand::MoveTo(_)) {
+                points.push(current);
+            }
+            match *command {
+                PathCommand::MoveTo(p) => {
+                    finish(&mut points, false, &mut subpaths);
+                    points.push(p);
+                    current = p;
+                    start = p;
+                }
+                PathCommand::LineTo(p) => {
+                    points.push(p);
+                    current = p;
+                }
+                PathCommand::CubicBezierTo { cp1, cp2, end } => {
+                    for i in 1..=CURVE_SEGMENTS {
+                        let t = i as f32 / CURVE_SEGMENTS as f32;
+                        points.push(cubic_point(current, cp1, cp2, end, t));
+                    }
+                    current = end;
+                }
+                PathCommand::QuadraticBezierTo { cp, end } => {
+                    for i in 1..=CURVE_SEGMENTS {
+                        let t = i as f32 / CURVE_SEGMENTS as f32;
+                        points.push(quad_point(current, cp, end, t));
+                    }
+                    current = end;
+                }
+                PathCommand::ClosePath => {
+                    finish(&mut points, true, &mut subpaths);
+                    current = start;
+                }
+            }
+        }
+        finish(&mut points, false, &mut subpaths);
+        subpaths
+    }
+
+    /// The bounds of everything that is drawn, including the stroke. `None` when the path
+    /// has no commands.
+    pub fn bounding_box(&self) -> Option<AABB> {
+        let mut min = Point::new(f32::INFINITY, f32::INFINITY);
+        let mut max = Point::new(f32::NEG_INFINITY, f32::NEG_INFINITY);
+        let mut include = |p: Point| {
+            min = Point::new(min.x.min(p.x), min.y.min(p.y));
+            max = Point::new(max.x.max(p.x), max.y.max(p.y));
+        };
+        let mut current = Point::new(0.0, 0.0);
+        let mut start = current;
+        for command in &self.commands {
+            match *command {
+                PathCommand::MoveTo(p) => {
+                    start = p;
+                    include(p);
+                    current = p;
+                }
+                PathCommand::LineTo(p) => {
+                    include(current);
+                    include(p);
+                    current = p;
+                }
+                PathCommand::CubicBezierTo { cp1, cp2, end } => {
+                    include(current);
+                    include(end);
+                    // The curve reaches furthest out wherever it turns around on an axis
+                    let roots_x = cubic_extrema(current.x, cp1.x, cp2.x, end.x);
+                    let roots_y = cubic_extrema(current.y, cp1.y, cp2.y, end.y);
+                    for t in roots_x.into_iter().chain(roots_y).flatten() {
+                        include(cubic_point(current, cp1, cp2, end, t));
+                    }
+                    current = end;
+                }
+                PathCommand::QuadraticBezierTo { cp, end } => {
+                    include(current);
+                    include(end);
+                    let roots = [
+                        quad_extremum(current.x, cp.x, end.x),
+                        quad_extremum(current.y, cp.y, end.y),
+                    ];
+                    for t in roots.into_iter().flatten() {
+                        include(quad_point(current, cp, end, t));
+                    }
+                    current = end;
+                }
+                PathCommand::ClosePath => {
+                    include(current);
+                    current = start;
+                }
+            }
+        }
+        if min.x > max.x {
+            return None;
+        }
+
+        let half_stroke = self.stroke_style().map_or(0.0, |s| s.width / 2.0);
+        Some(AABB::new(
+            Pos::new(min.x - half_stroke, min.y - half_stroke, 0.0),
+            Scale::new(
+                max.x - min.x + half_stroke * 2.0,
+                max.y - min.y + half_stroke * 2.0,
+            ),
+        ))
+    }
+
+    /// Whether `p` is inside the filled area of the path, following its [`FillRule`]. Every
+    /// subpath counts as closed, as when filling, and curves are approximated by lines.
+    pub fn contains(&self, p: Point) -> bool {
+        let mut winding = 0;
+        for (points, _) in self.flatten() {
+            for (i, a) in points.iter().enumerate() {
+                let b = points[(i + 1) % points.len()];
+                let side = (b.x - a.x) * (p.y - a.y) - (p.x - a.x) * (b.y - a.y);
+                if a.y <= p.y {
+                    if b.y > p.y && side > 0.0 {
+                        winding += 1;
+                    }
+                } else if b.y <= p.y && side < 0.0 {
+                    winding -= 1;
+                }
+            }
+        }
+        match self.fill_rule {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+
+    /// Whether `p` is on the drawn part of the path: inside its fill, if it has one, or within
+    /// its stroke.
+    pub fn hit_test(&self, p: Point) -> bool {
+        if self.fill_style().is_some() && self.contains(p) {
+            return true;
+        }
+        let stroke = match self.stroke_style() {
+            Some(stroke) => stroke,
+            None => return false,
+        };
+        let reach = stroke.width / 2.0;
+        self.flatten().iter().any(|(points, closed)| {
+            let segments = if *closed {
+                points.len()
+            } else {
+                points.len().saturating_sub(1)
+            };
+            (0..segments.max(1)).any(|i| {
+                let a = points[i];
+                let b = points[(i + 1) % points.len()];
+                segment_distance(p, a, b) <= reach
+            })
+        })
+    }
+
+    fn path(&self) -> CanvasPath {
+        let mut path = CanvasPath::new();
+        for command in &self.commands {
+            match *command {
+                PathCommand::MoveTo(p) => path.move_to(p.x, p.y),
+                PathCommand::LineTo(p) => path.line_to(p.x, p.y),
+                PathCommand::CubicBezierTo { cp1, cp2, end } => {
+                    path.bezier_to(cp1.x, cp1.y, cp2.x, cp2.y, end.x, end.y)
+                }
+                PathCommand::QuadraticBezierTo { cp, end } => {
+                    path.quad_to(cp.x, cp.y, end.x, end.y)
+                }
+                PathCommand::ClosePath => path.close(),
+            }
+        }
+        path
+    }
+
+    pub fn render(&self, canvas: &mut Canvas) {
+        if self.commands.is_empty() {
+            return;
+        }
+        let path = self.path();
+        if let Some(fill) = self.fill_style() {
+            let mut paint = fill.paint();
+            paint.set_fill_rule(self.fill_rule.into());
+            canvas.fill_path(&path, &paint);
+        }
+        if let Some(stroke) = self.stroke_style() {
+            canvas.stroke_path(&path, &stroke.paint());
+        }
+    }
+}
+
+/// Builds a [`Path`] one command at a time.
+#[derive(Clone, Debug, Default)]
+pub struct PathBuilder {
+    path: Path,
+}
+
+impl PathBuilder {
+    pub fn move_to(mut self, p: Point) -> Self {
+        self.path.commands.push(PathCommand::MoveTo(p));
+        self
+    }
+
+    pub fn line_to(mut self, p: Point) -> Self {
+        self.path.commands.push(PathCommand::LineTo(p));
+        self
+    }
+
+    pub fn cubic_to(mut self, cp1: Point, cp2: Point, end: Point) -> Self {
+        self.path
+            .commands
+            .push(PathCommand::CubicBezierTo { cp1, cp2, end });
+        self
+    }
+
+    pub fn quad_to(mut self, cp: Point, end: Point) -> Self {
+        self.path
+            .commands
+            .push(PathCommand::QuadraticBezierTo { cp, end });
+        self
+    }
+
+    pub fn close(mut self) -> Self {
+        self.path.commands.push(PathCommand::ClosePath);
+        self
+    }
+
+    pub fn stroke(mut self, color: Color, width: f32) -> Self {
+        self.path.stroke = Some((color, width));
+        self
+    }
+
+    pub fn fill(mut self, color: Color) -> Self {
+        self.path.fill = Some(color);
+        self
+    }
+
+    pub fn fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.path.fill_rule = fill_rule;
+        self
+    }
+
+    pub fn build(self) -> Path {
+        self.path
+    }
+}
+
+fn cubic_point(p0: Point, p1: Point, p2: Point, p3: Point, t: f32) -> Point {
+    let u = 1.0 - t;
+    let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+    Point::new(
+        a * p0.x + b * p1.x + c * p2.x + d * p3.x,
+        a * p0.y + b * p1.y + c * p2.y + d * p3.y,
+    )
+}
+
+fn quad_point(p0: Point, p1: Point, p2: Point, t: f32) -> Point {
+    let u = 1.0 - t;
+    let (a, b, c) = (u * u, 2.0 * u * t, t * t);
+    Point::new(
+        a * p0.x + b * p1.x + c * p2.x,
+        a * p0.y + b * p1.y + c * p2.y,
+    )
+}
+
+/// Where, between `0.0` and `1.0`, a cubic Bézier with the given coordinates along one axis
+/// turns around.
+fn cubic_extrema(p0: f32, p1: f32, p2: f32, p3: f32) -> [Option<f32>; 2] {
+    // The derivative, divided by 3, is a * t^2 + b * t + c
+    let a = -p0 + 3.0 * p1 - 3.0 * p2 + p3;
+    let b = 2.0 * (p0 - 2.0 * p1 + p2);
+    let c = p1 - p0;
+    let in_range = |t: f32| (t > 0.0 && t < 1.0).then_some(t);
+    if a.abs() < f32::EPSILON {
+        if b.abs() < f32::EPSILON {
+            return [None, None];
+        }
+        return [in_range(-c / b), None];
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return [None, None];
+    }
+    let root = discriminant.sqrt();
+    [
+        in_range((-b + root) / (2.0 * a)),
+        in_range((-b - root) / (2.0 * a)),
+    ]
+}
+
+/// Where, between `0.0` and `1.0`, a quadratic Bézier with the given coordinates along one
+/// axis turns around.
+fn quad_extremum(p0: f32, p1: f32, p2: f32) -> Option<f32> {
+    let denominator = p0 - 2.0 * p1 + p2;
+    if denominator.abs() < f32::EPSILON {
+        return None;
+    }
+    let t = (p0 - p1) / denominator;
+    (t > 0.0 && t < 1.0).then_some(t)
+}
+
+/// The distance from `p` to the closest point of the line segment from `a` to `b`.
+fn segment_distance(p: Point, a: Point, b: Point) -> f32 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let length2 = dx * dx + dy * dy;
+    if length2 == 0.0 {
+        return p.dist(a);
+    }
+    let t = (((p.x - a.x) * dx + (p.y - a.y) * dy) / length2).clamp(0.0, 1.0);
+    p.dist(Point::new(a.x + t * dx, a.y + t * dy))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_bounds(path: &Path, pos: (f32, f32), size: (f32, f32)) {
+        let bounds = path.bounding_box().unwrap();
+        let near = |a: f32, b: f32| (a - b).abs() < 0.001;
+        assert!(
+            near(bounds.pos.x, pos.0)
+                && near(bounds.pos.y, pos.1)
+                && near(bounds.width(), size.0)
+                && near(bounds.height(), size.1),
+            "{bounds:?} != {pos:?} {size:?}"
+        );
+    }
+
+    fn square(x: f32, y: f32, size: f32) -> PathBuilder {
+        Path::builder()
+            .move_to(Point::new(x, y))
+            .line_to(Point::new(x + size, y))
+            .line_to(Point::new(x + size, y + size))
+            .line_to(Point::new(x, y + size))
+            .close()
+    }
+
+    #[test]
+    fn test_builder() {
+        let path = square(0.0, 0.0, 10.0)
+            .fill(Color::RED)
+            .fill_rule(FillRule::EvenOdd)
+            .build();
+        assert_eq!(path.commands.len(), 5);
+        assert_eq!(path.commands[0], PathCommand::MoveTo(Point::new(0.0, 0.0)));
+        assert_eq!(path.commands[4], PathCommand::ClosePath);
+        assert_eq!(path.fill, Some(Color::RED));
+        assert_eq!(path.fill_rule, FillRule::EvenOdd);
+        assert_eq!(path.stroke, None);
+        assert!(path.stroke_style().is_none());
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        assert!(Path::default().bounding_box().is_none());
+        assert_bounds(&square(5.0, 5.0, 10.0).build(), (5.0, 5.0), (10.0, 10.0));
+        assert_bounds(
+            &square(5.0, 5.0, 10.0).stroke(Color::BLACK, 2.0).build(),
+            (4.0, 4.0),
+            (12.0, 12.0),
+        );
+
+        // The control points are further out than the curve itself
+        let quad = Path::builder()
+            .move_to(Point::new(0.0, 0.0))
+            .quad_to(Point::new(10.0, 20.0), Point::new(20.0, 0.0))
+            .build();
+        assert_bounds(&quad, (0.0, 0.0), (20.0, 10.0));
+        let cubic = Path::builder()
+            .move_to(Point::new(0.0, 0.0))
+            .cubic_to(
+                Point::new(0.0, -20.0),
+                Point::new(20.0, -20.0),
+                Point::new(20.0, 0.0),
+            )
+            .build();
+        assert_bounds(&cubic, (0.0, -15.0), (20.0, 15.0));
+    }
+
+    #[test]
+    fn test_contains() {
+        // A square with another inside of it, drawn in the same direction
+        let mut path = square(0.0, 0.0, 30.0).build();
+        path.commands
+            .extend(square(10.0, 10.0, 10.0).build().commands);
+        let (hole, solid, outside) = (
+            Point::new(15.0, 15.0),
+            Point::new(5.0, 5.0),
+            Point::new(35.0, 5.0),
+        );
+        assert!(path.contains(hole));
+        assert!(path.contains(solid));
+        assert!(!path.contains(outside));
+
+        path.fill_rule = FillRule::EvenOdd;
+        assert!(!path.contains(hole));
+        assert!(path.contains(solid));
+
+        // Unclosed subpaths are filled as if closed
+        let triangle = Path::builder()
+            .move_to(Point::new(0.0, 0.0))
+            .line_to(Point::new(10.0, 0.0))
+            .line_to(Point::new(0.0, 10.0))
+            .build();
+        assert!(triangle.contains(Point::new(2.0, 2.0)));
+        assert!(!triangle.contains(Point::new(8.0, 8.0)));
+    }
+
+    #[test]
+    fn test_hit_test() {
+        let outline = square(0.0, 0.0, 10.0).stroke(Color::BLACK, 2.0).build();
+        assert!(outline.hit_test(Point::new(10.5, 5.0)));
+        // The closing edge is part of the stroke
+        assert!(outline.hit_test(Point::new(-0.5, 5.0)));
+        assert!(!outline.hit_test(Point::new(5.0, 5.0)));
+        assert!(!outline.hit_test(Point::new(12.0, 5.0)));
+
+        let filled = square(0.0, 0.0, 10.0).fill(Color::RED).build();
+        assert!(filled.hit_test(Point::new(5.0, 5.0)));
+        assert!(!filled.hit_test(Point::new(10.5, 5.0)));
+    }
+
+    #[cfg(feature = "svg-path")]
+    #[test]
+    fn test_from_svg_path_data() {
+        let path = Path::from_svg_path_data("M0,0 h10 v10 H0 z").unwrap();
+        assert_eq!(path.commands, square(0.0, 0.0, 10.0).build().commands);
+
+        let path = Path::from_svg_path_data("m5 5 l5 0 q5 0 5 5 C20 20 0 20 5 10 Z").unwrap();
+        assert_eq!(
+            path.commands,
+            vec![
+                PathCommand::MoveTo(Point::new(5.0, 5.0)),
+                PathCommand::LineTo(Point::new(10.0, 5.0)),
+                PathCommand::QuadraticBezierTo {
+                    cp: Point::new(15.0, 5.0),
+                    end: Point::new(15.0, 10.0),
+                },
+                PathCommand::CubicBezierTo {
+                    cp1: Point::new(20.0, 20.0),
+                    cp2: Point::new(0.0, 20.0),
+                    end: Point::new(5.0, 10.0),
+                },
+                PathCommand::ClosePath,
+            ]
+        );
+
+        assert_eq!(
+            Path::from_svg_path_data("L 1 2"),
+            Err(SvgPathParseError::MissingMoveTo)
+        );
+        assert_eq!(
+            Path::from_svg_path_data("M 0 0 A 1 1 0 0 0 2 2"),
+            Err(SvgPathParseError::UnsupportedCommand('A'))
+        );
+        assert_eq!(
+            Path::from_svg_path_data("M 0 0 L 1"),
+            Err(SvgPathParseError::MissingCoordinates('L'))
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use crate::{Color, Point};
+
+use super::types::{Canvas, FillStyle, StrokeStyle};
+use derive_builder::Builder;
+use femtovg::Path;
+
+#[derive(Clone, Default, Debug, PartialEq, Builder)]
+pub struct Instance {
+    /// The corners, in drawing order. The last one is joined back to the first.
+    pub points: Vec<Point>,
+    #[builder(default = "None")]
+    pub color: Option<Color>,
+    #[builder(default = "None")]
+    pub border_color: Option<Color>,
+    #[builder(default = "1.")]
+    pub border_width: f32,
+}
+
+/// A closed shape with straight sides.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Polygon {
+    pub instance_data: Instance,
+}
+
+impl Polygon {
+    /// A polygon filled with `color`, without a border.
+    pub fn new(points: Vec<Point>, color: Color) -> Self {
+        Self {
+            instance_data: Instance {
+                points,
+                color: Some(color),
+                border_color: None,
+                border_width: 1.,
+            },
+        }
+    }
+
+    /// The same as [`Polygon::new`].
+    pub fn from_points(points: Vec<Point>, color: Color) -> Self {
+        Self::new(points, color)
+    }
+
+    /// A polygon with `sides` equal sides, whose corners are `radius` from `center`. The first
+    /// corner is straight above the center, and the rest follow clockwise.
+    pub fn regular(sides: u32, radius: f32, center: Point, color: Color) -> Self {
+        let points = (0..sides)
+            .map(|i| {
+                let angle =
+                    -std::f32::consts::FRAC_PI_2 + std::f32::consts::TAU * i as f32 / sides as f32;
+                Point::new(
+                    center.x + radius * angle.cos(),
+                    center.y + radius * angle.sin(),
+                )
+            })
+            .collect();
+        Self::new(points, color)
+    }
+
+    pub fn from_instance_data(instance_data: Instance) -> Self {
+        Self { instance_data }
+    }
+
+    /// Twice the area enclosed by the points, positive when they wind clockwise on screen.
+    fn signed_area2(points: &[Point]) -> f32 {
+        (0..points.len())
+            .map(|i| {
+                let (a, b) = (points[i], points[(i + 1) % points.len()]);
+                a.x * b.y - b.x * a.y
+            })
+            .sum()
+    }
+
+    /// The area enclosed by the sides.
+    pub fn area(&self) -> f32 {
+        Self::signed_area2(&self.instance_data.points).abs() / 2.0
+    }
+
+    /// Split the polygon into triangles by ear clipping, returned as indices into `points`.
+    /// Works for convex and concave polygons, as long as their sides do not cross.
+    pub fn triangulate(&self) -> Vec<[usize; 3]> {
+        let points = &self.instance_data.points;
+        if points.len() < 3 {
+            return vec![];
+        }
+        let winding = Self::signed_area2(points).signum();
+        // The z of the cross product of `a -> b` and `b -> c`, which is positive when `b` turns
+        // the same way as the polygon winds.
+        let turn = |a: Point, b: Point, c: Point| {
+            winding * ((b.x - a.x) * (c.y - b.y) - (b.y - a.y) * (c.x - b.x))
+        };
+
+        let mut remaining: Vec<usize> = (0..points.len()).collect();
+        let mut triangles = Vec::with_capacity(points.len() - 2);
+        while remaining.len() > 3 {
+            let n = remaining.len();
+            let ear = (0..n).find(|&i| {
+                let (a, b, c) = (
+                    remaining[(i + n - 1) % n],
+                    remaining[i],
+                    remaining[(i + 1) % n],
+                );
+                let (pa, pb, pc) = (points[a], points[b], points[c]);
+                // An ear is a convex corner without any other corner inside of it
+                turn(pa, pb, pc) > 0.0
+                    && remaining.iter().all(|&j| {
+                        let p = points[j];
+                        [a, b, c].contains(&j)
+                            || p == pa
+                            || p == pb
+                            || p == pc
+                            || turn(pa, pb, p) < 0.0
+                            || turn(pb, pc, p) < 0.0
+                            || turn(pc, pa, p) < 0.0
+                    })
+            });
+            match ear {
+                Some(i) => {
+                    triangles.push([
+                        remaining[(i + n - 1) % n],
+                        remaining[i],
+                        remaining[(i + 1) % n],
+                    ]);
+                    remaining.remove(i);
+                }
+                // Only left with corners in a straight line, which enclose nothing
+                None => return triangles,
+            }
+        }
+        triangles.push([remaining[0], remaining[1], remaining[2]]);
+        triangles
+    }
+
+    /// The fill inside the sides, if any.
+    pub fn fill_style(&self) -> Option<FillStyle> {
+        self.instance_data.color.map(FillStyle::new)
+    }
+
+    /// The stroke of the sides, if any.
+    pub fn stroke_style(&self) -> Option<StrokeStyle> {
+        let Instance {
+            border_color,
+            border_width,
+            ..
+        } = self.instance_data;
+        border_color
+            .filter(|_| border_width > 0.0)
+            .map(|color| StrokeStyle::new(color, border_width))
+    }
+
+    pub fn render(&self, canvas: &mut Canvas) {
+        let points = &self.instance_data.points;
+        if points.len() < 3 {
+            return;
+        }
+
+        let mut path = Path::new();
+        path.move_to(points[0].x, points[0].y);
+        for p in points.iter().skip(1) {
+            path.line_to(p.x, p.y);
+        }
+        path.close();
+
+        if let Some(fill) = self.fill_style() {
+            canvas.fill_path(&path, &fill.paint());
+        }
+        if let Some(stroke) = self.stroke_style() {
+            canvas.stroke_path(&path, &stroke.paint());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle_area(polygon: &Polygon, [a, b, c]: [usize; 3]) -> f32 {
+        let points = &polygon.instance_data.points;
+        Polygon::signed_area2(&[points[a], points[b], points[c]]).abs() / 2.0
+    }
+
+    fn assert_near(a: f32, b: f32) {
+        assert!((a - b).abs() < 0.001, "{a} != {b}");
+    }
+
+    #[test]
+    fn test_triangle() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(0.0, 10.0),
+        ];
+        let polygon = Polygon::from_points(points, Color::RED);
+        assert_eq!(polygon.area(), 50.0);
+        assert_eq!(polygon.triangulate(), vec![[0, 1, 2]]);
+
+        let line =
+            Polygon::from_points(vec![Point::new(0.0, 0.0), Point::new(1.0, 1.0)], Color::RED);
+        assert!(line.triangulate().is_empty());
+    }
+
+    #[test]
+    fn test_square() {
+        let square = Polygon::regular(4, 10.0, Point::new(0.0, 0.0), Color::RED);
+        assert_near(square.instance_data.points[0].x, 0.0);
+        assert_near(square.instance_data.points[0].y, -10.0);
+        assert_near(square.instance_data.points[1].x, 10.0);
+        assert_near(square.area(), 200.0);
+
+        // Counter-clockwise, with a redundant corner in the middle of the bottom side
+        let square = Polygon::from_points(
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(0.0, 10.0),
+                Point::new(5.0, 10.0),
+                Point::new(10.0, 10.0),
+                Point::new(10.0, 0.0),
+            ],
+            Color::RED,
+        );
+        let triangles = square.triangulate();
+        assert_eq!(triangles.len(), 3);
+        let area: f32 = triangles.iter().map(|t| triangle_area(&square, *t)).sum();
+        assert_eq!(area, 100.0);
+    }
+
+    #[test]
+    fn test_hexagon() {
+        let center = Point::new(50.0, 50.0);
+        let hexagon = Polygon::regular(6, 20.0, center, Color::BLUE);
+        let points = &hexagon.instance_data.points;
+        assert_eq!(points.len(), 6);
+        for (i, p) in points.iter().enumerate() {
+            assert_near(p.dist(center), 20.0);
+            // Every side is as long as the radius
+            assert_near(p.dist(points[(i + 1) % 6]), 20.0);
+        }
+        assert_near(hexagon.area(), 3.0 * 3.0_f32.sqrt() / 2.0 * 400.0);
+
+        let triangles = hexagon.triangulate();
+        assert_eq!(triangles.len(), 4);
+        let area: f32 = triangles.iter().map(|t| triangle_area(&hexagon, *t)).sum();
+        assert_near(area, hexagon.area());
+    }
+
+    #[test]
+    fn test_concave() {
+        // An arrow head, whose corner at (5, 5) points inwards
+        let arrow = Polygon::from_points(
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(5.0, 5.0),
+                Point::new(10.0, 0.0),
+                Point::new(5.0, 10.0),
+            ],
+            Color::RED,
+        );
+        assert_eq!(arrow.area(), 25.0);
+        let triangles = arrow.triangulate();
+        assert_eq!(triangles.len(), 2);
+        for t in triangles.iter() {
+            // The inward corner can't be clipped as an ear
+            assert_ne!(t[1], 1);
+            assert_eq!(triangle_area(&arrow, *t), 12.5);
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{Color, Point, Pos, Scale, AABB};
+
+use super::types::{Canvas, LineCap, LineJoin, StrokeStyle};
+use femtovg::Path;
+
+/// Connected line segments through `points`, drawn as a single stroke so that the corners
+/// between them are joined with `line_join`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Polyline {
+    pub points: Vec<Point>,
+    pub color: Color,
+    pub stroke_width: f32,
+    pub line_cap: LineCap,
+    pub line_join: LineJoin,
+}
+
+impl Polyline {
+    /// A polyline with round caps and joins, which suits charts and sparklines.
+    pub fn new(points: Vec<Point>, color: Color, stroke_width: f32) -> Self {
+        Self {
+            points,
+            color,
+            stroke_width,
+            line_cap: LineCap::Round,
+            line_join: LineJoin::Round,
+        }
+    }
+
+    pub fn stroke_style(&self) -> StrokeStyle {
+        StrokeStyle::new(self.color, self.stroke_width)
+            .cap(self.line_cap)
+            .join(self.line_join)
+    }
+
+    /// The points that are stroked, without repeats. A zero-length segment has no direction,
+    /// so it would leave the corner around it unjoined.
+    pub fn vertices(&self) -> Vec<Point> {
+        let mut vertices = self.points.clone();
+        vertices.dedup();
+        vertices
+    }
+
+    /// The bounds of the points, grown by half the stroke width. Miters that reach further
+    /// out at sharp corners are not included.
+    pub fn bounding_box(&self) -> Option<AABB> {
+        let first = self.points.first()?;
+        let (mut min, mut max) = (*first, *first);
+        for p in self.points.iter() {
+            min = Point::new(min.x.min(p.x), min.y.min(p.y));
+            max = Point::new(max.x.max(p.x), max.y.max(p.y));
+        }
+        let half_stroke = self.stroke_width / 2.0;
+        Some(AABB::new(
+            Pos::new(min.x - half_stroke, min.y - half_stroke, 0.0),
+            Scale::new(
+                max.x - min.x + half_stroke * 2.0,
+                max.y - min.y + half_stroke * 2.0,
+            ),
+        ))
+    }
+
+    pub fn render(&self, canvas: &mut Canvas) {
+        let vertices = self.vertices();
+        if vertices.is_empty() || self.stroke_width <= 0.0 || self.color.a == 0.0 {
+            return;
+        }
+        // One path for the whole polyline is tessellated and drawn in a single call
+        let mut path = Path::new();
+        path.move_to(vertices[0].x, vertices[0].y);
+        for p in vertices.iter().skip(1) {
+            path.line_to(p.x, p.y);
+        }
+        canvas.stroke_path(&path, &self.stroke_style().paint());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vertices() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+            Point::new(0.0, 0.0),
+        ];
+        let polyline = Polyline::new(points.clone(), Color::BLACK, 2.0);
+        // Every corner joins the end of one segment to the start of the next, with no
+        // zero-length segment between them to leave a gap
+        let vertices = polyline.vertices();
+        assert_eq!(vertices, vec![points[0], points[1], points[3], points[4]]);
+        assert!(vertices.windows(2).all(|w| w[0] != w[1]));
+        assert_eq!(polyline.stroke_style().join, LineJoin::Round);
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        assert!(Polyline::default().bounding_box().is_none());
+        let polyline = Polyline::new(
+            vec![
+                Point::new(5.0, 10.0),
+                Point::new(15.0, 0.0),
+                Point::new(25.0, 20.0),
+            ],
+            Color::BLACK,
+            4.0,
+        );
+        assert_eq!(
+            polyline.bounding_box(),
+            Some(AABB::new(Pos::new(3.0, -2.0, 0.0), Scale::new(24.0, 24.0)))
+        );
+    }
+}
@@ -1,11 +1,16 @@
-use super::types::Canvas;
+use super::line::dash_array_segments;
 use super::types::{self, Corners, Edges};
+use super::types::{Canvas, StrokeStyle};
+use crate::style::BoxShadow;
 use crate::types::{Color, Point, Pos, Scale, AABB};
 use bytemuck::{Pod, Zeroable};
 use derive_builder::Builder;
 use femtovg::{Color as fem_color, CompositeOperation, ImageId, Paint, Path};
+use std::hash::{Hash, Hasher};
 
-#[derive(Debug, Clone)]
+/// A gradient fill. Points are relative to the top left of the filled [`Rect`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Gradient {
     Linear {
         start: Point,
@@ -19,26 +24,118 @@ pub enum Gradient {
     },
 }
 
+impl Gradient {
+    /// The stops in order, with offsets clamped to `0.0`--`1.0`.
+    pub fn stops(&self) -> Vec<(f32, Color)> {
+        let (Gradient::Linear { stops, .. } | Gradient::Radial { stops, .. }) = self;
+        let mut stops: Vec<(f32, Color)> = stops
+            .iter()
+            .map(|(offset, color)| (offset.clamp(0.0, 1.0), *color))
+            .collect();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        stops
+    }
+
+    /// The paint of the gradient, with its points relative to `origin`. A single stop is a
+    /// solid color, and no stops are transparent.
+    pub(crate) fn paint(&self, origin: Pos) -> Paint {
+        let stops = self.stops();
+        if stops.len() < 2 {
+            let color = stops
+                .first()
+                .map_or(Color::TRANSPARENT, |(_, color)| *color);
+            return Paint::color(color.into());
+        }
+        let stops = stops
+            .into_iter()
+            .map(|(offset, c)| (offset, fem_color::from(c)));
+        match self {
+            Gradient::Linear { start, end, .. } => Paint::linear_gradient_stops(
+                origin.x + start.x,
+                origin.y + start.y,
+                origin.x + end.x,
+                origin.y + end.y,
+                stops,
+            ),
+            Gradient::Radial { center, radius, .. } => Paint::radial_gradient_stops(
+                origin.x + center.x,
+                origin.y + center.y,
+                radius.0,
+                radius.1,
+                stops,
+            ),
+        }
+    }
+}
+
+impl Hash for Gradient {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let (points, radius, stops) = match self {
+            Gradient::Linear { start, end, stops } => ([start, end], (0.0, 0.0), stops),
+            Gradient::Radial {
+                center,
+                radius,
+                stops,
+            } => ([center, center], *radius, stops),
+        };
+        points.hash(state);
+        ((radius.0 * 100.0) as i32, (radius.1 * 100.0) as i32).hash(state);
+        for (offset, color) in stops.iter() {
+            ((offset * 1000.0) as i32).hash(state);
+            color.hash(state);
+        }
+    }
+}
+
+/// What a [`Rect`] is filled with.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RectFill {
+    Solid(Color),
+    Gradient(Gradient),
+}
+
+impl From<Color> for RectFill {
+    fn from(c: Color) -> Self {
+        Self::Solid(c)
+    }
+}
+
+impl From<Gradient> for RectFill {
+    fn from(g: Gradient) -> Self {
+        Self::Gradient(g)
+    }
+}
+
 #[derive(Clone, Debug, Builder)]
 pub struct Instance {
     pub pos: Pos,
     pub scale: Scale,
     #[builder(default = "Color::TRANSPARENT")]
     pub color: Color,
-    #[builder(default = "(0., 0., 0., 0.)")]
+    /// `(top_left, top_right, bottom_right, bottom_left)`. The setter also takes a
+    /// [`BorderRadius`](crate::style::BorderRadius).
+    #[builder(setter(into), default = "(0., 0., 0., 0.)")]
     pub radius: (f32, f32, f32, f32),
     #[builder(default = "Color::TRANSPARENT")]
     pub border_color: Color,
     #[builder(default = "(0., 0., 0., 0.)")]
     pub border_size: (f32, f32, f32, f32),
+    /// Dash arrays for the `[top, right, bottom, left]` sides, with the same semantics as the dash
+    /// of a [`Line`](super::line::Line). Sides without one are solid.
+    #[builder(default = "None")]
+    pub border_dash: Option<[Option<Vec<f32>>; 4]>,
     #[builder(default = "None")]
     pub bg_image: Option<ImageId>,
+    /// Takes the place of `color` when set.
     #[builder(default = "None")]
     pub gradient: Option<Gradient>,
     #[builder(default = "CompositeOperation::SourceOver")]
     pub composite_operation: CompositeOperation,
     #[builder(default = "None")]
     pub scissor: Option<bool>,
+    /// Drawn behind the rect.
+    #[builder(default = "None")]
+    pub shadow: Option<BoxShadow>,
 }
 
 #[derive(Debug, Clone)]
@@ -47,7 +144,11 @@ pub struct Rect {
 }
 
 impl Rect {
-    pub fn new(pos: Pos, scale: Scale, color: Color) -> Self {
+    pub fn new<F: Into<RectFill>>(pos: Pos, scale: Scale, fill: F) -> Self {
+        let (color, gradient) = match fill.into() {
+            RectFill::Solid(c) => (c, None),
+            RectFill::Gradient(g) => (Color::TRANSPARENT, Some(g)),
+        };
         Self {
             instance_data: Instance {
                 pos,
@@ -57,9 +158,11 @@ impl Rect {
                 bg_image: None,
                 border_color: Color::TRANSPARENT,
                 border_size: (0., 0., 0., 0.),
-                gradient: None,
+                border_dash: None,
+                gradient,
                 composite_operation: CompositeOperation::SourceOver,
                 scissor: None,
+                shadow: None,
             },
         }
     }
@@ -68,6 +171,121 @@ impl Rect {
         Self { instance_data }
     }
 
+    /// A transparent rect with a 1px dashed border of `color`, e.g. to show a selection.
+    pub fn selection_box(aabb: AABB, color: Color) -> Self {
+        let mut rect = Self::new(aabb.pos, aabb.size(), Color::TRANSPARENT);
+        rect.instance_data.border_color = color;
+        rect.instance_data.border_size = (1., 1., 1., 1.);
+        rect.instance_data.border_dash = Some(std::array::from_fn(|_| Some(vec![4.0, 4.0])));
+        rect
+    }
+
+    /// The stroke of each side of the border, in the order of `border_size`. Sides without a
+    /// border are `None`.
+    pub fn border_styles(&self) -> [Option<StrokeStyle>; 4] {
+        let Instance {
+            border_color,
+            border_size,
+            border_dash,
+            ..
+        } = &self.instance_data;
+        let widths = [border_size.0, border_size.1, border_size.2, border_size.3];
+        // The index in `border_dash` of each side, in the order of `border_size`
+        let dash_sides = [0, 3, 2, 1];
+        std::array::from_fn(|i| {
+            (widths[i] > 0.).then(|| StrokeStyle {
+                dash: border_dash.as_ref().and_then(|d| d[dash_sides[i]].clone()),
+                ..StrokeStyle::new(*border_color, widths[i])
+            })
+        })
+    }
+
+    /// The `(start, end)` segments of each side of the border, in the order of `border_size`.
+    fn border_segments(&self) -> [Vec<(Point, Point)>; 4] {
+        let Instance { pos, scale, .. } = &self.instance_data;
+        let (left, top) = (pos.x, pos.y);
+        let (right, bottom) = (pos.x + scale.width, pos.y + scale.height);
+        let sides = [
+            (Point::new(left, top), Point::new(right, top)),
+            (Point::new(left, top), Point::new(left, bottom)),
+            (Point::new(left, bottom), Point::new(right, bottom)),
+            (Point::new(right, top), Point::new(right, bottom)),
+        ];
+
+        let styles = self.border_styles();
+        std::array::from_fn(|i| {
+            let (from, to) = sides[i];
+            match &styles[i] {
+                None => vec![],
+                Some(StrokeStyle {
+                    dash: Some(dash), ..
+                }) => dash_array_segments(from, to, dash, 0.0),
+                Some(_) => vec![(from, to)],
+            }
+        })
+    }
+
+    /// The current fill: the gradient if there is one, otherwise the solid color.
+    pub fn fill(&self) -> RectFill {
+        match &self.instance_data.gradient {
+            Some(g) => RectFill::Gradient(g.clone()),
+            None => RectFill::Solid(self.instance_data.color),
+        }
+    }
+
+    /// The shape that casts the shadow: the rect moved by the shadow's offset and grown by its
+    /// spread, with the largest corner radius grown to match. `None` without a visible shadow.
+    pub fn shadow_bounds(&self) -> Option<(AABB, f32)> {
+        let Instance {
+            pos,
+            scale,
+            radius,
+            shadow,
+            ..
+        } = &self.instance_data;
+        let shadow = shadow.filter(|s| s.is_visible())?;
+        let spread = shadow.spread.max(-scale.width.min(scale.height) / 2.0);
+        let bounds = AABB::new(
+            Pos::new(
+                pos.x + shadow.offset_x - spread,
+                pos.y + shadow.offset_y - spread,
+                pos.z,
+            ),
+            Scale::new(scale.width + spread * 2.0, scale.height + spread * 2.0),
+        );
+        let radius = radius.0.max(radius.1).max(radius.2).max(radius.3);
+        Some((bounds, (radius + spread).max(0.0)))
+    }
+
+    fn render_shadow(&self, canvas: &mut Canvas) {
+        let (bounds, radius, shadow) = match (self.shadow_bounds(), self.instance_data.shadow) {
+            (Some((bounds, radius)), Some(shadow)) => (bounds, radius, shadow),
+            _ => return,
+        };
+        let (x, y) = (bounds.pos.x, bounds.pos.y);
+        let (width, height) = (bounds.width(), bounds.height());
+        let blur = shadow.blur_radius.max(0.0);
+        let mut path = Path::new();
+        if blur == 0.0 {
+            path.rounded_rect(x, y, width, height, radius);
+            canvas.fill_path(&path, &Paint::color(shadow.color.into()));
+        } else {
+            // Fades out from `blur` inside the edge of the shape to `blur` outside of it
+            path.rect(x - blur, y - blur, width + blur * 2.0, height + blur * 2.0);
+            let paint = Paint::box_gradient(
+                x,
+                y,
+                width,
+                height,
+                radius,
+                blur * 2.0,
+                shadow.color.into(),
+                Color::TRANSPARENT.into(),
+            );
+            canvas.fill_path(&path, &paint);
+        }
+    }
+
     pub fn render(&self, canvas: &mut Canvas) {
         let Instance {
             pos,
@@ -75,16 +293,16 @@ impl Rect {
             color,
             radius,
             bg_image,
-            border_color,
-            border_size,
             gradient,
             composite_operation,
             scissor,
+            ..
         } = self.instance_data.clone();
         let origin = pos;
         let size = scale;
 
         canvas.global_composite_operation(composite_operation);
+        self.render_shadow(canvas);
         let mut path = Path::new();
         path.rounded_rect_varying(
             origin.x,
@@ -108,7 +326,10 @@ impl Rect {
                 0.0,
                 1.0,
             ),
-            None => Paint::color(color.into()),
+            None => match &gradient {
+                Some(gradient) => gradient.paint(origin),
+                None => Paint::color(color.into()),
+            },
         };
         canvas.fill_path(&path, &background);
 
@@ -117,45 +338,16 @@ impl Rect {
 
         // canvas.stroke_path(&path, &paint);
 
-        //Add borders
-        //border top
-        if border_size.0 > 0. {
-            let mut path = Path::new();
-            path.move_to(origin.x, origin.y);
-            path.line_to(origin.x + size.width, origin.y);
-            let mut paint = Paint::color(border_color.into());
-            paint.set_line_width(border_size.0);
-            canvas.stroke_path(&path, &paint);
-        }
-
-        //border left
-        if border_size.1 > 0. {
-            let mut path = Path::new();
-            path.move_to(origin.x, origin.y);
-            path.line_to(origin.x, origin.y + size.height);
-            let mut paint = Paint::color(border_color.into());
-            paint.set_line_width(border_size.1);
-            canvas.stroke_path(&path, &paint);
-        }
-
-        //border bottom
-        if border_size.2 > 0. {
-            let mut path = Path::new();
-            path.move_to(origin.x, origin.y + size.height);
-            path.line_to(origin.x + size.width, origin.y + size.height);
-            let mut paint = Paint::color(border_color.into());
-            paint.set_line_width(border_size.2);
-            canvas.stroke_path(&path, &paint);
-        }
-
-        //border right
-        if border_size.3 > 0. {
-            let mut path = Path::new();
-            path.move_to(origin.x + size.width, origin.y);
-            path.line_to(origin.x + size.width, origin.y + size.height);
-            let mut paint = Paint::color(border_color.into());
-            paint.set_line_width(border_size.3);
-            canvas.stroke_path(&path, &paint);
+        //Add borders: top, left, bottom, right
+        for (segments, stroke) in self.border_segments().iter().zip(self.border_styles()) {
+            if let Some(stroke) = stroke {
+                let mut path = Path::new();
+                for (a, b) in segments {
+                    path.move_to(a.x, a.y);
+                    path.line_to(b.x, b.y);
+                }
+                canvas.stroke_path(&path, &stroke.paint());
+            }
         }
 
         canvas.global_composite_operation(CompositeOperation::SourceOver);
@@ -182,25 +374,124 @@ impl Rect {
             }
             None => (),
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rect_fill() {
+        let rect = Rect::new(Pos::default(), Scale::new(10.0, 10.0), Color::RED);
+        assert_eq!(rect.fill(), RectFill::Solid(Color::RED));
+        assert_eq!(rect.instance_data.gradient, None);
+
+        let gradient = Gradient::Linear {
+            start: Point::new(0.0, 0.0),
+            end: Point::new(10.0, 0.0),
+            stops: vec![(0.0, Color::RED), (1.0, Color::BLUE)],
+        };
+        let rect = Rect::new(Pos::default(), Scale::new(10.0, 10.0), gradient.clone());
+        assert_eq!(rect.fill(), RectFill::Gradient(gradient));
+    }
+
+    #[test]
+    fn test_gradient_stops() {
+        let gradient = Gradient::Radial {
+            center: Point::new(5.0, 5.0),
+            radius: (0.0, 5.0),
+            stops: vec![(1.5, Color::BLUE), (0.5, Color::GREEN), (-1.0, Color::RED)],
+        };
+        // Out of range offsets are clamped, and the stops are sorted
+        assert_eq!(
+            gradient.stops(),
+            vec![(0.0, Color::RED), (0.5, Color::GREEN), (1.0, Color::BLUE)]
+        );
+    }
+
+    #[test]
+    fn test_border_dash() {
+        let mut rect = Rect::new(Pos::default(), Scale::new(20.0, 20.0), Color::RED);
+        rect.instance_data.border_size = (1., 1., 1., 1.);
+        rect.instance_data.border_dash = Some(std::array::from_fn(|_| Some(vec![5.0, 5.0])));
+
+        for side in rect.border_segments() {
+            // Dashes at 0, 10 and 20, with 5px transparent gaps between them
+            assert_eq!(side.len(), 3);
+            for (a, b) in side.iter().take(2) {
+                assert_eq!(a.dist(*b), 5.0);
+            }
+            for w in side.windows(2) {
+                assert_eq!(w[0].1.dist(w[1].0), 5.0);
+            }
+        }
+
+        // Sides without a dash array are solid, and sides without a border are not drawn
+        rect.instance_data.border_dash = Some([None, Some(vec![5.0, 5.0]), None, None]);
+        rect.instance_data.border_size = (1., 1., 0., 1.);
+        let [top, left, bottom, right] = rect.border_segments();
+        assert_eq!(top, vec![(Point::new(0.0, 0.0), Point::new(20.0, 0.0))]);
+        assert_eq!(left, vec![(Point::new(0.0, 0.0), Point::new(0.0, 20.0))]);
+        assert_eq!(right.len(), 3);
+        assert!(bottom.is_empty());
+    }
 
-        //Add gradient
-        // match gradient {
-        //     Some(gradient_type) => match gradient_type {
-        //         Gradient::Linear { start, end, stops } => {
-        //             let paint = Paint::linear_gradient_stops(start.x, start.y, end.x, end.y, stops);
-        //             canvas.fill_path(&path, &paint);
-        //         }
-        //         Gradient::Radial {
-        //             center,
-        //             radius,
-        //             stops,
-        //         } => {
-        //             let paint =
-        //                 Paint::radial_gradient_stops(center.x, center.y, radius.0, radius.1, stops);
-        //             canvas.fill_path(&path, &paint);
-        //         }
-        //     },
-        //     None => (),
-        // }
+    #[test]
+    fn test_selection_box() {
+        let aabb = AABB::new(Pos::new(10.0, 10.0, 0.0), Scale::new(40.0, 20.0));
+        let rect = Rect::selection_box(aabb, Color::BLUE);
+        assert_eq!(rect.fill(), RectFill::Solid(Color::TRANSPARENT));
+        assert_eq!(rect.instance_data.border_size, (1., 1., 1., 1.));
+        assert!(rect.border_segments().iter().all(|side| side.len() > 1));
+    }
+
+    #[test]
+    fn test_shadow_bounds() {
+        let mut rect = Rect::new(
+            Pos::new(10.0, 10.0, 0.0),
+            Scale::new(40.0, 20.0),
+            Color::RED,
+        );
+        rect.instance_data.radius = (4., 2., 0., 0.);
+        assert_eq!(rect.shadow_bounds(), None);
+
+        rect.instance_data.shadow = Some(BoxShadow::new(2.0, 4.0, 6.0, 3.0, Color::BLACK));
+        let (bounds, radius) = rect.shadow_bounds().unwrap();
+        assert_eq!(bounds.pos, Pos::new(9.0, 11.0, 0.0));
+        assert_eq!(bounds.size(), Scale::new(46.0, 26.0));
+        assert_eq!(radius, 7.0);
+
+        // A negative spread shrinks the shadow, but no further than to nothing
+        rect.instance_data.shadow = Some(BoxShadow::new(0.0, 0.0, 6.0, -30.0, Color::BLACK));
+        let (bounds, radius) = rect.shadow_bounds().unwrap();
+        assert_eq!(bounds.size(), Scale::new(20.0, 0.0));
+        assert_eq!(radius, 0.0);
+
+        rect.instance_data.shadow = Some(BoxShadow::NONE);
+        assert_eq!(rect.shadow_bounds(), None);
+    }
+
+    #[test]
+    fn test_border_styles() {
+        let mut rect = Rect::new(Pos::default(), Scale::new(20.0, 20.0), Color::RED);
+        assert_eq!(rect.border_styles(), [None, None, None, None]);
+
+        rect.instance_data.border_color = Color::BLUE;
+        rect.instance_data.border_size = (1., 2., 0., 3.);
+        // Dashed on the top and the right
+        rect.instance_data.border_dash =
+            Some([Some(vec![2.0, 2.0]), Some(vec![5.0, 5.0]), None, None]);
+        let [top, left, bottom, right] = rect.border_styles();
+        assert_eq!(
+            top,
+            Some(StrokeStyle::new(Color::BLUE, 1.).dash(vec![2.0, 2.0]))
+        );
+        assert_eq!(left, Some(StrokeStyle::new(Color::BLUE, 2.)));
+        assert_eq!(bottom, None);
+        assert_eq!(
+            right,
+            Some(StrokeStyle::new(Color::BLUE, 3.).dash(vec![5.0, 5.0]))
+        );
     }
 }
@@ -1,13 +1,60 @@
 use super::types::Canvas;
 use crate::{
-    renderer::text::TextRenderer,
-    style::FontWeight,
-    types::{Color, Pos},
+    renderer::text::{LineSpan, TextRenderer},
+    style::{
+        FontStyle, FontWeight, OverflowWrap, TextDecoration, TextOverflow, TextShadow, WordBreak,
+    },
+    types::{Color, Point, Pos},
     Scale,
 };
 use cosmic_text::FontSystem;
 use derive_builder::Builder;
-use femtovg::{Align, Paint};
+use femtovg::{Align, Paint, Path};
+
+/// The direction of a paragraph of text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TextDirection {
+    #[default]
+    Ltr,
+    Rtl,
+    /// The direction of the first strongly directional character.
+    Auto,
+}
+
+impl TextDirection {
+    /// Whether `text` is laid out right-to-left.
+    pub fn is_rtl(self, text: &str) -> bool {
+        match self {
+            TextDirection::Ltr => false,
+            TextDirection::Rtl => true,
+            TextDirection::Auto => text
+                .chars()
+                .find(|c| is_strong_rtl(*c) || c.is_alphabetic())
+                .map(is_strong_rtl)
+                .unwrap_or(false),
+        }
+    }
+
+    /// The physical alignment of text with the logical alignment `align`: `Left` means the start of the paragraph, and `Right` its end.
+    pub fn physical_align(self, align: Align, text: &str) -> Align {
+        match (align, self.is_rtl(text)) {
+            (Align::Left, true) => Align::Right,
+            (Align::Right, true) => Align::Left,
+            (align, _) => align,
+        }
+    }
+}
+
+/// Characters of the Hebrew, Arabic, Syriac, Thaana, NKo, Samaritan, and Mandaic scripts, and of their presentation forms.
+fn is_strong_rtl(c: char) -> bool {
+    matches!(c,
+        '\u{0590}'..='\u{08FF}'
+        | '\u{FB1D}'..='\u{FDFF}'
+        | '\u{FE70}'..='\u{FEFF}'
+        | '\u{10800}'..='\u{10FFF}'
+        | '\u{1E800}'..='\u{1EFFF}'
+    ) && c.is_alphabetic()
+}
 
 #[derive(Clone, Debug, PartialEq, Builder)]
 pub struct Instance {
@@ -17,16 +64,48 @@ pub struct Instance {
     pub font: Option<String>,
     #[builder(default = "FontWeight::Normal")]
     pub weight: FontWeight,
+    #[builder(default = "FontStyle::Normal")]
+    pub font_style: FontStyle,
+    /// Lines drawn along the text, in its color.
+    #[builder(default)]
+    pub decoration: TextDecoration,
     #[builder(default = "Default::default()")]
     pub color: Color,
     #[builder(default = "12.0")]
     pub font_size: f32,
     #[builder(default = "18.0")]
     pub line_height: f32,
+    /// Relative to the start of the paragraph, which is on the right for right-to-left text.
     #[builder(default = "Align::Left")]
     pub align: Align,
+    #[builder(default)]
+    pub direction: TextDirection,
+    #[builder(default)]
+    pub word_break: WordBreak,
+    #[builder(default)]
+    pub overflow_wrap: OverflowWrap,
+    /// Sorted x positions, relative to the start of the line, that tab characters advance to.
+    /// Past the last one, the stops repeat every `tab_stops.last()` pixels.
+    #[builder(default)]
+    pub tab_stops: Vec<f32>,
+    /// Without `tab_stops`, tabs advance to the next multiple of this many space widths.
+    #[builder(default = "4")]
+    pub tab_size: u8,
+    /// Extra space after every glyph, in pixels. Negative values bring glyphs closer together.
+    #[builder(default = "0.0")]
+    pub letter_spacing: f32,
+    /// How text that is wider than `scale`, or has more than `max_lines` lines, is cut off.
+    #[builder(default)]
+    pub overflow: TextOverflow,
+    /// The most lines to show. Further lines are dropped, or replaced by an ellipsis at the end
+    /// of the last line with [`TextOverflow::Ellipsis`].
+    #[builder(default)]
+    pub max_lines: Option<u32>,
     #[builder(default = "String::new()")]
     pub text: String,
+    /// Spread the glyphs out over this many pixels, as for a [`TextShadow`].
+    #[builder(default = "0.0")]
+    pub blur_radius: f32,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -48,9 +127,20 @@ impl Text {
                 font_size: 12.0,
                 font: None,
                 weight: FontWeight::Normal,
+                font_style: FontStyle::Normal,
+                decoration: TextDecoration::NONE,
                 line_height: 18.0,
                 align: Align::Left,
+                direction: TextDirection::Ltr,
+                word_break: WordBreak::Normal,
+                overflow_wrap: OverflowWrap::Normal,
+                tab_stops: vec![],
+                tab_size: 4,
+                letter_spacing: 0.0,
+                overflow: TextOverflow::Clip,
+                max_lines: None,
                 text: text.into(),
+                blur_radius: 0.0,
             },
         }
     }
@@ -59,14 +149,268 @@ impl Text {
         Self { instance_data }
     }
 
+    /// A copy of this text that draws `shadow`, to be rendered before it.
+    pub fn shadow(&self, shadow: &TextShadow) -> Self {
+        let mut instance_data = self.instance_data.clone();
+        instance_data.pos.x += shadow.offset_x;
+        instance_data.pos.y += shadow.offset_y;
+        instance_data.color = shadow.color;
+        instance_data.blur_radius = shadow.blur_radius;
+        Self { instance_data }
+    }
+
     pub fn render(&self, canvas: &mut Canvas, text_renderer: &mut TextRenderer) {
-        let Instance { color, .. } = self.instance_data;
+        let Instance {
+            pos,
+            scale,
+            color,
+            blur_radius,
+            overflow,
+            ..
+        } = self.instance_data;
+
+        let fade_top = if overflow == TextOverflow::Fade {
+            text_renderer.overflow_top(&self.instance_data)
+        } else {
+            None
+        };
 
-        if let Ok(draw_commands) = text_renderer.draw_text(canvas, self.instance_data.clone()) {
+        let offsets = blur_offsets(blur_radius);
+        let mut color = color;
+        // The samples overlap at the center, where they should add up to the original alpha
+        color.a = 1.0 - (1.0 - color.a).powf(1.0 / offsets.len() as f32);
+        for (dx, dy) in offsets {
+            let mut instance_data = self.instance_data.clone();
+            instance_data.pos.x += dx;
+            instance_data.pos.y += dy;
+            let fade_top = match fade_top {
+                Some(fade_top) => fade_top,
+                None => {
+                    Self::draw(canvas, text_renderer, instance_data, color);
+                    continue;
+                }
+            };
+
+            // Lines above the one that is cut off are drawn whole. Then draw all of that line
+            // (and of any below it) up to the fade, and each strip of the fade more transparent
+            // than the last. Otherwise the text is clipped where it always is.
+            let (mut top, bottom) = (pos.y - scale.height, pos.y + scale.height * 2.0);
+            let left = pos.x - scale.width;
+            if fade_top > 0.0 {
+                canvas.save();
+                canvas.intersect_scissor(left, top, scale.width * 3.0, pos.y + fade_top - top);
+                Self::draw(canvas, text_renderer, instance_data.clone(), color);
+                canvas.restore();
+                top = pos.y + fade_top;
+            }
+            let height = bottom - top;
+            for (x, width, alpha) in fade_strips(pos.x, scale.width) {
+                let mut strip_color = color;
+                strip_color.a *= alpha;
+                canvas.save();
+                if alpha == 1.0 {
+                    canvas.intersect_scissor(left, top, x + width - left, height);
+                } else {
+                    canvas.intersect_scissor(x, top, width, height);
+                }
+                Self::draw(canvas, text_renderer, instance_data.clone(), strip_color);
+                canvas.restore();
+            }
+        }
+    }
+
+    /// Draw the glyphs and decorations of `instance_data`, in `color`.
+    fn draw(
+        canvas: &mut Canvas,
+        text_renderer: &mut TextRenderer,
+        instance_data: Instance,
+        color: Color,
+    ) {
+        let Instance {
+            decoration,
+            font_size,
+            ..
+        } = instance_data;
+        if let Ok((draw_commands, spans)) = text_renderer.draw_text(canvas, instance_data) {
             for (_, cmds) in draw_commands.into_iter() {
                 let temp_paint = Paint::color(color.into());
                 canvas.draw_glyph_commands(cmds, &temp_paint, 1.0);
             }
+
+            let lines = decoration_lines(decoration, font_size, &spans);
+            if !lines.is_empty() {
+                let mut path = Path::new();
+                for (from, to) in lines {
+                    path.move_to(from.x, from.y);
+                    path.line_to(to.x, to.y);
+                }
+                let mut paint = Paint::color(color.into());
+                paint.set_line_width(decoration_thickness(font_size));
+                canvas.stroke_path(&path, &paint);
+            }
+        }
+    }
+}
+
+/// How wide the fade of [`TextOverflow::Fade`] is.
+const FADE_WIDTH: f32 = 20.0;
+/// How many steps of transparency the fade is drawn in.
+const FADE_STEPS: usize = 5;
+
+/// `(x, width, alpha)` of the parts of text from `x` that is `width` wide, that together fade
+/// it out towards its right edge: first everything before the fade at full alpha, and then the
+/// steps of the fade. Text past the right edge is not drawn.
+fn fade_strips(x: f32, width: f32) -> Vec<(f32, f32, f32)> {
+    let fade_width = FADE_WIDTH.min(width);
+    let fade_start = x + width - fade_width;
+    let step = fade_width / FADE_STEPS as f32;
+    let mut strips = vec![(x, width - fade_width, 1.0)];
+    for i in 0..FADE_STEPS {
+        let alpha = 1.0 - (i as f32 + 0.5) / FADE_STEPS as f32;
+        strips.push((fade_start + step * i as f32, step, alpha));
+    }
+    strips
+}
+
+/// How thick decoration lines are for text of `font_size`.
+fn decoration_thickness(font_size: f32) -> f32 {
+    (font_size / 14.0).max(1.0)
+}
+
+/// The `(from, to)` ends of every line of `decoration`, along each of `spans`. Each kind of
+/// line that is set is drawn, so an underline and a strikethrough can be combined.
+fn decoration_lines(
+    decoration: TextDecoration,
+    font_size: f32,
+    spans: &[LineSpan],
+) -> Vec<(Point, Point)> {
+    // Offsets from the baseline, as fractions of the font size, that suit most fonts
+    let offsets = [
+        (TextDecoration::UNDERLINE, 0.1),
+        (TextDecoration::STRIKETHROUGH, -0.3),
+        (TextDecoration::OVERLINE, -0.8),
+    ];
+    let mut lines = vec![];
+    for span in spans.iter().filter(|s| s.width > 0.0) {
+        for (d, offset) in offsets {
+            if decoration.contains(d) {
+                let y = span.baseline + font_size * offset;
+                lines.push((Point::new(span.x, y), Point::new(span.x + span.width, y)));
+            }
         }
     }
+    lines
+}
+
+/// Where to draw copies of blurred glyphs: the center, and rings at half and all of `radius`.
+fn blur_offsets(radius: f32) -> Vec<(f32, f32)> {
+    let mut offsets = vec![(0.0, 0.0)];
+    if radius <= 0.0 {
+        return offsets;
+    }
+    for r in [radius / 2.0, radius] {
+        for i in 0..8 {
+            let angle = i as f32 * std::f32::consts::FRAC_PI_4;
+            offsets.push((r * angle.cos(), r * angle.sin()));
+        }
+    }
+    offsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_direction() {
+        let arabic = "مرحبا بالعالم";
+        assert!(TextDirection::Rtl.is_rtl(arabic));
+        assert!(TextDirection::Auto.is_rtl(arabic));
+        assert!(!TextDirection::Auto.is_rtl("Hello مرحبا"));
+        assert!(TextDirection::Auto.is_rtl("123 שלום"));
+        assert!(!TextDirection::Ltr.is_rtl(arabic));
+
+        // Right-to-left paragraphs start from the right edge
+        let align = TextDirection::Rtl.physical_align(Align::Left, arabic);
+        assert_eq!(align, Align::Right);
+        let align = TextDirection::Rtl.physical_align(Align::Right, arabic);
+        assert_eq!(align, Align::Left);
+        let align = TextDirection::Rtl.physical_align(Align::Center, arabic);
+        assert_eq!(align, Align::Center);
+        let align = TextDirection::Ltr.physical_align(Align::Left, arabic);
+        assert_eq!(align, Align::Left);
+    }
+
+    #[test]
+    fn test_shadow() {
+        let text = Text::new(Pos::new(10.0, 20.0, 0.0), Scale::new(100.0, 20.0), "Hello");
+        let shadow = text.shadow(&TextShadow::new(0.0, 2.0, 4.0, Color::RED));
+        assert_eq!(shadow.instance_data.pos, Pos::new(10.0, 22.0, 0.0));
+        assert_eq!(shadow.instance_data.color, Color::RED);
+        assert_eq!(shadow.instance_data.blur_radius, 4.0);
+        assert_eq!(shadow.instance_data.text, "Hello");
+
+        assert_eq!(blur_offsets(0.0), vec![(0.0, 0.0)]);
+        let offsets = blur_offsets(4.0);
+        assert_eq!(offsets.len(), 17);
+        assert!(offsets
+            .iter()
+            .all(|(x, y)| (x * x + y * y).sqrt() <= 4.0 + f32::EPSILON * 4.0));
+    }
+
+    #[test]
+    fn test_decoration_lines() {
+        let spans = [
+            LineSpan {
+                x: 10.0,
+                width: 50.0,
+                baseline: 20.0,
+            },
+            LineSpan {
+                x: 10.0,
+                width: 0.0,
+                baseline: 40.0,
+            },
+        ];
+        assert!(decoration_lines(TextDecoration::NONE, 10.0, &spans).is_empty());
+
+        let underline = decoration_lines(TextDecoration::UNDERLINE, 10.0, &spans);
+        // Below the baseline, and empty lines have none
+        assert_eq!(
+            underline,
+            vec![(Point::new(10.0, 21.0), Point::new(60.0, 21.0))]
+        );
+
+        // Both lines are drawn when both are set
+        let both = TextDecoration::UNDERLINE | TextDecoration::STRIKETHROUGH;
+        let lines = decoration_lines(both, 10.0, &spans);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], underline[0]);
+        assert_eq!(lines[1], (Point::new(10.0, 17.0), Point::new(60.0, 17.0)));
+
+        let overline = decoration_lines(TextDecoration::OVERLINE, 10.0, &spans);
+        assert!(overline[0].0.y < lines[1].0.y);
+        assert_eq!(decoration_thickness(7.0), 1.0);
+        assert_eq!(decoration_thickness(28.0), 2.0);
+    }
+
+    #[test]
+    fn test_fade_strips() {
+        let strips = fade_strips(10.0, 100.0);
+        assert_eq!(strips.len(), FADE_STEPS + 1);
+        assert_eq!(strips[0], (10.0, 80.0, 1.0));
+        // The strips cover the last 20px without gaps, each more transparent than the last
+        for w in strips.windows(2) {
+            assert!((w[0].0 + w[0].1 - w[1].0).abs() < 0.001);
+            assert!(w[1].2 < w[0].2);
+        }
+        let last = strips.last().unwrap();
+        assert!((last.0 + last.1 - 110.0).abs() < 0.001);
+        assert!(last.2 > 0.0);
+
+        // Text narrower than the fade fades out over all of it
+        let strips = fade_strips(0.0, 10.0);
+        assert_eq!(strips[0].1, 0.0);
+        assert_eq!(strips[1].0, 0.0);
+    }
 }
@@ -0,0 +1,170 @@
+use crate::Point;
+
+use femtovg::Transform2D;
+
+/// An affine transform that a [`Renderable`](super::Renderable) is drawn with. The renderable
+/// is scaled and rotated around `origin`, and then moved by `translate`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    pub translate: Point,
+    /// Clockwise, in degrees.
+    pub rotate_deg: f32,
+    pub scale: Point,
+    /// The point that rotation and scaling happen around, in the same coordinates as the
+    /// renderable.
+    pub origin: Point,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Transform {
+    /// Draws renderables unchanged.
+    pub const IDENTITY: Self = Self {
+        translate: Point { x: 0.0, y: 0.0 },
+        rotate_deg: 0.0,
+        scale: Point { x: 1.0, y: 1.0 },
+        origin: Point { x: 0.0, y: 0.0 },
+    };
+
+    pub fn translate(x: f32, y: f32) -> Self {
+        Self {
+            translate: Point::new(x, y),
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn rotate(deg: f32) -> Self {
+        Self {
+            rotate_deg: deg,
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn scale(x: f32, y: f32) -> Self {
+        Self {
+            scale: Point::new(x, y),
+            ..Self::IDENTITY
+        }
+    }
+
+    /// This transform, rotating and scaling around `origin` instead.
+    pub fn around(mut self, origin: Point) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.translate == Self::IDENTITY.translate
+            && self.rotate_deg % 360.0 == 0.0
+            && self.scale == Self::IDENTITY.scale
+    }
+
+    /// The `[a, b, c, d, e, f]` matrix that maps `(x, y)` to
+    /// `(a * x + c * y + e, b * x + d * y + f)`.
+    fn coefficients(&self) -> [f32; 6] {
+        let (sin, cos) = self.rotate_deg.to_radians().sin_cos();
+        let (a, b) = (cos * self.scale.x, sin * self.scale.x);
+        let (c, d) = (-sin * self.scale.y, cos * self.scale.y);
+        let Point { x, y } = self.origin;
+        [
+            a,
+            b,
+            c,
+            d,
+            x + self.translate.x - (a * x + c * y),
+            y + self.translate.y - (b * x + d * y),
+        ]
+    }
+
+    /// Where `p` is drawn.
+    pub fn apply(&self, p: Point) -> Point {
+        let [a, b, c, d, e, f] = self.coefficients();
+        Point::new(a * p.x + c * p.y + e, b * p.x + d * p.y + f)
+    }
+
+    pub fn matrix(&self) -> Transform2D {
+        Transform2D(self.coefficients())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderables::{Rect, Renderable};
+    use crate::{Color, Pos, Scale};
+
+    fn assert_near(a: Point, b: Point) {
+        assert!(a.dist(b) < 0.001, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_apply() {
+        let p = Point::new(10.0, 20.0);
+        assert_eq!(Transform::IDENTITY.apply(p), p);
+        assert_eq!(
+            Transform::translate(5.0, -5.0).apply(p),
+            Point::new(15.0, 15.0)
+        );
+        assert_eq!(Transform::scale(2.0, 0.5).apply(p), Point::new(20.0, 10.0));
+        assert_near(Transform::rotate(90.0).apply(p), Point::new(-20.0, 10.0));
+
+        // Rotating and scaling around an origin leaves the origin in place
+        let origin = Point::new(10.0, 10.0);
+        let t = Transform {
+            rotate_deg: 45.0,
+            scale: Point::new(3.0, 3.0),
+            ..Transform::IDENTITY
+        }
+        .around(origin);
+        assert_near(t.apply(origin), origin);
+        assert_near(
+            Transform::rotate(180.0).around(origin).apply(p),
+            Point::new(10.0, 0.0),
+        );
+
+        // Translation happens after rotation and scaling
+        let t = Transform {
+            translate: Point::new(1.0, 1.0),
+            scale: Point::new(2.0, 2.0),
+            ..Transform::IDENTITY
+        }
+        .around(origin);
+        assert_eq!(t.apply(p), Point::new(11.0, 31.0));
+    }
+
+    #[test]
+    fn test_is_identity() {
+        assert!(Transform::default().is_identity());
+        assert!(Transform::rotate(360.0).is_identity());
+        assert!(Transform::IDENTITY
+            .around(Point::new(5.0, 5.0))
+            .is_identity());
+        assert!(!Transform::rotate(90.0).is_identity());
+        assert!(!Transform::translate(1.0, 0.0).is_identity());
+        assert!(!Transform::scale(1.0, 2.0).is_identity());
+    }
+
+    #[test]
+    fn test_with_transform() {
+        let rect = Renderable::Rect(Rect::new(
+            Pos::default(),
+            Scale::new(10.0, 10.0),
+            Color::RED,
+        ));
+        assert!(matches!(
+            rect.clone().with_transform(Transform::IDENTITY),
+            Renderable::Rect(_)
+        ));
+        match rect.with_transform(Transform::rotate(45.0)) {
+            Renderable::Transformed { inner, transform } => {
+                assert!(matches!(*inner, Renderable::Rect(_)));
+                assert_eq!(transform, Transform::rotate(45.0));
+            }
+            r => panic!("Expected a transformed rect, got {r:?}"),
+        }
+    }
+}
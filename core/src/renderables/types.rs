@@ -1594,4 +1594,111 @@ pub struct Margin {
     pub right: f32,
     pub bottom: f32,
     pub left: f32,
-}
\ No newline at end of file
+}
+/// How the ends of a stroke (and of each of its dashes) are drawn.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub enum LineCap {
+    /// End exactly at the end point.
+    #[default]
+    Butt,
+    /// End with a semicircle, centered on the end point.
+    Round,
+    /// Extend past the end point by half the line width.
+    Square,
+}
+
+impl From<LineCap> for femtovg::LineCap {
+    fn from(cap: LineCap) -> Self {
+        match cap {
+            LineCap::Butt => femtovg::LineCap::Butt,
+            LineCap::Round => femtovg::LineCap::Round,
+            LineCap::Square => femtovg::LineCap::Square,
+        }
+    }
+}
+
+/// How the corners of a stroke are drawn.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub enum LineJoin {
+    /// Extend the outer edges until they meet.
+    #[default]
+    Miter,
+    /// Round off the corner with a circular arc.
+    Round,
+    /// Cut off the corner.
+    Bevel,
+}
+
+impl From<LineJoin> for femtovg::LineJoin {
+    fn from(join: LineJoin) -> Self {
+        match join {
+            LineJoin::Miter => femtovg::LineJoin::Miter,
+            LineJoin::Round => femtovg::LineJoin::Round,
+            LineJoin::Bevel => femtovg::LineJoin::Bevel,
+        }
+    }
+}
+
+/// How the inside of a shape is painted.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct FillStyle {
+    pub color: crate::Color,
+}
+
+impl FillStyle {
+    pub fn new(color: crate::Color) -> Self {
+        Self { color }
+    }
+
+    pub fn paint(&self) -> femtovg::Paint {
+        femtovg::Paint::color(self.color.into())
+    }
+}
+
+/// How a line, or the outline of a shape, is painted.
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct StrokeStyle {
+    pub color: crate::Color,
+    pub width: f32,
+    pub cap: LineCap,
+    pub join: LineJoin,
+    /// Alternating dash and gap lengths, as with SVG's `stroke-dasharray`. `None` is solid.
+    pub dash: Option<Vec<f32>>,
+}
+
+impl StrokeStyle {
+    /// A solid stroke with butt caps and miter joins.
+    pub fn new(color: crate::Color, width: f32) -> Self {
+        Self {
+            color,
+            width,
+            cap: LineCap::Butt,
+            join: LineJoin::Miter,
+            dash: None,
+        }
+    }
+
+    pub fn cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    pub fn join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    pub fn dash(mut self, dash: Vec<f32>) -> Self {
+        self.dash = Some(dash);
+        self
+    }
+
+    /// The paint for this stroke. Dashes are not part of it: they are drawn as separate segments.
+    pub fn paint(&self) -> femtovg::Paint {
+        let mut paint = femtovg::Paint::color(self.color.into());
+        paint.set_line_width(self.width);
+        paint.set_line_cap(self.cap.into());
+        paint.set_line_join(self.join.into());
+        paint
+    }
+}
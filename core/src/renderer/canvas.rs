@@ -3,6 +3,7 @@ use super::svg::{load_svg_paths, SvgData};
 use super::text::TextRenderer;
 use super::{Caches, RendererContext};
 use crate::font_cache::FontCache;
+use crate::renderables::image::take_released_images;
 use crate::renderables::Renderable;
 use crate::{node::Node, types::PixelSize};
 use crate::{AssetParams, ImgFilter};
@@ -90,6 +91,72 @@ pub fn load_assets_to_canvas(
     loaded_assets
 }
 
+/// `alpha` is the product of the opacities of the renderables that wrap `renderable`.
+fn render_renderable(
+    renderable: &Renderable,
+    canvas: &mut Canvas<OpenGl>,
+    images: &mut HashMap<String, ImageId>,
+    svgs: &mut HashMap<String, SvgData>,
+    text_renderer: &mut TextRenderer,
+    alpha: f32,
+) {
+    match renderable {
+        Renderable::Rect(rect) => {
+            rect.render(canvas);
+        }
+        Renderable::Line(line) => {
+            line.render(canvas);
+        }
+        Renderable::Circle(circle) => {
+            circle.render(canvas);
+        }
+        Renderable::Image(image) => {
+            image.render(canvas, images);
+        }
+        Renderable::Svg(svg) => {
+            svg.render(canvas, svgs);
+        }
+        Renderable::Text(text) => {
+            text.render(canvas, text_renderer);
+        }
+        Renderable::RadialGradient(rg) => {
+            rg.render(canvas);
+        }
+        Renderable::LinearGradient(lg) => {
+            lg.render(canvas);
+        }
+        Renderable::Curve(curve) => {
+            curve.render(canvas);
+        }
+        Renderable::Polygon(polygon) => {
+            polygon.render(canvas);
+        }
+        Renderable::Arc(arc) => {
+            arc.render(canvas);
+        }
+        Renderable::Path(path) => {
+            path.render(canvas);
+        }
+        Renderable::Polyline(polyline) => {
+            polyline.render(canvas);
+        }
+        Renderable::Transformed { inner, transform } => {
+            canvas.save();
+            canvas.set_transform(&transform.matrix());
+            render_renderable(inner, canvas, images, svgs, text_renderer, alpha);
+            canvas.restore();
+        }
+        Renderable::WithOpacity { inner, opacity } => {
+            // The global alpha of the canvas replaces that of any wrapper around this one
+            let alpha = alpha * opacity.clamp(0.0, 1.0);
+            canvas.save();
+            canvas.set_global_alpha(alpha);
+            render_renderable(inner, canvas, images, svgs, text_renderer, alpha);
+            canvas.restore();
+        }
+    }
+}
+
 pub struct CanvasRenderer {
     fonts: cosmic_text::fontdb::Database,
     text_renderer: TextRenderer,
@@ -158,35 +225,24 @@ impl super::Renderer for CanvasRenderer {
             Color::rgba(0, 0, 0, 0),
         );
 
-        for (renderable, _, _) in node.iter_renderables() {
-            match renderable {
-                Renderable::Rect(rect) => {
-                    rect.render(canvas);
-                }
-                Renderable::Line(line) => {
-                    line.render(canvas);
-                }
-                Renderable::Circle(circle) => {
-                    circle.render(canvas);
-                }
-                Renderable::Image(image) => {
-                    image.render(canvas, &mut context.images);
-                }
-                Renderable::Svg(svg) => {
-                    svg.render(canvas, &mut self.svgs);
-                }
-                Renderable::Text(text) => {
-                    text.render(canvas, text_renderer);
-                }
-                Renderable::RadialGradient(rg) => {
-                    rg.render(canvas);
-                }
-                Renderable::Curve(curve) => {
-                    curve.render(canvas);
-                }
+        // Images that were loaded on first use are reloaded if they are drawn again
+        for name in take_released_images() {
+            if let Some(image_id) = context.images.remove(&name) {
+                canvas.delete_image(image_id);
             }
         }
 
+        for (renderable, _, _) in node.iter_renderables() {
+            render_renderable(
+                renderable,
+                canvas,
+                &mut context.images,
+                &mut self.svgs,
+                text_renderer,
+                1.0,
+            );
+        }
+
         // Tell renderer to execute all drawing commands
         canvas.flush();
 
@@ -1,9 +1,11 @@
 use std::collections::HashMap;
+use std::ops::Range;
 
 use cosmic_text::fontdb::Database;
 use cosmic_text::{
     Align as CosmicAlign, Attrs, AttrsList, Buffer, CacheKey, Color as FontColor, Family,
-    FontSystem, LayoutGlyph, Metrics, Shaping, Stretch, Style, SubpixelBin, Weight, Wrap,
+    FontSystem, LayoutGlyph, LayoutRun, Metrics, Shaping, Stretch, Style, SubpixelBin, Weight,
+    Wrap,
 };
 use femtovg::renderer::OpenGl;
 use femtovg::{
@@ -20,7 +22,8 @@ use crate::font_cache::{
     DEFAULT_FONT_SIZE, DEFAULT_LINE_HEIGHT, GLYPH_MARGIN, GLYPH_PADDING, TEXTURE_SIZE,
 };
 use crate::renderables::text::Instance;
-use crate::{Pos, Scale};
+use crate::style::{FontStyle, OverflowWrap, TextOverflow, WordBreak};
+use crate::{Color, Pos, Scale};
 
 // const DEFAULT_FONT_SIZE: f32= 12.;
 // const DEFAULT_LINE_HEIGHT: f32 = 16.;
@@ -34,6 +37,221 @@ pub struct TextConfig {
     pub subpixel: bool,
 }
 
+/// Where a line of drawn text is, for drawing decorations along it.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LineSpan {
+    /// The left edge of the first glyph.
+    pub x: f32,
+    pub width: f32,
+    pub baseline: f32,
+}
+
+/// Where tab characters advance to.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TabStops {
+    /// Sorted x positions. Past the last one, they repeat every `stops.last()` pixels.
+    pub stops: Vec<f32>,
+    /// The distance between uniform stops, used when there are no `stops`.
+    pub width: f32,
+    /// Where the spaces that each tab is laid out as are, in each line of the laid out text.
+    spans: Vec<Vec<Range<usize>>>,
+}
+
+impl TabStops {
+    /// The x position that a tab which starts at `x` advances to.
+    pub fn next(&self, x: f32) -> f32 {
+        match self.stops.last() {
+            Some(&period) if period > 0.0 => {
+                let offset = (x / period).floor().max(0.0) * period;
+                self.stops
+                    .iter()
+                    .map(|stop| stop + offset)
+                    .find(|stop| *stop > x)
+                    .unwrap_or(period + offset)
+            }
+            _ if self.width > 0.0 => ((x / self.width).floor() + 1.0) * self.width,
+            _ => x,
+        }
+    }
+
+    /// The spaces of the tab at `offset` in line `line` of the laid out text, if any.
+    fn span(&self, line: usize, offset: usize) -> Option<&Range<usize>> {
+        self.spans
+            .get(line)?
+            .iter()
+            .find(|span| span.contains(&offset))
+    }
+
+    /// Where `offset` into line `line` of the laid out text is in that line of the text before
+    /// its tabs were replaced by spaces.
+    fn original_offset(&self, line: usize, offset: usize) -> usize {
+        let mut removed = 0;
+        for span in self.spans.get(line).into_iter().flatten() {
+            if offset <= span.start {
+                break;
+            }
+            if offset < span.end {
+                return span.start - removed;
+            }
+            removed += span.len() - 1;
+        }
+        offset - removed
+    }
+
+    /// How far each glyph of `run` is moved right, and how far it advances. Glyphs are moved by
+    /// `letter_spacing` after each glyph before them, and the spaces of each tab advance exactly
+    /// to its stop, relative to the left of the line.
+    fn shifts(&self, run: &LayoutRun, letter_spacing: f32) -> Vec<(f32, f32)> {
+        let left = run.glyphs.first().map_or(0.0, |glyph| glyph.x);
+        let mut shift = 0.0;
+        let mut tab_x = None;
+        run.glyphs
+            .iter()
+            .map(|glyph| {
+                let glyph_shift = shift;
+                let mut advance = glyph.w + letter_spacing;
+                if let Some(span) = self.span(run.line_i, glyph.start) {
+                    let x = *tab_x.get_or_insert(glyph.x + shift - left);
+                    if glyph.end == span.end {
+                        advance += self.next(x) - (glyph.x + shift + glyph.w - left);
+                        tab_x = None;
+                    }
+                }
+                shift += advance - glyph.w;
+                (glyph_shift, advance)
+            })
+            .collect()
+    }
+}
+
+/// What the last visible line of truncated text ends with.
+const ELLIPSIS: &str = "\u{2026}";
+
+/// The width of `text` on a single line, in the font of `attrs`.
+fn text_width(fs: &mut FontSystem, attrs: Attrs, metrics: Metrics, text: &str) -> f32 {
+    let mut buffer = Buffer::new(fs, metrics);
+    buffer.set_text(fs, text, attrs, Shaping::Advanced);
+    buffer.shape_until(fs, 1);
+    buffer
+        .layout_runs()
+        .flat_map(|run| run.glyphs.iter())
+        .map(|glyph| glyph.w)
+        .sum()
+}
+
+/// `text` with each of its tabs replaced by about as many spaces as reach the next of `tabs`,
+/// so that lines are wrapped and aligned with their tabs at full width. Where the spaces of each
+/// tab are is kept in `tabs`, which [`TabStops::shifts`] moves the rest of the way to the stop.
+fn expand_tabs(
+    fs: &mut FontSystem,
+    attrs: Attrs,
+    metrics: Metrics,
+    text: &str,
+    tabs: &mut TabStops,
+    letter_spacing: f32,
+) -> String {
+    let space = text_width(fs, attrs, metrics, " ") + letter_spacing;
+    let mut buffer = Buffer::new(fs, metrics);
+    buffer.set_wrap(fs, Wrap::None);
+    buffer.set_text(fs, text, attrs, Shaping::Advanced);
+    buffer.shape_until(fs, i32::MAX);
+
+    // How many spaces the tab at each line and offset in the line is replaced by
+    let mut counts = HashMap::new();
+    for run in buffer.layout_runs() {
+        let mut shift = 0.0;
+        for glyph in run.glyphs.iter() {
+            if run.text.get(glyph.start..glyph.end) == Some("\t") {
+                let x = glyph.x + shift;
+                let width = tabs.next(x) - x;
+                let count = if space > 0.0 {
+                    ((width / space).round() as usize).max(1)
+                } else {
+                    1
+                };
+                counts.insert((run.line_i, glyph.start), count);
+                shift += width - glyph.w;
+            }
+            shift += letter_spacing;
+        }
+    }
+
+    let mut expanded = String::with_capacity(text.len());
+    tabs.spans.clear();
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            expanded.push('\n');
+        }
+        let line_start = expanded.len();
+        let mut spans = vec![];
+        for (offset, c) in line.char_indices() {
+            if c == '\t' {
+                let count = counts.get(&(i, offset)).copied().unwrap_or(1);
+                let start = expanded.len() - line_start;
+                spans.push(start..start + count);
+                expanded.extend(std::iter::repeat(' ').take(count));
+            } else {
+                expanded.push(c);
+            }
+        }
+        tabs.spans.push(spans);
+    }
+    expanded
+}
+
+/// A laid-out line of text, for [`truncated_text`].
+struct LineEnds {
+    /// Where in the text the line that was wrapped into this one starts.
+    start: usize,
+    /// Where in the text each glyph ends, and the x position of its right edge.
+    glyphs: Vec<(usize, f32)>,
+}
+
+/// What to lay out instead of `text`, so that no more than `max_lines` of its `lines` are
+/// shown, or `None` if it already fits. With the width of an `ellipsis`, the last line that is
+/// shown is also cut short to fit it within `width`, and ends with it. Without one, lines past
+/// `max_lines` are dropped and lines that are too wide are left to be clipped.
+fn truncated_text(
+    text: &str,
+    lines: &[LineEnds],
+    width: f32,
+    max_lines: Option<u32>,
+    ellipsis: Option<f32>,
+) -> Option<String> {
+    let limit = max_lines.map_or(usize::MAX, |max| max.max(1) as usize);
+    let last = lines.get(lines.len().min(limit).checked_sub(1)?)?;
+    let more_lines = lines.len() > limit;
+    let too_wide = last.glyphs.iter().any(|(_, right)| *right > width);
+    match ellipsis {
+        _ if !more_lines && !too_wide => None,
+        None if !more_lines => None,
+        None => {
+            let end = last.glyphs.iter().map(|(end, _)| *end).max();
+            Some(text[..end.unwrap_or(last.start)].to_string())
+        }
+        Some(ellipsis) => {
+            let end = last
+                .glyphs
+                .iter()
+                .filter(|(_, right)| *right + ellipsis <= width)
+                .map(|(end, _)| *end)
+                .max();
+            Some(format!(
+                "{}{ELLIPSIS}",
+                text[..end.unwrap_or(last.start)].trim_end()
+            ))
+        }
+    }
+}
+
+fn cosmic_style(font_style: FontStyle) -> Style {
+    match font_style {
+        FontStyle::Normal => Style::Normal,
+        FontStyle::Italic => Style::Italic,
+        FontStyle::Oblique => Style::Oblique,
+    }
+}
+
 pub struct FontTexture {
     atlas: Atlas,
     image_id: ImageId,
@@ -80,100 +298,215 @@ impl TextRenderer {
         self.glyph_textures.clear();
     }
 
+    /// The commands that draw the glyphs of `instance`, and where each of its lines is.
     pub fn draw_text(
         &mut self,
         canvas: &mut Canvas<OpenGl>,
         instance: Instance,
-    ) -> Result<Vec<(FontColor, GlyphDrawCommands)>, ErrorKind> {
+    ) -> Result<(Vec<(FontColor, GlyphDrawCommands)>, Vec<LineSpan>), ErrorKind> {
+        let (tabs, _) = self.layout(&instance, Some(instance.color));
         let Instance {
             pos,
             scale,
-            color,
-            align,
-            font,
-            weight,
-            font_size,
-            line_height,
-            text,
+            letter_spacing,
+            ..
         } = instance;
 
-        let fs = &mut self.font_system;
-        let buffer = &mut self.buffer;
-
-        buffer.set_metrics(fs, Metrics::new(font_size, line_height));
-
-        let mut attrs = Attrs::new()
-            .weight(Weight(weight as u16))
-            .stretch(Stretch::Normal)
-            .style(Style::Normal)
-            .color(FontColor::rgba(
-                color.r as u8,
-                color.g as u8,
-                color.b as u8,
-                (color.a * 255.) as u8,
-            ));
-
-        if font.is_some() {
-            attrs = attrs.family(Family::Name(font.as_ref().unwrap()));
-        }
-
-        buffer.set_wrap(fs, Wrap::None);
-        buffer.set_text(fs, &text, attrs, Shaping::Advanced);
-        buffer.set_size(fs, scale.width, scale.height);
-
-        for line in buffer.lines.iter_mut() {
-            // TODO spans
-            line.set_attrs_list(AttrsList::new(attrs));
-            line.set_align(match align {
-                Align::Left => Some(CosmicAlign::Left),
-                Align::Center => Some(CosmicAlign::Center),
-                Align::Right => Some(CosmicAlign::Right),
-            });
-        }
-
-        buffer.shape_until(fs, i32::MAX);
-
         let config = TextConfig {
             hint: true,
             subpixel: true,
         };
 
-        self.fill_to_cmds(canvas, scale, pos, (0., 0.), config)
+        let spans = self.line_spans(pos, &tabs, letter_spacing);
+        let cmds =
+            self.fill_to_cmds(canvas, scale, pos, (0., 0.), config, &tabs, letter_spacing)?;
+        Ok((cmds, spans))
+    }
+
+    /// The lines of the shaped buffer that have glyphs, drawn at `position`.
+    fn line_spans(&self, position: Pos, tabs: &TabStops, letter_spacing: f32) -> Vec<LineSpan> {
+        self.buffer
+            .layout_runs()
+            .filter_map(|run| {
+                let shifts = tabs
+                    .shifts(&run, letter_spacing)
+                    .into_iter()
+                    .map(|(shift, _)| shift);
+                let mut glyphs = run.glyphs.iter().zip(shifts);
+                let (first, first_shift) = glyphs.next()?;
+                let (left, right) = glyphs.fold(
+                    (first.x + first_shift, first.x + first_shift + first.w),
+                    |(left, right), (glyph, shift)| {
+                        (
+                            left.min(glyph.x + shift),
+                            right.max(glyph.x + shift + glyph.w),
+                        )
+                    },
+                );
+                Some(LineSpan {
+                    x: position.x + left,
+                    width: right - left,
+                    baseline: position.y + run.line_y,
+                })
+            })
+            .collect()
     }
 
     pub fn measure_text(
         &mut self,
         instance: Instance,
     ) -> (Option<f32>, Option<f32>, Vec<LayoutGlyph>) {
+        let (tabs, _) = self.layout(&instance, None);
         let Instance {
             pos,
+            scale,
+            letter_spacing,
+            ..
+        } = instance;
+
+        let config = TextConfig {
+            hint: true,
+            subpixel: true,
+        };
+
+        let (w, h, glyphs) =
+            self.measure_glyphs(scale, pos, (0., 0.), config, &tabs, letter_spacing);
+        (Some(w), Some(h), glyphs)
+    }
+
+    /// Where the text of `instance` starts to be cut off, relative to its position: `0.0` if a
+    /// line that is shown is too wide, or else the top of the last line that is shown, if lines
+    /// past `max_lines` are dropped. `None` if the text fits, or is only clipped.
+    pub fn overflow_top(&mut self, instance: &Instance) -> Option<f32> {
+        self.layout(instance, None).1
+    }
+
+    /// Shape the text of `instance` into the buffer, with its tabs replaced by spaces and cut
+    /// short as its `overflow` and `max_lines` ask, and return the tab stops to lay it out with
+    /// and its [`#overflow_top`][TextRenderer#method.overflow_top]. Glyphs are colored with `color`,
+    /// if any.
+    fn layout(&mut self, instance: &Instance, color: Option<Color>) -> (TabStops, Option<f32>) {
+        let Instance {
             scale,
             align,
-            font,
+            ref font,
             weight,
+            font_style,
             font_size,
             line_height,
-            text,
+            direction,
+            word_break,
+            overflow_wrap,
+            ref tab_stops,
+            tab_size,
+            letter_spacing,
+            overflow,
+            max_lines,
+            ref text,
             ..
-        } = instance;
-
-        let fs = &mut self.font_system;
-        let buffer = &mut self.buffer;
+        } = *instance;
+        let align = direction.physical_align(align, text);
+        let metrics = Metrics::new(font_size, line_height);
 
-        buffer.set_metrics(fs, Metrics::new(font_size, line_height));
+        self.buffer.set_metrics(&mut self.font_system, metrics);
 
         let mut attrs = Attrs::new()
             .weight(Weight(weight as u16))
             .stretch(Stretch::Normal)
-            .style(Style::Normal);
+            .style(cosmic_style(font_style));
+        if let Some(color) = color {
+            attrs = attrs.color(FontColor::rgba(
+                color.r as u8,
+                color.g as u8,
+                color.b as u8,
+                (color.a * 255.) as u8,
+            ));
+        }
+        if let Some(font) = font {
+            attrs = attrs.family(Family::Name(font));
+        }
+
+        let mut tabs = TabStops {
+            width: if text.contains('\t') && tab_stops.is_empty() {
+                tab_size as f32 * text_width(&mut self.font_system, attrs, metrics, " ")
+            } else {
+                0.0
+            },
+            stops: tab_stops.clone(),
+            spans: vec![],
+        };
+        let expanded;
+        let text = if text.contains('\t') {
+            let fs = &mut self.font_system;
+            expanded = expand_tabs(fs, attrs, metrics, text, &mut tabs, letter_spacing);
+            &expanded
+        } else {
+            text
+        };
+
+        self.buffer
+            .set_size(&mut self.font_system, scale.width, scale.height);
+        self.set_text(text, attrs, align);
+        self.shape(word_break, overflow_wrap, scale.width);
 
-        if font.is_some() {
-            attrs = attrs.family(Family::Name(font.as_ref().unwrap()));
+        if overflow == TextOverflow::Clip && max_lines.is_none() {
+            return (tabs, None);
         }
 
-        buffer.set_wrap(fs, Wrap::None);
-        buffer.set_text(fs, &text, attrs, Shaping::Advanced);
-        buffer.set_size(fs, scale.width, scale.height);
+        let line_starts: Vec<usize> = text
+            .split('\n')
+            .scan(0, |start, line| {
+                let line_start = *start;
+                *start += line.len() + 1;
+                Some(line_start)
+            })
+            .collect();
+        let lines: Vec<LineEnds> = self
+            .buffer
+            .layout_runs()
+            .map(|run| {
+                let start = line_starts.get(run.line_i).copied().unwrap_or(text.len());
+                let shifts = tabs.shifts(&run, letter_spacing);
+                LineEnds {
+                    start,
+                    glyphs: run
+                        .glyphs
+                        .iter()
+                        .zip(shifts)
+                        .map(|(g, (shift, _))| (start + g.end, g.x + shift + g.w))
+                        .collect(),
+                }
+            })
+            .collect();
+
+        let shown = max_lines.map_or(lines.len(), |max| lines.len().min(max.max(1) as usize));
+        let too_wide = lines[..shown]
+            .iter()
+            .any(|line| line.glyphs.iter().any(|(_, right)| *right > scale.width));
+        let overflow_top = if too_wide {
+            Some(0.0)
+        } else if shown < lines.len() {
+            Some((shown - 1) as f32 * metrics.line_height)
+        } else {
+            None
+        };
+
+        let ellipsis = (overflow == TextOverflow::Ellipsis)
+            .then(|| text_width(&mut self.font_system, attrs, metrics, ELLIPSIS));
+        if let Some(truncated) = truncated_text(text, &lines, scale.width, max_lines, ellipsis) {
+            self.set_text(&truncated, attrs, align);
+            self.shape(word_break, overflow_wrap, scale.width);
+        }
+
+        (tabs, overflow_top)
+    }
+
+    /// Replace the text of the buffer, with `attrs` and `align` on every line.
+    fn set_text(&mut self, text: &str, attrs: Attrs, align: Align) {
+        let fs = &mut self.font_system;
+        let buffer = &mut self.buffer;
+
+        buffer.set_text(fs, text, attrs, Shaping::Advanced);
 
         for line in buffer.lines.iter_mut() {
             // TODO spans
@@ -184,24 +517,46 @@ impl TextRenderer {
                 Align::Right => Some(CosmicAlign::Right),
             });
         }
+    }
 
-        buffer.shape_until(fs, i32::MAX);
+    /// Shape the buffer, wrapping lines that are longer than `width` where `word_break` and
+    /// `overflow_wrap` allow.
+    fn shape(&mut self, word_break: WordBreak, overflow_wrap: OverflowWrap, width: f32) {
+        let fs = &mut self.font_system;
+        let buffer = &mut self.buffer;
 
-        let config = TextConfig {
-            hint: true,
-            subpixel: true,
+        let wrap = match (word_break, overflow_wrap) {
+            (WordBreak::BreakAll, _) => Wrap::Glyph,
+            (WordBreak::KeepAll, _) | (_, OverflowWrap::BreakWord | OverflowWrap::Anywhere) => {
+                Wrap::Word
+            }
+            (WordBreak::Normal, OverflowWrap::Normal) => Wrap::None,
         };
+        buffer.set_wrap(fs, wrap);
+        buffer.shape_until(fs, i32::MAX);
 
-        let (w, h, glyphs) = self.measure_glyphs(scale, pos, (0., 0.), config);
-        (Some(w), Some(h), glyphs)
+        // A word that is longer than the line still overflows it, so break within words
+        if wrap == Wrap::Word
+            && overflow_wrap != OverflowWrap::Normal
+            && buffer.layout_runs().any(|run| run.line_w > width)
+        {
+            buffer.set_wrap(fs, Wrap::Glyph);
+            buffer.shape_until(fs, i32::MAX);
+        }
     }
 
+    /// The size of the shaped text, and its glyphs. With `letter_spacing`, the advance of each
+    /// glyph includes the spacing after it, and the spaces of each tab are one glyph. The `start`
+    /// and `end` of each glyph are byte offsets into the whole text before its tabs were replaced,
+    /// and its `y` is moved down by the top of the line it is laid out on.
     pub fn measure_glyphs(
         &mut self,
         scale: Scale,
         position: Pos,
         justify: (f32, f32),
         config: TextConfig,
+        tabs: &TabStops,
+        letter_spacing: f32,
     ) -> (f32, f32, Vec<LayoutGlyph>) {
         let fs = &mut self.font_system;
         let buffer = &mut self.buffer;
@@ -211,13 +566,47 @@ impl TextRenderer {
         let total_height = lines as f32 * buffer.metrics().line_height;
         let mut total_width: f32 = 0.;
 
+        let line_height = buffer.metrics().line_height;
         let mut glyphs: Vec<LayoutGlyph> = vec![];
+        let line_starts: Vec<usize> = buffer
+            .lines
+            .iter()
+            .enumerate()
+            .scan(0, |start, (i, line)| {
+                let line_start = *start;
+                *start += tabs.original_offset(i, line.text().len()) + 1;
+                Some(line_start)
+            })
+            .collect();
 
-        for run in buffer.layout_runs() {
-            for glyph in run.glyphs {
-                total_width += glyph.w;
-                glyphs.push(glyph.clone());
+        // The width of the longest line
+        for (row, run) in buffer.layout_runs().enumerate() {
+            let shifts = tabs.shifts(&run, letter_spacing);
+            let line_start = line_starts.get(run.line_i).copied().unwrap_or_default();
+            let mut line_width: f32 = 0.;
+            for (glyph, (shift, advance)) in run.glyphs.iter().zip(shifts) {
+                line_width += advance;
+                let start = line_start + tabs.original_offset(run.line_i, glyph.start);
+                let end = line_start + tabs.original_offset(run.line_i, glyph.end);
+                match glyphs.last_mut() {
+                    Some(last)
+                        if last.start == start && tabs.span(run.line_i, glyph.start).is_some() =>
+                    {
+                        last.w += advance;
+                        last.end = end;
+                    }
+                    _ => {
+                        let mut glyph = glyph.clone();
+                        glyph.x += shift;
+                        glyph.w = advance;
+                        glyph.y += row as f32 * line_height;
+                        glyph.start = start;
+                        glyph.end = end;
+                        glyphs.push(glyph);
+                    }
+                }
             }
+            total_width = total_width.max(line_width);
         }
 
         (total_width, total_height, glyphs)
@@ -230,6 +619,8 @@ impl TextRenderer {
         position: Pos,
         justify: (f32, f32),
         config: TextConfig,
+        tabs: &TabStops,
+        letter_spacing: f32,
     ) -> Result<Vec<(FontColor, GlyphDrawCommands)>, ErrorKind> {
         let fs = &mut self.font_system;
         let buffer = &mut self.buffer;
@@ -241,10 +632,11 @@ impl TextRenderer {
         let lines = buffer.layout_runs().filter(|run| run.line_w != 0.0).count();
         let total_height = lines as f32 * buffer.metrics().line_height;
         for run in buffer.layout_runs() {
-            for glyph in run.glyphs {
+            let shifts = tabs.shifts(&run, letter_spacing);
+            for (glyph, (shift, _)) in run.glyphs.iter().zip(shifts) {
                 let physical_glyph = glyph.physical(
                     (
-                        position.x,
+                        position.x + shift,
                         position.y + scale.height * justify.1 - total_height * justify.1,
                     ),
                     1.0,
@@ -425,3 +817,281 @@ impl TextRenderer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderables::text::InstanceBuilder;
+
+    #[test]
+    fn test_next_tab_stop() {
+        let tabs = TabStops {
+            stops: vec![40.0, 80.0],
+            ..Default::default()
+        };
+        assert_eq!(tabs.next(7.0), 40.0);
+        assert_eq!(tabs.next(40.0), 80.0);
+        // Past the last stop, they repeat every 80px
+        assert_eq!(tabs.next(85.0), 120.0);
+        assert_eq!(tabs.next(130.0), 160.0);
+
+        let uniform = TabStops {
+            width: 30.0,
+            ..Default::default()
+        };
+        assert_eq!(uniform.next(10.0), 30.0);
+        assert_eq!(uniform.next(30.0), 60.0);
+    }
+
+    #[test]
+    fn test_tab_stops() {
+        let mut fonts = Database::new();
+        fonts.load_font_data(
+            include_bytes!("../../../examples/kitchen/src/assets/fonts/SpaceGrotesk-Regular.ttf")
+                .to_vec(),
+        );
+        let mut text_renderer = TextRenderer::new(fonts);
+        let instance = InstanceBuilder::default()
+            .pos(Pos::default())
+            .scale(Scale::new(500.0, 100.0))
+            .font(Some("Space Grotesk".to_string()))
+            .tab_stops(vec![40.0, 80.0])
+            .text("a\tb\tc".to_string())
+            .build()
+            .unwrap();
+
+        let (_, _, glyphs) = text_renderer.measure_text(instance);
+        let x_of = |start: usize| glyphs.iter().find(|g| g.start == start).unwrap().x;
+        assert!((x_of(2) - 40.0).abs() < 0.01);
+        assert!((x_of(4) - 80.0).abs() < 0.01);
+        // Each tab is one glyph, which reaches its stop
+        assert_eq!(glyphs.len(), 5);
+        let tab = glyphs.iter().find(|g| g.start == 1).unwrap();
+        assert_eq!(tab.end, 2);
+        assert!((tab.x + tab.w - 40.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_tabs_aligned() {
+        let mut fonts = Database::new();
+        fonts.load_font_data(
+            include_bytes!("../../../examples/kitchen/src/assets/fonts/SpaceGrotesk-Regular.ttf")
+                .to_vec(),
+        );
+        let mut text_renderer = TextRenderer::new(fonts);
+        let instance = InstanceBuilder::default()
+            .pos(Pos::default())
+            .scale(Scale::new(500.0, 100.0))
+            .font(Some("Space Grotesk".to_string()))
+            .align(Align::Center)
+            .tab_stops(vec![100.0])
+            .text("a\tb".to_string())
+            .build()
+            .unwrap();
+
+        // The line is centred with its tab at about its full width, which is at most half a
+        // space off from the stop before the tab is moved onto it
+        let (width, _, glyphs) = text_renderer.measure_text(instance);
+        let left = glyphs[0].x;
+        let right = glyphs.last().map(|g| g.x + g.w).unwrap();
+        assert!(width > 100.0);
+        assert!((left - (500.0 - right)).abs() < 3.0);
+        // The tab stop is relative to the start of the line
+        assert!((glyphs[2].x - left - 100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_glyph_lines() {
+        let mut fonts = Database::new();
+        fonts.load_font_data(
+            include_bytes!("../../../examples/kitchen/src/assets/fonts/SpaceGrotesk-Regular.ttf")
+                .to_vec(),
+        );
+        let mut text_renderer = TextRenderer::new(fonts);
+        let instance = InstanceBuilder::default()
+            .pos(Pos::default())
+            .scale(Scale::new(500.0, 100.0))
+            .font(Some("Space Grotesk".to_string()))
+            .line_height(20.0)
+            .text("ab\n\ncd".to_string())
+            .build()
+            .unwrap();
+
+        let (_, _, glyphs) = text_renderer.measure_text(instance);
+        let glyph_at = |start: usize| glyphs.iter().find(|g| g.start == start).unwrap();
+        // Offsets are into the whole text, and the empty line still takes up a row
+        assert_eq!(glyph_at(4).end, 5);
+        assert_eq!(glyph_at(1).y, glyph_at(0).y);
+        assert_eq!(glyph_at(4).y - glyph_at(0).y, 40.0);
+    }
+
+    /// Lines of `text` split at `\n`, with every character 10px wide.
+    fn mono_lines(text: &str) -> Vec<LineEnds> {
+        let mut start = 0;
+        text.split('\n')
+            .map(|line| {
+                let ends = LineEnds {
+                    start,
+                    glyphs: (1..=line.len())
+                        .map(|i| (start + i, i as f32 * 10.0))
+                        .collect(),
+                };
+                start += line.len() + 1;
+                ends
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_truncated_text() {
+        let text = "abcdefghij";
+        let lines = mono_lines(text);
+        // Fits
+        assert_eq!(truncated_text(text, &lines, 100.0, None, Some(10.0)), None);
+        assert_eq!(truncated_text(text, &lines, 50.0, None, None), None);
+        // Too wide: the ellipsis takes the place of the characters that it would overlap
+        assert_eq!(
+            truncated_text(text, &lines, 55.0, None, Some(10.0)),
+            Some("abcd\u{2026}".to_string())
+        );
+        assert_eq!(
+            truncated_text(text, &lines, 5.0, None, Some(10.0)),
+            Some("\u{2026}".to_string())
+        );
+    }
+
+    #[test]
+    fn test_truncated_lines() {
+        let text = "one two\nthree\nfour";
+        let lines = mono_lines(text);
+        assert_eq!(
+            truncated_text(text, &lines, 100.0, Some(3), Some(10.0)),
+            None
+        );
+        // The last line that is shown ends with an ellipsis, even when it fits
+        assert_eq!(
+            truncated_text(text, &lines, 100.0, Some(2), Some(10.0)),
+            Some("one two\nthree\u{2026}".to_string())
+        );
+        assert_eq!(
+            truncated_text(text, &lines, 40.0, Some(1), Some(10.0)),
+            Some("one\u{2026}".to_string())
+        );
+        // Without an ellipsis, the lines past the last are dropped
+        assert_eq!(
+            truncated_text(text, &lines, 40.0, Some(1), None),
+            Some("one two".to_string())
+        );
+        assert_eq!(truncated_text("", &[], 40.0, Some(1), Some(10.0)), None);
+    }
+
+    #[test]
+    fn test_ellipsis() {
+        let mut fonts = Database::new();
+        fonts.load_font_data(
+            include_bytes!("../../../examples/kitchen/src/assets/fonts/SpaceGrotesk-Regular.ttf")
+                .to_vec(),
+        );
+        let mut text_renderer = TextRenderer::new(fonts);
+        let text = "The quick brown fox jumps over the lazy dog";
+        let mut measure = |overflow: TextOverflow, max_lines: Option<u32>, wrap: bool| {
+            let instance = InstanceBuilder::default()
+                .pos(Pos::default())
+                .scale(Scale::new(100.0, 500.0))
+                .font(Some("Space Grotesk".to_string()))
+                .text(text.to_string())
+                .overflow(overflow)
+                .max_lines(max_lines)
+                .overflow_wrap(if wrap {
+                    OverflowWrap::BreakWord
+                } else {
+                    OverflowWrap::Normal
+                })
+                .build()
+                .unwrap();
+            let (width, height, glyphs) = text_renderer.measure_text(instance);
+            (width.unwrap(), height.unwrap(), glyphs.len())
+        };
+
+        // On one line, the text is cut short to fit
+        let (clipped_width, _, clipped_glyphs) = measure(TextOverflow::Clip, None, false);
+        assert!(clipped_width > 100.0);
+        let (width, _, glyphs) = measure(TextOverflow::Ellipsis, None, false);
+        assert!(width <= 100.0);
+        assert!(glyphs < clipped_glyphs);
+
+        // Wrapped lines past `max_lines` are dropped
+        let (_, wrapped_height, _) = measure(TextOverflow::Clip, None, true);
+        let (_, height, _) = measure(TextOverflow::Ellipsis, Some(2), true);
+        assert!(height < wrapped_height);
+        let (width, clipped_height, _) = measure(TextOverflow::Clip, Some(2), true);
+        assert_eq!(height, clipped_height);
+        assert!(width <= 100.0);
+    }
+
+    #[test]
+    fn test_overflow_top() {
+        let mut fonts = Database::new();
+        fonts.load_font_data(
+            include_bytes!("../../../examples/kitchen/src/assets/fonts/SpaceGrotesk-Regular.ttf")
+                .to_vec(),
+        );
+        let mut text_renderer = TextRenderer::new(fonts);
+        let mut overflow_top = |text: &str, max_lines: Option<u32>, wrap: bool| {
+            let instance = InstanceBuilder::default()
+                .pos(Pos::default())
+                .scale(Scale::new(100.0, 500.0))
+                .font(Some("Space Grotesk".to_string()))
+                .text(text.to_string())
+                .line_height(20.0)
+                .overflow(TextOverflow::Fade)
+                .max_lines(max_lines)
+                .overflow_wrap(if wrap {
+                    OverflowWrap::BreakWord
+                } else {
+                    OverflowWrap::Normal
+                })
+                .build()
+                .unwrap();
+            text_renderer.overflow_top(&instance)
+        };
+        let text = "The quick brown fox jumps over the lazy dog";
+
+        assert_eq!(overflow_top("Fox", None, false), None);
+        // A line that is too wide fades from the top
+        assert_eq!(overflow_top(text, None, false), Some(0.0));
+        // Wrapped text that fits is not cut off, until lines are dropped
+        assert_eq!(overflow_top(text, None, true), None);
+        assert_eq!(overflow_top(text, Some(2), true), Some(20.0));
+        assert_eq!(overflow_top(text, Some(1), true), Some(0.0));
+    }
+
+    #[test]
+    fn test_letter_spacing() {
+        let mut fonts = Database::new();
+        fonts.load_font_data(
+            include_bytes!("../../../examples/kitchen/src/assets/fonts/SpaceGrotesk-Regular.ttf")
+                .to_vec(),
+        );
+        let mut text_renderer = TextRenderer::new(fonts);
+        let mut width = |text: &str, letter_spacing: f32| {
+            let instance = InstanceBuilder::default()
+                .pos(Pos::default())
+                .scale(Scale::new(500.0, 100.0))
+                .font(Some("Space Grotesk".to_string()))
+                .letter_spacing(letter_spacing)
+                .text(text.to_string())
+                .build()
+                .unwrap();
+            text_renderer.measure_text(instance).0.unwrap()
+        };
+
+        // Each character adds the spacing once
+        for text in ["abcd", "abcdefgh"] {
+            let chars = text.len() as f32;
+            let normal = width(text, 0.0);
+            assert!((width(text, 2.0) - normal - 2.0 * chars).abs() < 0.01);
+            assert!((width(text, -0.5) - normal + 0.5 * chars).abs() < 0.01);
+        }
+    }
+}
@@ -1,15 +1,22 @@
 //! Dynamic styling of Components.
 //!
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 use std::sync::{Mutex, OnceLock};
 
 use cosmic_text::Weight;
 
+use crate::renderables::rect::Gradient;
 use crate::types::*;
 use crate::{layout::*, size};
 
+#[cfg(feature = "toml-theme")]
+mod theme;
+#[cfg(feature = "toml-theme")]
+pub use theme::{StyleLoadError, StyleWatcher};
+
 #[derive(Debug, Copy, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BorderWidth {
     pub top: f32,
     pub left: f32,
@@ -17,7 +24,30 @@ pub struct BorderWidth {
     pub right: f32,
 }
 
+impl BorderWidth {
+    /// The border, as the [`Padding`] that keeps content from overlapping it.
+    pub fn to_padding(self) -> Padding {
+        Padding::new(self.top, self.right, self.bottom, self.left)
+    }
+
+    /// The content area of `outer`, inside of this border.
+    pub fn to_inset_rect(self, outer: AABB) -> AABB {
+        outer.inset(self.to_padding())
+    }
+
+    /// `left + right`
+    pub fn total_horizontal(self) -> f32 {
+        self.left + self.right
+    }
+
+    /// `top + bottom`
+    pub fn total_vertical(self) -> f32 {
+        self.top + self.bottom
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VerticalPosition {
     Bottom,
     Center,
@@ -31,6 +61,7 @@ impl Default for VerticalPosition {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HorizontalPosition {
     Left,
     Center,
@@ -44,6 +75,7 @@ impl Default for HorizontalPosition {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FontWeight {
     Thin = 100,
     ExtraLight = 200,
@@ -62,30 +94,556 @@ impl Default for FontWeight {
     }
 }
 
+/// Whether text is upright or slanted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FontStyle {
+    Normal,
+    /// The italic face of the font, or a slanted normal face if it has none.
+    Italic,
+    /// The oblique face of the font, or a slanted normal face if it has none.
+    Oblique,
+}
+
+impl Default for FontStyle {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+/// How text that does not fit is cut off.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextOverflow {
+    #[default]
+    Clip,
+    /// End the last visible line with `…`.
+    Ellipsis,
+    /// Fade the text out over the last 20px of its width.
+    Fade,
+}
+
+/// Lines drawn along text, as a set of flags that can be combined with `|`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextDecoration(u8);
+
+impl TextDecoration {
+    pub const NONE: Self = Self(0);
+    /// Below the baseline.
+    pub const UNDERLINE: Self = Self(1);
+    /// Through the middle of lower case letters.
+    pub const STRIKETHROUGH: Self = Self(1 << 1);
+    /// Above the tops of the letters.
+    pub const OVERLINE: Self = Self(1 << 2);
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for TextDecoration {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for TextDecoration {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0
+    }
+}
+
+impl std::fmt::Display for TextDecoration {
+    /// The names of the class of each line, e.g. `underline line-through`, or `none`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "none");
+        }
+        let names: Vec<&str> = [
+            (Self::UNDERLINE, "underline"),
+            (Self::STRIKETHROUGH, "line-through"),
+            (Self::OVERLINE, "overline"),
+        ]
+        .into_iter()
+        .filter(|(d, _)| self.contains(*d))
+        .map(|(_, name)| name)
+        .collect();
+        write!(f, "{}", names.join(" "))
+    }
+}
+
+/// The radius of each corner of a box.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BorderRadius {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl BorderRadius {
+    pub fn new(top_left: f32, top_right: f32, bottom_right: f32, bottom_left: f32) -> Self {
+        Self {
+            top_left,
+            top_right,
+            bottom_right,
+            bottom_left,
+        }
+    }
+
+    /// The same `radius` on every corner.
+    pub fn uniform(radius: f32) -> Self {
+        Self::new(radius, radius, radius, radius)
+    }
+}
+
+impl Hash for BorderRadius {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        ((self.top_left * 100.0) as i32).hash(state);
+        ((self.top_right * 100.0) as i32).hash(state);
+        ((self.bottom_right * 100.0) as i32).hash(state);
+        ((self.bottom_left * 100.0) as i32).hash(state);
+    }
+}
+
+impl From<(f32, f32, f32, f32)> for BorderRadius {
+    /// From `(top_left, top_right, bottom_right, bottom_left)`.
+    fn from((top_left, top_right, bottom_right, bottom_left): (f32, f32, f32, f32)) -> Self {
+        Self::new(top_left, top_right, bottom_right, bottom_left)
+    }
+}
+
+impl From<BorderRadius> for (f32, f32, f32, f32) {
+    fn from(r: BorderRadius) -> Self {
+        (r.top_left, r.top_right, r.bottom_right, r.bottom_left)
+    }
+}
+
+/// Where lines of text may break, other than at spaces.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WordBreak {
+    /// Only where the Unicode line breaking rules allow, e.g. at spaces and between CJK characters.
+    Normal,
+    /// Between any two characters.
+    BreakAll,
+    /// Only at spaces, and not between CJK characters.
+    KeepAll,
+}
+
+impl Default for WordBreak {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+/// Whether a word that is too long for its line may be broken, so that it does not overflow.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OverflowWrap {
+    Normal,
+    /// Break overflowing words between characters.
+    BreakWord,
+    /// Like `BreakWord`. There is no difference here, since text is not sized by its min-content.
+    Anywhere,
+}
+
+impl Default for OverflowWrap {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+/// A copy of text drawn behind it, like the CSS `text-shadow`.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextShadow {
+    pub offset_x: f32,
+    pub offset_y: f32,
+    /// How far the shadow is spread out. `0.0` draws sharp glyphs.
+    pub blur_radius: f32,
+    pub color: Color,
+}
+
+impl TextShadow {
+    pub fn new(offset_x: f32, offset_y: f32, blur_radius: f32, color: Color) -> Self {
+        Self {
+            offset_x,
+            offset_y,
+            blur_radius,
+            color,
+        }
+    }
+}
+
+impl Hash for TextShadow {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        ((self.offset_x * 100.0) as i32).hash(state);
+        ((self.offset_y * 100.0) as i32).hash(state);
+        ((self.blur_radius * 100.0) as i32).hash(state);
+        self.color.hash(state);
+    }
+}
+
+/// A blurred copy of a shape drawn behind it, like the CSS `box-shadow`.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoxShadow {
+    pub offset_x: f32,
+    pub offset_y: f32,
+    /// How far the edge of the shadow fades out over. `0.0` draws a sharp edge.
+    pub blur_radius: f32,
+    /// How much larger than the shape the shadow is. Negative values shrink it.
+    pub spread: f32,
+    pub color: Color,
+}
+
+impl BoxShadow {
+    /// No shadow, as used by the `shadow-none` class.
+    pub const NONE: Self = Self {
+        offset_x: 0.0,
+        offset_y: 0.0,
+        blur_radius: 0.0,
+        spread: 0.0,
+        color: Color::TRANSPARENT,
+    };
+
+    pub fn new(offset_x: f32, offset_y: f32, blur_radius: f32, spread: f32, color: Color) -> Self {
+        Self {
+            offset_x,
+            offset_y,
+            blur_radius,
+            spread,
+            color,
+        }
+    }
+
+    /// Whether drawing this shadow would show anything.
+    pub fn is_visible(&self) -> bool {
+        self.color.a > 0.0
+    }
+}
+
+impl Hash for BoxShadow {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        ((self.offset_x * 100.0) as i32).hash(state);
+        ((self.offset_y * 100.0) as i32).hash(state);
+        ((self.blur_radius * 100.0) as i32).hash(state);
+        ((self.spread * 100.0) as i32).hash(state);
+        self.color.hash(state);
+    }
+}
+
+/// The axis along which a [`CursorStyle::Resize`] cursor resizes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResizeDirection {
+    /// Up and down.
+    NorthSouth,
+    /// Left and right.
+    EastWest,
+    NorthEastSouthWest,
+    NorthWestSouthEast,
+    /// Any direction.
+    All,
+}
+
+/// The mouse cursor shown while hovering over a Component, set with the `cursor` style key.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CursorStyle {
+    Default,
+    /// A pointing hand, for things that can be clicked.
+    Pointer,
+    /// An I-beam, for editable text.
+    Text,
+    Crosshair,
+    Grab,
+    Grabbing,
+    Resize(ResizeDirection),
+    NotAllowed,
+    /// Busy, but still interactive.
+    Progress,
+    /// Busy, and not interactive.
+    Wait,
+}
+
+impl Default for CursorStyle {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
+impl CursorStyle {
+    /// The name that [`Window::set_cursor`][crate::window::Window::set_cursor] takes for this
+    /// cursor.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Default => "Arrow",
+            Self::Pointer => "PointingHand",
+            Self::Text => "Ibeam",
+            Self::Crosshair => "Cross",
+            Self::Grab => "Hand",
+            Self::Grabbing => "HandGrabbing",
+            Self::Resize(ResizeDirection::NorthSouth) => "SizeNS",
+            Self::Resize(ResizeDirection::EastWest) => "SizeWE",
+            Self::Resize(ResizeDirection::NorthEastSouthWest) => "SizeNESW",
+            Self::Resize(ResizeDirection::NorthWestSouthEast) => "SizeNWSE",
+            Self::Resize(ResizeDirection::All) => "Size",
+            Self::NotAllowed => "NoEntry",
+            Self::Progress => "Progress",
+            Self::Wait => "Wait",
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StyleVal {
     Dimension(Dimension),
     Size(Size),
     Rect(Rect),
     Point(Point),
     Pos(Pos),
-    Color(Color),
+    /// Serialized as a `"#RRGGBBAA"` [hex color code][Color::to_hex].
+    Color(#[cfg_attr(feature = "style-serde", serde(with = "hex_color"))] Color),
     Layout(Layout),
     HorizontalPosition(HorizontalPosition),
     VerticalPosition(VerticalPosition),
     BorderWidth(BorderWidth),
+    BorderRadius(BorderRadius),
     FontWeight(FontWeight),
+    FontStyle(FontStyle),
+    TextDecoration(TextDecoration),
+    TextOverflow(TextOverflow),
+    WordBreak(WordBreak),
+    OverflowWrap(OverflowWrap),
+    Cursor(CursorStyle),
     Float(f64),
     Int(u32),
     Bool(bool),
-    String(&'static str),
+    Gradient(Gradient),
+    /// Drawn in order, so the first shadow is at the back.
+    TextShadow(Vec<TextShadow>),
+    BoxShadow(BoxShadow),
+    /// Serialized as a plain string, so it is last: the other variants are tried first when
+    /// deserializing.
+    #[cfg_attr(feature = "style-serde", serde(untagged))]
+    String(
+        #[cfg_attr(
+            feature = "style-serde",
+            serde(deserialize_with = "deserialize_static_str")
+        )]
+        StaticStr,
+    ),
 } // Impls below
 
+/// A minimum viewport width, at which responsive style entries start to apply. The widths
+/// match the breakpoints of Tailwind.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Breakpoint {
+    Sm,
+    Md,
+    Lg,
+    Xl,
+    Xl2,
+}
+
+impl Breakpoint {
+    /// From narrowest to widest.
+    pub const ALL: [Breakpoint; 5] = [Self::Sm, Self::Md, Self::Lg, Self::Xl, Self::Xl2];
+
+    /// The logical width in pixels that this breakpoint starts at.
+    pub fn min_width(self) -> f32 {
+        match self {
+            Self::Sm => 640.0,
+            Self::Md => 768.0,
+            Self::Lg => 1024.0,
+            Self::Xl => 1280.0,
+            Self::Xl2 => 1536.0,
+        }
+    }
+
+    /// The widest breakpoint that a viewport `width` wide reaches. Viewports narrower than
+    /// `Sm` count as `Sm`.
+    pub fn for_width(width: f32) -> Self {
+        Self::ALL
+            .into_iter()
+            .rev()
+            .find(|b| width >= b.min_width())
+            .unwrap_or(Self::Sm)
+    }
+}
+
+/// A range of widths that a component is allocated, for style entries that only apply to
+/// components of those widths. Both bounds are inclusive logical pixels.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContainerQuery {
+    pub min_width: Option<f32>,
+    pub max_width: Option<f32>,
+}
+
+impl ContainerQuery {
+    pub fn matches(&self, width: f32) -> bool {
+        self.min_width.map_or(true, |min| width >= min)
+            && self.max_width.map_or(true, |max| width <= max)
+    }
+
+    /// The size of the range, so that narrower queries can take priority.
+    fn range(&self) -> f32 {
+        self.max_width.unwrap_or(f32::INFINITY) - self.min_width.unwrap_or(0.0)
+    }
+}
+
+impl Eq for ContainerQuery {}
+
+impl Hash for ContainerQuery {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.min_width.map(f32::to_bits).hash(state);
+        self.max_width.map(f32::to_bits).hash(state);
+    }
+}
+
+/// The interaction states that a component can be in, as a set of flags. Style entries for a
+/// state are only used while the component is in it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComponentState(u8);
+
+impl ComponentState {
+    pub const HOVER: Self = Self(1);
+    pub const ACTIVE: Self = Self(1 << 1);
+    pub const FOCUS: Self = Self(1 << 2);
+    pub const DISABLED: Self = Self(1 << 3);
+
+    /// Every state, from the one whose entries take priority to the one whose entries are tried
+    /// last.
+    pub const ALL: [Self; 4] = [Self::DISABLED, Self::ACTIVE, Self::FOCUS, Self::HOVER];
+
+    pub fn empty() -> Self {
+        Self(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// `other` is added when `on` is true.
+    pub fn set(&mut self, other: Self, on: bool) {
+        if on {
+            self.0 |= other.0
+        } else {
+            self.0 &= !other.0
+        }
+    }
+
+    /// The single states in this set, in the priority order of [`ComponentState::ALL`].
+    pub fn iter(&self) -> impl Iterator<Item = Self> + '_ {
+        Self::ALL
+            .into_iter()
+            .filter(|s| !s.is_empty() && self.contains(*s))
+    }
+}
+
+impl std::ops::BitOr for ComponentState {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for ComponentState {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StyleKey {
-    struct_name: &'static str,
-    parameter_name: &'static str,
-    class: Option<&'static str>, // TODO should this be an array?
+    #[cfg_attr(
+        feature = "style-serde",
+        serde(deserialize_with = "deserialize_static_str")
+    )]
+    struct_name: StaticStr,
+    #[cfg_attr(
+        feature = "style-serde",
+        serde(deserialize_with = "deserialize_static_str")
+    )]
+    parameter_name: StaticStr,
+    #[cfg_attr(
+        feature = "style-serde",
+        serde(deserialize_with = "deserialize_static_class")
+    )]
+    class: Option<StaticStr>, // TODO should this be an array?
+    #[cfg_attr(
+        feature = "style-serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    breakpoint: Option<Breakpoint>,
+    #[cfg_attr(
+        feature = "style-serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    container_query: Option<ContainerQuery>,
+    #[cfg_attr(
+        feature = "style-serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    state: Option<ComponentState>,
+}
+
+/// A `&'static str`. Behind an alias, `serde` does not try to borrow it from the data that
+/// it is deserialized from, which is not `'static`. It is interned instead.
+type StaticStr = &'static str;
+
+#[cfg(feature = "style-serde")]
+fn deserialize_static_str<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<&'static str, D::Error> {
+    <String as serde::Deserialize>::deserialize(deserializer).map(intern)
+}
+
+#[cfg(feature = "style-serde")]
+fn deserialize_static_class<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<&'static str>, D::Error> {
+    <Option<String> as serde::Deserialize>::deserialize(deserializer).map(|c| c.map(intern))
+}
+
+/// A [`Color`] as a `"#RRGGBBAA"` [hex color code][Color::to_hex], for [`StyleVal::Color`].
+#[cfg(feature = "style-serde")]
+mod hex_color {
+    use crate::types::Color;
+
+    pub fn serialize<S: serde::Serializer>(
+        color: &Color,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&color.to_hex())
+    }
+
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Color, D::Error> {
+        let hex = <String as serde::Deserialize>::deserialize(deserializer)?;
+        Color::from_hex(&hex).map_err(serde::de::Error::custom)
+    }
 }
 
 impl StyleKey {
@@ -98,26 +656,85 @@ impl StyleKey {
             struct_name,
             parameter_name,
             class,
+            breakpoint: None,
+            container_query: None,
+            state: None,
         }
     }
+
+    /// Only apply the entry when the viewport reaches `breakpoint`.
+    pub fn breakpoint(mut self, breakpoint: Breakpoint) -> Self {
+        self.breakpoint = Some(breakpoint);
+        self
+    }
+
+    /// Only apply the entry to components whose width matches `query`.
+    pub fn container_query(mut self, query: ContainerQuery) -> Self {
+        self.container_query = Some(query);
+        self
+    }
+
+    /// Only apply the entry while the component is in `state`, e.g. `Button:hover`.
+    pub fn state(mut self, state: ComponentState) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Keys ordered by component, parameter and then class, for listing them in a stable order.
+    fn order(&self, other: &Self) -> std::cmp::Ordering {
+        (self.struct_name, self.parameter_name, self.class)
+            .cmp(&(other.struct_name, other.parameter_name, other.class))
+            .then_with(|| format!("{self:?}").cmp(&format!("{other:?}")))
+    }
 }
 
 type StyleMap = HashMap<StyleKey, StyleVal>;
 type StyleOverrideMap = HashMap<&'static str, StyleVal>;
 
 /// A map between things to be styled ([`StyleKey`]s) and the style values ([`StyleVal`]s).
-#[derive(Clone, Debug, PartialEq)]
-pub struct Style(StyleMap);
+///
+/// Also holds the container queries that its keys use, narrowest first.
+#[derive(Clone, Debug)]
+pub struct Style(StyleMap, Vec<ContainerQuery>);
 #[derive(Clone, Default, Debug)]
 pub struct StyleOverride(StyleOverrideMap);
 
+/// All of the style entries of a single component, keyed by `(parameter_name, class)`.
+/// Returned by [`Style::for_component`].
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct StyleSnapshot(HashMap<(&'static str, Option<&'static str>), StyleVal>);
+
 impl Style {
+    /// The [default](#impl-Default-for-Style) style.
     pub fn new() -> Self {
         Default::default()
     }
 
-    pub fn add(mut self, k: StyleKey, v: StyleVal) -> Self {
+    /// A style with no entries.
+    pub fn empty() -> Self {
+        Self::from_map(StyleMap::new())
+    }
+
+    fn from_map(map: StyleMap) -> Self {
+        let mut style = Self(StyleMap::new(), vec![]);
+        for (k, v) in map.into_iter() {
+            style.insert(k, v);
+        }
+        style
+    }
+
+    fn insert(&mut self, k: StyleKey, v: StyleVal) {
+        if let Some(query) = k.container_query {
+            if !self.1.contains(&query) {
+                self.1.push(query);
+                self.1.sort_by(|a, b| a.range().total_cmp(&b.range()));
+            }
+        }
         self.0.insert(k, v);
+    }
+
+    pub fn add(mut self, k: StyleKey, v: StyleVal) -> Self {
+        self.insert(k, v);
         self
     }
 
@@ -125,11 +742,43 @@ impl Style {
         self.0.get(&k).cloned()
     }
 
+    /// The value of `k` for a viewport at `breakpoint`: from the entry of the widest breakpoint
+    /// up to `breakpoint` that has one, or else from `k`.
+    pub fn get_responsive(&self, k: StyleKey, breakpoint: Option<Breakpoint>) -> Option<StyleVal> {
+        Breakpoint::ALL
+            .into_iter()
+            .rev()
+            .filter(|b| breakpoint.map_or(false, |max| *b <= max))
+            .find_map(|b| self.get(k.clone().breakpoint(b)))
+            .or_else(|| self.get(k))
+    }
+
+    /// Like [`#get_responsive`][Style#method.get_responsive], but the entry of the narrowest
+    /// container query that matches `container_width` takes priority.
+    pub fn get_for_container(
+        &self,
+        k: StyleKey,
+        breakpoint: Option<Breakpoint>,
+        container_width: Option<f32>,
+    ) -> Option<StyleVal> {
+        container_width
+            .and_then(|width| {
+                self.1
+                    .iter()
+                    .filter(|q| q.matches(width))
+                    .find_map(|q| self.get(k.clone().container_query(*q)))
+            })
+            .or_else(|| self.get_responsive(k, breakpoint))
+    }
+
     pub fn style(&self, component: &'static str, parameter_name: &'static str) -> Option<StyleVal> {
         let key = StyleKey {
             struct_name: component,
             parameter_name,
             class: None,
+            breakpoint: None,
+            container_query: None,
+            state: None,
         };
         self.get(key)
     }
@@ -144,40 +793,334 @@ impl Style {
             struct_name: component,
             parameter_name,
             class: Some(class),
+            breakpoint: None,
+            container_query: None,
+            state: None,
         };
         self.get(key)
     }
+
+    /// The value that a `component` with the given `class` gets for `parameter_name` from this
+    /// style, resolved the way [`Styled#style_val`][Styled#method.style_val] does: `class`
+    /// may hold several space separated classes, of which the last with an entry wins, and
+    /// otherwise the unclassed entry is used. Unlike `style_val` this reads this style directly,
+    /// rather than the current one, and ignores breakpoints, container queries and states.
+    pub fn computed(
+        &self,
+        component: &'static str,
+        parameter_name: &'static str,
+        class: Option<&'static str>,
+    ) -> Option<StyleVal> {
+        class
+            .into_iter()
+            .flat_map(|c| c.split_whitespace().rev())
+            .find_map(|c| self.style_for_class(component, parameter_name, c))
+            .or_else(|| self.style(component, parameter_name))
+    }
+
+    /// The entries of `base` and `overrides` together. Where both have an entry for the same
+    /// key, the one in `overrides` wins.
+    pub fn merge(base: Style, overrides: Style) -> Style {
+        base.extend(overrides)
+    }
+
+    /// Add every entry of `other`, replacing the value of any key that this style already has.
+    pub fn extend(mut self, other: Style) -> Self {
+        for (k, v) in other.0.into_iter() {
+            self.insert(k, v);
+        }
+        self
+    }
+
+    /// The entries of `new` that `old` does not have, or has a different value for, in
+    /// arbitrary order. Entries that were removed in `new` are not included.
+    pub fn diff(old: &Style, new: &Style) -> Vec<(StyleKey, StyleVal)> {
+        new.0
+            .iter()
+            .filter(|(k, v)| old.0.get(*k) != Some(*v))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+
+    /// Remove every entry belonging to the component `name`, including its class entries.
+    pub fn reset_component(mut self, name: &'static str) -> Self {
+        self.0.retain(|k, _| k.struct_name != name);
+        self
+    }
+
+    /// All of the keys registered for the component `name`.
+    pub fn keys_for_component(&self, name: &'static str) -> Vec<&StyleKey> {
+        self.0.keys().filter(|k| k.struct_name == name).collect()
+    }
+
+    /// Like [`#keys_for_component`][Style#method.keys_for_component], but owned and sorted by
+    /// parameter and then class, so that the parameters a component exposes can be listed.
+    pub fn list_keys_for_component(&self, name: &'static str) -> Vec<StyleKey> {
+        let mut keys: Vec<StyleKey> = self.keys_for_component(name).into_iter().cloned().collect();
+        keys.sort_by(StyleKey::order);
+        keys
+    }
+
+    /// A copy of every entry belonging to the component `name`, including its class entries.
+    /// Breakpoint, container query and state entries are left out.
+    pub fn for_component(&self, name: &'static str) -> StyleSnapshot {
+        StyleSnapshot(
+            self.0
+                .iter()
+                .filter(|(k, _)| {
+                    k.struct_name == name
+                        && k.breakpoint.is_none()
+                        && k.container_query.is_none()
+                        && k.state.is_none()
+                })
+                .map(|(k, v)| ((k.parameter_name, k.class), v.clone()))
+                .collect(),
+        )
+    }
+
+    /// The number of entries.
+    pub fn count(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains_key(&self, k: &StyleKey) -> bool {
+        self.0.contains_key(k)
+    }
+
+    /// All of the keys, in arbitrary order.
+    pub fn keys(&self) -> impl Iterator<Item = &StyleKey> {
+        self.0.keys()
+    }
+
+    /// All of the values, in arbitrary order.
+    pub fn values(&self) -> impl Iterator<Item = &StyleVal> {
+        self.0.values()
+    }
 }
 
-impl Default for Style {
-    fn default() -> Self {
-        let map = StyleMap::from([
-            // Button
-            (
-                StyleKey::new("Button", "text_color", None),
-                Color::BLACK.into(),
-            ),
-            (
-                StyleKey::new("Button", "text_color", Some("text-black")),
-                Color::BLACK.into(),
-            ),
-            (
-                StyleKey::new("Button", "text_color", Some("text-white")),
-                Color::WHITE.into(),
-            ),
-            (
-                StyleKey::new("Button", "text_color", Some("text-blue")),
-                Color::BLUE.into(),
-            ),
-            (
-                StyleKey::new("Button", "font", Some("font-space-mono")),
-                "SpaceMono-Bold".into(),
-            ),
-            (
-                StyleKey::new("Button", "font", Some("font-space-grotesk")),
-                "Space Grotesk".into(),
-            ),
+/// Why [`Style::from_json_str`] or [`Style::to_json_str`] failed.
+#[cfg(feature = "style-serde")]
+pub use serde_json::Error as StyleJsonError;
+
+#[cfg(feature = "style-serde")]
+impl Style {
+    /// Read a style that was written by [`#to_json_str`][Style#method.to_json_str].
+    pub fn from_json_str(s: &str) -> Result<Self, StyleJsonError> {
+        serde_json::from_str(s)
+    }
+
+    /// This style as JSON: a list of `[key, value]` entries, ordered by component, parameter
+    /// and class.
+    pub fn to_json_str(&self) -> Result<String, StyleJsonError> {
+        serde_json::to_string(self)
+    }
+}
+
+/// A list of `[key, value]` entries, since keys are not strings.
+#[cfg(feature = "style-serde")]
+impl serde::Serialize for Style {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entries: Vec<(&StyleKey, &StyleVal)> = self.0.iter().collect();
+        entries.sort_by(|a, b| a.0.order(b.0));
+        serializer.collect_seq(entries)
+    }
+}
+
+#[cfg(feature = "style-serde")]
+impl<'de> serde::Deserialize<'de> for Style {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let entries = <Vec<(StyleKey, StyleVal)> as serde::Deserialize>::deserialize(deserializer)?;
+        let mut style = Style::empty();
+        for (k, v) in entries.into_iter() {
+            style.insert(k, v);
+        }
+        Ok(style)
+    }
+}
+
+impl StyleSnapshot {
+    pub fn get(&self, parameter: &'static str, class: Option<&'static str>) -> Option<&StyleVal> {
+        self.0.get(&(parameter, class))
+    }
+
+    /// The `(parameter_name, class)` pairs and their values, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = ((&'static str, Option<&'static str>), &StyleVal)> {
+        self.0.iter().map(|(k, v)| (*k, v))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl StyleOverride {
+    /// The overridden parameters and their values, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &StyleVal)> {
+        self.0.iter().map(|(k, v)| (*k, v))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// A [`Style`] with an entry for each override, keyed to `component` without a class.
+    pub fn into_style(self, component: &'static str) -> Style {
+        Style::from_map(
+            self.0
+                .into_iter()
+                .map(|(parameter, v)| (StyleKey::new(component, parameter, None), v))
+                .collect(),
+        )
+    }
+}
+
+/// Named style values, like CSS variables. See [`Palette::as_style_vars`].
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct StyleVariables(HashMap<String, StyleVal>);
+
+impl StyleVariables {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn add<V: Into<StyleVal>>(mut self, name: impl Into<String>, v: V) -> Self {
+        self.0.insert(name.into(), v.into());
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&StyleVal> {
+        self.0.get(name)
+    }
+
+    /// The names and their values, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &StyleVal)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// A scale of colors from light to dark around a `base` color, like the 50--950 scales of
+/// Tailwind.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Palette {
+    pub base: Color,
+    /// Ordered from lightest to darkest, including `base`.
+    pub steps: Vec<Color>,
+}
+
+impl Palette {
+    /// `light_steps` colors between `base` and white, then `base`, then `dark_steps` colors
+    /// between `base` and black. The steps are evenly spaced in HSL lightness, and keep the
+    /// hue and saturation of `base`.
+    pub fn generate(base: Color, light_steps: u8, dark_steps: u8) -> Palette {
+        let (_, _, l) = base.to_hsl();
+        let mut steps = vec![];
+        for i in (1..=light_steps).rev() {
+            let factor = (1.0 - l) * i as f32 / (light_steps as f32 + 1.0);
+            steps.push(base.lighten(factor));
+        }
+        steps.push(base);
+        for i in 1..=dark_steps {
+            let factor = l * i as f32 / (dark_steps as f32 + 1.0);
+            steps.push(base.darken(factor));
+        }
+        Palette { base, steps }
+    }
+
+    /// The index of `base` in `steps`.
+    fn base_index(&self) -> usize {
+        self.steps
+            .iter()
+            .position(|c| *c == self.base)
+            .unwrap_or(self.steps.len() / 2)
+    }
+
+    /// Name the steps `"{prefix}-500"` for `base`, counting down to `"{prefix}-100"` for the
+    /// lightest step and up to `"{prefix}-900"` for the darkest.
+    pub fn as_style_vars(&self, prefix: &'static str) -> StyleVariables {
+        let base_index = self.base_index();
+        let light_steps = base_index as f32;
+        let dark_steps = (self.steps.len() - base_index - 1) as f32;
+        let mut vars = StyleVariables::new();
+        for (i, color) in self.steps.iter().enumerate() {
+            let weight = match i.cmp(&base_index) {
+                std::cmp::Ordering::Less => 500.0 - 400.0 * (base_index - i) as f32 / light_steps,
+                std::cmp::Ordering::Equal => 500.0,
+                std::cmp::Ordering::Greater => 500.0 + 400.0 * (i - base_index) as f32 / dark_steps,
+            };
+            vars = vars.add(format!("{prefix}-{}", weight.round() as u32), *color);
+        }
+        vars
+    }
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        let map = StyleMap::from([
+            // Button
+            (
+                StyleKey::new("Button", "cursor", None),
+                CursorStyle::Pointer.into(),
+            ),
+            (
+                StyleKey::new("Button", "text_color", None),
+                Color::BLACK.into(),
+            ),
+            (
+                StyleKey::new("Button", "text_color", Some("text-black")),
+                Color::BLACK.into(),
+            ),
+            (
+                StyleKey::new("Button", "text_color", Some("text-white")),
+                Color::WHITE.into(),
+            ),
+            (
+                StyleKey::new("Button", "text_color", Some("text-blue")),
+                Color::BLUE.into(),
+            ),
+            (
+                StyleKey::new("Button", "font", Some("font-space-mono")),
+                "SpaceMono-Bold".into(),
+            ),
+            (
+                StyleKey::new("Button", "font", Some("font-space-grotesk")),
+                "Space Grotesk".into(),
+            ),
             (StyleKey::new("Button", "font_size", None), 12.0.into()),
+            (
+                StyleKey::new("Button", "font_size", None).breakpoint(Breakpoint::Sm),
+                12.0.into(),
+            ),
+            (
+                StyleKey::new("Button", "font_size", None).breakpoint(Breakpoint::Md),
+                14.0.into(),
+            ),
+            (
+                StyleKey::new("Button", "font_size", None).container_query(ContainerQuery {
+                    max_width: Some(200.0),
+                    ..Default::default()
+                }),
+                10.0.into(),
+            ),
             (
                 StyleKey::new("Button", "font_size", Some("text-xs")),
                 14.0.into(),
@@ -210,6 +1153,34 @@ impl Default for Style {
                 StyleKey::new("Button", "font_weight", None),
                 FontWeight::Normal.into(),
             ),
+            (
+                StyleKey::new("Button", "font_style", None),
+                FontStyle::Normal.into(),
+            ),
+            (
+                StyleKey::new("Button", "font_style", Some("font-normal")),
+                FontStyle::Normal.into(),
+            ),
+            (
+                StyleKey::new("Button", "font_style", Some("font-italic")),
+                FontStyle::Italic.into(),
+            ),
+            (
+                StyleKey::new("Button", "text_decoration", None),
+                TextDecoration::NONE.into(),
+            ),
+            (
+                StyleKey::new("Button", "text_decoration", Some("underline")),
+                TextDecoration::UNDERLINE.into(),
+            ),
+            (
+                StyleKey::new("Button", "text_decoration", Some("line-through")),
+                TextDecoration::STRIKETHROUGH.into(),
+            ),
+            (
+                StyleKey::new("Button", "text_decoration", Some("overline")),
+                TextDecoration::OVERLINE.into(),
+            ),
             (
                 StyleKey::new("Button", "font_weight", Some("font-thin")),
                 FontWeight::Thin.into(),
@@ -274,6 +1245,26 @@ impl Default for Style {
                 StyleKey::new("Button", "border_color", None),
                 Color::BLACK.into(),
             ),
+            (
+                StyleKey::new("Button", "shadow", None),
+                BoxShadow::NONE.into(),
+            ),
+            (
+                StyleKey::new("Button", "shadow", Some("shadow-none")),
+                BoxShadow::NONE.into(),
+            ),
+            (
+                StyleKey::new("Button", "shadow", Some("shadow-sm")),
+                BoxShadow::new(0.0, 1.0, 2.0, 0.0, Color::rgba(0., 0., 0., 0.05)).into(),
+            ),
+            (
+                StyleKey::new("Button", "shadow", Some("shadow")),
+                BoxShadow::new(0.0, 1.0, 3.0, 0.0, Color::rgba(0., 0., 0., 0.1)).into(),
+            ),
+            (
+                StyleKey::new("Button", "shadow", Some("shadow-lg")),
+                BoxShadow::new(0.0, 10.0, 15.0, -3.0, Color::rgba(0., 0., 0., 0.1)).into(),
+            ),
             (StyleKey::new("Button", "border_width", None), 0.0.into()),
             (
                 StyleKey::new("Button", "border_width", Some("border-0")),
@@ -304,6 +1295,70 @@ impl Default for Style {
                 0.0.into(),
             ),
             (StyleKey::new("Button", "radius", None), 0.0.into()),
+            (
+                StyleKey::new("Button", "radius", Some("rounded-sm")),
+                2.0.into(),
+            ),
+            (
+                StyleKey::new("Button", "radius", Some("rounded")),
+                4.0.into(),
+            ),
+            (
+                StyleKey::new("Button", "radius", Some("rounded-md")),
+                6.0.into(),
+            ),
+            (
+                StyleKey::new("Button", "radius", Some("rounded-lg")),
+                8.0.into(),
+            ),
+            (
+                StyleKey::new("Button", "radius", Some("rounded-xl")),
+                12.0.into(),
+            ),
+            (
+                StyleKey::new("Button", "radius", Some("rounded-2xl")),
+                16.0.into(),
+            ),
+            (
+                StyleKey::new("Button", "radius", Some("rounded-3xl")),
+                24.0.into(),
+            ),
+            (
+                StyleKey::new("Button", "size", Some("btn-xs")),
+                size!(64., 24.).into(),
+            ),
+            (
+                StyleKey::new("Button", "size", Some("btn-sm")),
+                size!(80., 32.).into(),
+            ),
+            (
+                StyleKey::new("Button", "size", Some("btn-md")),
+                size!(96., 40.).into(),
+            ),
+            (
+                StyleKey::new("Button", "size", Some("btn-xl")),
+                size!(128., 48.).into(),
+            ),
+            (
+                StyleKey::new("Button", "size", Some("btn-xxl")),
+                size!(160., 56.).into(),
+            ),
+            (
+                StyleKey::new("Button", "text_color", Some("link-button")),
+                Color::BLUE.into(),
+            ),
+            (
+                StyleKey::new("Button", "background_color", Some("link-button")),
+                Color::TRANSPARENT.into(),
+            ),
+            (
+                StyleKey::new("Button", "highlight_color", Some("link-button")),
+                Color::TRANSPARENT.into(),
+            ),
+            (
+                StyleKey::new("Button", "active_color", Some("link-button")),
+                Color::TRANSPARENT.into(),
+            ),
             (
                 StyleKey::new("IconButton", "radius", Some("rounded-sm")),
                 2.0.into(),
@@ -380,6 +1435,10 @@ impl Default for Style {
             (StyleKey::new("Button", "padding", Some("p-8")), 32.0.into()),
             (StyleKey::new("Button", "padding", Some("p-9")), 36.0.into()),
             // IconButton
+            (
+                StyleKey::new("IconButton", "cursor", None),
+                CursorStyle::Pointer.into(),
+            ),
             (
                 StyleKey::new("IconButton", "size", None),
                 size!(18., 18.).into(),
@@ -539,7 +1598,61 @@ impl Default for Style {
                 24.0.into(),
             ),
             (StyleKey::new("IconButton", "padding", None), 10.0.into()),
+            // Checkbox
+            (
+                StyleKey::new("Checkbox", "cursor", None),
+                CursorStyle::Pointer.into(),
+            ),
+            (
+                StyleKey::new("Checkbox", "background_color", None),
+                Color::WHITE.into(),
+            ),
+            (
+                StyleKey::new("Checkbox", "border_color", None),
+                Color::MID_GREY.into(),
+            ),
+            (
+                StyleKey::new("Checkbox", "check_color", None),
+                Color::BLACK.into(),
+            ),
+            (
+                StyleKey::new("Checkbox", "text_color", None),
+                Color::BLACK.into(),
+            ),
+            (StyleKey::new("Checkbox", "border_width", None), 2.0.into()),
+            (StyleKey::new("Checkbox", "font_size", None), 14.0.into()),
+            (StyleKey::new("Checkbox", "label_spacing", None), 8.0.into()),
+            (StyleKey::new("Checkbox", "size", None), 16.0.into()),
+            (StyleKey::new("Checkbox", "radius", None), 3.0.into()),
+            (
+                StyleKey::new("Checkbox", "size", Some("checkbox-sm")),
+                12.0.into(),
+            ),
+            (
+                StyleKey::new("Checkbox", "radius", Some("checkbox-sm")),
+                2.0.into(),
+            ),
+            (
+                StyleKey::new("Checkbox", "size", Some("checkbox-md")),
+                16.0.into(),
+            ),
+            (
+                StyleKey::new("Checkbox", "radius", Some("checkbox-md")),
+                3.0.into(),
+            ),
+            (
+                StyleKey::new("Checkbox", "size", Some("checkbox-lg")),
+                20.0.into(),
+            ),
+            (
+                StyleKey::new("Checkbox", "radius", Some("checkbox-lg")),
+                4.0.into(),
+            ),
             // RadioButton
+            (
+                StyleKey::new("RadioButton", "cursor", None),
+                CursorStyle::Pointer.into(),
+            ),
             (
                 StyleKey::new("RadioButton", "text_color", None),
                 Color::BLACK.into(),
@@ -568,9 +1681,56 @@ impl Default for Style {
                 StyleKey::new("RadioButton", "border_width", None),
                 2.0.into(),
             ),
+            (
+                StyleKey::new("RadioButton", "indeterminate_color", None),
+                Color::rgb(132., 132., 132.).into(),
+            ),
             (StyleKey::new("RadioButton", "radius", None), 4.0.into()),
             (StyleKey::new("RadioButton", "padding", None), 2.0.into()),
+            // ProgressBar
+            (
+                StyleKey::new("ProgressBar", "track_color", None),
+                Color::LIGHT_GREY.into(),
+            ),
+            (
+                StyleKey::new("ProgressBar", "fill_color", None),
+                Color::rgb(45., 138., 255.).into(),
+            ),
+            (StyleKey::new("ProgressBar", "height", None), 4.0.into()),
+            (StyleKey::new("ProgressBar", "radius", None), 2.0.into()),
+            (
+                StyleKey::new("ProgressBar", "animation_duration_ms", None),
+                1500.0.into(),
+            ),
+            (
+                StyleKey::new("ProgressBar", "height", Some("progress-xs")),
+                2.0.into(),
+            ),
+            (
+                StyleKey::new("ProgressBar", "radius", Some("progress-xs")),
+                1.0.into(),
+            ),
+            (
+                StyleKey::new("ProgressBar", "height", Some("progress-sm")),
+                4.0.into(),
+            ),
+            (
+                StyleKey::new("ProgressBar", "radius", Some("progress-sm")),
+                2.0.into(),
+            ),
+            (
+                StyleKey::new("ProgressBar", "height", Some("progress-md")),
+                8.0.into(),
+            ),
+            (
+                StyleKey::new("ProgressBar", "radius", Some("progress-md")),
+                4.0.into(),
+            ),
             // Select
+            (
+                StyleKey::new("Select", "cursor", None),
+                CursorStyle::Pointer.into(),
+            ),
             (
                 StyleKey::new("Select", "text_color", None),
                 Color::BLACK.into(),
@@ -588,6 +1748,26 @@ impl Default for Style {
                 StyleKey::new("Select", "border_color", None),
                 Color::BLACK.into(),
             ),
+            (
+                StyleKey::new("Select", "shadow", None),
+                BoxShadow::NONE.into(),
+            ),
+            (
+                StyleKey::new("Select", "shadow", Some("shadow-none")),
+                BoxShadow::NONE.into(),
+            ),
+            (
+                StyleKey::new("Select", "shadow", Some("shadow-sm")),
+                BoxShadow::new(0.0, 1.0, 2.0, 0.0, Color::rgba(0., 0., 0., 0.05)).into(),
+            ),
+            (
+                StyleKey::new("Select", "shadow", Some("shadow")),
+                BoxShadow::new(0.0, 1.0, 3.0, 0.0, Color::rgba(0., 0., 0., 0.1)).into(),
+            ),
+            (
+                StyleKey::new("Select", "shadow", Some("shadow-lg")),
+                BoxShadow::new(0.0, 10.0, 15.0, -3.0, Color::rgba(0., 0., 0., 0.1)).into(),
+            ),
             (
                 StyleKey::new("Select", "caret_color", None),
                 Color::BLACK.into(),
@@ -596,22 +1776,230 @@ impl Default for Style {
             (StyleKey::new("Select", "radius", None), 4.0.into()),
             (StyleKey::new("Select", "padding", None), 2.0.into()),
             (StyleKey::new("Select", "max_height", None), 250.0.into()),
-            // Toggle
             (
-                StyleKey::new("Toggle", "background_color", None),
-                Color::LIGHT_GREY.into(),
+                StyleKey::new("Select", "group_label_color", None),
+                Color::DARK_GREY.into(),
             ),
             (
-                StyleKey::new("Toggle", "highlight_color", None),
-                Color::DARK_GREY.into(),
+                StyleKey::new("Select", "group_label_font_size", None),
+                11.0.into(),
             ),
             (
-                StyleKey::new("Toggle", "active_color", None),
+                StyleKey::new("Select", "group_label_background", None),
+                Color::LIGHT_GREY.into(),
+            ),
+            (
+                StyleKey::new("Select", "group_separator_color", None),
                 Color::MID_GREY.into(),
             ),
             (
-                StyleKey::new("Toggle", "border_color", None),
-                Color::BLACK.into(),
+                StyleKey::new("Select", "group_label_padding", None),
+                2.0.into(),
+            ),
+            (StyleKey::new("Select", "group_indent", None), 8.0.into()),
+            (
+                StyleKey::new("Select", "chip_background", None),
+                Color::LIGHT_GREY.into(),
+            ),
+            (
+                StyleKey::new("Select", "chip_color", None),
+                Color::BLACK.into(),
+            ),
+            (StyleKey::new("Select", "chip_radius", None), 4.0.into()),
+            // Slider
+            (
+                StyleKey::new("Slider", "cursor", None),
+                CursorStyle::Pointer.into(),
+            ),
+            (
+                StyleKey::new("Slider", "track_color", None),
+                Color::rgb(64., 64., 68.).into(),
+            ),
+            (
+                StyleKey::new("Slider", "active_color", None),
+                Color::WHITE.into(),
+            ),
+            (
+                StyleKey::new("Slider", "thumb_color", None),
+                Color::WHITE.into(),
+            ),
+            (
+                StyleKey::new("Slider", "disabled_color", None),
+                Color::MID_GREY.into(),
+            ),
+            (StyleKey::new("Slider", "thumb_size", None), 18.0.into()),
+            (StyleKey::new("Slider", "track_height", None), 4.0.into()),
+            (
+                StyleKey::new("Slider", "thumb_size", Some("slider-sm")),
+                12.0.into(),
+            ),
+            (
+                StyleKey::new("Slider", "track_height", Some("slider-sm")),
+                2.0.into(),
+            ),
+            (
+                StyleKey::new("Slider", "thumb_size", Some("slider-md")),
+                18.0.into(),
+            ),
+            (
+                StyleKey::new("Slider", "track_height", Some("slider-md")),
+                4.0.into(),
+            ),
+            (
+                StyleKey::new("Slider", "thumb_size", Some("slider-lg")),
+                24.0.into(),
+            ),
+            (
+                StyleKey::new("Slider", "track_height", Some("slider-lg")),
+                6.0.into(),
+            ),
+            // Accordion
+            (
+                StyleKey::new("Accordion", "cursor", None),
+                CursorStyle::Pointer.into(),
+            ),
+            (
+                StyleKey::new("Accordion", "header_background", None),
+                Color::rgb(245., 245., 245.).into(),
+            ),
+            (
+                StyleKey::new("Accordion", "header_color", None),
+                Color::BLACK.into(),
+            ),
+            (
+                StyleKey::new("Accordion", "border_color", None),
+                Color::LIGHT_GREY.into(),
+            ),
+            (StyleKey::new("Accordion", "border_width", None), 1.0.into()),
+            (StyleKey::new("Accordion", "padding", None), 12.0.into()),
+            (StyleKey::new("Accordion", "font_size", None), 14.0.into()),
+            (
+                StyleKey::new("Accordion", "indicator_size", None),
+                10.0.into(),
+            ),
+            (
+                StyleKey::new("Accordion", "border_width", Some("accordion-flush")),
+                0.0.into(),
+            ),
+            // Modal
+            (
+                StyleKey::new("Modal", "backdrop_color", None),
+                Color::rgba(0., 0., 0., 0.5).into(),
+            ),
+            (
+                StyleKey::new("Modal", "background_color", None),
+                Color::WHITE.into(),
+            ),
+            (
+                StyleKey::new("Modal", "title_color", None),
+                Color::BLACK.into(),
+            ),
+            (
+                StyleKey::new("Modal", "shadow", None),
+                BoxShadow::new(0.0, 20.0, 25.0, -5.0, Color::rgba(0., 0., 0., 0.1)).into(),
+            ),
+            (StyleKey::new("Modal", "border_radius", None), 8.0.into()),
+            (StyleKey::new("Modal", "padding", None), 24.0.into()),
+            (StyleKey::new("Modal", "max_width", None), 560.0.into()),
+            (StyleKey::new("Modal", "title_font_size", None), 18.0.into()),
+            (StyleKey::new("Modal", "slide_distance", None), 24.0.into()),
+            (
+                StyleKey::new("Modal", "animation_duration_ms", None),
+                200.0.into(),
+            ),
+            // Tabs
+            (
+                StyleKey::new("Tabs", "cursor", None),
+                CursorStyle::Pointer.into(),
+            ),
+            (
+                StyleKey::new("Tabs", "tab_background", None),
+                Color::TRANSPARENT.into(),
+            ),
+            (
+                StyleKey::new("Tabs", "active_tab_background", None),
+                Color::TRANSPARENT.into(),
+            ),
+            (
+                StyleKey::new("Tabs", "indicator_color", None),
+                Color::rgb(45., 138., 255.).into(),
+            ),
+            (
+                StyleKey::new("Tabs", "text_color", None),
+                Color::DARK_GREY.into(),
+            ),
+            (
+                StyleKey::new("Tabs", "active_text_color", None),
+                Color::BLACK.into(),
+            ),
+            (StyleKey::new("Tabs", "indicator_width", None), 2.0.into()),
+            (StyleKey::new("Tabs", "tab_padding", None), 8.0.into()),
+            (StyleKey::new("Tabs", "tab_font_size", None), 12.0.into()),
+            (StyleKey::new("Tabs", "pill", None), false.into()),
+            (
+                StyleKey::new("Tabs", "pill", Some("tabs-pills")),
+                true.into(),
+            ),
+            (
+                StyleKey::new("Tabs", "active_text_color", Some("tabs-pills")),
+                Color::WHITE.into(),
+            ),
+            // Toast
+            (
+                StyleKey::new("Toast", "info_background_color", None),
+                Color::rgb(45., 45., 50.).into(),
+            ),
+            (
+                StyleKey::new("Toast", "success_background_color", None),
+                Color::rgb(22., 130., 70.).into(),
+            ),
+            (
+                StyleKey::new("Toast", "warning_background_color", None),
+                Color::rgb(190., 120., 10.).into(),
+            ),
+            (
+                StyleKey::new("Toast", "error_background_color", None),
+                Color::rgb(200., 40., 40.).into(),
+            ),
+            (
+                StyleKey::new("Toast", "text_color", None),
+                Color::WHITE.into(),
+            ),
+            (
+                StyleKey::new("Toast", "action_color", None),
+                Color::rgb(140., 200., 255.).into(),
+            ),
+            (
+                StyleKey::new("Toast", "shadow", None),
+                BoxShadow::new(0.0, 4.0, 6.0, -1.0, Color::rgba(0., 0., 0., 0.1)).into(),
+            ),
+            (StyleKey::new("Toast", "radius", None), 6.0.into()),
+            (StyleKey::new("Toast", "max_width", None), 360.0.into()),
+            (StyleKey::new("Toast", "gap", None), 8.0.into()),
+            (StyleKey::new("Toast", "margin", None), 16.0.into()),
+            (StyleKey::new("Toast", "padding", None), 12.0.into()),
+            (StyleKey::new("Toast", "font_size", None), 14.0.into()),
+            (StyleKey::new("Toast", "swipe_distance", None), 80.0.into()),
+            // Toggle
+            (
+                StyleKey::new("Toggle", "cursor", None),
+                CursorStyle::Pointer.into(),
+            ),
+            (
+                StyleKey::new("Toggle", "background_color", None),
+                Color::LIGHT_GREY.into(),
+            ),
+            (
+                StyleKey::new("Toggle", "highlight_color", None),
+                Color::DARK_GREY.into(),
+            ),
+            (
+                StyleKey::new("Toggle", "active_color", None),
+                Color::MID_GREY.into(),
+            ),
+            (
+                StyleKey::new("Toggle", "border_color", None),
+                Color::BLACK.into(),
             ),
             (StyleKey::new("Toggle", "border_width", None), 2.0.into()),
             // ToolTip
@@ -630,7 +2018,12 @@ impl Default for Style {
             ),
             (StyleKey::new("ToolTip", "border_width", None), 2.0.into()),
             (StyleKey::new("ToolTip", "padding", None), 4.0.into()),
+            (StyleKey::new("ToolTip", "arrow_size", None), 8.0.into()),
             // TextBox
+            (
+                StyleKey::new("TextBox", "cursor", None),
+                CursorStyle::Text.into(),
+            ),
             (StyleKey::new("TextBox", "font_size", None), 12.0.into()),
             (
                 StyleKey::new("TextBox", "font_size", Some("text-xs")),
@@ -664,6 +2057,10 @@ impl Default for Style {
                 StyleKey::new("TextBox", "text_color", Some("light")),
                 Color::rgb(14., 14., 14.).into(),
             ),
+            (
+                StyleKey::new("TextBox", "text_shadow", None),
+                StyleVal::TextShadow(vec![]),
+            ),
             (
                 StyleKey::new("TextBox", "placeholder_color", None),
                 Color::rgb(132., 132., 132.).into(),
@@ -688,6 +2085,10 @@ impl Default for Style {
                 StyleKey::new("TextBox", "selection_color", None),
                 Color::MID_GREY.into(),
             ),
+            (
+                StyleKey::new("TextBox", "find_highlight_color", None),
+                Color::YELLOW.into(),
+            ),
             (
                 StyleKey::new("TextBox", "cursor_color", None),
                 Color::WHITE.into(),
@@ -700,6 +2101,26 @@ impl Default for Style {
                 StyleKey::new("TextBox", "border_color", None),
                 Color::rgb(132., 132., 132.).into(),
             ),
+            (
+                StyleKey::new("TextBox", "shadow", None),
+                BoxShadow::NONE.into(),
+            ),
+            (
+                StyleKey::new("TextBox", "shadow", Some("shadow-none")),
+                BoxShadow::NONE.into(),
+            ),
+            (
+                StyleKey::new("TextBox", "shadow", Some("shadow-sm")),
+                BoxShadow::new(0.0, 1.0, 2.0, 0.0, Color::rgba(0., 0., 0., 0.05)).into(),
+            ),
+            (
+                StyleKey::new("TextBox", "shadow", Some("shadow")),
+                BoxShadow::new(0.0, 1.0, 3.0, 0.0, Color::rgba(0., 0., 0., 0.1)).into(),
+            ),
+            (
+                StyleKey::new("TextBox", "shadow", Some("shadow-lg")),
+                BoxShadow::new(0.0, 10.0, 15.0, -3.0, Color::rgba(0., 0., 0., 0.1)).into(),
+            ),
             (
                 StyleKey::new("TextBox", "border_color", Some("light")),
                 Color::rgb(209., 209., 209.).into(),
@@ -929,6 +2350,103 @@ impl Default for Style {
                 StyleKey::new("TextBox", "font_weight", None),
                 FontWeight::Normal.into(),
             ),
+            (
+                StyleKey::new("TextBox", "font_style", None),
+                FontStyle::Normal.into(),
+            ),
+            (
+                StyleKey::new("TextBox", "font_style", Some("font-normal")),
+                FontStyle::Normal.into(),
+            ),
+            (
+                StyleKey::new("TextBox", "font_style", Some("font-italic")),
+                FontStyle::Italic.into(),
+            ),
+            (
+                StyleKey::new("TextBox", "text_decoration", None),
+                TextDecoration::NONE.into(),
+            ),
+            (
+                StyleKey::new("TextBox", "text_decoration", Some("underline")),
+                TextDecoration::UNDERLINE.into(),
+            ),
+            (
+                StyleKey::new("TextBox", "text_decoration", Some("line-through")),
+                TextDecoration::STRIKETHROUGH.into(),
+            ),
+            (
+                StyleKey::new("TextBox", "text_decoration", Some("overline")),
+                TextDecoration::OVERLINE.into(),
+            ),
+            (StyleKey::new("TextBox", "letter_spacing", None), 0.0.into()),
+            (
+                StyleKey::new("TextBox", "letter_spacing", Some("tracking-tighter")),
+                (-0.8).into(),
+            ),
+            (
+                StyleKey::new("TextBox", "letter_spacing", Some("tracking-tight")),
+                (-0.4).into(),
+            ),
+            (
+                StyleKey::new("TextBox", "letter_spacing", Some("tracking-normal")),
+                (0.0).into(),
+            ),
+            (
+                StyleKey::new("TextBox", "letter_spacing", Some("tracking-wide")),
+                (0.4).into(),
+            ),
+            (
+                StyleKey::new("TextBox", "letter_spacing", Some("tracking-wider")),
+                (0.8).into(),
+            ),
+            (
+                StyleKey::new("TextBox", "letter_spacing", Some("tracking-widest")),
+                (1.6).into(),
+            ),
+            (StyleKey::new("TextBox", "multiline", None), false.into()),
+            (
+                StyleKey::new("TextBox", "multiline", Some("textarea")),
+                true.into(),
+            ),
+            (StyleKey::new("TextBox", "resize", None), "none".into()),
+            (
+                StyleKey::new("TextBox", "resize", Some("textarea")),
+                "vertical".into(),
+            ),
+            (
+                StyleKey::new("TextBox", "suggestion_background", None),
+                Color::WHITE.into(),
+            ),
+            (
+                StyleKey::new("TextBox", "suggestion_hover_color", None),
+                Color::LIGHT_GREY.into(),
+            ),
+            (
+                StyleKey::new("TextBox", "suggestion_max_items", None),
+                8u32.into(),
+            ),
+            (
+                StyleKey::new("TextBox", "suggestion_min_chars", None),
+                1u32.into(),
+            ),
+            (
+                StyleKey::new("TextBox", "counter_color", None),
+                Color::MID_GREY.into(),
+            ),
+            (
+                StyleKey::new("TextBox", "counter_warning_color", None),
+                Color::RED.into(),
+            ),
+            (
+                StyleKey::new("TextBox", "counter_font_size", None),
+                10.0.into(),
+            ),
+            // NumberInput, which is styled as a TextBox
+            (StyleKey::new("TextBox", "button_width", None), 24.0.into()),
+            (
+                StyleKey::new("TextBox", "error_color", None),
+                Color::RED.into(),
+            ),
             // Text
             (StyleKey::new("Text", "size", None), 12.0.into()),
             (StyleKey::new("Text", "size", Some("text-xs")), 14.0.into()),
@@ -950,6 +2468,99 @@ impl Default for Style {
                 StyleKey::new("Text", "font_weight", None),
                 FontWeight::Normal.into(),
             ),
+            (
+                StyleKey::new("Text", "font_style", None),
+                FontStyle::Normal.into(),
+            ),
+            (
+                StyleKey::new("Text", "font_style", Some("font-normal")),
+                FontStyle::Normal.into(),
+            ),
+            (
+                StyleKey::new("Text", "font_style", Some("font-italic")),
+                FontStyle::Italic.into(),
+            ),
+            (
+                StyleKey::new("Text", "text_decoration", None),
+                TextDecoration::NONE.into(),
+            ),
+            (
+                StyleKey::new("Text", "text_decoration", Some("underline")),
+                TextDecoration::UNDERLINE.into(),
+            ),
+            (
+                StyleKey::new("Text", "text_decoration", Some("line-through")),
+                TextDecoration::STRIKETHROUGH.into(),
+            ),
+            (
+                StyleKey::new("Text", "text_decoration", Some("overline")),
+                TextDecoration::OVERLINE.into(),
+            ),
+            (StyleKey::new("Text", "letter_spacing", None), 0.0.into()),
+            (
+                StyleKey::new("Text", "letter_spacing", Some("tracking-tighter")),
+                (-0.8).into(),
+            ),
+            (
+                StyleKey::new("Text", "letter_spacing", Some("tracking-tight")),
+                (-0.4).into(),
+            ),
+            (
+                StyleKey::new("Text", "letter_spacing", Some("tracking-normal")),
+                (0.0).into(),
+            ),
+            (
+                StyleKey::new("Text", "letter_spacing", Some("tracking-wide")),
+                (0.4).into(),
+            ),
+            (
+                StyleKey::new("Text", "letter_spacing", Some("tracking-wider")),
+                (0.8).into(),
+            ),
+            (
+                StyleKey::new("Text", "letter_spacing", Some("tracking-widest")),
+                (1.6).into(),
+            ),
+            (
+                StyleKey::new("Text", "text_overflow", None),
+                TextOverflow::Clip.into(),
+            ),
+            (
+                StyleKey::new("Text", "text_overflow", Some("truncate")),
+                TextOverflow::Ellipsis.into(),
+            ),
+            (
+                StyleKey::new("Text", "text_overflow", Some("text-ellipsis")),
+                TextOverflow::Ellipsis.into(),
+            ),
+            (
+                StyleKey::new("Text", "text_overflow", Some("text-clip")),
+                TextOverflow::Clip.into(),
+            ),
+            (
+                StyleKey::new("Text", "max_lines", Some("lines-1")),
+                1u32.into(),
+            ),
+            (
+                StyleKey::new("Text", "max_lines", Some("lines-2")),
+                2u32.into(),
+            ),
+            (
+                StyleKey::new("Text", "max_lines", Some("lines-3")),
+                3u32.into(),
+            ),
+            (
+                StyleKey::new("Text", "max_lines", Some("lines-4")),
+                4u32.into(),
+            ),
+            (
+                StyleKey::new("Text", "max_lines", Some("lines-5")),
+                5u32.into(),
+            ),
+            (
+                StyleKey::new("Text", "max_lines", Some("lines-6")),
+                6u32.into(),
+            ),
             (
                 StyleKey::new("Text", "font_weight", Some("font-thin")),
                 FontWeight::Thin.into(),
@@ -986,6 +2597,34 @@ impl Default for Style {
                 StyleKey::new("Text", "font_weight", Some("font-black")),
                 FontWeight::Black.into(),
             ),
+            (
+                StyleKey::new("Text", "word_break", None),
+                WordBreak::Normal.into(),
+            ),
+            (
+                StyleKey::new("Text", "text_shadow", None),
+                StyleVal::TextShadow(vec![]),
+            ),
+            (
+                StyleKey::new("Text", "word_break", Some("word-break-all")),
+                WordBreak::BreakAll.into(),
+            ),
+            (
+                StyleKey::new("Text", "word_break", Some("word-keep-all")),
+                WordBreak::KeepAll.into(),
+            ),
+            (
+                StyleKey::new("Text", "overflow_wrap", None),
+                OverflowWrap::Normal.into(),
+            ),
+            (
+                StyleKey::new("Text", "overflow_wrap", Some("break-word")),
+                OverflowWrap::BreakWord.into(),
+            ),
+            (
+                StyleKey::new("Text", "overflow_wrap", Some("break-anywhere")),
+                OverflowWrap::Anywhere.into(),
+            ),
             (StyleKey::new("Text", "color", None), Color::BLACK.into()),
             (
                 StyleKey::new("Text", "color", Some("light")),
@@ -1091,10 +2730,26 @@ impl Default for Style {
                 StyleKey::new("Scroll", "bar_active_color", None),
                 Color::DARK_GREY.into(),
             ),
+            (
+                StyleKey::new("Scroll", "animation_duration_ms", None),
+                250.0.into(),
+            ),
+            (StyleKey::new("Scroll", "snap", None), "none".into()),
+            (
+                StyleKey::new("Scroll", "snap", Some("snap")),
+                "mandatory".into(),
+            ),
+            (StyleKey::new("Scroll", "snap_padding", None), 0.0.into()),
             //Image
             (StyleKey::new("Image", "radius", None), 0.0.into()),
         ]);
-        Self(map)
+        Self::from_map(map)
+    }
+}
+
+impl PartialEq for Style {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
     }
 }
 
@@ -1114,14 +2769,189 @@ pub fn current_style(component: &'static str, parameter_name: &'static str) -> O
         .style(component, parameter_name)
 }
 
-fn get_current_style(k: StyleKey) -> Option<StyleVal> {
-    _current_style().lock().unwrap().get(k)
+/// Every entry of the current style for the component `name`. See [`Style::for_component`].
+pub fn current_style_for_component(name: &'static str) -> StyleSnapshot {
+    _current_style().lock().unwrap().for_component(name)
 }
 
-/// Implemented by the [`component`][macro@crate::component] attribute macro, for "Styled" Components.
-pub trait Styled: Sized {
-    #[doc(hidden)]
-    fn name() -> &'static str;
+/// Add an entry to the current style, replacing any value that `k` already had.
+pub fn set_current_style_val(k: StyleKey, v: StyleVal) {
+    _current_style().lock().unwrap().insert(k, v);
+}
+
+fn _viewport_width() -> &'static Mutex<Option<f32>> {
+    static VIEWPORT_WIDTH: OnceLock<Mutex<Option<f32>>> = OnceLock::new();
+    VIEWPORT_WIDTH.get_or_init(|| Mutex::new(None))
+}
+
+/// Set the logical width of the viewport, which picks the [`Breakpoint`] entries of the style
+/// that apply. The `UI` sets this whenever the window is resized.
+pub fn set_viewport_width(width: f32) {
+    *_viewport_width().lock().unwrap() = Some(width);
+}
+
+/// The breakpoint of the viewport, once its width has been set.
+pub fn current_breakpoint() -> Option<Breakpoint> {
+    _viewport_width().lock().unwrap().map(Breakpoint::for_width)
+}
+
+thread_local! {
+    static CONTAINER_WIDTH: std::cell::Cell<Option<f32>> = std::cell::Cell::new(None);
+}
+
+/// Resolve [`ContainerQuery`] entries of the style against `width` while running `f`. Nodes
+/// run layout and rendering with the logical width of their component.
+pub fn with_container_width<R>(width: Option<f32>, f: impl FnOnce() -> R) -> R {
+    let previous = CONTAINER_WIDTH.with(|w| w.replace(width));
+    let result = f();
+    CONTAINER_WIDTH.with(|w| w.set(previous));
+    result
+}
+
+/// The width set by the innermost [`with_container_width`].
+pub fn container_width() -> Option<f32> {
+    CONTAINER_WIDTH.with(|w| w.get())
+}
+
+/// A `'static` copy of `s`, e.g. a class list or a deserialized name. Each distinct string is
+/// only leaked once, so views can rebuild their class lists on every render.
+fn intern(s: String) -> &'static str {
+    static STRINGS: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    let mut interned = STRINGS
+        .get_or_init(|| Mutex::new(HashSet::new()))
+        .lock()
+        .unwrap();
+    if let Some(s) = interned.get(s.as_str()) {
+        return s;
+    }
+    let s: &'static str = Box::leak(s.into_boxed_str());
+    interned.insert(s);
+    s
+}
+
+fn get_current_style(k: StyleKey) -> Option<StyleVal> {
+    let breakpoint = current_breakpoint();
+    let width = container_width();
+    _current_style()
+        .lock()
+        .unwrap()
+        .get_for_container(k, breakpoint, width)
+}
+
+/// The reason a [`Styled#style_val_result`][Styled#method.style_val_result] lookup failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StyleLookupError {
+    /// No value is registered for this component and parameter, and no class was set.
+    NotRegistered {
+        component: &'static str,
+        parameter: &'static str,
+    },
+    /// A class was set, but neither it nor the unclassed fallback has a value for this parameter.
+    NoMatchForClass {
+        component: &'static str,
+        parameter: &'static str,
+        class: &'static str,
+    },
+}
+
+impl std::fmt::Display for StyleLookupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NotRegistered {
+                component,
+                parameter,
+            } => write!(f, "no style registered for {component}.{parameter}"),
+            Self::NoMatchForClass {
+                component,
+                parameter,
+                class,
+            } => write!(
+                f,
+                "no style registered for {component}.{parameter} with class \"{class}\" or without a class"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StyleLookupError {}
+
+/// The variant of [`StyleVal`] that [`StyleVal::from_str`] should parse.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StyleValType {
+    Color,
+    Float,
+    Int,
+    Bool,
+    FontWeight,
+    HorizontalPosition,
+    VerticalPosition,
+}
+
+/// Why [`StyleVal::from_str`] could not parse a string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StyleParseError {
+    pub input: String,
+    pub expected: StyleValType,
+}
+
+impl std::fmt::Display for StyleParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "could not parse \"{}\" as a {:?}",
+            self.input, self.expected
+        )
+    }
+}
+
+impl std::error::Error for StyleParseError {}
+
+/// Parse hex, as [`Color::from_hex`] does, or a name of one of the [`Color`] constants.
+fn parse_color(s: &str) -> Option<Color> {
+    if s.starts_with('#') {
+        return Color::from_hex(s).ok();
+    }
+    Some(match s.to_ascii_lowercase().replace('-', "_").as_str() {
+        "transparent" => Color::TRANSPARENT,
+        "black" => Color::BLACK,
+        "white" => Color::WHITE,
+        "light_grey" => Color::LIGHT_GREY,
+        "mid_grey" => Color::MID_GREY,
+        "dark_grey" => Color::DARK_GREY,
+        "red" => Color::RED,
+        "green" => Color::GREEN,
+        "blue" => Color::BLUE,
+        "yellow" => Color::YELLOW,
+        "magenta" => Color::MAGENTA,
+        _ => return None,
+    })
+}
+
+/// Parse a weight name, e.g. `semibold`, or a multiple of 100 from 100 to 900.
+fn parse_font_weight(s: &str) -> Option<FontWeight> {
+    Some(
+        match s.to_ascii_lowercase().replace(['-', ' '], "_").as_str() {
+            "thin" | "100" => FontWeight::Thin,
+            "extra_light" | "extralight" | "200" => FontWeight::ExtraLight,
+            "light" | "300" => FontWeight::Light,
+            "normal" | "400" => FontWeight::Normal,
+            "medium" | "500" => FontWeight::Medium,
+            "semibold" | "semi_bold" | "600" => FontWeight::Semibold,
+            "bold" | "700" => FontWeight::Bold,
+            "extra_bold" | "extrabold" | "800" => FontWeight::ExtraBold,
+            "black" | "900" => FontWeight::Black,
+            _ => return None,
+        },
+    )
+}
+
+/// Derives [`Styled`][trait@Styled], for structs that are not declared with the [`component`][macro@crate::component] attribute macro.
+pub use mctk_macros::Styled;
+
+/// Implemented by the [`component`][macro@crate::component] attribute macro, for "Styled" Components, or by `#[derive(Styled)]`.
+pub trait Styled: Sized {
+    #[doc(hidden)]
+    fn name() -> &'static str;
     #[doc(hidden)]
     fn class(&self) -> Option<&'static str>;
     #[doc(hidden)]
@@ -1131,11 +2961,42 @@ pub trait Styled: Sized {
     #[doc(hidden)]
     fn style_overrides_mut(&mut self) -> &mut StyleOverride;
 
+    /// Set the classes of the component, replacing any that it had. Separate multiple classes
+    /// with spaces, e.g. `"bg-blue text-white rounded-lg"`. When more than one class has an
+    /// entry for a parameter, the last one wins.
     fn with_class(mut self, class: &'static str) -> Self {
         *self.class_mut() = Some(class);
         self
     }
 
+    /// Like [`#with_class`][Styled#method.with_class], with each class given separately.
+    fn with_classes(mut self, classes: &[&'static str]) -> Self {
+        *self.class_mut() = match classes {
+            [] => None,
+            [class] => Some(*class),
+            _ => Some(intern(classes.join(" "))),
+        };
+        self
+    }
+
+    /// Add `class` after the classes that the component already has, so that its entries win.
+    /// Does nothing if the component already has `class`.
+    fn add_class(&mut self, class: &'static str) {
+        *self.class_mut() = match self.class() {
+            Some(c) if c.split_whitespace().any(|c| c == class) => Some(c),
+            Some(c) => Some(intern(format!("{c} {class}"))),
+            None => Some(class),
+        };
+    }
+
+    /// The classes of the component, from the one whose entries win to the one tried last.
+    #[doc(hidden)]
+    fn classes(&self) -> Vec<&'static str> {
+        self.class()
+            .map(|c| c.split_whitespace().rev().collect())
+            .unwrap_or_default()
+    }
+
     fn style<V: Into<StyleVal>>(mut self, parameter: &'static str, val: V) -> Self {
         self.style_overrides_mut().0.insert(parameter, val.into());
         self
@@ -1154,24 +3015,63 @@ pub trait Styled: Sized {
             struct_name: Self::name(),
             parameter_name,
             class,
+            breakpoint: None,
+            container_query: None,
+            state: None,
         }
     }
 
     fn style_val(&self, param: &'static str) -> Option<StyleVal> {
+        self.style_val_result(param).ok()
+    }
+
+    /// Like [`#style_val`][Styled#method.style_val], but reports why the lookup failed.
+    fn style_val_result(&self, param: &'static str) -> Result<StyleVal, StyleLookupError> {
         if let Some(v) = self.style_overrides().0.get(param) {
-            Some(v.clone())
+            Ok(v.clone())
         } else if let Some(c) = self.class() {
             // println!("param {:?} class {:?}", param, c);
-            for c in c.split(" ").collect::<Vec<&str>>() {
+            for c in self.classes() {
                 if let Some(v) = get_current_style(self.style_key(param, Some(c))) {
-                    return Some(v);
+                    return Ok(v);
                 }
             }
-            get_current_style(self.style_key(param, None))
+            get_current_style(self.style_key(param, None)).ok_or(
+                StyleLookupError::NoMatchForClass {
+                    component: Self::name(),
+                    parameter: param,
+                    class: c,
+                },
+            )
         } else {
-            get_current_style(self.style_key(param, None))
+            get_current_style(self.style_key(param, None)).ok_or(StyleLookupError::NotRegistered {
+                component: Self::name(),
+                parameter: param,
+            })
         }
     }
+
+    /// The value of `param` for a component in `state`, e.g. from a `Button:hover` entry.
+    /// Each single state is tried in [priority order](ComponentState::ALL), first with the
+    /// component's classes and then without. Falls back to
+    /// [`#style_val`][Styled#method.style_val] when no state has an entry.
+    fn style_val_for_state(&self, param: &'static str, state: ComponentState) -> Option<StyleVal> {
+        self.state_style_val(param, state)
+            .or_else(|| self.style_val(param))
+    }
+
+    /// Like [`#style_val_for_state`][Styled#method.style_val_for_state], without the fallback.
+    #[doc(hidden)]
+    fn state_style_val(&self, param: &'static str, state: ComponentState) -> Option<StyleVal> {
+        let classes = self.classes();
+        state.iter().find_map(|s| {
+            classes
+                .iter()
+                .map(|c| Some(*c))
+                .chain(std::iter::once(None))
+                .find_map(|c| get_current_style(self.style_key(param, c).state(s)))
+        })
+    }
 }
 
 #[macro_export]
@@ -1184,11 +3084,79 @@ macro_rules! style {
     // ->
     // Style::new().add(StyleKey::new("Widget", "color", Some("class")), Color::BLACK.into())
 
+    // hover.Widget.color = Color::RED;
+    // ->
+    // Style::new().add(StyleKey::new("Widget", "color", None).state(ComponentState::HOVER), Color::RED.into())
+
+    // if dark_mode { Widget.color = Color::BLACK; } else { Widget.color = Color::WHITE; }
+    // ->
+    // { let style = ...; if dark_mode { style.add(...) } else { style.add(...) } }
+
     //Finish it
     ( @ { } -> ($($result:tt)*) ) => (
-        $crate::style::Style::new() $($result)*
+        $($result)*
+    );
+
+    ( @ { if $($rest:tt)* } -> ($($result:tt)*) ) => (
+        style!(@if [] { $($rest)* } -> ($($result)*))
+    );
+
+    ( @if [$($cond:tt)*] { { $($then:tt)* } else { $($else:tt)* } $($rest:tt)* } -> ($($result:tt)*) ) => (
+        style!(@ { $($rest)* } -> (({
+            let style = $($result)*;
+            if $($cond)* {
+                style!(@ { $($then)* } -> (style))
+            } else {
+                style!(@ { $($else)* } -> (style))
+            }
+        })))
+    );
+
+    ( @if [$($cond:tt)*] { { $($then:tt)* } $($rest:tt)* } -> ($($result:tt)*) ) => (
+        style!(@ { $($rest)* } -> (({
+            let style = $($result)*;
+            if $($cond)* {
+                style!(@ { $($then)* } -> (style))
+            } else {
+                style
+            }
+        })))
+    );
+
+    // Collect the condition, up to the block of entries
+    ( @if [$($cond:tt)*] { $next:tt $($rest:tt)* } -> ($($result:tt)*) ) => (
+        style!(@if [$($cond)* $next] { $($rest)* } -> ($($result)*))
+    );
+
+    ( @ { hover . $($rest:tt)* } -> ($($result:tt)*) ) => (
+        style!(@state [HOVER] { $($rest)* } -> ($($result)*))
+    );
+
+    ( @ { active . $($rest:tt)* } -> ($($result:tt)*) ) => (
+        style!(@state [ACTIVE] { $($rest)* } -> ($($result)*))
+    );
+
+    ( @ { focus . $($rest:tt)* } -> ($($result:tt)*) ) => (
+        style!(@state [FOCUS] { $($rest)* } -> ($($result)*))
+    );
+
+    ( @ { disabled . $($rest:tt)* } -> ($($result:tt)*) ) => (
+        style!(@state [DISABLED] { $($rest)* } -> ($($result)*))
+    );
+
+    ( @state [$state:ident] { $component:ident . $param:ident = $val:expr ; $($rest:tt)* } -> ($($result:tt)*) ) => (
+        style!(@ { $($rest)* } -> (
+            $($result)*
+            .add($crate::style::StyleKey::new(stringify!($component), stringify!($param), None).state($crate::style::ComponentState::$state), $val.into())
+        ))
     );
 
+    ( @state [$state:ident] { $class:ident . $component:ident . $param:ident = $val:expr ; $($rest:tt)* } -> ($($result:tt)*) ) => (
+        style!(@ { $($rest)* } -> (
+            $($result)*
+            .add($crate::style::StyleKey::new(stringify!($component), stringify!($param), Some(stringify!($class))).state($crate::style::ComponentState::$state), $val.into())
+        ))
+    );
 
     ( @ { $component:ident . $param:ident = $val:expr ; $($rest:tt)* } -> ($($result:tt)*) ) => (
         style!(@ { $($rest)* } -> (
@@ -1206,7 +3174,7 @@ macro_rules! style {
 
     // Entry point
     ( $( $tt:tt )* ) => (
-        style!(@ { $($tt)* } -> ())
+        style!(@ { $($tt)* } -> ($crate::style::Style::new()))
     );
 
 }
@@ -1224,7 +3192,10 @@ impl From<StyleVal> for BorderWidth {
     fn from(v: StyleVal) -> Self {
         match v {
             StyleVal::BorderWidth(c) => c,
-            x => panic!("Tried to coerce {x:?} into a border width"),
+            x => panic!(
+                "Tried to coerce {} {x} into a border width",
+                x.variant_name()
+            ),
         }
     }
 }
@@ -1232,7 +3203,29 @@ impl From<Option<StyleVal>> for BorderWidth {
     fn from(v: Option<StyleVal>) -> Self {
         match v {
             Some(StyleVal::BorderWidth(c)) => c,
-            x => panic!("Tried to coerce {x:?} into a border width"),
+            x => panic!(
+                "Tried to coerce {} {x} into a border width",
+                x.variant_name()
+            ),
+        }
+    }
+}
+
+impl From<BorderRadius> for StyleVal {
+    fn from(r: BorderRadius) -> Self {
+        Self::BorderRadius(r)
+    }
+}
+impl From<StyleVal> for BorderRadius {
+    /// A `Float` is the same radius on every corner.
+    fn from(v: StyleVal) -> Self {
+        match v {
+            StyleVal::BorderRadius(r) => r,
+            StyleVal::Float(r) => BorderRadius::uniform(r as f32),
+            x => panic!(
+                "Tried to coerce {} {x} into a border radius",
+                x.variant_name()
+            ),
         }
     }
 }
@@ -1246,7 +3239,7 @@ impl From<StyleVal> for Color {
     fn from(v: StyleVal) -> Self {
         match v {
             StyleVal::Color(c) => c,
-            x => panic!("Tried to coerce {x:?} into a Color"),
+            x => panic!("Tried to coerce {} {x} into a Color", x.variant_name()),
         }
     }
 }
@@ -1254,7 +3247,7 @@ impl From<Option<StyleVal>> for Color {
     fn from(v: Option<StyleVal>) -> Self {
         match v {
             Some(StyleVal::Color(c)) => c,
-            x => panic!("Tried to coerce {x:?} into a Color"),
+            x => panic!("Tried to coerce {} {x} into a Color", x.variant_name()),
         }
     }
 }
@@ -1267,7 +3260,7 @@ impl From<StyleVal> for Dimension {
     fn from(v: StyleVal) -> Self {
         match v {
             StyleVal::Dimension(c) => c,
-            x => panic!("Tried to coerce {x:?} into a Dimension"),
+            x => panic!("Tried to coerce {} {x} into a Dimension", x.variant_name()),
         }
     }
 }
@@ -1275,7 +3268,7 @@ impl From<Option<StyleVal>> for Dimension {
     fn from(v: Option<StyleVal>) -> Self {
         match v {
             Some(StyleVal::Dimension(c)) => c,
-            x => panic!("Tried to coerce {x:?} into a Dimension"),
+            x => panic!("Tried to coerce {} {x} into a Dimension", x.variant_name()),
         }
     }
 }
@@ -1288,7 +3281,7 @@ impl From<StyleVal> for Size {
     fn from(v: StyleVal) -> Self {
         match v {
             StyleVal::Size(c) => c,
-            x => panic!("Tried to coerce {x:?} into a Size"),
+            x => panic!("Tried to coerce {} {x} into a Size", x.variant_name()),
         }
     }
 }
@@ -1296,7 +3289,7 @@ impl From<Option<StyleVal>> for Size {
     fn from(v: Option<StyleVal>) -> Self {
         match v {
             Some(StyleVal::Size(c)) => c,
-            x => panic!("Tried to coerce {x:?} into a Size"),
+            x => panic!("Tried to coerce {} {x} into a Size", x.variant_name()),
         }
     }
 }
@@ -1309,7 +3302,7 @@ impl From<StyleVal> for Pos {
     fn from(v: StyleVal) -> Self {
         match v {
             StyleVal::Pos(c) => c,
-            x => panic!("Tried to coerce {x:?} into a Pos"),
+            x => panic!("Tried to coerce {} {x} into a Pos", x.variant_name()),
         }
     }
 }
@@ -1317,7 +3310,7 @@ impl From<Option<StyleVal>> for Pos {
     fn from(v: Option<StyleVal>) -> Self {
         match v {
             Some(StyleVal::Pos(c)) => c,
-            x => panic!("Tried to coerce {x:?} into a Pos"),
+            x => panic!("Tried to coerce {} {x} into a Pos", x.variant_name()),
         }
     }
 }
@@ -1330,7 +3323,7 @@ impl From<StyleVal> for Point {
     fn from(v: StyleVal) -> Self {
         match v {
             StyleVal::Point(c) => c,
-            x => panic!("Tried to coerce {x:?} into a Point"),
+            x => panic!("Tried to coerce {} {x} into a Point", x.variant_name()),
         }
     }
 }
@@ -1338,7 +3331,51 @@ impl From<Option<StyleVal>> for Point {
     fn from(v: Option<StyleVal>) -> Self {
         match v {
             Some(StyleVal::Point(c)) => c,
-            x => panic!("Tried to coerce {x:?} into a Point"),
+            x => panic!("Tried to coerce {} {x} into a Point", x.variant_name()),
+        }
+    }
+}
+impl From<Gradient> for StyleVal {
+    fn from(g: Gradient) -> Self {
+        Self::Gradient(g)
+    }
+}
+impl From<StyleVal> for Gradient {
+    fn from(v: StyleVal) -> Self {
+        match v {
+            StyleVal::Gradient(g) => g,
+            x => panic!("Tried to coerce {} {x} into a Gradient", x.variant_name()),
+        }
+    }
+}
+impl From<TextShadow> for StyleVal {
+    fn from(s: TextShadow) -> Self {
+        Self::TextShadow(vec![s])
+    }
+}
+impl From<Vec<TextShadow>> for StyleVal {
+    fn from(s: Vec<TextShadow>) -> Self {
+        Self::TextShadow(s)
+    }
+}
+impl From<BoxShadow> for StyleVal {
+    fn from(s: BoxShadow) -> Self {
+        Self::BoxShadow(s)
+    }
+}
+impl From<StyleVal> for BoxShadow {
+    fn from(v: StyleVal) -> Self {
+        match v {
+            StyleVal::BoxShadow(s) => s,
+            x => panic!("Tried to coerce {} {x} into a BoxShadow", x.variant_name()),
+        }
+    }
+}
+impl From<StyleVal> for Vec<TextShadow> {
+    fn from(v: StyleVal) -> Self {
+        match v {
+            StyleVal::TextShadow(s) => s,
+            x => panic!("Tried to coerce {} {x} into a TextShadow", x.variant_name()),
         }
     }
 }
@@ -1351,7 +3388,7 @@ impl From<StyleVal> for Rect {
     fn from(v: StyleVal) -> Self {
         match v {
             StyleVal::Rect(c) => c,
-            x => panic!("Tried to coerce {x:?} into a Rect"),
+            x => panic!("Tried to coerce {} {x} into a Rect", x.variant_name()),
         }
     }
 }
@@ -1359,7 +3396,7 @@ impl From<Option<StyleVal>> for Rect {
     fn from(v: Option<StyleVal>) -> Self {
         match v {
             Some(StyleVal::Rect(c)) => c,
-            x => panic!("Tried to coerce {x:?} into a Rect"),
+            x => panic!("Tried to coerce {} {x} into a Rect", x.variant_name()),
         }
     }
 }
@@ -1372,7 +3409,7 @@ impl From<StyleVal> for Layout {
     fn from(v: StyleVal) -> Self {
         match v {
             StyleVal::Layout(c) => c,
-            x => panic!("Tried to coerce {x:?} into a Layout"),
+            x => panic!("Tried to coerce {} {x} into a Layout", x.variant_name()),
         }
     }
 }
@@ -1380,7 +3417,7 @@ impl From<Option<StyleVal>> for Layout {
     fn from(v: Option<StyleVal>) -> Self {
         match v {
             Some(StyleVal::Layout(c)) => c,
-            x => panic!("Tried to coerce {x:?} into a Layout"),
+            x => panic!("Tried to coerce {} {x} into a Layout", x.variant_name()),
         }
     }
 }
@@ -1393,7 +3430,10 @@ impl From<StyleVal> for VerticalPosition {
     fn from(v: StyleVal) -> Self {
         match v {
             StyleVal::VerticalPosition(c) => c,
-            x => panic!("Tried to coerce {x:?} into a VerticalPosition"),
+            x => panic!(
+                "Tried to coerce {} {x} into a VerticalPosition",
+                x.variant_name()
+            ),
         }
     }
 }
@@ -1401,7 +3441,10 @@ impl From<Option<StyleVal>> for VerticalPosition {
     fn from(v: Option<StyleVal>) -> Self {
         match v {
             Some(StyleVal::VerticalPosition(c)) => c,
-            x => panic!("Tried to coerce {x:?} into a VerticalPosition"),
+            x => panic!(
+                "Tried to coerce {} {x} into a VerticalPosition",
+                x.variant_name()
+            ),
         }
     }
 }
@@ -1414,7 +3457,10 @@ impl From<StyleVal> for HorizontalPosition {
     fn from(v: StyleVal) -> Self {
         match v {
             StyleVal::HorizontalPosition(c) => c,
-            x => panic!("Tried to coerce {x:?} into a HorizontalPosition"),
+            x => panic!(
+                "Tried to coerce {} {x} into a HorizontalPosition",
+                x.variant_name()
+            ),
         }
     }
 }
@@ -1422,7 +3468,10 @@ impl From<Option<StyleVal>> for HorizontalPosition {
     fn from(v: Option<StyleVal>) -> Self {
         match v {
             Some(StyleVal::HorizontalPosition(c)) => c,
-            x => panic!("Tried to coerce {x:?} into a HorizontalPosition"),
+            x => panic!(
+                "Tried to coerce {} {x} into a HorizontalPosition",
+                x.variant_name()
+            ),
         }
     }
 }
@@ -1435,7 +3484,7 @@ impl From<StyleVal> for FontWeight {
     fn from(v: StyleVal) -> Self {
         match v {
             StyleVal::FontWeight(c) => c,
-            x => panic!("Tried to coerce {x:?} into a FontWeight"),
+            x => panic!("Tried to coerce {} {x} into a FontWeight", x.variant_name()),
         }
     }
 }
@@ -1443,7 +3492,127 @@ impl From<Option<StyleVal>> for FontWeight {
     fn from(v: Option<StyleVal>) -> Self {
         match v {
             Some(StyleVal::FontWeight(c)) => c,
-            x => panic!("Tried to coerce {x:?} into a FontWeight"),
+            x => panic!("Tried to coerce {} {x} into a FontWeight", x.variant_name()),
+        }
+    }
+}
+impl From<FontStyle> for StyleVal {
+    fn from(c: FontStyle) -> Self {
+        Self::FontStyle(c)
+    }
+}
+impl From<StyleVal> for FontStyle {
+    fn from(v: StyleVal) -> Self {
+        match v {
+            StyleVal::FontStyle(c) => c,
+            x => panic!("Tried to coerce {} {x} into a FontStyle", x.variant_name()),
+        }
+    }
+}
+impl From<Option<StyleVal>> for FontStyle {
+    fn from(v: Option<StyleVal>) -> Self {
+        match v {
+            Some(StyleVal::FontStyle(c)) => c,
+            x => panic!("Tried to coerce {} {x} into a FontStyle", x.variant_name()),
+        }
+    }
+}
+impl From<TextDecoration> for StyleVal {
+    fn from(d: TextDecoration) -> Self {
+        Self::TextDecoration(d)
+    }
+}
+impl From<StyleVal> for TextDecoration {
+    fn from(v: StyleVal) -> Self {
+        match v {
+            StyleVal::TextDecoration(d) => d,
+            x => panic!(
+                "Tried to coerce {} {x} into a TextDecoration",
+                x.variant_name()
+            ),
+        }
+    }
+}
+impl From<Option<StyleVal>> for TextDecoration {
+    fn from(v: Option<StyleVal>) -> Self {
+        match v {
+            Some(StyleVal::TextDecoration(d)) => d,
+            x => panic!(
+                "Tried to coerce {} {x} into a TextDecoration",
+                x.variant_name()
+            ),
+        }
+    }
+}
+impl From<TextOverflow> for StyleVal {
+    fn from(o: TextOverflow) -> Self {
+        Self::TextOverflow(o)
+    }
+}
+impl From<StyleVal> for TextOverflow {
+    fn from(v: StyleVal) -> Self {
+        match v {
+            StyleVal::TextOverflow(o) => o,
+            x => panic!(
+                "Tried to coerce {} {x} into a TextOverflow",
+                x.variant_name()
+            ),
+        }
+    }
+}
+impl From<Option<StyleVal>> for TextOverflow {
+    fn from(v: Option<StyleVal>) -> Self {
+        match v {
+            Some(StyleVal::TextOverflow(o)) => o,
+            x => panic!(
+                "Tried to coerce {} {x} into a TextOverflow",
+                x.variant_name()
+            ),
+        }
+    }
+}
+impl From<CursorStyle> for StyleVal {
+    fn from(c: CursorStyle) -> Self {
+        Self::Cursor(c)
+    }
+}
+impl From<StyleVal> for CursorStyle {
+    fn from(v: StyleVal) -> Self {
+        match v {
+            StyleVal::Cursor(c) => c,
+            x => panic!(
+                "Tried to coerce {} {x} into a CursorStyle",
+                x.variant_name()
+            ),
+        }
+    }
+}
+impl From<WordBreak> for StyleVal {
+    fn from(c: WordBreak) -> Self {
+        Self::WordBreak(c)
+    }
+}
+impl From<StyleVal> for WordBreak {
+    fn from(v: StyleVal) -> Self {
+        match v {
+            StyleVal::WordBreak(c) => c,
+            x => panic!("Tried to coerce {} {x} into a WordBreak", x.variant_name()),
+        }
+    }
+}
+impl From<OverflowWrap> for StyleVal {
+    fn from(c: OverflowWrap) -> Self {
+        Self::OverflowWrap(c)
+    }
+}
+impl From<StyleVal> for OverflowWrap {
+    fn from(v: StyleVal) -> Self {
+        match v {
+            StyleVal::OverflowWrap(c) => c,
+            x => panic!(
+                "Tried to coerce {} {x} into an OverflowWrap",
+                x.variant_name()
+            ),
         }
     }
 }
@@ -1456,7 +3625,7 @@ impl From<StyleVal> for f64 {
     fn from(v: StyleVal) -> Self {
         match v {
             StyleVal::Float(c) => c,
-            x => panic!("Tried to coerce {x:?} into a float"),
+            x => panic!("Tried to coerce {} {x} into a float", x.variant_name()),
         }
     }
 }
@@ -1469,7 +3638,7 @@ impl From<StyleVal> for u32 {
     fn from(v: StyleVal) -> Self {
         match v {
             StyleVal::Int(c) => c,
-            x => panic!("Tried to coerce {x:?} into an int"),
+            x => panic!("Tried to coerce {} {x} into an int", x.variant_name()),
         }
     }
 }
@@ -1482,7 +3651,7 @@ impl From<StyleVal> for bool {
     fn from(v: StyleVal) -> Self {
         match v {
             StyleVal::Bool(c) => c,
-            x => panic!("Tried to coerce {x:?} into a bool"),
+            x => panic!("Tried to coerce {} {x} into a bool", x.variant_name()),
         }
     }
 }
@@ -1495,12 +3664,116 @@ impl From<StyleVal> for &str {
     fn from(v: StyleVal) -> Self {
         match v {
             StyleVal::String(c) => c,
-            x => panic!("Tried to coerce {x:?} into a string"),
+            x => panic!("Tried to coerce {} {x} into a string", x.variant_name()),
+        }
+    }
+}
+
+/// `Auto`, `12px` or `50%`
+fn fmt_dimension(d: &Dimension, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    match d {
+        Dimension::Auto => write!(f, "Auto"),
+        Dimension::Px(x) => write!(f, "{x}px"),
+        Dimension::Pct(x) => write!(f, "{x}%"),
+    }
+}
+
+/// Compact values, e.g. `#ff0000ff` for [`Color::RED`] or `14` for `Float(14.0)`. Sides are in
+/// CSS order: top, right, bottom, left.
+impl std::fmt::Display for StyleVal {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Dimension(d) => fmt_dimension(d, f),
+            Self::Size(s) => {
+                fmt_dimension(&s.width, f)?;
+                write!(f, " ")?;
+                fmt_dimension(&s.height, f)
+            }
+            Self::Rect(r) => {
+                for (i, d) in [r.top, r.right, r.bottom, r.left].iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    fmt_dimension(d, f)?;
+                }
+                Ok(())
+            }
+            Self::Point(p) => write!(f, "({}, {})", p.x, p.y),
+            Self::Pos(p) => write!(f, "({}, {}, {})", p.x, p.y, p.z),
+            Self::Color(c) => write!(f, "{}", c.to_hex()),
+            Self::BorderWidth(b) => write!(f, "{} {} {} {}", b.top, b.right, b.bottom, b.left),
+            Self::BorderRadius(r) => write!(
+                f,
+                "{} {} {} {}",
+                r.top_left, r.top_right, r.bottom_right, r.bottom_left
+            ),
+            Self::HorizontalPosition(p) => write!(f, "{p:?}"),
+            Self::VerticalPosition(p) => write!(f, "{p:?}"),
+            Self::FontWeight(w) => write!(f, "{w:?}"),
+            Self::FontStyle(s) => write!(f, "{s:?}"),
+            Self::TextDecoration(d) => write!(f, "{d}"),
+            Self::TextOverflow(o) => write!(f, "{o:?}"),
+            Self::WordBreak(w) => write!(f, "{w:?}"),
+            Self::OverflowWrap(w) => write!(f, "{w:?}"),
+            Self::Cursor(c) => write!(f, "{c:?}"),
+            Self::Float(x) => write!(f, "{x}"),
+            Self::Int(x) => write!(f, "{x}"),
+            Self::Bool(x) => write!(f, "{x}"),
+            Self::String(x) => write!(f, "\"{x}\""),
+            Self::Layout(l) => write!(f, "{l:?}"),
+            Self::Gradient(g) => write!(f, "{g:?}"),
+            Self::TextShadow(s) => write!(f, "{s:?}"),
+            Self::BoxShadow(s) => write!(f, "{s:?}"),
         }
     }
 }
 
 impl StyleVal {
+    /// The name of this variant, e.g. `"Color"`.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            Self::Dimension(_) => "Dimension",
+            Self::Size(_) => "Size",
+            Self::Rect(_) => "Rect",
+            Self::Point(_) => "Point",
+            Self::Pos(_) => "Pos",
+            Self::Color(_) => "Color",
+            Self::Layout(_) => "Layout",
+            Self::HorizontalPosition(_) => "HorizontalPosition",
+            Self::VerticalPosition(_) => "VerticalPosition",
+            Self::BorderWidth(_) => "BorderWidth",
+            Self::BorderRadius(_) => "BorderRadius",
+            Self::FontWeight(_) => "FontWeight",
+            Self::FontStyle(_) => "FontStyle",
+            Self::TextDecoration(_) => "TextDecoration",
+            Self::TextOverflow(_) => "TextOverflow",
+            Self::WordBreak(_) => "WordBreak",
+            Self::OverflowWrap(_) => "OverflowWrap",
+            Self::Cursor(_) => "Cursor",
+            Self::Float(_) => "Float",
+            Self::Int(_) => "Int",
+            Self::Bool(_) => "Bool",
+            Self::String(_) => "String",
+            Self::Gradient(_) => "Gradient",
+            Self::TextShadow(_) => "TextShadow",
+            Self::BoxShadow(_) => "BoxShadow",
+        }
+    }
+
+    /// The [`StyleValType`] that [`StyleVal::from_str`] would parse this variant with, if any.
+    pub fn value_type(&self) -> Option<StyleValType> {
+        match self {
+            Self::Color(_) => Some(StyleValType::Color),
+            Self::Float(_) => Some(StyleValType::Float),
+            Self::Int(_) => Some(StyleValType::Int),
+            Self::Bool(_) => Some(StyleValType::Bool),
+            Self::FontWeight(_) => Some(StyleValType::FontWeight),
+            Self::HorizontalPosition(_) => Some(StyleValType::HorizontalPosition),
+            Self::VerticalPosition(_) => Some(StyleValType::VerticalPosition),
+            _ => None,
+        }
+    }
+
     pub fn dimension(self) -> Dimension {
         self.into()
     }
@@ -1517,6 +3790,55 @@ impl StyleVal {
         self.into()
     }
 
+    /// Parse `s` as the given variant, e.g. when loading styles from a configuration file.
+    ///
+    /// Colors can be hex (`#ff0000`) or named (`red`), and positions are lower case
+    /// (`left`, `center`).
+    pub fn from_str(type_hint: StyleValType, s: &str) -> Result<StyleVal, StyleParseError> {
+        let t = s.trim();
+        let val = match type_hint {
+            StyleValType::Color => parse_color(t).map(StyleVal::Color),
+            StyleValType::Float => t.parse().ok().map(StyleVal::Float),
+            StyleValType::Int => t.parse().ok().map(StyleVal::Int),
+            StyleValType::Bool => t.parse().ok().map(StyleVal::Bool),
+            StyleValType::FontWeight => parse_font_weight(t).map(StyleVal::FontWeight),
+            StyleValType::HorizontalPosition => match t {
+                "left" => Some(HorizontalPosition::Left),
+                "center" => Some(HorizontalPosition::Center),
+                "right" => Some(HorizontalPosition::Right),
+                _ => None,
+            }
+            .map(StyleVal::HorizontalPosition),
+            StyleValType::VerticalPosition => match t {
+                "top" => Some(VerticalPosition::Top),
+                "center" => Some(VerticalPosition::Center),
+                "bottom" => Some(VerticalPosition::Bottom),
+                _ => None,
+            }
+            .map(StyleVal::VerticalPosition),
+        };
+        val.ok_or_else(|| StyleParseError {
+            input: s.to_string(),
+            expected: type_hint,
+        })
+    }
+
+    pub fn gradient(self) -> Gradient {
+        self.into()
+    }
+
+    pub fn border_radius(self) -> BorderRadius {
+        self.into()
+    }
+
+    pub fn text_shadow(self) -> Vec<TextShadow> {
+        self.into()
+    }
+
+    pub fn box_shadow(self) -> BoxShadow {
+        self.into()
+    }
+
     pub fn pos(self) -> Pos {
         self.into()
     }
@@ -1537,6 +3859,30 @@ impl StyleVal {
         self.into()
     }
 
+    pub fn font_style(self) -> FontStyle {
+        self.into()
+    }
+
+    pub fn text_decoration(self) -> TextDecoration {
+        self.into()
+    }
+
+    pub fn text_overflow(self) -> TextOverflow {
+        self.into()
+    }
+
+    pub fn word_break(self) -> WordBreak {
+        self.into()
+    }
+
+    pub fn cursor(self) -> CursorStyle {
+        self.into()
+    }
+
+    pub fn overflow_wrap(self) -> OverflowWrap {
+        self.into()
+    }
+
     pub fn color(self) -> Color {
         self.into()
     }
@@ -1593,13 +3939,32 @@ mod tests {
         }
     }
 
-    fn test_style() -> Style {
-        Style::new()
-            .add(StyleKey::new("Widget", "color", None), Color::WHITE.into())
+    #[derive(Default, Styled)]
+    #[styled(name = "Widget")]
+    struct DerivedWidget {
+        class: Option<&'static str>,
+        style_overrides: StyleOverride,
+    }
+
+    fn test_style() -> Style {
+        Style::new()
+            .add(StyleKey::new("Widget", "color", None), Color::WHITE.into())
             .add(
                 StyleKey::new("Widget", "color", Some("dark")),
                 Color::BLACK.into(),
             )
+            .add(
+                StyleKey::new("Widget", "color", Some("accent")),
+                Color::BLUE.into(),
+            )
+            .add(
+                StyleKey::new("Widget", "color", None).state(ComponentState::HOVER),
+                Color::RED.into(),
+            )
+            .add(
+                StyleKey::new("Widget", "color", Some("dark")).state(ComponentState::HOVER),
+                Color::GREEN.into(),
+            )
     }
 
     #[test]
@@ -1635,12 +4000,981 @@ mod tests {
         assert_eq!(c, Color::BLUE);
     }
 
+    #[test]
+    fn test_merge() {
+        let base = Style::empty()
+            .add(StyleKey::new("Widget", "color", None), Color::WHITE.into())
+            .add(StyleKey::new("Widget", "size", None), 12.0.into())
+            .add(
+                StyleKey::new("Widget", "color", Some("dark")),
+                Color::BLACK.into(),
+            );
+        let overrides = Style::empty()
+            .add(StyleKey::new("Widget", "color", None), Color::RED.into())
+            .add(
+                StyleKey::new("Widget", "color", Some("accent")),
+                Color::BLUE.into(),
+            )
+            .add(StyleKey::new("Button", "color", None), Color::GREEN.into());
+
+        let s = Style::merge(base.clone(), overrides.clone());
+        assert_eq!(s.count(), 5);
+        assert_eq!(s.style("Widget", "color"), Some(Color::RED.into()));
+        assert_eq!(s.style("Widget", "size"), Some(12.0.into()));
+        assert_eq!(
+            s.style_for_class("Widget", "color", "dark"),
+            Some(Color::BLACK.into())
+        );
+        assert_eq!(
+            s.style_for_class("Widget", "color", "accent"),
+            Some(Color::BLUE.into())
+        );
+        assert_eq!(s.style("Button", "color"), Some(Color::GREEN.into()));
+        assert_eq!(base.clone().extend(overrides.clone()), s);
+
+        // The other way around, the base wins
+        let s = Style::merge(overrides.clone(), base.clone());
+        assert_eq!(s.style("Widget", "color"), Some(Color::WHITE.into()));
+        assert_eq!(s.count(), 5);
+
+        assert_eq!(base.clone().extend(Style::empty()), base);
+        assert_eq!(Style::empty().extend(base.clone()), base);
+
+        // Container queries are carried over
+        let narrow = ContainerQuery {
+            min_width: None,
+            max_width: Some(100.0),
+        };
+        let key = StyleKey::new("Widget", "size", None);
+        let s = base.extend(Style::empty().add(key.clone().container_query(narrow), 8.0.into()));
+        assert_eq!(
+            s.get_for_container(key.clone(), None, Some(50.0)),
+            Some(8.0.into())
+        );
+        assert_eq!(
+            s.get_for_container(key, None, Some(150.0)),
+            Some(12.0.into())
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = Style::empty()
+            .add(StyleKey::new("Widget", "color", None), Color::WHITE.into())
+            .add(StyleKey::new("Widget", "size", None), 12.0.into())
+            .add(
+                StyleKey::new("Widget", "color", Some("dark")),
+                Color::BLACK.into(),
+            );
+        assert!(Style::diff(&old, &old).is_empty());
+
+        let new = old
+            .clone()
+            .add(StyleKey::new("Widget", "color", None), Color::RED.into())
+            .add(
+                StyleKey::new("Widget", "color", Some("accent")),
+                Color::BLUE.into(),
+            )
+            .add(StyleKey::new("Widget", "size", None), 12.0.into());
+        let mut changes = Style::diff(&old, &new);
+        changes.sort_by_key(|(k, _)| k.class);
+        assert_eq!(
+            changes,
+            vec![
+                (StyleKey::new("Widget", "color", None), Color::RED.into()),
+                (
+                    StyleKey::new("Widget", "color", Some("accent")),
+                    Color::BLUE.into()
+                ),
+            ]
+        );
+
+        // Removed entries are not part of the diff
+        let new = old.clone().reset_component("Widget");
+        assert!(Style::diff(&old, &new).is_empty());
+        assert_eq!(Style::diff(&new, &old).len(), 3);
+    }
+
+    #[test]
+    fn test_multiple_classes() {
+        set_current_style(test_style());
+
+        // The last class with an entry wins
+        let w = Widget::default().with_class("dark accent");
+        let c: Color = w.style_val("color").into();
+        assert_eq!(c, Color::BLUE);
+        let w = Widget::default().with_class("accent  dark");
+        let c: Color = w.style_val("color").into();
+        assert_eq!(c, Color::BLACK);
+        let w = Widget::default().with_class("dark unknown");
+        let c: Color = w.style_val("color").into();
+        assert_eq!(c, Color::BLACK);
+
+        let w = Widget::default().with_classes(&["accent", "dark"]);
+        assert_eq!(w.class(), Some("accent dark"));
+        let c: Color = w.style_val("color").into();
+        assert_eq!(c, Color::BLACK);
+        assert_eq!(Widget::default().with_classes(&[]).class(), None);
+
+        let mut w = Widget::default();
+        w.add_class("accent");
+        assert_eq!(w.class(), Some("accent"));
+        w.add_class("dark");
+        w.add_class("accent");
+        assert_eq!(w.class(), Some("accent dark"));
+        assert_eq!(w.classes(), vec!["dark", "accent"]);
+        let c: Color = w.style_val("color").into();
+        assert_eq!(c, Color::BLACK);
+    }
+
+    #[test]
+    fn test_style_val_for_state() {
+        set_current_style(test_style());
+
+        let w = Widget::default();
+        let c: Color = w.style_val_for_state("color", ComponentState::HOVER).into();
+        assert_eq!(c, Color::RED);
+        // States without entries fall back to the plain value
+        let c: Color = w
+            .style_val_for_state("color", ComponentState::empty())
+            .into();
+        assert_eq!(c, Color::WHITE);
+        let c: Color = w.style_val_for_state("color", ComponentState::FOCUS).into();
+        assert_eq!(c, Color::WHITE);
+        let state = ComponentState::FOCUS | ComponentState::HOVER;
+        let c: Color = w.style_val_for_state("color", state).into();
+        assert_eq!(c, Color::RED);
+
+        let w = Widget::default().with_class("dark");
+        let c: Color = w.style_val_for_state("color", ComponentState::HOVER).into();
+        assert_eq!(c, Color::GREEN);
+        let c: Color = w
+            .style_val_for_state("color", ComponentState::ACTIVE)
+            .into();
+        assert_eq!(c, Color::BLACK);
+    }
+
+    #[test]
+    fn test_component_state() {
+        let mut state = ComponentState::HOVER | ComponentState::DISABLED;
+        assert!(state.contains(ComponentState::HOVER));
+        assert!(!state.contains(ComponentState::ACTIVE));
+        assert_eq!(
+            state.iter().collect::<Vec<_>>(),
+            vec![ComponentState::DISABLED, ComponentState::HOVER]
+        );
+
+        state.set(ComponentState::DISABLED, false);
+        state |= ComponentState::FOCUS;
+        assert_eq!(state, ComponentState::FOCUS | ComponentState::HOVER);
+        assert!(ComponentState::default().is_empty());
+    }
+
+    #[test]
+    fn test_derive_styled() {
+        set_current_style(test_style());
+
+        assert_eq!(DerivedWidget::name(), "Widget");
+        let c: Color = DerivedWidget::default().style_val("color").into();
+        assert_eq!(c, Color::WHITE);
+
+        let w = DerivedWidget::default().with_class("dark");
+        assert_eq!(w.class(), Some("dark"));
+        let c: Color = w.style_val("color").into();
+        assert_eq!(c, Color::BLACK);
+
+        let w = w.style("color", Color::BLUE);
+        let c: Color = w.style_val("color").into();
+        assert_eq!(c, Color::BLUE);
+    }
+
+    #[test]
+    fn test_style_override_iter() {
+        let w = Widget::default().style("color", Color::BLUE);
+        let overrides: Vec<_> = w.style_overrides().iter().collect();
+        assert_eq!(overrides, vec![("color", &StyleVal::Color(Color::BLUE))]);
+        assert_eq!(w.style_overrides().len(), 1);
+        assert!(Widget::default().style_overrides().is_empty());
+
+        let s = w.style_overrides.into_style("Widget");
+        assert_eq!(s.style("Widget", "color"), Some(Color::BLUE.into()));
+        assert_eq!(s.count(), 1);
+    }
+
+    #[test]
+    fn test_style_val_result_errors() {
+        set_current_style(test_style());
+
+        let w = Widget::default();
+        assert_eq!(w.style_val_result("color"), Ok(Color::WHITE.into()));
+        assert_eq!(
+            w.style_val_result("size"),
+            Err(StyleLookupError::NotRegistered {
+                component: "Widget",
+                parameter: "size",
+            })
+        );
+
+        let w = Widget::default().with_class("dark");
+        assert_eq!(
+            w.style_val_result("size"),
+            Err(StyleLookupError::NoMatchForClass {
+                component: "Widget",
+                parameter: "size",
+                class: "dark",
+            })
+        );
+        assert_eq!(w.style_val("size"), None);
+    }
+
+    #[test]
+    fn test_border_width_inset() {
+        let border = BorderWidth {
+            top: 2.0,
+            right: 4.0,
+            bottom: 2.0,
+            left: 4.0,
+        };
+        assert_eq!(border.total_horizontal(), 8.0);
+        assert_eq!(border.total_vertical(), 4.0);
+
+        let outer = AABB::new(Pos::default(), [100.0, 50.0].into());
+        let inner = border.to_inset_rect(outer);
+        assert_eq!(inner.pos, Pos::new(4.0, 2.0, 0.0));
+        assert_eq!(inner.size(), [92.0, 46.0].into());
+    }
+
+    #[test]
+    fn test_reset_component() {
+        assert!(Style::default()
+            .style("Button", "background_color")
+            .is_some());
+        let s = Style::default().reset_component("Button");
+        assert_eq!(s.style("Button", "background_color"), None);
+        assert!(s.keys_for_component("Button").is_empty());
+        assert!(!s.keys_for_component("Text").is_empty());
+
+        let s = test_style().reset_component("Widget");
+        assert_eq!(s, Style::new());
+    }
+
+    #[test]
+    fn test_style_introspection() {
+        let s = Style::default();
+        assert!(s.count() > 0);
+        assert!(!s.is_empty());
+        assert!(s.contains_key(&StyleKey::new("Button", "background_color", None)));
+        assert_eq!(s.keys().count(), s.count());
+        assert_eq!(s.values().count(), s.count());
+
+        assert!(Style::empty().is_empty());
+        assert_eq!(Style::empty().count(), 0);
+        let s = Style::empty().add(StyleKey::new("Widget", "color", None), Color::WHITE.into());
+        assert_eq!(s.count(), 1);
+        assert!(!s.contains_key(&StyleKey::new("Widget", "color", Some("dark"))));
+    }
+
+    #[test]
+    fn test_box_shadow() {
+        let style = Style::default();
+        for component in ["Button", "Select", "TextBox"] {
+            let shadow = style.style(component, "shadow").unwrap().box_shadow();
+            assert!(!shadow.is_visible());
+            let shadow = style
+                .style_for_class(component, "shadow", "shadow-lg")
+                .unwrap()
+                .box_shadow();
+            assert_eq!(shadow.offset_y, 10.0);
+            assert_eq!(shadow.spread, -3.0);
+            assert!(shadow.is_visible());
+            let none = style.style_for_class(component, "shadow", "shadow-none");
+            assert_eq!(none, Some(BoxShadow::NONE.into()));
+        }
+        assert_eq!(StyleVal::from(BoxShadow::NONE).variant_name(), "BoxShadow");
+    }
+
+    #[test]
+    fn test_font_style() {
+        let style = Style::default();
+        for component in ["Text", "Button", "TextBox"] {
+            assert_eq!(
+                style.style(component, "font_style"),
+                Some(FontStyle::Normal.into())
+            );
+            let italic = style
+                .style_for_class(component, "font_style", "font-italic")
+                .unwrap();
+            assert_eq!(italic.font_style(), FontStyle::Italic);
+            assert_eq!(
+                style.style_for_class(component, "font_style", "font-normal"),
+                Some(FontStyle::Normal.into())
+            );
+        }
+        assert_eq!(format!("{}", StyleVal::from(FontStyle::Oblique)), "Oblique");
+    }
+
+    #[test]
+    fn test_text_decoration() {
+        let style = Style::default();
+        for component in ["Text", "Button", "TextBox"] {
+            let none = style.style(component, "text_decoration").unwrap();
+            assert!(none.text_decoration().is_empty());
+            for (class, decoration) in [
+                ("underline", TextDecoration::UNDERLINE),
+                ("line-through", TextDecoration::STRIKETHROUGH),
+                ("overline", TextDecoration::OVERLINE),
+            ] {
+                let val = style.style_for_class(component, "text_decoration", class);
+                assert_eq!(val, Some(decoration.into()));
+            }
+        }
+
+        let both = TextDecoration::UNDERLINE | TextDecoration::STRIKETHROUGH;
+        assert!(both.contains(TextDecoration::UNDERLINE));
+        assert!(both.contains(TextDecoration::STRIKETHROUGH));
+        assert!(!both.contains(TextDecoration::OVERLINE));
+        assert_eq!(
+            format!("{}", StyleVal::from(both)),
+            "underline line-through"
+        );
+        assert_eq!(format!("{}", TextDecoration::NONE), "none");
+    }
+
+    #[test]
+    fn test_letter_spacing() {
+        let style = Style::default();
+        for component in ["Text", "TextBox"] {
+            let spacing = |class| {
+                style
+                    .style_for_class(component, "letter_spacing", class)
+                    .unwrap()
+                    .f32()
+            };
+            assert_eq!(style.style(component, "letter_spacing").unwrap().f32(), 0.0);
+            assert_eq!(spacing("tracking-normal"), 0.0);
+            assert!(spacing("tracking-tighter") < spacing("tracking-tight"));
+            assert!(spacing("tracking-tight") < 0.0);
+            assert!(spacing("tracking-wide") > 0.0);
+            assert!(spacing("tracking-wide") < spacing("tracking-wider"));
+            assert!(spacing("tracking-wider") < spacing("tracking-widest"));
+        }
+    }
+
+    #[test]
+    fn test_text_overflow() {
+        let style = Style::default();
+        assert_eq!(
+            style
+                .style("Text", "text_overflow")
+                .unwrap()
+                .text_overflow(),
+            TextOverflow::Clip
+        );
+        assert_eq!(
+            style
+                .style_for_class("Text", "text_overflow", "truncate")
+                .unwrap()
+                .text_overflow(),
+            TextOverflow::Ellipsis
+        );
+        assert_eq!(
+            StyleVal::from(TextOverflow::Fade).variant_name(),
+            "TextOverflow"
+        );
+    }
+
+    #[test]
+    fn test_max_lines() {
+        let style = Style::default();
+        assert!(style.style("Text", "max_lines").is_none());
+        for lines in 1..=6u32 {
+            let class = format!("lines-{lines}");
+            assert_eq!(
+                style
+                    .style_for_class("Text", "max_lines", &class)
+                    .unwrap()
+                    .u32(),
+                lines
+            );
+        }
+    }
+
+    #[test]
+    fn test_slider_sizes() {
+        let style = Style::default();
+        let size = |param: &'static str, class: &'static str| {
+            style.style_for_class("Slider", param, class).unwrap().f32()
+        };
+        assert_eq!(style.style("Slider", "thumb_size").unwrap().f32(), 18.0);
+        assert_eq!(size("thumb_size", "slider-sm"), 12.0);
+        assert_eq!(size("track_height", "slider-lg"), 6.0);
+        assert!(size("thumb_size", "slider-sm") < size("thumb_size", "slider-md"));
+        assert!(size("track_height", "slider-md") < size("track_height", "slider-lg"));
+    }
+
+    #[test]
+    fn test_progress_bar_sizes() {
+        let style = Style::default();
+        let height = |class: &'static str| {
+            style
+                .style_for_class("ProgressBar", "height", class)
+                .unwrap()
+                .f32()
+        };
+        assert!(height("progress-xs") < height("progress-sm"));
+        assert!(height("progress-sm") < height("progress-md"));
+        assert!(style
+            .style("ProgressBar", "animation_duration_ms")
+            .is_some());
+    }
+
+    #[test]
+    fn test_checkbox_sizes() {
+        let style = Style::default();
+        let size = |class: &'static str| {
+            style
+                .style_for_class("Checkbox", "size", class)
+                .unwrap()
+                .f32()
+        };
+        assert_eq!(
+            style.style("Checkbox", "size").unwrap().f32(),
+            size("checkbox-md")
+        );
+        assert!(size("checkbox-sm") < size("checkbox-md"));
+        assert!(size("checkbox-md") < size("checkbox-lg"));
+    }
+
+    #[test]
+    fn test_number_input_style() {
+        let style = Style::default();
+        // NumberInput has the TextBox parameters, and its own
+        assert!(style.style("TextBox", "border_color").is_some());
+        assert!(style.style("TextBox", "button_width").is_some());
+        assert!(style.style("TextBox", "error_color").is_some());
+    }
+
+    #[test]
+    fn test_textarea_style() {
+        let style = Style::default();
+        assert!(!style.style("TextBox", "multiline").unwrap().bool());
+        assert!(style
+            .style_for_class("TextBox", "multiline", "textarea")
+            .unwrap()
+            .bool());
+        assert_eq!(
+            style
+                .style_for_class("TextBox", "resize", "textarea")
+                .unwrap()
+                .str(),
+            "vertical"
+        );
+    }
+
+    #[test]
+    fn test_suggestion_style() {
+        let style = Style::default();
+        assert!(style.style("TextBox", "suggestion_background").is_some());
+        assert!(style.style("TextBox", "suggestion_hover_color").is_some());
+        assert_eq!(
+            style
+                .style("TextBox", "suggestion_max_items")
+                .unwrap()
+                .u32(),
+            8
+        );
+        assert_eq!(
+            style
+                .style("TextBox", "suggestion_min_chars")
+                .unwrap()
+                .u32(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_tabs_pills() {
+        let style = Style::default();
+        assert!(!style.style("Tabs", "pill").unwrap().bool());
+        assert!(style
+            .style_for_class("Tabs", "pill", "tabs-pills")
+            .unwrap()
+            .bool());
+    }
+
+    #[test]
+    fn test_accordion_flush() {
+        let style = Style::default();
+        assert_eq!(style.style("Accordion", "border_width").unwrap().f32(), 1.0);
+        assert_eq!(
+            style
+                .style_for_class("Accordion", "border_width", "accordion-flush")
+                .unwrap()
+                .f32(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_computed() {
+        let style = Style::empty()
+            .add(StyleKey::new("Text", "size", None), 12.0.into())
+            .add(StyleKey::new("Text", "size", Some("big")), 20.0.into())
+            .add(StyleKey::new("Text", "size", Some("huge")), 30.0.into())
+            .add(
+                StyleKey::new("Text", "color", Some("red")),
+                Color::RED.into(),
+            );
+
+        assert_eq!(style.computed("Text", "size", None).unwrap().f32(), 12.0);
+        assert_eq!(
+            style.computed("Text", "size", Some("big")).unwrap().f32(),
+            20.0
+        );
+        // The last class with an entry wins, and classes without one fall back
+        assert_eq!(
+            style
+                .computed("Text", "size", Some("huge big"))
+                .unwrap()
+                .f32(),
+            20.0
+        );
+        assert_eq!(
+            style
+                .computed("Text", "size", Some("big other"))
+                .unwrap()
+                .f32(),
+            20.0
+        );
+        assert_eq!(
+            style.computed("Text", "size", Some("other")).unwrap().f32(),
+            12.0
+        );
+        assert_eq!(
+            style
+                .computed("Text", "color", Some("red"))
+                .unwrap()
+                .color(),
+            Color::RED
+        );
+        assert!(style.computed("Text", "color", None).is_none());
+        assert!(style.computed("Button", "size", None).is_none());
+
+        let keys = style.list_keys_for_component("Text");
+        assert_eq!(
+            keys,
+            vec![
+                StyleKey::new("Text", "color", Some("red")),
+                StyleKey::new("Text", "size", None),
+                StyleKey::new("Text", "size", Some("big")),
+                StyleKey::new("Text", "size", Some("huge")),
+            ]
+        );
+        assert!(style.list_keys_for_component("Button").is_empty());
+    }
+
+    #[cfg(feature = "style-serde")]
+    #[test]
+    fn test_json_round_trip() {
+        let color = Color::rgba(16.0, 32.0, 64.0, 128.0 / 255.0);
+        let px = Dimension::Px(12.5);
+        let values = vec![
+            StyleVal::Dimension(px),
+            StyleVal::Size(Size {
+                width: px,
+                height: Dimension::Pct(50.0),
+            }),
+            StyleVal::Rect(Rect {
+                left: px,
+                ..Default::default()
+            }),
+            StyleVal::Point(Point::new(1.0, -2.0)),
+            StyleVal::Pos(Pos::new(1.0, 2.0, 3.0)),
+            StyleVal::Color(color),
+            StyleVal::Layout(Layout {
+                direction: Direction::Column,
+                gap: 4.0,
+                z_index: Some(2.0),
+                debug: Some("debug".to_string()),
+                ..Default::default()
+            }),
+            StyleVal::HorizontalPosition(HorizontalPosition::Center),
+            StyleVal::VerticalPosition(VerticalPosition::Bottom),
+            StyleVal::BorderWidth(BorderWidth {
+                top: 1.0,
+                left: 2.0,
+                bottom: 3.0,
+                right: 4.0,
+            }),
+            StyleVal::BorderRadius(BorderRadius::new(1.0, 2.0, 3.0, 4.0)),
+            StyleVal::FontWeight(FontWeight::Bold),
+            StyleVal::FontStyle(FontStyle::Italic),
+            StyleVal::TextDecoration(TextDecoration::UNDERLINE | TextDecoration::STRIKETHROUGH),
+            StyleVal::TextOverflow(TextOverflow::Fade),
+            StyleVal::WordBreak(WordBreak::BreakAll),
+            StyleVal::OverflowWrap(OverflowWrap::Anywhere),
+            StyleVal::Cursor(CursorStyle::Resize(ResizeDirection::EastWest)),
+            StyleVal::Float(0.1),
+            StyleVal::Int(7),
+            StyleVal::Bool(true),
+            StyleVal::String("Space \"Grotesk\""),
+            StyleVal::Gradient(Gradient::Radial {
+                center: Point::new(5.0, 5.0),
+                radius: (0.0, 10.0),
+                stops: vec![(0.0, color), (1.0, Color::WHITE)],
+            }),
+            StyleVal::TextShadow(vec![
+                TextShadow::new(1.0, 1.0, 2.0, color),
+                TextShadow::new(0.0, -1.0, 0.0, Color::BLACK),
+            ]),
+            StyleVal::BoxShadow(BoxShadow::new(0.0, 4.0, 8.0, -2.0, color)),
+        ];
+        for v in values.iter() {
+            let json = serde_json::to_string(v).unwrap();
+            assert_eq!(
+                &serde_json::from_str::<StyleVal>(&json).unwrap(),
+                v,
+                "{json}"
+            );
+        }
+        assert_eq!(
+            serde_json::to_string(&StyleVal::String("Space Grotesk")).unwrap(),
+            r#""Space Grotesk""#
+        );
+        assert_eq!(
+            serde_json::to_string(&StyleVal::Color(color)).unwrap(),
+            r##"{"Color":"#10204080"}"##
+        );
+
+        let mut style = Style::empty()
+            .add(
+                StyleKey::new("Text", "color", Some("muted")).state(ComponentState::HOVER),
+                StyleVal::Color(color),
+            )
+            .add(
+                StyleKey::new("Text", "size", None).container_query(ContainerQuery {
+                    min_width: Some(100.0),
+                    max_width: None,
+                }),
+                StyleVal::Float(20.0),
+            )
+            .add(
+                StyleKey::new("Text", "size", None).breakpoint(Breakpoint::Md),
+                StyleVal::Float(18.0),
+            );
+        for (i, v) in values.into_iter().enumerate() {
+            let parameter: &'static str = Box::leak(format!("param{i}").into_boxed_str());
+            style = style.add(StyleKey::new("Widget", parameter, None), v);
+        }
+        let json = style.to_json_str().unwrap();
+        // Entries are written in a stable order
+        assert_eq!(json, style.clone().to_json_str().unwrap());
+        let read = Style::from_json_str(&json).unwrap();
+        assert!(Style::diff(&style, &read).is_empty());
+        assert!(Style::diff(&read, &style).is_empty());
+        assert_eq!(read.1, style.1);
+
+        assert!(Style::from_json_str("[[{\"struct_name\": \"Text\"}, 1]]").is_err());
+        assert!(Style::from_json_str(
+            r#"[[{"struct_name":"Text","parameter_name":"color","class":null},{"Color":"red"}]]"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_for_component() {
+        let style = Style::default();
+        let snapshot = style.for_component("Button");
+        for parameter in [
+            "background_color",
+            "text_color",
+            "font_size",
+            "border_width",
+            "radius",
+            "padding",
+        ] {
+            assert_eq!(
+                snapshot.get(parameter, None).cloned(),
+                style.style("Button", parameter)
+            );
+        }
+        assert_eq!(
+            snapshot.get("padding", Some("p-2")).cloned(),
+            style.style_for_class("Button", "padding", "p-2")
+        );
+        // Responsive entries are left out of the snapshot
+        let plain_keys = style
+            .keys_for_component("Button")
+            .into_iter()
+            .filter(|k| k.breakpoint.is_none() && k.container_query.is_none() && k.state.is_none())
+            .count();
+        assert_eq!(snapshot.len(), plain_keys);
+        assert!(plain_keys < style.keys_for_component("Button").len());
+        assert!(snapshot.get("color", None).is_none());
+
+        assert!(style.for_component("Widget").is_empty());
+    }
+
+    #[test]
+    fn test_style_val_display() {
+        assert_eq!(format!("{}", StyleVal::Float(14.0)), "14");
+        assert_eq!(format!("{}", StyleVal::Float(12.5)), "12.5");
+        assert_eq!(format!("{}", StyleVal::Color(Color::RED)), "#ff0000ff");
+        assert_eq!(
+            format!("{}", StyleVal::from(Color::rgba(0.0, 128.0, 255.0, 0.5))),
+            "#0080ff80"
+        );
+        assert_eq!(format!("{}", StyleVal::from(FontWeight::Bold)), "Bold");
+        assert_eq!(format!("{}", StyleVal::Bool(true)), "true");
+        let border = BorderWidth {
+            top: 1.0,
+            left: 2.0,
+            bottom: 1.0,
+            right: 2.0,
+        };
+        assert_eq!(format!("{}", StyleVal::from(border)), "1 2 1 2");
+        assert_eq!(
+            format!("{}", StyleVal::from(size!(10.0, Auto))),
+            "10px Auto"
+        );
+        assert_eq!(StyleVal::Float(14.0).variant_name(), "Float");
+    }
+
+    #[test]
+    #[should_panic(expected = "Tried to coerce Float 14 into a Color")]
+    fn test_coerce_panic_message() {
+        let _: Color = StyleVal::Float(14.0).into();
+    }
+
+    #[test]
+    fn test_style_val_from_str() {
+        use StyleValType as T;
+        assert_eq!(
+            StyleVal::from_str(T::Color, "#ff0000"),
+            Ok(StyleVal::Color(crate::types::Color::RED))
+        );
+        assert_eq!(
+            StyleVal::from_str(T::Color, "#0000ff80"),
+            Ok(StyleVal::Color(crate::types::Color::rgba(
+                0.0,
+                0.0,
+                255.0,
+                128.0 / 255.0
+            )))
+        );
+        assert_eq!(
+            StyleVal::from_str(T::Color, "#fff"),
+            Ok(StyleVal::Color(crate::types::Color::rgb(
+                255.0, 255.0, 255.0
+            )))
+        );
+        // The same hex as Color::from_hex reads, including `#rgba`
+        assert_eq!(
+            StyleVal::from_str(T::Color, "#f008"),
+            Ok(StyleVal::Color(
+                crate::types::Color::from_hex("#ff000088").unwrap()
+            ))
+        );
+        assert_eq!(
+            StyleVal::from_str(T::Color, "Yellow"),
+            Ok(StyleVal::Color(crate::types::Color::YELLOW))
+        );
+        assert_eq!(
+            StyleVal::from_str(T::Float, " 1.5 "),
+            Ok(StyleVal::Float(1.5))
+        );
+        assert_eq!(StyleVal::from_str(T::Int, "12"), Ok(StyleVal::Int(12)));
+        assert_eq!(
+            StyleVal::from_str(T::Bool, "true"),
+            Ok(StyleVal::Bool(true))
+        );
+        assert_eq!(
+            StyleVal::from_str(T::FontWeight, "semibold"),
+            Ok(StyleVal::FontWeight(super::FontWeight::Semibold))
+        );
+        assert_eq!(
+            StyleVal::from_str(T::HorizontalPosition, "right"),
+            Ok(StyleVal::HorizontalPosition(
+                super::HorizontalPosition::Right
+            ))
+        );
+
+        assert_eq!(
+            StyleVal::from_str(T::Color, "#ff000"),
+            Err(StyleParseError {
+                input: "#ff000".to_string(),
+                expected: T::Color,
+            })
+        );
+        assert!(StyleVal::from_str(T::Color, "#gg0000").is_err());
+        assert!(StyleVal::from_str(T::Int, "-1").is_err());
+        assert!(StyleVal::from_str(T::Bool, "yes").is_err());
+        assert!(StyleVal::from_str(T::VerticalPosition, "left").is_err());
+    }
+
     #[test]
     fn test_style_macro() {
         let s = style!(
             Widget.color = Color::WHITE;
             dark.Widget.color = Color::BLACK;
+            accent.Widget.color = Color::BLUE;
+            hover.Widget.color = Color::RED;
+            hover.dark.Widget.color = Color::GREEN;
         );
         assert_eq!(s, test_style());
     }
+
+    #[test]
+    fn test_style_macro_conditions() {
+        let key = StyleKey::new("Widget", "color", None);
+        let s = style!(if true {
+            Widget.color = Color::WHITE;
+        });
+        assert_eq!(s.get(key.clone()), Some(Color::WHITE.into()));
+        assert_eq!(s.count(), Style::new().count() + 1);
+
+        let s = style!(if false {
+            Widget.color = Color::WHITE;
+        });
+        assert_eq!(s, Style::new());
+
+        let dark_mode = true;
+        let s = style!(
+            Widget.size = 12.0;
+            if dark_mode && !false {
+                Widget.color = Color::BLACK;
+            } else {
+                Widget.color = Color::WHITE;
+            }
+            dark.Widget.color = Color::BLACK;
+        );
+        assert_eq!(s.get(key.clone()), Some(Color::BLACK.into()));
+        assert_eq!(s.style("Widget", "size"), Some(StyleVal::Float(12.0)));
+        assert!(s.style_for_class("Widget", "color", "dark").is_some());
+
+        let s = style!(if !dark_mode {
+            Widget.color = Color::BLACK;
+        } else {
+            Widget.color = Color::WHITE;
+        });
+        assert_eq!(s.get(key), Some(Color::WHITE.into()));
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        assert_eq!(Breakpoint::for_width(500.0), Breakpoint::Sm);
+        assert_eq!(Breakpoint::for_width(800.0), Breakpoint::Md);
+        assert_eq!(Breakpoint::for_width(1536.0), Breakpoint::Xl2);
+
+        let style = Style::new();
+        let key = StyleKey::new("Button", "font_size", None);
+        set_viewport_width(800.0);
+        assert_eq!(current_breakpoint(), Some(Breakpoint::Md));
+        let size = style.get_responsive(key.clone(), current_breakpoint());
+        assert_eq!(size, Some(StyleVal::Float(14.0)));
+
+        set_viewport_width(500.0);
+        assert_eq!(current_breakpoint(), Some(Breakpoint::Sm));
+        let size = style.get_responsive(key.clone(), current_breakpoint());
+        assert_eq!(size, Some(StyleVal::Float(12.0)));
+
+        // Wider breakpoints fall back to the widest entry below them, then to the base entry
+        let style = Style::empty()
+            .add(key.clone(), 10.0.into())
+            .add(key.clone().breakpoint(Breakpoint::Md), 14.0.into());
+        let size = style.get_responsive(key.clone(), Some(Breakpoint::Xl));
+        assert_eq!(size, Some(StyleVal::Float(14.0)));
+        let size = style.get_responsive(key.clone(), Some(Breakpoint::Sm));
+        assert_eq!(size, Some(StyleVal::Float(10.0)));
+        assert_eq!(style.get_responsive(key, None), Some(StyleVal::Float(10.0)));
+    }
+
+    #[test]
+    fn test_container_queries() {
+        let narrow = ContainerQuery {
+            max_width: Some(200.0),
+            ..Default::default()
+        };
+        assert!(narrow.matches(150.0) && narrow.matches(200.0));
+        assert!(!narrow.matches(400.0));
+
+        let style = Style::new();
+        let key = StyleKey::new("Button", "font_size", None);
+        let size = style.get_for_container(key.clone(), None, Some(150.0));
+        assert_eq!(size, Some(StyleVal::Float(10.0)));
+        let size = style.get_for_container(key.clone(), None, Some(400.0));
+        assert_eq!(size, Some(StyleVal::Float(12.0)));
+        // Container queries win over breakpoints
+        let size = style.get_for_container(key.clone(), Some(Breakpoint::Md), Some(150.0));
+        assert_eq!(size, Some(StyleVal::Float(10.0)));
+
+        // The narrowest matching query wins
+        let tiny = ContainerQuery {
+            max_width: Some(100.0),
+            ..Default::default()
+        };
+        let style = style.add(key.clone().container_query(tiny), 8.0.into());
+        let size = style.get_for_container(key.clone(), None, Some(80.0));
+        assert_eq!(size, Some(StyleVal::Float(8.0)));
+        let size = style.get_for_container(key, None, Some(150.0));
+        assert_eq!(size, Some(StyleVal::Float(10.0)));
+
+        assert_eq!(container_width(), None);
+        let width = with_container_width(Some(150.0), container_width);
+        assert_eq!(width, Some(150.0));
+        assert_eq!(container_width(), None);
+    }
+
+    #[test]
+    fn test_border_radius() {
+        // Plain radii are the same on every corner
+        let radius: BorderRadius = StyleVal::Float(4.0).into();
+        assert_eq!(radius, BorderRadius::uniform(4.0));
+
+        let top = BorderRadius::new(8.0, 8.0, 0.0, 0.0);
+        assert_eq!(StyleVal::from(top).border_radius(), top);
+        assert_eq!(<(f32, f32, f32, f32)>::from(top), (8.0, 8.0, 0.0, 0.0));
+        assert_eq!(BorderRadius::from((8.0, 8.0, 0.0, 0.0)), top);
+
+        let rect = crate::renderables::rect::InstanceBuilder::default()
+            .pos(Pos::default())
+            .scale(Scale::new(10.0, 10.0))
+            .radius(top)
+            .build()
+            .unwrap();
+        assert_eq!(rect.radius, (8.0, 8.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_palette() {
+        let base = Color::rgb(59.0, 130.0, 246.0);
+        let palette = Palette::generate(base, 4, 4);
+        assert_eq!(palette.steps.len(), 9);
+        assert_eq!(palette.steps[4], base);
+
+        let lightness: Vec<f32> = palette.steps.iter().map(|c| c.to_hsl().2).collect();
+        // Lighter toward the start, and darker toward the end, without reaching white or black
+        assert!(lightness.windows(2).all(|l| l[0] > l[1]));
+        assert!(lightness[0] < 1.0 && lightness[8] > 0.0);
+        let (h, s, _) = base.to_hsl();
+        let (step_h, step_s, _) = palette.steps[1].to_hsl();
+        assert!((h - step_h).abs() < 1.0 && (s - step_s).abs() < 0.02);
+
+        let vars = palette.as_style_vars("blue");
+        assert_eq!(vars.len(), 9);
+        assert_eq!(vars.get("blue-500"), Some(&StyleVal::Color(base)));
+        assert_eq!(
+            vars.get("blue-100"),
+            Some(&StyleVal::Color(palette.steps[0]))
+        );
+        assert_eq!(
+            vars.get("blue-900"),
+            Some(&StyleVal::Color(palette.steps[8]))
+        );
+
+        let vars = Palette::generate(base, 2, 1).as_style_vars("blue");
+        let mut names: Vec<&str> = vars.iter().map(|(name, _)| name).collect();
+        names.sort();
+        assert_eq!(names, vec!["blue-100", "blue-300", "blue-500", "blue-900"]);
+    }
 }
@@ -0,0 +1,405 @@
+//! Themes that are loaded from TOML files, so that styles can be changed without recompiling.
+//!
+//! Each table is a component, and each of its sub-tables a class of it:
+//! ```toml
+//! [Text]
+//! size = 14
+//! color = "#333333"
+//!
+//! [Text.font-bold]
+//! font_weight = "bold"
+//! ```
+//! Values are parsed as the type that the [default style](Style::default) has for the same
+//! component and parameter, with [`StyleVal::from_str`]. Other strings that are hex colors are
+//! read as colors, and the rest as strings. Other numbers and booleans are read as an `Int`
+//! when they are whole and not negative, or else as a `Float` or `Bool`.
+//!
+//! A class can also be an inline table, like `font-bold = { font_weight = "bold" }` in the table
+//! of `Text`. Arrays, dates, and tables in a class have no style value, so they are errors.
+
+use std::fmt;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use serde::de::{Deserialize, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, Visitor};
+use toml::{Spanned, Table, Value};
+
+use super::*;
+
+/// Why [`Style::from_toml_file`] could not load a theme.
+#[derive(Debug)]
+pub enum StyleLoadError {
+    /// The file could not be read.
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// The file is not a theme that can be read. Lines start at 1, and are `None` when the
+    /// error has no place in the file.
+    Parse {
+        path: PathBuf,
+        line: Option<usize>,
+        message: String,
+    },
+}
+
+impl fmt::Display for StyleLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StyleLoadError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            StyleLoadError::Parse {
+                path,
+                line: Some(line),
+                message,
+            } => write!(f, "{}:{}: {}", path.display(), line, message),
+            StyleLoadError::Parse {
+                path,
+                line: None,
+                message,
+            } => write!(f, "{}: {}", path.display(), message),
+        }
+    }
+}
+
+impl std::error::Error for StyleLoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StyleLoadError::Io { error, .. } => Some(error),
+            StyleLoadError::Parse { .. } => None,
+        }
+    }
+}
+
+impl Style {
+    /// Load the theme in the TOML file at `path`. The style only has the entries of the file, so
+    /// it is usually [merged](Style::merge) over the default style.
+    pub fn from_toml_file(path: &Path) -> Result<Style, StyleLoadError> {
+        let toml = fs::read_to_string(path).map_err(|error| StyleLoadError::Io {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        parse_theme(&toml, path)
+    }
+}
+
+fn parse_theme(toml: &str, path: &Path) -> Result<Style, StyleLoadError> {
+    let error = |span: Option<Range<usize>>, message: String| StyleLoadError::Parse {
+        path: path.to_path_buf(),
+        line: span.map(|span| toml[..span.start].matches('\n').count() + 1),
+        message,
+    };
+    let table: Table = toml::from_str(toml).map_err(|e| error(e.span(), e.message().into()))?;
+    let defaults = Style::default();
+    let mut style = Style::empty();
+    for (component, entries) in table {
+        let entries = match entries {
+            Value::Table(entries) => entries,
+            _ => {
+                return Err(error(
+                    value_span(toml, &[component.as_str()]),
+                    format!("`{component}` must be the table of a component"),
+                ))
+            }
+        };
+        let component = intern(component);
+        for (key, value) in entries {
+            let (class, values) = match value {
+                Value::Table(values) => (Some(intern(key)), values),
+                value => (None, Table::from_iter([(key, value)])),
+            };
+            for (parameter, value) in values {
+                let parameter = intern(parameter);
+                let hint = type_hint(&defaults, component, parameter);
+                let val = style_val(value, hint).map_err(|message| {
+                    let path: Vec<&str> = [Some(component), class, Some(parameter)]
+                        .into_iter()
+                        .flatten()
+                        .collect();
+                    error(value_span(toml, &path), message)
+                })?;
+                style.insert(StyleKey::new(component, parameter, class), val);
+            }
+        }
+    }
+    Ok(style)
+}
+
+/// The type that `defaults` has for `parameter` of `component`: from its entry without a class,
+/// or else from any of its class entries.
+fn type_hint(
+    defaults: &Style,
+    component: &'static str,
+    parameter: &'static str,
+) -> Option<StyleValType> {
+    defaults
+        .style(component, parameter)
+        .or_else(|| {
+            defaults
+                .keys_for_component(component)
+                .into_iter()
+                .find(|k| k.parameter_name == parameter)
+                .and_then(|k| defaults.get(k.clone()))
+        })
+        .and_then(|v| v.value_type())
+}
+
+fn style_val(value: Value, hint: Option<StyleValType>) -> Result<StyleVal, String> {
+    match (value, hint) {
+        (Value::String(s), Some(hint)) => StyleVal::from_str(hint, &s).map_err(|e| e.to_string()),
+        (Value::Integer(i), Some(hint)) => {
+            StyleVal::from_str(hint, &i.to_string()).map_err(|e| e.to_string())
+        }
+        (Value::Float(f), Some(hint)) => {
+            StyleVal::from_str(hint, &f.to_string()).map_err(|e| e.to_string())
+        }
+        (Value::Boolean(b), Some(hint)) => {
+            StyleVal::from_str(hint, &b.to_string()).map_err(|e| e.to_string())
+        }
+        (Value::String(s), None) => Ok(match parse_color(&s).filter(|_| s.starts_with('#')) {
+            Some(color) => StyleVal::Color(color),
+            None => StyleVal::String(intern(s)),
+        }),
+        (Value::Integer(i), None) => Ok(match u32::try_from(i) {
+            Ok(i) => StyleVal::Int(i),
+            Err(_) => StyleVal::Float(i as f64),
+        }),
+        (Value::Float(f), None) => Ok(StyleVal::Float(f)),
+        (Value::Boolean(b), None) => Ok(StyleVal::Bool(b)),
+        (value, _) => Err(format!(
+            "{} values can not be used as a style value",
+            value.type_str()
+        )),
+    }
+}
+
+/// Where the value at `path` is in `toml`. A [`Table`] does not keep the spans of its values,
+/// so this reads the file again, down to that value.
+fn value_span(toml: &str, path: &[&str]) -> Option<Range<usize>> {
+    let deserializer = toml::Deserializer::new(toml);
+    SpanOf(path).deserialize(deserializer).ok().flatten()
+}
+
+/// Reads the span of the value at a path of keys, and skips over everything else.
+struct SpanOf<'a>(&'a [&'a str]);
+
+impl<'de> DeserializeSeed<'de> for SpanOf<'_> {
+    type Value = Option<Range<usize>>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        if self.0.is_empty() {
+            Spanned::<IgnoredAny>::deserialize(deserializer).map(|v| Some(v.span()))
+        } else {
+            deserializer.deserialize_map(self)
+        }
+    }
+}
+
+impl<'de> Visitor<'de> for SpanOf<'_> {
+    type Value = Option<Range<usize>>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a table")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut span = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == self.0[0] {
+                span = map.next_value_seed(SpanOf(&self.0[1..]))?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(span)
+    }
+}
+
+/// Loads a TOML theme again whenever its file changes, and passes it to a callback. The file is
+/// polled from a background thread, which stops when the watcher is dropped.
+///
+/// ```no_run
+/// use mctk_core::style::{Style, StyleWatcher};
+///
+/// let watcher = StyleWatcher::new("theme.toml", |theme: Style| {
+///     // E.g. merge the theme over the default style, and make it current
+/// });
+/// ```
+pub struct StyleWatcher {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl StyleWatcher {
+    /// How often [`StyleWatcher::new`] checks the file.
+    pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+    /// Watch the theme at `path`. `callback` is called from the background thread, with the
+    /// new theme, each time that the file changes. It is not called for the file as it is when
+    /// watching starts, nor for changes that cannot be loaded, which are logged as warnings.
+    pub fn new(path: impl Into<PathBuf>, callback: impl Fn(Style) + Send + 'static) -> Self {
+        Self::with_interval(path, Self::POLL_INTERVAL, callback)
+    }
+
+    /// Like [`StyleWatcher::new`], checking the file every `interval`.
+    pub fn with_interval(
+        path: impl Into<PathBuf>,
+        interval: Duration,
+        callback: impl Fn(Style) + Send + 'static,
+    ) -> Self {
+        let path = path.into();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let stop = stop.clone();
+            thread::spawn(move || {
+                let mut last = fs::read_to_string(&path).ok();
+                while !stop.load(Ordering::Relaxed) {
+                    thread::sleep(interval);
+                    let toml = match fs::read_to_string(&path) {
+                        Ok(toml) if last.as_ref() != Some(&toml) => toml,
+                        _ => continue,
+                    };
+                    match parse_theme(&toml, &path) {
+                        Ok(theme) => callback(theme),
+                        Err(e) => tracing::warn!("Could not reload the theme: {}", e),
+                    }
+                    last = Some(toml);
+                }
+            })
+        };
+        Self {
+            stop,
+            thread: Some(thread),
+        }
+    }
+}
+
+impl Drop for StyleWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("mctk-theme-{}-{name}.toml", std::process::id()));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_from_toml_file() {
+        let path = temp_file(
+            "load",
+            r##"
+# A theme
+[Text]
+size = 1_4 # Whole, but a float in the default style
+color = "#ff0000"
+font = 'Space Grotesk'
+h_alignment = "center"
+lines-2 = { max_lines = 3 }
+
+[Text."font-bold"]
+font_weight = "semibold"
+
+[Custom]
+count = 2
+ratio = -0.5
+enabled = true
+label = "say \"hi\"é"
+tint = "#00ff0080"
+"##,
+        );
+        let style = Style::from_toml_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(style.count(), 11);
+        assert_eq!(style.style("Text", "size"), Some(StyleVal::Float(14.0)));
+        assert_eq!(style.style("Text", "color"), Some(Color::RED.into()));
+        assert_eq!(
+            style.style("Text", "font"),
+            Some(StyleVal::String("Space Grotesk"))
+        );
+        assert_eq!(
+            style.style("Text", "h_alignment"),
+            Some(HorizontalPosition::Center.into())
+        );
+        assert_eq!(
+            style.style_for_class("Text", "font_weight", "font-bold"),
+            Some(FontWeight::Semibold.into())
+        );
+        assert_eq!(
+            style.style_for_class("Text", "max_lines", "lines-2"),
+            Some(StyleVal::Int(3))
+        );
+        assert_eq!(style.style("Custom", "count"), Some(StyleVal::Int(2)));
+        assert_eq!(style.style("Custom", "ratio"), Some(StyleVal::Float(-0.5)));
+        assert_eq!(style.style("Custom", "enabled"), Some(StyleVal::Bool(true)));
+        assert_eq!(
+            style.style("Custom", "label"),
+            Some(StyleVal::String("say \"hi\"é"))
+        );
+        assert_eq!(
+            style.style("Custom", "tint"),
+            Some(Color::rgba(0.0, 255.0, 0.0, 128.0 / 255.0).into())
+        );
+    }
+
+    #[test]
+    fn test_load_errors() {
+        let line = |toml: &str| {
+            let path = temp_file("error", toml);
+            let result = Style::from_toml_file(&path);
+            fs::remove_file(&path).unwrap();
+            match result {
+                Err(StyleLoadError::Parse { line, .. }) => line.unwrap(),
+                r => panic!("Expected a parse error, got {r:?}"),
+            }
+        };
+        assert_eq!(line("size = 12"), 1);
+        assert_eq!(line("[Text]\n\nsize = \"big\""), 3);
+        assert_eq!(line("[Text]\ncolor = \"#12\""), 2);
+        assert_eq!(line("[Text]\nsize = [12]"), 2);
+        assert_eq!(line("[Text]\nsize = 12 13"), 2);
+        assert_eq!(line("[Text.a.b]"), 1);
+        assert_eq!(line("[Text\nsize = 12"), 1);
+        assert_eq!(line("[Text]\nlabel = \"open"), 2);
+
+        let missing = std::env::temp_dir().join("mctk-theme-missing.toml");
+        let error = Style::from_toml_file(&missing).unwrap_err();
+        assert!(matches!(error, StyleLoadError::Io { .. }));
+        assert!(error.to_string().contains("mctk-theme-missing.toml"));
+    }
+
+    #[test]
+    fn test_style_watcher() {
+        let path = temp_file("watch", "[Text]\nsize = 12\n");
+        let (sender, receiver) = mpsc::channel();
+        let watcher = StyleWatcher::with_interval(&path, Duration::from_millis(10), move |theme| {
+            let _ = sender.send(theme);
+        });
+
+        // A change that cannot be loaded is skipped
+        fs::write(&path, "[Text]\nsize = \"big\"\n").unwrap();
+        fs::write(&path, "[Text]\nsize = 16\n").unwrap();
+        let theme = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(theme.style("Text", "size"), Some(StyleVal::Float(16.0)));
+
+        drop(watcher);
+        fs::remove_file(&path).unwrap();
+        assert!(receiver.recv_timeout(Duration::from_millis(50)).is_err());
+    }
+}
@@ -1,9 +1,11 @@
 use bytemuck::{Pod, Zeroable};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::mem;
 use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign};
 use std::path::PathBuf;
+use std::str::FromStr;
 
 /// Data that can be shared between processes, e.g. by the Clipboard or Drag and Drop.
 #[derive(Debug, Clone, PartialEq)]
@@ -186,7 +188,10 @@ impl From<Point> for PixelPoint {
 }
 
 /// An `(x, y)` coordinate.
+///
+/// Unlike [`Pos`], a `Point` has no z-index. It is used for 2D geometry like mouse positions, offsets, and the anchors of [`renderables`][crate::renderables].
 #[derive(Debug, Default, Copy, Clone, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Point {
     pub x: f32,
@@ -211,6 +216,11 @@ impl Point {
     pub fn dist(self, p2: Point) -> f32 {
         ((self.x - p2.x).powf(2.0) + (self.y - p2.y).powf(2.0)).sqrt()
     }
+
+    /// A [`Pos`] at this point, with a z-index of `0.0`.
+    pub fn to_pos(self) -> Pos {
+        self.into()
+    }
 }
 
 impl Scalable for Point {
@@ -302,7 +312,10 @@ impl SubAssign for Point {
 }
 
 /// A Position coordinate `(x, y, z)`. The `z` dimension refers to the [z-index](https://developer.mozilla.org/en-US/docs/Web/CSS/z-index).
+///
+/// Used where stacking order matters, like the position of an [`AABB`] of a Node. Use [`#to_point`][Pos#method.to_point] to drop the z-index.
 #[derive(Debug, Copy, Clone, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "style-serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Pos {
     pub x: f32,
@@ -378,6 +391,11 @@ impl Pos {
             z: self.z.round(),
         }
     }
+
+    /// The `(x, y)` [`Point`] of this position, without its z-index.
+    pub fn to_point(self) -> Point {
+        self.into()
+    }
 }
 
 impl Add for Pos {
@@ -466,6 +484,39 @@ impl PixelAABB {
     }
 }
 
+/// Insets from each edge of a box, in logical pixels.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Padding {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
+impl Padding {
+    pub fn new(top: f32, right: f32, bottom: f32, left: f32) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// The same inset on all four sides.
+    pub fn uniform(p: f32) -> Self {
+        Self::new(p, p, p, p)
+    }
+}
+
+impl Mul<f32> for Padding {
+    type Output = Self;
+
+    fn mul(self, f: f32) -> Self {
+        Self::new(self.top * f, self.right * f, self.bottom * f, self.left * f)
+    }
+}
+
 /// An [Axis-Aligned Bounding Box](https://en.wikipedia.org/wiki/Minimum_bounding_box). Used by some of the advanced [`Component`](crate::Component) methods, including [`render`](crate::Component#render).
 #[derive(Debug, Copy, Clone, PartialEq, Default)]
 #[repr(C)]
@@ -511,6 +562,21 @@ impl AABB {
             && p.y <= self.bottom_right.y
     }
 
+    /// Shrink each side inwards by the given [`Padding`].
+    pub fn inset(self, padding: Padding) -> Self {
+        Self {
+            pos: Pos::new(
+                self.pos.x + padding.left,
+                self.pos.y + padding.top,
+                self.pos.z,
+            ),
+            bottom_right: Point::new(
+                self.bottom_right.x - padding.right,
+                self.bottom_right.y - padding.bottom,
+            ),
+        }
+    }
+
     /// Mutate `self`, translating by `(x, y)`.
     pub fn translate_mut(&mut self, x: f32, y: f32) {
         self.pos.x += x;
@@ -754,6 +820,229 @@ impl Color {
     pub fn rgba(r: f32, g: f32, b: f32, a: f32) -> Self {
         Self { r, g, b, a }
     }
+
+    /// HSL constructor, with `A = 1.0`. The hue is in degrees, saturation and lightness are
+    /// 0.0--1.0.
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Self {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = l - chroma / 2.0;
+        Self::rgb((r + m) * 255.0, (g + m) * 255.0, (b + m) * 255.0)
+    }
+
+    /// HSLA constructor. Like [`Color::from_hsl`], with an alpha of 0.0--1.0.
+    pub fn from_hsla(h: f32, s: f32, l: f32, a: f32) -> Self {
+        Self {
+            a,
+            ..Self::from_hsl(h, s, l)
+        }
+    }
+
+    /// The `(hue, saturation, lightness)` of this color. The hue is in degrees, saturation and
+    /// lightness are 0.0--1.0.
+    pub fn to_hsl(self) -> (f32, f32, f32) {
+        let (r, g, b) = (self.r / 255.0, self.g / 255.0, self.b / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let chroma = max - min;
+        let l = (max + min) / 2.0;
+        if chroma == 0.0 {
+            return (0.0, 0.0, l);
+        }
+
+        let s = chroma / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            ((g - b) / chroma).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / chroma + 2.0
+        } else {
+            (r - g) / chroma + 4.0
+        };
+        (h * 60.0, s, l)
+    }
+
+    /// The `(hue, saturation, lightness, alpha)` of this color, as in [`Color::to_hsl`].
+    pub fn to_hsla(self) -> (f32, f32, f32, f32) {
+        let (h, s, l) = self.to_hsl();
+        (h, s, l, self.a)
+    }
+
+    /// This color with `f` applied to its HSL values. The alpha is kept.
+    fn map_hsl(self, f: impl Fn(f32, f32, f32) -> (f32, f32, f32)) -> Self {
+        let (h, s, l) = self.to_hsl();
+        let (h, s, l) = f(h, s, l);
+        Self {
+            a: self.a,
+            ..Self::from_hsl(h, s.clamp(0.0, 1.0), l.clamp(0.0, 1.0))
+        }
+    }
+
+    /// Reduce the saturation by `factor` (0.0--1.0). `desaturate(1.0)` is a grey of the same
+    /// lightness.
+    pub fn desaturate(self, factor: f32) -> Self {
+        self.map_hsl(|h, s, l| (h, s - factor, l))
+    }
+
+    /// Increase the saturation by `factor` (0.0--1.0). `saturate(1.0)` is fully saturated.
+    pub fn saturate(self, factor: f32) -> Self {
+        self.map_hsl(|h, s, l| (h, s + factor, l))
+    }
+
+    /// Increase the lightness by `factor` (0.0--1.0).
+    pub fn lighten(self, factor: f32) -> Self {
+        self.map_hsl(|h, s, l| (h, s, l + factor))
+    }
+
+    /// Reduce the lightness by `factor` (0.0--1.0).
+    pub fn darken(self, factor: f32) -> Self {
+        self.map_hsl(|h, s, l| (h, s, l - factor))
+    }
+
+    /// Parse a hex color code, as `"#RGB"`, `"#RGBA"`, `"#RRGGBB"` or `"#RRGGBBAA"`. The `#` is
+    /// optional. Without an alpha, the color is opaque.
+    ///
+    /// ```
+    /// use mctk_core::Color;
+    /// assert_eq!(Color::from_hex("#ff0000"), Ok(Color::RED));
+    /// assert_eq!("f00".parse(), Ok(Color::RED));
+    /// ```
+    pub fn from_hex(s: &str) -> Result<Self, ColorParseError> {
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        let digits = hex
+            .chars()
+            .map(|c| {
+                c.to_digit(16)
+                    .map(|d| d as u8)
+                    .ok_or(ColorParseError::InvalidDigit(c))
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+        let channels: Vec<u8> = match digits.len() {
+            3 | 4 => digits.iter().map(|d| d * 0x11).collect(),
+            6 | 8 => digits.chunks(2).map(|d| d[0] * 0x10 + d[1]).collect(),
+            len => return Err(ColorParseError::InvalidLength(len)),
+        };
+        let a = channels.get(3).map_or(1.0, |a| u8_to_norm(*a));
+        Ok(Self::rgba(
+            channels[0] as f32,
+            channels[1] as f32,
+            channels[2] as f32,
+            a,
+        ))
+    }
+
+    /// This color as a `"#RRGGBBAA"` hex color code, that [`Color::from_hex`] reads back.
+    pub fn to_hex(&self) -> String {
+        let channel = |c: f32| c.round().clamp(0.0, 255.0) as u8;
+        format!(
+            "#{:02x}{:02x}{:02x}{:02x}",
+            channel(self.r),
+            channel(self.g),
+            channel(self.b),
+            channel(self.a * 255.0)
+        )
+    }
+
+    /// Blend from `a` to `b` by `t` (clamped to 0.0--1.0), in linear sRGB. This keeps the
+    /// brightness of the blend even, where [`Color::mix_gamma`] gets dark in the middle.
+    pub fn mix(a: Color, b: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |a: f32, b: f32| {
+            let (a, b) = (srgb_to_linear(a / 255.0), srgb_to_linear(b / 255.0));
+            linear_to_srgb(a + (b - a) * t) * 255.0
+        };
+        Color::rgba(
+            channel(a.r, b.r),
+            channel(a.g, b.g),
+            channel(a.b, b.b),
+            a.a + (b.a - a.a) * t,
+        )
+    }
+
+    /// Blend from `a` to `b` by `t` (clamped to 0.0--1.0), directly on the gamma-encoded
+    /// values. This is how most design tools and CSS mix colors.
+    pub fn mix_gamma(a: Color, b: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |a: f32, b: f32| a + (b - a) * t;
+        Color::rgba(
+            channel(a.r, b.r),
+            channel(a.g, b.g),
+            channel(a.b, b.b),
+            channel(a.a, b.a),
+        )
+    }
+
+    /// `n` colors evenly spaced from `base` to white, both included. The alpha of `base` is
+    /// kept.
+    pub fn tints(base: Color, n: usize) -> Vec<Color> {
+        Self::scale(
+            base,
+            Color {
+                a: base.a,
+                ..Color::WHITE
+            },
+            n,
+        )
+    }
+
+    /// `n` colors evenly spaced from `base` to black, both included. The alpha of `base` is
+    /// kept.
+    pub fn shades(base: Color, n: usize) -> Vec<Color> {
+        Self::scale(
+            base,
+            Color {
+                a: base.a,
+                ..Color::BLACK
+            },
+            n,
+        )
+    }
+
+    /// `n` colors [mixed][Color::mix] evenly from `from` to `to`.
+    fn scale(from: Color, to: Color, n: usize) -> Vec<Color> {
+        let steps = n.saturating_sub(1).max(1) as f32;
+        (0..n)
+            .map(|i| Self::mix(from, to, i as f32 / steps))
+            .collect()
+    }
+}
+
+/// Why a string could not be parsed by [`Color::from_hex`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorParseError {
+    /// The number of hex digits, which was not 3, 4, 6 or 8.
+    InvalidLength(usize),
+    /// A character that is not a hex digit.
+    InvalidDigit(char),
+}
+
+impl fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorParseError::InvalidLength(len) => {
+                write!(f, "expected 3, 4, 6 or 8 hex digits, found {}", len)
+            }
+            ColorParseError::InvalidDigit(c) => write!(f, "invalid hex digit `{}`", c),
+        }
+    }
+}
+
+impl std::error::Error for ColorParseError {}
+
+impl FromStr for Color {
+    type Err = ColorParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s)
+    }
 }
 
 impl From<[f32; 4]> for Color {
@@ -872,6 +1161,24 @@ fn norm_to_u8(x: f32) -> u8 {
     (x * 255.0) as u8
 }
 
+/// Decode a gamma-encoded sRGB channel (0.0--1.0) to linear light.
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Encode a channel in linear light (0.0--1.0) with the sRGB gamma.
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
 /// 8 bit [`Color`] constructor. Useful when defining static colors.
 ///
 /// Has two forms, (R, G, B) and (R, G, B, A), where the former assumes an alpha of `0xFF`.
@@ -917,12 +1224,212 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_pos_point_conversion() {
+        let p = Point::new(1.5, -2.0);
+        assert_eq!(p.to_pos(), Pos::new(1.5, -2.0, 0.0));
+        assert_eq!(p.to_pos().to_point(), p);
+        assert_eq!(Pos::new(1.0, 2.0, 3.0).to_point(), Point::new(1.0, 2.0));
+    }
+
+    #[test]
+    fn test_aabb_inset() {
+        let aabb = AABB::new(Pos::default(), [100.0, 100.0].into());
+        let inset = aabb.inset(Padding::new(10.0, 20.0, 30.0, 40.0));
+        assert_eq!(inset.pos, Pos::new(40.0, 10.0, 0.0));
+        assert_eq!(inset.bottom_right, Point::new(80.0, 70.0));
+    }
+
     #[test]
     fn test_color_from() {
         // A float that is representable in 8 bits:
         let c: Color = (0.49803921568).into();
         assert_eq!(c, Into::<Color>::into(Into::<u32>::into(c)))
     }
+
+    #[test]
+    fn test_color_hsl() {
+        let (h, s, l) = Color::rgb(51.0, 102.0, 204.0).to_hsl();
+        assert!((h - 220.0).abs() < 0.01);
+        assert!((s - 0.6).abs() < 0.001);
+        assert!((l - 0.5).abs() < 0.001);
+        let c = Color::from_hsl(h, s, l);
+        assert!((c.r - 51.0).abs() < 0.01 && (c.g - 102.0).abs() < 0.01);
+        assert!((c.b - 204.0).abs() < 0.01);
+
+        let grey = Color::RED.desaturate(1.0);
+        assert!(grey.to_hsl().1.abs() < 0.001);
+        assert_eq!((grey.r, grey.g, grey.b), (127.5, 127.5, 127.5));
+        assert!((Color::from_hsl(0.0, 0.5, 0.5).saturate(1.0).to_hsl().1 - 1.0).abs() < 0.001);
+
+        let lighter = Color::from_hsl(0.0, 1.0, 0.5).lighten(0.2);
+        assert!((lighter.to_hsl().2 - 0.7).abs() < 0.001);
+        assert!((Color::RED.darken(0.2).to_hsl().2 - 0.3).abs() < 0.001);
+        // Alpha is kept, and lightness is clamped
+        let c = Color::rgba(255.0, 0.0, 0.0, 0.5).lighten(1.0);
+        assert_eq!(c, Color::rgba(255.0, 255.0, 255.0, 0.5));
+    }
+
+    #[test]
+    fn test_color_hsl_round_trip() {
+        let near = |a: Color, b: Color| {
+            (a.r - b.r).abs() < 0.01
+                && (a.g - b.g).abs() < 0.01
+                && (a.b - b.b).abs() < 0.01
+                && (a.a - b.a).abs() < f32::EPSILON
+        };
+        for c in [
+            Color::BLACK,
+            Color::WHITE,
+            Color::MID_GREY,
+            Color::RED,
+            Color::GREEN,
+            Color::BLUE,
+            Color::YELLOW,
+            Color::MAGENTA,
+            Color::rgba(12.0, 200.0, 99.0, 0.25),
+            Color::rgba(250.0, 128.0, 3.0, 0.75),
+        ] {
+            let (h, s, l, a) = c.to_hsla();
+            assert!(near(Color::from_hsla(h, s, l, a), c), "{c:?}");
+            let (h, s, l) = c.to_hsl();
+            assert!(
+                near(Color::from_hsl(h, s, l), Color { a: 1.0, ..c }),
+                "{c:?}"
+            );
+        }
+        assert_eq!(
+            Color::from_hsla(0.0, 1.0, 0.5, 0.5),
+            Color::rgba(255.0, 0.0, 0.0, 0.5)
+        );
+    }
+
+    fn colors() -> Vec<Color> {
+        // A spread of colors over every channel, including the extremes
+        let levels = [0.0, 1.0, 37.0, 128.0, 201.0, 254.0, 255.0];
+        let mut colors = vec![];
+        for (i, r) in levels.iter().enumerate() {
+            for (j, g) in levels.iter().enumerate() {
+                for b in levels.iter() {
+                    let a = ((i + j) % 5) as f32 / 4.0;
+                    colors.push(Color::rgba(*r, *g, *b, a));
+                }
+            }
+        }
+        colors
+    }
+
+    fn near(a: Color, b: Color) -> bool {
+        [(a.r, b.r), (a.g, b.g), (a.b, b.b)]
+            .iter()
+            .all(|(x, y)| (x - y).abs() < 0.05)
+            && (a.a - b.a).abs() < 0.0001
+    }
+
+    #[test]
+    fn test_color_mix() {
+        let colors = colors();
+        for (a, b) in colors.iter().zip(colors.iter().rev()) {
+            let (a, b) = (*a, *b);
+            for mix in [Color::mix, Color::mix_gamma] {
+                // The ends are the colors themselves, and `t` is clamped past them
+                assert!(near(mix(a, b, 0.0), a), "{a:?} {b:?}");
+                assert!(near(mix(a, b, 1.0), b), "{a:?} {b:?}");
+                assert_eq!(mix(a, b, -1.0), mix(a, b, 0.0));
+                assert_eq!(mix(a, b, 2.0), mix(a, b, 1.0));
+                // Mixing is symmetric, and every channel moves steadily from one end to the
+                // other
+                assert!(near(mix(a, b, 0.3), mix(b, a, 0.7)), "{a:?} {b:?}");
+                let mut prev = a;
+                for i in 1..=10 {
+                    let c = mix(a, b, i as f32 / 10.0);
+                    for (p, c, a, b) in [
+                        (prev.r, c.r, a.r, b.r),
+                        (prev.g, c.g, a.g, b.g),
+                        (prev.b, c.b, a.b, b.b),
+                        (prev.a, c.a, a.a, b.a),
+                    ] {
+                        assert!((c - p) * (b - a) >= -0.001, "{a:?} {b:?}");
+                        assert!(c >= a.min(b) - 0.05 && c <= a.max(b) + 0.05);
+                    }
+                    prev = c;
+                }
+            }
+        }
+
+        // A linear blend of black and white is brighter than the gamma blend
+        let linear = Color::mix(Color::BLACK, Color::WHITE, 0.5);
+        let gamma = Color::mix_gamma(Color::BLACK, Color::WHITE, 0.5);
+        assert_eq!(gamma.r, 127.5);
+        assert!((linear.r - 188.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_color_tints() {
+        for base in colors() {
+            let tints = Color::tints(base, 5);
+            assert_eq!(tints.len(), 5);
+            assert!(near(tints[0], base));
+            assert!(near(
+                tints[4],
+                Color {
+                    a: base.a,
+                    ..Color::WHITE
+                }
+            ));
+            assert!(tints
+                .windows(2)
+                .all(|w| w[1].to_hsl().2 >= w[0].to_hsl().2 - 0.001));
+
+            let shades = Color::shades(base, 5);
+            assert!(near(
+                shades[4],
+                Color {
+                    a: base.a,
+                    ..Color::BLACK
+                }
+            ));
+            assert!(shades
+                .windows(2)
+                .all(|w| w[1].to_hsl().2 <= w[0].to_hsl().2 + 0.001));
+        }
+        assert!(Color::tints(Color::RED, 0).is_empty());
+        let tints = Color::tints(Color::RED, 1);
+        assert!(tints.len() == 1 && near(tints[0], Color::RED));
+    }
+
+    #[test]
+    fn test_color_hex() {
+        let c = Color::rgba(0x11 as f32, 0x22 as f32, 0x33 as f32, 0x44 as f32 / 255.0);
+        assert_eq!(Color::from_hex("#123"), Ok(Color { a: 1.0, ..c }));
+        assert_eq!(Color::from_hex("#1234"), Ok(c));
+        assert_eq!(Color::from_hex("#112233"), Ok(Color { a: 1.0, ..c }));
+        assert_eq!(Color::from_hex("#11223344"), Ok(c));
+        assert_eq!(Color::from_hex("11223344"), Ok(c));
+        assert_eq!("#FF0000".parse::<Color>(), Ok(Color::RED));
+
+        assert_eq!(c.to_hex(), "#11223344");
+        assert_eq!(Color::WHITE.to_hex(), "#ffffffff");
+        assert_eq!(
+            Color::from_hex(&Color::MID_GREY.to_hex()),
+            Ok(Color::MID_GREY)
+        );
+
+        assert_eq!(Color::from_hex(""), Err(ColorParseError::InvalidLength(0)));
+        assert_eq!(
+            Color::from_hex("#12345"),
+            Err(ColorParseError::InvalidLength(5))
+        );
+        assert_eq!(
+            Color::from_hex("##123"),
+            Err(ColorParseError::InvalidDigit('#'))
+        );
+        assert_eq!(
+            Color::from_hex("#12g"),
+            Err(ColorParseError::InvalidDigit('g'))
+        );
+        assert!("#12345".parse::<Color>().is_err());
+    }
 }
 
 #[derive(Debug, Clone, Default)]
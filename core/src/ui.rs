@@ -1,3 +1,4 @@
+use crate::accessibility;
 use crate::component::{Message, RootComponent};
 use crate::event::{self, Event, EventCache, EventInput};
 use crate::input::*;
@@ -6,6 +7,7 @@ use crate::raw_handle::RawWaylandHandle;
 use crate::renderer::canvas::{self, GlCanvasContext};
 use crate::renderer::gl::{self};
 use crate::renderer::Renderer;
+use crate::style::{self, CursorStyle};
 use crate::{component::Component, node::Node, types::PixelSize};
 use crate::{lay, node::Registration, size, types::*, window::Window};
 use crossbeam_channel::{unbounded, Receiver, Sender};
@@ -40,6 +42,8 @@ pub struct UI<W: Window, A: Component + Default + Send + Sync, B> {
     node_dirty: Arc<RwLock<bool>>,
     frame_dirty: Arc<RwLock<bool>>,
     app_params: B,
+    /// The cursor last set on the window, `None` when it is the default pointer.
+    cursor: Option<CursorStyle>,
 }
 
 #[derive(PartialEq)]
@@ -104,6 +108,7 @@ impl<
         // dbg!(scale_factor);
         let physical_size = Arc::new(RwLock::new(window.physical_size()));
         let logical_size = Arc::new(RwLock::new(window.logical_size()));
+        style::set_viewport_width(window.logical_size().width as f32);
         println!(
             "New window with physical size {:?} client size {:?} and scale factor {:?}",
             physical_size, logical_size, scale_factor
@@ -146,6 +151,7 @@ impl<
             logical_size,
             event_cache,
             node_dirty,
+            cursor: None,
         };
         n
     }
@@ -196,9 +202,7 @@ impl<
                     {
                         // We need to acquire a lock on the node once we `view` it, because we remove its state at this point
                         let mut old = node.write().unwrap();
-                        let mut new_registrations: Vec<Registration> = vec![];
-                        new.view(Some(&mut old), &mut new_registrations);
-                        *registrations.write().unwrap() = new_registrations;
+                        view_node(&mut new, Some(&mut old), &registrations);
 
                         let renderer = renderer.read().unwrap();
 
@@ -301,6 +305,7 @@ impl<
         // update the size for window, ui
         window.set_size(width, height);
         self.logical_size = Arc::new(RwLock::new(window.logical_size()));
+        style::set_viewport_width(window.logical_size().width as f32);
 
         // update the renderer canvas
         let mut renderer = self.renderer.write().unwrap();
@@ -449,18 +454,74 @@ impl<
         self.handle_dirty_event(&blur_event);
 
         self.event_cache.focus = self.node.read().unwrap().id; // The root note gets focus
+        if let Some(listener) = accessibility::accessibility_listener() {
+            listener.focus_changed(self.event_cache.focus);
+        }
     }
 
     fn handle_focus_or_blur<T: EventInput>(&mut self, event: &Event<T>) {
         if event.focus.is_none() {
             self.blur();
         } else if event.focus != Some(self.event_cache.focus) {
-            self.blur();
-            self.event_cache.focus = event.focus.unwrap();
-            let mut focus_event = Event::new(event::Focus, &self.event_cache);
-            focus_event.target = Some(self.event_cache.focus);
-            self.node_mut().focus(&mut focus_event);
-            self.handle_dirty_event(&focus_event);
+            self.focus(event.focus.unwrap());
+        }
+    }
+
+    /// Move the focus to the Node `node_id`.
+    fn focus(&mut self, node_id: u64) {
+        self.blur();
+        self.event_cache.focus = node_id;
+        let mut focus_event = Event::new(event::Focus, &self.event_cache);
+        focus_event.target = Some(self.event_cache.focus);
+        self.node_mut().focus(&mut focus_event);
+        self.handle_dirty_event(&focus_event);
+        if let Some(listener) = accessibility::accessibility_listener() {
+            listener.focus_changed(node_id);
+        }
+    }
+
+    /// Handle a key press while the Node `trap` traps the focus. The key goes to the focused Node
+    /// if it is inside the trap, and then to the trap itself, unless its bubbling was stopped.
+    /// Tab moves the focus between the trap's focusable descendants instead of reaching the trap.
+    fn handle_trapped_key_down(&mut self, event: &mut Event<event::KeyDown>, trap: u64) {
+        let focus = self.event_cache.focus;
+        let inside = focus != trap
+            && self
+                .node_ref()
+                .find(trap)
+                .map_or(false, |n| n.find(focus).is_some());
+        if inside {
+            self.handle_event(Node::key_down, event, Some(focus));
+            if !event.bubbles {
+                return;
+            }
+        }
+
+        if event.input.0 == Key::Tab {
+            let next = self
+                .node_ref()
+                .find(trap)
+                .and_then(|n| n.next_focus(focus, event.modifiers_held.shift));
+            if let Some(next) = next {
+                if next != self.event_cache.focus {
+                    self.focus(next);
+                }
+            }
+        } else {
+            let mut trap_event = Event::new(event::KeyDown(event.input.0), &self.event_cache);
+            self.handle_event(Node::key_down, &mut trap_event, Some(trap));
+        }
+    }
+
+    /// Show the cursor of the Node `target`, which the mouse is now over.
+    fn update_cursor(&mut self, target: Option<u64>) {
+        let cursor = target.and_then(|t| self.node_ref().cursor_at(t));
+        if cursor != self.cursor {
+            match cursor {
+                Some(c) => self.window.read().unwrap().set_cursor(c.name()),
+                None => self.window.read().unwrap().unset_cursor(),
+            }
+            self.cursor = cursor;
         }
     }
 
@@ -512,8 +573,9 @@ impl<
                 if new_size.width != 0 && new_size.height != 0 {
                     let scale_factor = self.window.read().unwrap().scale_factor();
                     *self.physical_size.write().unwrap() = new_size;
-                    *self.logical_size.write().unwrap() =
-                        self.window.read().unwrap().logical_size();
+                    let logical_size = self.window.read().unwrap().logical_size();
+                    *self.logical_size.write().unwrap() = logical_size;
+                    style::set_viewport_width(logical_size.width as f32);
                     *self.scale_factor.write().unwrap() = scale_factor;
                     self.event_cache.scale_factor = scale_factor;
                     *self.node_dirty.write().unwrap() = true;
@@ -574,6 +636,13 @@ impl<
                         self.handle_event(Node::mouse_enter, &mut enter_event, motion_event.target);
                     }
                     self.event_cache.mouse_over = motion_event.target;
+                    self.update_cursor(motion_event.target);
+                    #[cfg(feature = "debug-inspector")]
+                    crate::inspector::set_hovered(
+                        motion_event
+                            .target
+                            .and_then(|t| self.node_ref().inspect_at(t)),
+                    );
                 }
             }
             Input::Motion(Motion::Scroll { x, y }) => {
@@ -608,6 +677,11 @@ impl<
                     {
                         is_double_click = true;
                     }
+                    self.event_cache.click_count = if is_double_click {
+                        self.event_cache.click_count + 1
+                    } else {
+                        1
+                    };
                     self.event_cache.last_mouse_click = Instant::now();
                     self.event_cache.last_mouse_click_position = self.event_cache.mouse_position;
                 }
@@ -675,8 +749,13 @@ impl<
             Input::Press(Button::Keyboard(k)) => {
                 self.event_cache.key_down(*k);
                 let mut event = Event::new(event::KeyDown(*k), &self.event_cache);
-                let focus = event.focus;
-                self.handle_event(Node::key_down, &mut event, focus);
+                let trap = self.node_ref().focus_trap();
+                if let Some(trap) = trap {
+                    self.handle_trapped_key_down(&mut event, trap);
+                } else {
+                    let focus = event.focus;
+                    self.handle_event(Node::key_down, &mut event, focus);
+                }
             }
             Input::Release(Button::Keyboard(k)) => {
                 if self.event_cache.key_held(*k) {
@@ -711,6 +790,11 @@ impl<
                 {
                     is_double_tap = true;
                 }
+                self.event_cache.click_count = if is_double_tap {
+                    self.event_cache.click_count + 1
+                } else {
+                    1
+                };
                 self.event_cache.last_touch_down = Instant::now();
                 self.event_cache.last_touch_position = pos;
 
@@ -835,6 +919,16 @@ impl<
                     self.handle_event(Node::text_entry, &mut event, focus);
                 }
             }
+            Input::ImeCompose(s) => {
+                let mut event = Event::new(event::ImeCompose(s.clone()), &self.event_cache);
+                let focus = event.focus;
+                self.handle_event(Node::ime_compose, &mut event, focus);
+            }
+            Input::ImeCommit(s) => {
+                let mut event = Event::new(event::ImeCommit(s.clone()), &self.event_cache);
+                let focus = event.focus;
+                self.handle_event(Node::ime_commit, &mut event, focus);
+            }
             Input::Focus(false) => {
                 self.event_cache.clear();
                 let mut event = Event::new(event::Blur, &self.event_cache);
@@ -1006,3 +1100,130 @@ impl<
         *self.node_dirty.write().unwrap() = dirty;
     }
 }
+
+/// View the Node tree `new`, which replaces `old`, and pass it to the accessibility listener.
+fn view_node(new: &mut Node, old: Option<&mut Node>, registrations: &RwLock<Vec<Registration>>) {
+    let mut new_registrations: Vec<Registration> = vec![];
+    new.view(old, &mut new_registrations);
+    *registrations.write().unwrap() = new_registrations;
+    if let Some(listener) = accessibility::accessibility_listener() {
+        listener.update(&*new);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accessibility::{
+        set_accessibility_listener, AccessibilityListener, AccessibilityProvider,
+    };
+    use crate::widgets::{Button, Div};
+    use crate::{node, txt};
+    use raw_window_handle::{
+        RawDisplayHandle, RawWindowHandle, WaylandDisplayHandle, WaylandWindowHandle,
+    };
+    use std::sync::Mutex;
+
+    struct TestWindow;
+
+    impl Window for TestWindow {
+        fn logical_size(&self) -> PixelSize {
+            PixelSize {
+                width: 100,
+                height: 100,
+            }
+        }
+
+        fn physical_size(&self) -> PixelSize {
+            self.logical_size()
+        }
+
+        fn scale_factor(&self) -> f32 {
+            1.0
+        }
+
+        fn fonts(&self) -> cosmic_text::fontdb::Database {
+            cosmic_text::fontdb::Database::new()
+        }
+
+        fn assets(&self) -> HashMap<String, AssetParams> {
+            HashMap::new()
+        }
+
+        fn svgs(&self) -> HashMap<String, String> {
+            HashMap::new()
+        }
+
+        fn exit(&mut self) {}
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    unsafe impl HasRawWindowHandle for TestWindow {
+        fn raw_window_handle(&self) -> RawWindowHandle {
+            RawWindowHandle::Wayland(WaylandWindowHandle::empty())
+        }
+    }
+
+    unsafe impl HasRawDisplayHandle for TestWindow {
+        fn raw_display_handle(&self) -> RawDisplayHandle {
+            RawDisplayHandle::Wayland(WaylandDisplayHandle::empty())
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct App;
+
+    impl Component for App {
+        fn view(&self) -> Option<Node> {
+            Some(node!(Div::new()).push(node!(Button::new(txt!("Ok")))))
+        }
+    }
+
+    impl RootComponent<()> for App {}
+
+    #[derive(Default)]
+    struct Listener(Mutex<Vec<String>>);
+
+    impl AccessibilityListener for Listener {
+        fn update(&self, root: &dyn AccessibilityProvider) {
+            let labels: Vec<_> = root
+                .accessible_children()
+                .into_iter()
+                .filter_map(|n| n.label)
+                .collect();
+            self.0.lock().unwrap().push(format!("update {labels:?}"));
+        }
+
+        fn focus_changed(&self, id: u64) {
+            self.0.lock().unwrap().push(format!("focus {id}"));
+        }
+    }
+
+    #[test]
+    fn test_accessibility_listener() {
+        let listener = Arc::new(Listener::default());
+        set_accessibility_listener(listener.clone());
+        let mut ui: UI<TestWindow, App, ()> = UI::new(TestWindow, ());
+
+        let mut new = Node::new(Box::<App>::default(), 0, Layout::default());
+        view_node(&mut new, None, &ui.registrations);
+        let button = new.accessible_children()[0].id;
+        let root = new.id;
+        *ui.node_mut() = new;
+
+        ui.focus(button);
+        ui.blur();
+        assert_eq!(
+            *listener.0.lock().unwrap(),
+            vec![
+                "update [\"Ok\"]".to_string(),
+                format!("focus {root}"),
+                format!("focus {button}"),
+                format!("focus {root}"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,526 @@
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use mctk_macros::{component, state_component_impl};
+
+use crate::component::{Component, ComponentHasher, Message, RenderContext};
+use crate::font_cache::FontCache;
+use crate::input::Key;
+use crate::layout::{Alignment, Direction, ScrollPosition};
+use crate::renderables::{Polyline, Rect, Renderable};
+use crate::style::{CursorStyle, Styled};
+use crate::types::*;
+use crate::{event, lay, msg, node, rect, size, size_pct, txt, Node};
+
+use super::{Div, Text};
+
+#[derive(Debug)]
+enum AccordionMsg {
+    Toggle(usize),
+}
+
+/// A section of an [`Accordion`]: a header, and the content that it shows and hides.
+pub struct AccordionItem {
+    pub header: String,
+    pub expanded: bool,
+    // Moved into the accordion's view, which is only made once for each instance
+    content: Mutex<Option<Box<dyn Component + Send + Sync>>>,
+}
+
+impl fmt::Debug for AccordionItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AccordionItem")
+            .field("header", &self.header)
+            .field("expanded", &self.expanded)
+            .finish()
+    }
+}
+
+impl AccordionItem {
+    pub fn new<S: Into<String>, C: Component + Send + Sync + 'static>(
+        header: S,
+        content: C,
+    ) -> Self {
+        Self {
+            header: header.into(),
+            expanded: false,
+            content: Mutex::new(Some(Box::new(content))),
+        }
+    }
+
+    pub fn expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self
+    }
+}
+
+#[derive(Debug, Default)]
+struct AccordionState {
+    expanded: Vec<bool>,
+    /// How far open each item is, from 0 to 1. Moves towards `expanded` over `animation_ms`.
+    openness: Vec<f32>,
+    last_tick: Option<Instant>,
+}
+
+/// A column of headers that each expand to show their item's content, and collapse to hide it.
+///
+/// Clicking a header, or pressing Return or Space while it has the focus, toggles its item and
+/// calls `on_change` with the item's position and whether it is now expanded. Unless
+/// `allow_multiple` is set, expanding an item collapses the others, each with an `on_change`.
+#[component(State = "AccordionState", Styled, Internal)]
+pub struct Accordion {
+    pub items: Vec<AccordionItem>,
+    pub allow_multiple: bool,
+    /// How long an item takes to open or close. No animation when 0.
+    pub animation_ms: u32,
+    pub on_change: Option<Box<dyn Fn(usize, bool) -> Message + Send + Sync>>,
+}
+
+impl fmt::Debug for Accordion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Accordion")
+            .field("items", &self.items)
+            .field("allow_multiple", &self.allow_multiple)
+            .field("animation_ms", &self.animation_ms)
+            .finish()
+    }
+}
+
+impl Accordion {
+    pub fn new(items: Vec<AccordionItem>) -> Self {
+        Self {
+            items,
+            allow_multiple: false,
+            animation_ms: 200,
+            on_change: None,
+            state: Some(AccordionState::default()),
+            dirty: false,
+            class: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    pub fn allow_multiple(mut self, allow_multiple: bool) -> Self {
+        self.allow_multiple = allow_multiple;
+        self
+    }
+
+    pub fn animation_ms(mut self, animation_ms: u32) -> Self {
+        self.animation_ms = animation_ms;
+        self
+    }
+
+    pub fn on_change(
+        mut self,
+        change_fn: Box<dyn Fn(usize, bool) -> Message + Send + Sync>,
+    ) -> Self {
+        self.on_change = Some(change_fn);
+        self
+    }
+
+    fn toggle(&mut self, index: usize) -> Vec<Message> {
+        let mut changed = vec![];
+        let expand = match self.state_ref().expanded.get(index) {
+            Some(expanded) => !expanded,
+            None => return vec![],
+        };
+        if expand && !self.allow_multiple {
+            for (i, expanded) in self.state_mut().expanded.iter_mut().enumerate() {
+                if *expanded && i != index {
+                    *expanded = false;
+                    changed.push((i, false));
+                }
+            }
+        }
+        self.state_mut().expanded[index] = expand;
+        changed.push((index, expand));
+
+        if self.animation_ms == 0 {
+            self.step_animation(0.0);
+        }
+        match &self.on_change {
+            Some(change_fn) => changed.into_iter().map(|(i, e)| change_fn(i, e)).collect(),
+            None => vec![],
+        }
+    }
+
+    fn is_animating(&self) -> bool {
+        let state = self.state_ref();
+        state
+            .expanded
+            .iter()
+            .zip(state.openness.iter())
+            .any(|(expanded, openness)| *openness != if *expanded { 1.0 } else { 0.0 })
+    }
+
+    /// Open and close the items for `dt` seconds.
+    fn step_animation(&mut self, dt: f32) {
+        let delta = if self.animation_ms == 0 {
+            1.0
+        } else {
+            dt * 1000.0 / self.animation_ms as f32
+        };
+        let state = self.state_mut();
+        for (expanded, openness) in state.expanded.iter().zip(state.openness.iter_mut()) {
+            *openness = if *expanded {
+                (*openness + delta).min(1.0)
+            } else {
+                (*openness - delta).max(0.0)
+            };
+        }
+    }
+}
+
+#[state_component_impl(AccordionState)]
+impl Component for Accordion {
+    fn init(&mut self) {
+        let expanded: Vec<bool> = self.items.iter().map(|item| item.expanded).collect();
+        self.state_mut().openness = expanded
+            .iter()
+            .map(|e| if *e { 1.0 } else { 0.0 })
+            .collect();
+        self.state_mut().expanded = expanded;
+    }
+
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        for item in self.items.iter() {
+            item.header.hash(hasher);
+            item.expanded.hash(hasher);
+        }
+    }
+
+    fn new_props(&mut self) {
+        let expanded: Vec<bool> = self.items.iter().map(|item| item.expanded).collect();
+        let mut openness = std::mem::take(&mut self.state_mut().openness);
+        openness.resize(expanded.len(), 0.0);
+        self.state_mut().openness = openness;
+        self.state_mut().expanded = expanded;
+    }
+
+    fn on_tick(&mut self, _event: &mut event::Event<event::Tick>) {
+        if !self.is_animating() {
+            self.state_mut().last_tick = None;
+            return;
+        }
+        let now = Instant::now();
+        let dt = self
+            .state_ref()
+            .last_tick
+            .map(|t| now.duration_since(t).as_secs_f32())
+            .unwrap_or(0.0);
+        self.state_mut().last_tick = Some(now);
+        self.step_animation(dt);
+    }
+
+    fn update(&mut self, message: Message) -> Vec<Message> {
+        match message.downcast_ref::<AccordionMsg>() {
+            Some(AccordionMsg::Toggle(index)) => self.toggle(*index),
+            None => vec![message],
+        }
+    }
+
+    fn view(&self) -> Option<Node> {
+        let mut base = node!(
+            Div::new(),
+            lay![
+                direction: Direction::Column,
+                size_pct: [100, Auto],
+                cross_alignment: Alignment::Stretch
+            ]
+        );
+        let state = self.state_ref();
+        for (index, item) in self.items.iter().enumerate() {
+            let expanded = state.expanded.get(index).copied().unwrap_or(false);
+            let openness = state.openness.get(index).copied().unwrap_or(0.0);
+            base = base.push(
+                node!(
+                    AccordionHeader {
+                        index,
+                        header: item.header.clone(),
+                        expanded,
+                        class: self.class,
+                        style_overrides: self.style_overrides.clone(),
+                    },
+                    lay![size_pct: [100, Auto]]
+                )
+                .key(index as u64 * 2),
+            );
+
+            let mut panel = node!(
+                AccordionPanel {
+                    openness,
+                    state: Some(AccordionPanelState::default()),
+                    dirty: false,
+                },
+                lay![size_pct: [100, Auto]]
+            )
+            .key(index as u64 * 2 + 1);
+            if let Some(content) = item.content.lock().unwrap().take() {
+                panel = panel.push(Node::new(content, 0, lay![size_pct: [100, Auto]]));
+            }
+            base = base.push(panel);
+        }
+        Some(base)
+    }
+}
+
+#[component(Styled = "Accordion", Internal)]
+#[derive(Debug)]
+struct AccordionHeader {
+    index: usize,
+    header: String,
+    expanded: bool,
+}
+
+impl AccordionHeader {
+    /// A chevron of `size` centered on `center`, pointing down when expanded, or else right.
+    fn indicator(&self, center: Pos, size: f32, color: Color) -> Polyline {
+        let h = size / 2.0;
+        let q = size / 4.0;
+        let points = if self.expanded {
+            [(-h, -q), (0.0, q), (h, -q)]
+        } else {
+            [(-q, -h), (q, 0.0), (-q, h)]
+        };
+        Polyline::new(
+            points
+                .iter()
+                .map(|(x, y)| Point::new(center.x + x, center.y + y))
+                .collect(),
+            color,
+            (size / 6.0).max(1.0),
+        )
+    }
+}
+
+impl Component for AccordionHeader {
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.header.hash(hasher);
+        self.expanded.hash(hasher);
+    }
+
+    fn cursor(&self) -> Option<CursorStyle> {
+        self.style_val("cursor").map(|v| v.cursor())
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        event.stop_bubbling();
+        // Take the focus, so Return and Space toggle this header
+        event.focus();
+        event.emit(msg!(AccordionMsg::Toggle(self.index)));
+    }
+
+    fn on_key_down(&mut self, event: &mut event::Event<event::KeyDown>) {
+        if matches!(event.input.0, Key::Return | Key::Space) {
+            event.stop_bubbling();
+            event.emit(msg!(AccordionMsg::Toggle(self.index)));
+        }
+    }
+
+    fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
+        let padding: f32 = self.style_val("padding").unwrap().f32();
+        let indicator_size: f32 = self.style_val("indicator_size").unwrap().f32();
+        let border_width: f32 = self.style_val("border_width").unwrap().f32();
+        let AABB { pos, .. } = context.aabb;
+        let Scale { width, height } = context.aabb.size();
+
+        let mut rs = vec![Renderable::Rect(Rect::new(
+            pos,
+            context.aabb.size(),
+            Color::from(self.style_val("header_background")),
+        ))];
+        if border_width > 0.0 && self.index > 0 {
+            // Between this item and the one above
+            rs.push(Renderable::Rect(Rect::new(
+                pos,
+                Scale::new(width, border_width),
+                self.style_val("border_color").into(),
+            )));
+        }
+        let center = Pos::new(
+            pos.x + width - padding - indicator_size / 2.0,
+            pos.y + height / 2.0,
+            pos.z,
+        );
+        rs.push(Renderable::Polyline(self.indicator(
+            center,
+            indicator_size,
+            self.style_val("header_color").into(),
+        )));
+        Some(rs)
+    }
+
+    fn view(&self) -> Option<Node> {
+        let padding: f32 = self.style_val("padding").unwrap().f32();
+        let indicator_size: f32 = self.style_val("indicator_size").unwrap().f32();
+        Some(
+            node!(
+                Div::new(),
+                lay![
+                    size_pct: [100, Auto],
+                    padding: [padding, padding, padding, padding * 2.0 + indicator_size]
+                ]
+            )
+            .push(node!(Text::new(txt!(self.header.clone()))
+                .style("size", self.style_val("font_size").unwrap())
+                .style("color", self.style_val("header_color").unwrap())
+                .maybe_style("font", self.style_val("font")))),
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+struct AccordionPanelState {
+    /// The height of the content, as last laid out.
+    content_height: Option<f32>,
+}
+
+/// Shows `openness` of the height of its content, clipping the rest.
+#[component(State = "AccordionPanelState", Internal)]
+#[derive(Debug)]
+struct AccordionPanel {
+    openness: f32,
+}
+
+#[state_component_impl(AccordionPanelState)]
+impl Component for AccordionPanel {
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        ((self.openness * 1000.0) as u32).hash(hasher);
+    }
+
+    fn fill_bounds(
+        &mut self,
+        _width: Option<f32>,
+        _height: Option<f32>,
+        _max_width: Option<f32>,
+        _max_height: Option<f32>,
+        _font_cache: &mut FontCache,
+        _scale_factor: f32,
+    ) -> (Option<f32>, Option<f32>) {
+        match self.state_ref().content_height {
+            Some(height) => (None, Some(height * self.openness)),
+            // Until the content has been measured, an open panel takes its natural height
+            None if self.openness >= 1.0 => (None, None),
+            None => (None, Some(0.0)),
+        }
+    }
+
+    fn full_control(&self) -> bool {
+        true
+    }
+
+    fn set_aabb(
+        &mut self,
+        _aabb: &mut AABB,
+        _parent_aabb: AABB,
+        children: Vec<(&mut AABB, Option<Scale>, Option<Point>)>,
+        _frame: AABB,
+        scale_factor: f32,
+    ) {
+        if let Some((child_aabb, _, _)) = children.first() {
+            // In logical pixels, like the layout that fill_bounds gives
+            let height = Some(child_aabb.height() / scale_factor);
+            if self.state_ref().content_height != height {
+                self.state_mut().content_height = height;
+            }
+        }
+    }
+
+    // Scrollable, so that the content is clipped to the panel
+    fn scroll_position(&self) -> Option<ScrollPosition> {
+        Some(ScrollPosition {
+            x: None,
+            y: Some(0.0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accordion(allow_multiple: bool) -> Accordion {
+        let mut accordion = Accordion::new(vec![
+            AccordionItem::new("One", Div::new()).expanded(true),
+            AccordionItem::new("Two", Div::new()),
+            AccordionItem::new("Three", Div::new()),
+        ])
+        .allow_multiple(allow_multiple)
+        .on_change(Box::new(|index, expanded| msg!((index, expanded))));
+        accordion.init();
+        accordion
+    }
+
+    fn changes(m: Vec<Message>) -> Vec<(usize, bool)> {
+        m.iter()
+            .map(|m| *m.downcast_ref::<(usize, bool)>().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut single = accordion(false);
+        assert_eq!(
+            changes(single.update(msg!(AccordionMsg::Toggle(1)))),
+            vec![(0, false), (1, true)]
+        );
+        assert_eq!(single.state_ref().expanded, vec![false, true, false]);
+        assert_eq!(
+            changes(single.update(msg!(AccordionMsg::Toggle(1)))),
+            vec![(1, false)]
+        );
+
+        let mut multiple = accordion(true);
+        assert_eq!(
+            changes(multiple.update(msg!(AccordionMsg::Toggle(2)))),
+            vec![(2, true)]
+        );
+        assert_eq!(multiple.state_ref().expanded, vec![true, false, true]);
+    }
+
+    #[test]
+    fn test_animation() {
+        let mut accordion = accordion(false).animation_ms(100);
+        accordion.update(msg!(AccordionMsg::Toggle(1)));
+        assert!(accordion.is_animating());
+
+        accordion.step_animation(0.025);
+        assert_eq!(accordion.state_ref().openness, vec![0.75, 0.25, 0.0]);
+        accordion.step_animation(1.0);
+        assert_eq!(accordion.state_ref().openness, vec![0.0, 1.0, 0.0]);
+        assert!(!accordion.is_animating());
+
+        // Without animation, items open and close at once
+        let mut accordion = accordion.animation_ms(0);
+        accordion.update(msg!(AccordionMsg::Toggle(2)));
+        assert_eq!(accordion.state_ref().openness, vec![0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_indicator() {
+        let header = AccordionHeader {
+            index: 0,
+            header: "One".to_string(),
+            expanded: false,
+            class: None,
+            style_overrides: Default::default(),
+        };
+        let center = Pos::new(10.0, 10.0, 0.0);
+        // Points right while collapsed
+        let right = header.indicator(center, 8.0, Color::BLACK);
+        assert_eq!(right.points[1], Point::new(12.0, 10.0));
+
+        let header = AccordionHeader {
+            expanded: true,
+            ..header
+        };
+        let down = header.indicator(center, 8.0, Color::BLACK);
+        assert_eq!(down.points[1], Point::new(10.0, 12.0));
+    }
+}
@@ -1,9 +1,10 @@
+use std::hash::Hash;
 use std::time::Instant;
 
 // use super::ToolTip;
-use crate::component::{Component, Message};
+use crate::component::{AriaRole, Component, Message};
 use crate::font_cache::TextSegment;
-use crate::style::Styled;
+use crate::style::{ComponentState, CursorStyle, Styled};
 use crate::{event, lay, rect};
 use crate::{node, node::Node};
 use crate::{size_pct, types::*};
@@ -13,6 +14,7 @@ use mctk_macros::{component, state_component_impl};
 struct ButtonState {
     hover: bool,
     pressed: bool,
+    toggled: bool,
     tool_tip_open: Option<Point>,
     hover_start: Option<Instant>,
 }
@@ -25,12 +27,31 @@ pub struct Button {
     pub on_release: Option<Box<dyn Fn() -> Message + Send + Sync>>,
     pub on_double_click: Option<Box<dyn Fn() -> Message + Send + Sync>>,
     pub tool_tip: Option<String>,
+    /// When set, each click flips the button between its on and off states.
+    pub toggle_mode: bool,
+    /// Whether a `toggle_mode` button starts out on.
+    pub toggled: bool,
+    pub on_toggle: Option<Box<dyn Fn(bool) -> Message + Send + Sync>>,
+    /// A disabled button ignores presses and clicks, and shows the not-allowed cursor.
+    pub disabled: bool,
+    /// Makes this a link to `href`: clicks call `on_navigate`, and `Ctrl+Click` (or `Cmd+Click`)
+    /// calls `on_open_url` to open it in a new window.
+    pub href: Option<String>,
+    /// Called with the `href` of a clicked link, for in-app navigation.
+    pub on_navigate: Option<Box<dyn Fn(&str) -> Message + Send + Sync>>,
+    /// Called with the `href` of a link that is `Ctrl+Click`ed, for the platform to open it in a
+    /// new window.
+    pub on_open_url: Option<Box<dyn Fn(&str) -> Message + Send + Sync>>,
 }
 
 impl std::fmt::Debug for Button {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.debug_struct("Button")
             .field("label", &self.label)
+            .field("toggle_mode", &self.toggle_mode)
+            .field("toggled", &self.toggled)
+            .field("disabled", &self.disabled)
+            .field("href", &self.href)
             .finish()
     }
 }
@@ -44,6 +65,13 @@ impl Button {
             tool_tip: None,
             on_press: None,
             on_release: None,
+            toggle_mode: false,
+            toggled: false,
+            on_toggle: None,
+            disabled: false,
+            href: None,
+            on_navigate: None,
+            on_open_url: None,
             state: Some(ButtonState::default()),
             dirty: false,
             class: Default::default(),
@@ -74,47 +102,156 @@ impl Button {
         self.tool_tip = Some(t);
         self
     }
+
+    pub fn toggle_mode(mut self, toggled: bool) -> Self {
+        self.toggle_mode = true;
+        self.toggled = toggled;
+        self
+    }
+
+    pub fn on_toggle(mut self, f: Box<dyn Fn(bool) -> Message + Send + Sync>) -> Self {
+        self.on_toggle = Some(f);
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Make this button a link to `href`. Adds the `link-button` class, unless another class has
+    /// already been set.
+    pub fn href<S: Into<String>>(mut self, href: S) -> Self {
+        self.href = Some(href.into());
+        if self.class().is_none() {
+            self = self.with_class("link-button");
+        }
+        self
+    }
+
+    pub fn on_navigate(mut self, f: Box<dyn Fn(&str) -> Message + Send + Sync>) -> Self {
+        self.on_navigate = Some(f);
+        self
+    }
+
+    pub fn on_open_url(mut self, f: Box<dyn Fn(&str) -> Message + Send + Sync>) -> Self {
+        self.on_open_url = Some(f);
+        self
+    }
+
+    /// The message for a click on a link button: `on_open_url` when `new_window`, otherwise
+    /// `on_navigate`.
+    fn follow_link(&self, new_window: bool) -> Option<Message> {
+        let href = self.href.as_ref()?;
+        let f = if new_window {
+            &self.on_open_url
+        } else {
+            &self.on_navigate
+        };
+        f.as_ref().map(|f| f(href))
+    }
+
+    /// Flip a `toggle_mode` button, returning the `on_toggle` message for its new state.
+    fn toggle(&mut self) -> Option<Message> {
+        if !self.toggle_mode {
+            return None;
+        }
+        let toggled = !self.state_ref().toggled;
+        self.state_mut().toggled = toggled;
+        self.on_toggle.as_ref().map(|f| f(toggled))
+    }
+
+    /// A toggled on `toggle_mode` button counts as active.
+    fn component_state(&self) -> ComponentState {
+        let mut state = ComponentState::empty();
+        state.set(ComponentState::HOVER, self.state_ref().hover);
+        state.set(
+            ComponentState::ACTIVE,
+            self.state_ref().pressed || (self.toggle_mode && self.state_ref().toggled),
+        );
+        state.set(ComponentState::DISABLED, self.disabled);
+        state
+    }
+
+    /// The fill for the button's current state. `hover` and `active` entries for
+    /// `background_color` take priority over the `highlight_color` and `active_color`
+    /// parameters.
+    fn background_color(&self, state: ComponentState) -> Color {
+        let param = if state.contains(ComponentState::ACTIVE) {
+            "active_color"
+        } else if state.contains(ComponentState::HOVER) {
+            "highlight_color"
+        } else {
+            "background_color"
+        };
+        self.state_style_val("background_color", state)
+            .or_else(|| self.style_val(param))
+            .into()
+    }
 }
 
 #[state_component_impl(ButtonState)]
 impl Component for Button {
+    fn init(&mut self) {
+        self.state_mut().toggled = self.toggled;
+    }
+
+    fn props_hash(&self, hasher: &mut crate::component::ComponentHasher) {
+        self.toggled.hash(hasher);
+    }
+
+    fn new_props(&mut self) {
+        self.state_mut().toggled = self.toggled;
+    }
+
     fn view(&self) -> Option<Node> {
-        let radius: f32 = self.style_val("radius").unwrap().f32();
+        let radius = self.style_val("radius").unwrap().border_radius();
         let padding: f64 = self.style_val("padding").unwrap().into();
-        let active_color: Color = self.style_val("active_color").into();
-        let highlight_color: Color = self.style_val("highlight_color").into();
         let background_color: Color = self.style_val("background_color").into();
         let border_color: Color = self.style_val("border_color").into();
         let border_width: f32 = self.style_val("border_width").unwrap().f32();
+        // Without a `btn-*` size class, the button fills its parent
+        let size = self
+            .style_val("size")
+            .map(|v| v.size())
+            .unwrap_or(size_pct!(100.0));
+
+        let fill = self.background_color(self.component_state());
+        // A gradient replaces the resting background, but not the highlight and active colors
+        let background_gradient = self
+            .style_val("background_gradient")
+            .filter(|_| fill == background_color)
+            .map(|v| v.gradient());
 
-        let mut base = node!(
-            super::RoundedRect {
-                background_color: if self.state_ref().pressed {
-                    active_color
-                } else if self.state_ref().hover {
-                    highlight_color
-                } else {
-                    background_color
-                },
-                border_color,
-                border_width: (border_width, border_width, border_width, border_width),
-                radius: (radius, radius, radius, radius),
-                ..Default::default()
-            },
-            lay!(
-                size: size_pct!(100.0),
-                padding: rect!(padding),
-                margin: rect!(border_width / 2.0),
-                cross_alignment: crate::layout::Alignment::Center,
-                axis_alignment: crate::layout::Alignment::Center,
-            )
-        )
-        .push(node!(super::Text::new(self.label.clone())
+        let background = super::RoundedRect {
+            background_color: fill,
+            border_color,
+            border_width: (border_width, border_width, border_width, border_width),
+            radius: radius.into(),
+            shadow: self.style_val("shadow").map(|v| v.box_shadow()),
+            ..Default::default()
+        };
+        let layout = lay!(
+            size: size,
+            padding: rect!(padding),
+            margin: rect!(border_width / 2.0),
+            cross_alignment: crate::layout::Alignment::Center,
+            axis_alignment: crate::layout::Alignment::Center,
+        );
+        // Keyed alike, so that the label is kept when the gradient comes and goes
+        let base = match background_gradient {
+            Some(gradient) => node!(background.background_gradient(gradient), layout, 0),
+            None => node!(background, layout, 0),
+        };
+
+        let mut base = base.push(node!(super::Text::new(self.label.clone())
             .style("size", self.style_val("font_size").unwrap())
             .style("color", self.style_val("text_color").unwrap())
             .style("h_alignment", self.style_val("h_alignment").unwrap())
             .maybe_style("font", self.style_val("font"))
             .maybe_style("font_weight", self.style_val("font_weight"))
+            .maybe_style("font_style", self.style_val("font_style"))
+            .maybe_style("text_decoration", self.style_val("text_decoration"))
             .maybe_style("line_height", self.style_val("line_height")),));
 
         // if let (Some(p), Some(tt)) = (self.state_ref().tool_tip_open, self.tool_tip.as_ref()) {
@@ -130,6 +267,30 @@ impl Component for Button {
         Some(base)
     }
 
+    fn aria_role(&self) -> Option<AriaRole> {
+        if self.href.is_some() {
+            Some(AriaRole::Link)
+        } else {
+            Some(AriaRole::Button)
+        }
+    }
+
+    fn aria_label(&self) -> Option<String> {
+        Some(self.label.iter().map(|s| s.text.as_str()).collect())
+    }
+
+    fn aria_pressed(&self) -> Option<bool> {
+        self.toggle_mode.then(|| self.state_ref().toggled)
+    }
+
+    fn cursor(&self) -> Option<CursorStyle> {
+        if self.disabled {
+            Some(CursorStyle::NotAllowed)
+        } else {
+            self.style_val("cursor").map(|v| v.cursor())
+        }
+    }
+
     fn on_mouse_motion(&mut self, event: &mut event::Event<event::MouseMotion>) {
         let dirty = self.dirty;
         self.state_mut().hover_start = Some(Instant::now());
@@ -184,6 +345,9 @@ impl Component for Button {
     }
 
     fn on_mouse_down(&mut self, event: &mut event::Event<event::MouseDown>) {
+        if self.disabled {
+            return;
+        }
         self.state_mut().pressed = true;
         if let Some(f) = &self.on_press {
             event.emit(f());
@@ -191,6 +355,9 @@ impl Component for Button {
     }
 
     fn on_mouse_up(&mut self, event: &mut event::Event<event::MouseUp>) {
+        if self.disabled {
+            return;
+        }
         self.state_mut().pressed = false;
         if let Some(f) = &self.on_release {
             event.emit(f());
@@ -198,12 +365,25 @@ impl Component for Button {
     }
 
     fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        if self.disabled {
+            return;
+        }
+        if let Some(m) = self.toggle() {
+            event.emit(m);
+        }
         if let Some(f) = &self.on_click {
             event.emit(f());
         }
+        let held = event.modifiers_held;
+        if let Some(m) = self.follow_link(held.ctrl || held.meta) {
+            event.emit(m);
+        }
     }
 
     fn on_touch_down(&mut self, event: &mut event::Event<event::TouchDown>) {
+        if self.disabled {
+            return;
+        }
         self.state_mut().pressed = true;
         if let Some(f) = &self.on_press {
             event.emit(f());
@@ -211,6 +391,9 @@ impl Component for Button {
     }
 
     fn on_touch_up(&mut self, event: &mut event::Event<event::TouchUp>) {
+        if self.disabled {
+            return;
+        }
         self.state_mut().pressed = false;
         if let Some(f) = &self.on_release {
             event.emit(f());
@@ -218,8 +401,86 @@ impl Component for Button {
     }
 
     fn on_double_click(&mut self, event: &mut event::Event<event::DoubleClick>) {
+        if self.disabled {
+            return;
+        }
         if let Some(f) = &self.on_double_click {
             event.emit(f());
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{msg, size, txt};
+
+    #[test]
+    fn test_toggle_mode() {
+        let mut button = Button::new(txt!("Bold"))
+            .toggle_mode(false)
+            .on_toggle(Box::new(|on| msg!(on)));
+        button.init();
+        assert_eq!(button.component_state(), ComponentState::empty());
+        assert_eq!(button.aria_pressed(), Some(false));
+
+        let m = button.toggle().unwrap();
+        assert_eq!(m.downcast_ref::<bool>(), Some(&true));
+        assert_eq!(button.component_state(), ComponentState::ACTIVE);
+        assert_eq!(button.aria_pressed(), Some(true));
+
+        let m = button.toggle().unwrap();
+        assert_eq!(m.downcast_ref::<bool>(), Some(&false));
+    }
+
+    #[test]
+    fn test_toggle_mode_off() {
+        let mut button = Button::new(txt!("Ok")).on_toggle(Box::new(|on| msg!(on)));
+        assert!(button.toggle().is_none());
+        assert_eq!(button.aria_pressed(), None);
+    }
+
+    #[test]
+    fn test_size_classes() {
+        let button = Button::new(txt!("Ok"));
+        assert!(button.style_val("size").is_none());
+
+        let button = Button::new(txt!("Ok")).with_class("btn-md");
+        assert_eq!(button.style_val("size").unwrap().size(), size!(96., 40.));
+
+        let button = Button::new(txt!("Ok")).with_class("rounded-lg");
+        assert_eq!(button.style_val("radius").unwrap().f32(), 8.0);
+    }
+
+    #[test]
+    fn test_cursor() {
+        let button = Button::new(txt!("Ok"));
+        assert_eq!(button.cursor(), Some(CursorStyle::Pointer));
+
+        let button = Button::new(txt!("Ok")).disabled(true);
+        assert_eq!(button.cursor(), Some(CursorStyle::NotAllowed));
+    }
+
+    #[test]
+    fn test_link() {
+        let button = Button::new(txt!("Ok"));
+        assert_eq!(button.aria_role(), Some(AriaRole::Button));
+        assert!(button.follow_link(false).is_none());
+
+        let link = Button::new(txt!("Docs"))
+            .href("/docs")
+            .on_navigate(Box::new(|href| msg!(format!("navigate {href}"))))
+            .on_open_url(Box::new(|href| msg!(format!("open {href}"))));
+        assert_eq!(link.aria_role(), Some(AriaRole::Link));
+        assert_eq!(link.class(), Some("link-button"));
+        assert_eq!(
+            link.style_val("background_color"),
+            Some(Color::TRANSPARENT.into())
+        );
+
+        let m = link.follow_link(false).unwrap();
+        assert_eq!(m.downcast_ref::<String>().unwrap(), "navigate /docs");
+        let m = link.follow_link(true).unwrap();
+        assert_eq!(m.downcast_ref::<String>().unwrap(), "open /docs");
+    }
+}
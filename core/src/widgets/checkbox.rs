@@ -0,0 +1,218 @@
+use std::fmt;
+use std::hash::Hash;
+
+use mctk_macros::component;
+
+use crate::component::{Component, ComponentHasher, Message, RenderContext};
+use crate::layout::{Alignment, Direction};
+use crate::renderables::{
+    rect::InstanceBuilder as RectInstanceBuilder, Polyline, Rect, Renderable,
+};
+use crate::style::{CursorStyle, Styled};
+use crate::types::*;
+use crate::{event, lay, node, rect, size, txt, Key, Node};
+
+use super::{Div, Text};
+
+/// A box that is checked or unchecked, with an optional label to its right.
+///
+/// An `indeterminate` checkbox is shown with a dash, as neither checked nor unchecked, and
+/// becomes checked when it is toggled. Clicking the checkbox or pressing Space while it has the
+/// focus calls `on_change` with the new value.
+#[component(Styled, Internal)]
+pub struct Checkbox {
+    pub checked: bool,
+    pub indeterminate: bool,
+    pub label: Option<String>,
+    pub on_change: Option<Box<dyn Fn(bool) -> Message + Send + Sync>>,
+}
+
+impl fmt::Debug for Checkbox {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Checkbox")
+            .field("checked", &self.checked)
+            .field("indeterminate", &self.indeterminate)
+            .field("label", &self.label)
+            .finish()
+    }
+}
+
+impl Checkbox {
+    pub fn new(checked: bool) -> Self {
+        Self {
+            checked,
+            indeterminate: false,
+            label: None,
+            on_change: None,
+            class: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    pub fn indeterminate(mut self, indeterminate: bool) -> Self {
+        self.indeterminate = indeterminate;
+        self
+    }
+
+    pub fn label<S: Into<String>>(mut self, label: S) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn on_change(mut self, change_fn: Box<dyn Fn(bool) -> Message + Send + Sync>) -> Self {
+        self.on_change = Some(change_fn);
+        self
+    }
+
+    fn toggle(&self) -> Option<Message> {
+        let checked = self.indeterminate || !self.checked;
+        self.on_change.as_ref().map(|f| f(checked))
+    }
+
+    /// The mark inside a box of `size` at `pos`: a tick when checked, or a dash when
+    /// indeterminate.
+    fn mark(&self, pos: Pos, size: f32, color: Color) -> Option<Renderable> {
+        let stroke = (size / 8.0).max(1.5);
+        if self.indeterminate {
+            let width = size / 2.0;
+            Some(Renderable::Rect(Rect::new(
+                Pos::new(
+                    pos.x + (size - width) / 2.0,
+                    pos.y + (size - stroke) / 2.0,
+                    pos.z,
+                ),
+                Scale::new(width, stroke),
+                color,
+            )))
+        } else if self.checked {
+            let point = |x: f32, y: f32| Point::new(pos.x + size * x, pos.y + size * y);
+            Some(Renderable::Polyline(Polyline::new(
+                vec![point(0.25, 0.5), point(0.42, 0.68), point(0.75, 0.32)],
+                color,
+                stroke,
+            )))
+        } else {
+            None
+        }
+    }
+}
+
+impl Component for Checkbox {
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.label.hash(hasher);
+    }
+
+    fn render_hash(&self, hasher: &mut ComponentHasher) {
+        self.checked.hash(hasher);
+        self.indeterminate.hash(hasher);
+    }
+
+    fn cursor(&self) -> Option<CursorStyle> {
+        self.style_val("cursor").map(|v| v.cursor())
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        event.stop_bubbling();
+        // Take the focus, so Space toggles the checkbox
+        event.focus();
+        if let Some(m) = self.toggle() {
+            event.emit(m);
+        }
+    }
+
+    fn on_key_down(&mut self, event: &mut event::Event<event::KeyDown>) {
+        if event.input.0 == Key::Space {
+            event.stop_bubbling();
+            if let Some(m) = self.toggle() {
+                event.emit(m);
+            }
+        }
+    }
+
+    fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
+        let size: f32 = self.style_val("size").unwrap().f32();
+        let border_width: f32 = self.style_val("border_width").unwrap().f32();
+        let radius: f32 = self.style_val("radius").unwrap().f32();
+        let AABB { pos, .. } = context.aabb;
+        // The box is at the left, centered vertically against the label
+        let pos = Pos::new(pos.x, pos.y + (context.aabb.height() - size) / 2.0, pos.z);
+
+        let mut rs = vec![];
+        let instance_data = RectInstanceBuilder::default()
+            .pos(pos)
+            .scale(Scale::new(size, size))
+            .color(self.style_val("background_color").into())
+            .border_color(self.style_val("border_color").into())
+            .border_size((border_width, border_width, border_width, border_width))
+            .radius((radius, radius, radius, radius))
+            .build()
+            .unwrap();
+        rs.push(Renderable::Rect(Rect::from_instance_data(instance_data)));
+        if let Some(mark) = self.mark(pos, size, self.style_val("check_color").into()) {
+            rs.push(mark);
+        }
+        Some(rs)
+    }
+
+    fn view(&self) -> Option<Node> {
+        let size: f32 = self.style_val("size").unwrap().f32();
+        let label_spacing: f32 = self.style_val("label_spacing").unwrap().f32();
+        let mut base = node!(
+            Div::new(),
+            lay![
+                direction: Direction::Row,
+                cross_alignment: Alignment::Center,
+                min_size: [size, size],
+                padding: [0.0, size + label_spacing, 0.0, 0.0]
+            ]
+        );
+        if let Some(label) = &self.label {
+            base = base.push(node!(Text::new(txt!(label.clone()))
+                .style("size", self.style_val("font_size").unwrap())
+                .style("color", self.style_val("text_color").unwrap())
+                .maybe_style("font", self.style_val("font"))));
+        }
+        Some(base)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg;
+
+    fn checkbox(checked: bool) -> Checkbox {
+        Checkbox::new(checked).on_change(Box::new(|checked| msg!(checked)))
+    }
+
+    #[test]
+    fn test_toggle() {
+        let toggled = |c: Checkbox| *c.toggle().unwrap().downcast_ref::<bool>().unwrap();
+        assert!(toggled(checkbox(false)));
+        assert!(!toggled(checkbox(true)));
+        // Indeterminate always moves to checked
+        assert!(toggled(checkbox(false).indeterminate(true)));
+        assert!(toggled(checkbox(true).indeterminate(true)));
+    }
+
+    #[test]
+    fn test_mark() {
+        let pos = Pos::new(0.0, 0.0, 0.0);
+        assert!(checkbox(false).mark(pos, 16.0, Color::BLACK).is_none());
+        assert!(matches!(
+            checkbox(true).mark(pos, 16.0, Color::BLACK),
+            Some(Renderable::Polyline(_))
+        ));
+        // The dash takes precedence over the tick
+        assert!(matches!(
+            checkbox(true)
+                .indeterminate(true)
+                .mark(pos, 16.0, Color::BLACK),
+            Some(Renderable::Rect(_))
+        ));
+    }
+}
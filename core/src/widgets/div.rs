@@ -1,7 +1,10 @@
 use std::hash::Hash;
 use std::ops::Neg;
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
 
-use crate::component::{Component, ComponentHasher, RenderContext};
+use crate::animation::{EaseOutAnimator, SpringAnimator};
+use crate::component::{Component, ComponentHasher, Message, RenderContext};
 use crate::event;
 use crate::layout::*;
 use crate::renderables::rect::InstanceBuilder;
@@ -12,6 +15,13 @@ use crate::types::*;
 use mctk_macros::{component, state_component_impl};
 
 const MIN_BAR_SIZE: f32 = 10.0;
+/// How far past its ends content can be over-scrolled, as a fraction of the viewport size.
+const OVER_SCROLL_MAX: f32 = 0.3;
+/// How much of a drag past the ends of the content moves it.
+const OVER_SCROLL_RESISTANCE: f32 = 0.5;
+/// How close a snap point has to be for [`ScrollSnap::Proximity`] to snap to it, as a fraction of
+/// the viewport size.
+const SNAP_PROXIMITY: f32 = 0.25;
 
 #[derive(Debug, Default)]
 pub struct DivState {
@@ -23,16 +33,199 @@ pub struct DivState {
     over_x_bar: bool,
     x_bar_pressed: bool,
     drag_start_position: Point,
+    dragging: bool,
     scaled_scroll_bar_width: f32,
+    scaled_content_padding: Padding,
+    /// The furthest in-bounds scroll position, as of the last render or drag.
+    max_scroll_position: Point,
+    x_spring: Option<SpringAnimator>,
+    y_spring: Option<SpringAnimator>,
+    x_tween: Option<EaseOutAnimator>,
+    y_tween: Option<EaseOutAnimator>,
+    last_tick: Option<Instant>,
+    /// The size of the viewport, as of the last layout.
+    viewport: Scale,
+    /// Where the children are within the content, as of the last layout.
+    children: Vec<AABB>,
+    scaled_snap_padding: f32,
+}
+
+/// One of the ends of a [`Div`]'s scrollable content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollEdge {
+    Top,
+    Bottom,
+    /// The left end.
+    Start,
+    /// The right end.
+    End,
+}
+
+/// Whether a [`Div`]'s scroll position settles on the edges of its children, like a carousel.
+///
+/// Snap points are the left and top edges of the direct children, less the `snap_padding` style
+/// parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScrollSnap {
+    /// Use the `snap` style parameter, which the `snap` class sets to `"mandatory"`.
+    #[default]
+    None,
+    /// Like `Mandatory`, and each wheel scroll moves to the next snap point.
+    Always,
+    /// When a drag is released, animate to the nearest snap point.
+    Mandatory,
+    /// When a drag is released near a snap point, animate to it.
+    Proximity,
+}
+
+impl ScrollSnap {
+    fn from_style(name: &str) -> Self {
+        match name {
+            "always" => ScrollSnap::Always,
+            "mandatory" => ScrollSnap::Mandatory,
+            "proximity" => ScrollSnap::Proximity,
+            _ => ScrollSnap::None,
+        }
+    }
+}
+
+/// Where a [`ScrollController`] was asked to scroll to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ScrollRequest {
+    Edge(ScrollEdge),
+    Offset(f32),
+    Child(usize),
+}
+
+/// How far a [`Div`]'s content is scrolled, in physical pixels from its top left.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScrollOffset {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl From<Point> for ScrollOffset {
+    fn from(p: Point) -> Self {
+        Self { x: p.x, y: p.y }
+    }
+}
+
+impl From<ScrollOffset> for Point {
+    fn from(offset: ScrollOffset) -> Self {
+        Point::new(offset.x, offset.y)
+    }
+}
+
+/// A handle for scrolling a [`Div`] from outside of it, e.g. from its parent's `update`.
+/// Keep one in the parent's state and give the `Div` a clone with [`Div::controller`].
+/// Requests are applied on the `Div`'s next tick.
+#[derive(Debug, Clone, Default)]
+pub struct ScrollController(Arc<RwLock<Option<(ScrollRequest, bool)>>>);
+
+impl ScrollController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn scroll_to_top(&self) {
+        self.request(ScrollRequest::Edge(ScrollEdge::Top), false);
+    }
+
+    pub fn scroll_to_bottom(&self) {
+        self.request(ScrollRequest::Edge(ScrollEdge::Bottom), false);
+    }
+
+    pub fn scroll_to_start(&self) {
+        self.request(ScrollRequest::Edge(ScrollEdge::Start), false);
+    }
+
+    pub fn scroll_to_end(&self) {
+        self.request(ScrollRequest::Edge(ScrollEdge::End), false);
+    }
+
+    pub fn scroll_to_top_animated(&self) {
+        self.request(ScrollRequest::Edge(ScrollEdge::Top), true);
+    }
+
+    pub fn scroll_to_bottom_animated(&self) {
+        self.request(ScrollRequest::Edge(ScrollEdge::Bottom), true);
+    }
+
+    pub fn scroll_to_start_animated(&self) {
+        self.request(ScrollRequest::Edge(ScrollEdge::Start), true);
+    }
+
+    pub fn scroll_to_end_animated(&self) {
+        self.request(ScrollRequest::Edge(ScrollEdge::End), true);
+    }
+
+    /// See [`Div::scroll_to`].
+    pub fn scroll_to(&self, offset_px: f32, animated: bool) {
+        self.request(ScrollRequest::Offset(offset_px), animated);
+    }
+
+    /// See [`Div::scroll_into_view`].
+    pub fn scroll_into_view(&self, child: usize, animated: bool) {
+        self.request(ScrollRequest::Child(child), animated);
+    }
+
+    fn request(&self, request: ScrollRequest, animated: bool) {
+        *self.0.write().unwrap() = Some((request, animated));
+    }
+
+    fn take(&self) -> Option<(ScrollRequest, bool)> {
+        self.0.write().unwrap().take()
+    }
 }
 
 #[component(State = "DivState", Styled = "Scroll", Internal)]
-#[derive(Debug, Default)]
+#[derive(Default)]
 pub struct Div {
     pub background: Option<Color>,
     pub border_color: Option<Color>,
     pub border_width: Option<f32>,
     pub radius: Option<(f32, f32, f32, f32)>,
+    /// Insets the scrolled content, but not the scroll bars, which stay at the outer edge.
+    pub content_padding: Option<Padding>,
+    /// Let content be dragged past its ends, springing back when it is released.
+    pub over_scroll: bool,
+    pub snap: ScrollSnap,
+    /// Called with the scroll position whenever it changes, including while over-scrolled.
+    pub on_scroll: Option<Box<dyn Fn(Point) -> Message + Send + Sync>>,
+    pub controller: Option<ScrollController>,
+    /// When set, the scroll position is controlled by the parent: scrolling only calls
+    /// `on_scroll`, and the parent is expected to pass the new offset back on its next `view`.
+    pub scroll_offset: Option<ScrollOffset>,
+}
+
+impl std::fmt::Debug for Div {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Div")
+            .field("background", &self.background)
+            .field("border_color", &self.border_color)
+            .field("border_width", &self.border_width)
+            .field("radius", &self.radius)
+            .field("content_padding", &self.content_padding)
+            .field("over_scroll", &self.over_scroll)
+            .field("snap", &self.snap)
+            .field("controller", &self.controller)
+            .field("scroll_offset", &self.scroll_offset)
+            .finish()
+    }
+}
+
+/// `position` clamped to `0.0..=max`, or when over-scrolling, allowed past either end with
+/// resistance, up to `limit`.
+fn over_scroll_position(position: f32, max: f32, limit: Option<f32>) -> f32 {
+    let max = max.max(0.0);
+    match limit {
+        Some(limit) if position < 0.0 => -(position.neg() * OVER_SCROLL_RESISTANCE).min(limit),
+        Some(limit) if position > max => {
+            max + ((position - max) * OVER_SCROLL_RESISTANCE).min(limit)
+        }
+        _ => position.min(max).max(0.0),
+    }
+    .round()
 }
 
 impl Div {
@@ -69,6 +262,206 @@ impl Div {
         self
     }
 
+    pub fn content_padding(mut self, padding: Padding) -> Self {
+        self.content_padding = Some(padding);
+        self
+    }
+
+    pub fn over_scroll(mut self, over_scroll: bool) -> Self {
+        self.over_scroll = over_scroll;
+        self
+    }
+
+    pub fn snap(mut self, snap: ScrollSnap) -> Self {
+        self.snap = snap;
+        self
+    }
+
+    pub fn on_scroll(mut self, f: Box<dyn Fn(Point) -> Message + Send + Sync>) -> Self {
+        self.on_scroll = Some(f);
+        self
+    }
+
+    pub fn controller(mut self, controller: ScrollController) -> Self {
+        self.controller = Some(controller);
+        self
+    }
+
+    /// Control the scroll position from the parent. See the `scroll_offset` field.
+    pub fn with_scroll_offset(mut self, offset: ScrollOffset) -> Self {
+        self.scroll_offset = Some(offset);
+        self
+    }
+
+    /// How far the content is currently scrolled.
+    pub fn current_scroll_offset(&self) -> ScrollOffset {
+        self.state
+            .as_ref()
+            .map(|s| s.scroll_position.into())
+            .unwrap_or_default()
+    }
+
+    /// How far the content is scrolled along its scrolling axis, vertically if it scrolls both
+    /// ways, in physical pixels.
+    pub fn scroll_offset(&self) -> f32 {
+        let offset = self.current_scroll_offset();
+        if self.x_scrollable() && !self.y_scrollable() {
+            offset.x
+        } else {
+            offset.y
+        }
+    }
+
+    /// Scroll to `offset_px` physical pixels along the scrolling axis, vertically if the content
+    /// scrolls both ways. When `animated`, the scroll eases out over the `animation_duration_ms`
+    /// style parameter.
+    pub fn scroll_to(&mut self, offset_px: f32, animated: bool) {
+        if self.state.is_none() {
+            return;
+        }
+        let vertical = !(self.x_scrollable() && !self.y_scrollable());
+        let max = self.state_ref().max_scroll_position;
+        let max = if vertical { max.y } else { max.x };
+        self.scroll_axis_to(vertical, offset_px.min(max).max(0.0), animated);
+    }
+
+    /// Scroll as little as needed for the child at index `child` to be in view, as of the last
+    /// layout. A child bigger than the viewport is scrolled to its start.
+    ///
+    /// Children are addressed by index, since their Node ids are only assigned once they are
+    /// viewed. Through a [`ScrollController`], a child that hasn't been laid out yet is scrolled
+    /// to once it has been.
+    pub fn scroll_into_view(&mut self, child: usize, animated: bool) {
+        if self.state.is_none() {
+            return;
+        }
+        let child = match self.state_ref().children.get(child) {
+            Some(child) => *child,
+            None => return,
+        };
+        let viewport = self.state_ref().viewport;
+        let position = self.state_ref().scroll_position;
+        // The position that shows `start..end` in a viewport of `size`, closest to `current`
+        let into_view = |current: f32, start: f32, end: f32, size: f32| {
+            if start < current || end - start > size {
+                start
+            } else if end > current + size {
+                end - size
+            } else {
+                current
+            }
+        };
+
+        if self.y_scrollable() {
+            let y = into_view(
+                position.y,
+                child.pos.y,
+                child.bottom_right.y,
+                viewport.height,
+            );
+            if y != position.y {
+                self.scroll_axis_to(true, y, animated);
+            }
+        }
+        if self.x_scrollable() {
+            let x = into_view(
+                position.x,
+                child.pos.x,
+                child.bottom_right.x,
+                viewport.width,
+            );
+            if x != position.x {
+                self.scroll_axis_to(false, x, animated);
+            }
+        }
+    }
+
+    /// Jump to the top of the content.
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_to_edge(ScrollEdge::Top, false);
+    }
+
+    /// Jump to the bottom of the content.
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll_to_edge(ScrollEdge::Bottom, false);
+    }
+
+    /// Jump to the left end of the content.
+    pub fn scroll_to_start(&mut self) {
+        self.scroll_to_edge(ScrollEdge::Start, false);
+    }
+
+    /// Jump to the right end of the content.
+    pub fn scroll_to_end(&mut self) {
+        self.scroll_to_edge(ScrollEdge::End, false);
+    }
+
+    /// Scroll to the top of the content, easing out over the next few ticks.
+    pub fn scroll_to_top_animated(&mut self) {
+        self.scroll_to_edge(ScrollEdge::Top, true);
+    }
+
+    /// Scroll to the bottom of the content, easing out over the next few ticks.
+    pub fn scroll_to_bottom_animated(&mut self) {
+        self.scroll_to_edge(ScrollEdge::Bottom, true);
+    }
+
+    /// Scroll to the left end of the content, easing out over the next few ticks.
+    pub fn scroll_to_start_animated(&mut self) {
+        self.scroll_to_edge(ScrollEdge::Start, true);
+    }
+
+    /// Scroll to the right end of the content, easing out over the next few ticks.
+    pub fn scroll_to_end_animated(&mut self) {
+        self.scroll_to_edge(ScrollEdge::End, true);
+    }
+
+    fn scroll_to_edge(&mut self, edge: ScrollEdge, animated: bool) {
+        if self.state.is_none() {
+            return;
+        }
+        let max = self.state_ref().max_scroll_position;
+        match edge {
+            ScrollEdge::Top => self.scroll_axis_to(true, 0.0, animated),
+            ScrollEdge::Bottom => self.scroll_axis_to(true, max.y, animated),
+            ScrollEdge::Start => self.scroll_axis_to(false, 0.0, animated),
+            ScrollEdge::End => self.scroll_axis_to(false, max.x, animated),
+        }
+    }
+
+    /// Scroll the vertical or horizontal position to `target`.
+    fn scroll_axis_to(&mut self, vertical: bool, target: f32, animated: bool) {
+        let duration = self
+            .style_val("animation_duration_ms")
+            .map_or(0.0, |v| v.f32() / 1000.0);
+        let position = self.state_ref().scroll_position;
+        let state = self.state_mut();
+        let (current, spring, tween, scroll_position) = if vertical {
+            (
+                position.y,
+                &mut state.y_spring,
+                &mut state.y_tween,
+                &mut state.scroll_position.y,
+            )
+        } else {
+            (
+                position.x,
+                &mut state.x_spring,
+                &mut state.x_tween,
+                &mut state.scroll_position.x,
+            )
+        };
+        let target = target.round();
+        *spring = None;
+        *tween = None;
+        if animated && duration > 0.0 {
+            *tween = Some(EaseOutAnimator::new(current, target, duration));
+        } else {
+            *scroll_position = target;
+        }
+        state.last_tick = None;
+    }
+
     fn x_scrollable(&self) -> bool {
         self.style_val("x").unwrap().into()
     }
@@ -81,6 +474,15 @@ impl Div {
         self.x_scrollable() || self.y_scrollable()
     }
 
+    /// The size of the area that content is scrolled through: `size` less the content padding.
+    fn viewport_size(&self, size: Scale) -> Scale {
+        let padding = self.state_ref().scaled_content_padding;
+        Scale {
+            width: size.width - padding.left - padding.right,
+            height: size.height - padding.top - padding.bottom,
+        }
+    }
+
     fn handle_drag_start(&mut self) {
         let x_bar_pressed = self.state_ref().over_x_bar;
         let y_bar_pressed = self.state_ref().over_y_bar;
@@ -88,6 +490,11 @@ impl Div {
         self.state_mut().x_bar_pressed = x_bar_pressed;
         self.state_mut().y_bar_pressed = y_bar_pressed;
         self.state_mut().drag_start_position = drag_start;
+        self.state_mut().dragging = true;
+        self.state_mut().x_spring = None;
+        self.state_mut().y_spring = None;
+        self.state_mut().x_tween = None;
+        self.state_mut().y_tween = None;
     }
 
     fn handle_on_drag(
@@ -98,7 +505,7 @@ impl Div {
     ) {
         if self.scrollable() {
             let start_position = self.state_ref().drag_start_position;
-            let size = current_physical_aabb.size();
+            let size = self.viewport_size(current_physical_aabb.size());
             let inner_scale = current_inner_scale.unwrap();
             let mut scroll_position = self.state_ref().scroll_position;
             println!(
@@ -131,20 +538,20 @@ impl Div {
                 println!("Div::handle_on_drag() drag {:?}", drag);
                 let delta_position = drag;
                 let max_position = inner_scale.height - size.height;
-                scroll_position.y = (start_position.y + delta_position)
-                    .round()
-                    .min(max_position)
-                    .max(0.0);
+                let limit = self.over_scroll.then_some(size.height * OVER_SCROLL_MAX);
+                scroll_position.y =
+                    over_scroll_position(start_position.y + delta_position, max_position, limit);
+                self.state_mut().max_scroll_position.y = max_position.max(0.0);
             }
 
             if self.x_scrollable() && !self.state_ref().x_bar_pressed {
                 let drag = physical_delta.x.neg();
                 let delta_position = drag;
                 let max_position = inner_scale.width - size.width;
-                scroll_position.x = (start_position.x + delta_position)
-                    .round()
-                    .min(max_position)
-                    .max(0.0);
+                let limit = self.over_scroll.then_some(size.width * OVER_SCROLL_MAX);
+                scroll_position.x =
+                    over_scroll_position(start_position.x + delta_position, max_position, limit);
+                self.state_mut().max_scroll_position.x = max_position.max(0.0);
             }
 
             self.state_mut().scroll_position = scroll_position;
@@ -155,12 +562,213 @@ impl Div {
         if self.scrollable() {
             self.state_mut().x_bar_pressed = false;
             self.state_mut().y_bar_pressed = false;
+            self.state_mut().dragging = false;
+
+            // Spring back to the nearest end of the content
+            let position = self.state_ref().scroll_position;
+            let max = self.state_ref().max_scroll_position;
+            let spring = |p: f32, max: f32| {
+                let target = p.min(max).max(0.0);
+                (p != target).then(|| SpringAnimator::preset_bounce().start(p, target))
+            };
+            self.state_mut().x_spring = spring(position.x, max.x);
+            self.state_mut().y_spring = spring(position.y, max.y);
+            self.state_mut().last_tick = None;
+
+            // Otherwise settle on a snap point
+            if self.x_scrollable() && self.state_ref().x_spring.is_none() {
+                self.snap_axis(false);
+            }
+            if self.y_scrollable() && self.state_ref().y_spring.is_none() {
+                self.snap_axis(true);
+            }
+        }
+    }
+
+    /// The `snap` field, or else the `snap` style parameter.
+    fn snap_mode(&self) -> ScrollSnap {
+        match self.snap {
+            ScrollSnap::None => self
+                .style_val("snap")
+                .map_or(ScrollSnap::None, |v| ScrollSnap::from_style(v.str())),
+            snap => snap,
+        }
+    }
+
+    /// The scroll positions that line the children up with the start of the viewport, in order.
+    fn snap_points(&self, vertical: bool) -> Vec<f32> {
+        let state = self.state_ref();
+        let max = if vertical {
+            state.max_scroll_position.y
+        } else {
+            state.max_scroll_position.x
+        };
+        let mut points: Vec<f32> = state
+            .children
+            .iter()
+            .map(|c| if vertical { c.pos.y } else { c.pos.x })
+            .map(|p| (p - state.scaled_snap_padding).min(max).max(0.0).round())
+            .collect();
+        points.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        points.dedup();
+        points
+    }
+
+    /// Animate to the nearest snap point, if the snap mode calls for it.
+    fn snap_axis(&mut self, vertical: bool) {
+        let snap = self.snap_mode();
+        if snap == ScrollSnap::None {
+            return;
+        }
+        let position = self.state_ref().scroll_position;
+        let position = if vertical { position.y } else { position.x };
+        let nearest = self.snap_points(vertical).into_iter().min_by(|a, b| {
+            (a - position)
+                .abs()
+                .partial_cmp(&(b - position).abs())
+                .unwrap()
+        });
+        if let Some(target) = nearest {
+            let viewport = self.state_ref().viewport;
+            let viewport = if vertical {
+                viewport.height
+            } else {
+                viewport.width
+            };
+            let near = (target - position).abs() <= viewport * SNAP_PROXIMITY;
+            if target != position && (snap != ScrollSnap::Proximity || near) {
+                self.scroll_axis_to(vertical, target, true);
+            }
+        }
+    }
+
+    /// With [`ScrollSnap::Always`], animate to the next snap point in the direction of `delta`.
+    /// Returns whether it did.
+    fn snap_step(&mut self, delta: Point) -> bool {
+        if self.snap_mode() != ScrollSnap::Always {
+            return false;
+        }
+        let mut stepped = false;
+        for vertical in [false, true] {
+            let scrollable = if vertical {
+                self.y_scrollable()
+            } else {
+                self.x_scrollable()
+            };
+            let delta = if vertical { delta.y } else { delta.x };
+            if !scrollable || delta == 0.0 {
+                continue;
+            }
+            // Steps made while still moving go on from where the last one is headed
+            let state = self.state_ref();
+            let (position, tween) = if vertical {
+                (state.scroll_position.y, &state.y_tween)
+            } else {
+                (state.scroll_position.x, &state.x_tween)
+            };
+            let from = tween.as_ref().map_or(position, |t| t.to);
+            let points = self.snap_points(vertical);
+            let next = if delta > 0.0 {
+                points.into_iter().find(|p| *p > from)
+            } else {
+                points.into_iter().rev().find(|p| *p < from)
+            };
+            if let Some(target) = next {
+                self.scroll_axis_to(vertical, target, true);
+                stepped = true;
+            }
+        }
+        stepped
+    }
+
+    /// Advance any over-scroll spring back by `dt` seconds. Returns whether the scroll position
+    /// changed.
+    fn step_over_scroll(&mut self, dt: f32) -> bool {
+        let state = self.state_mut();
+        let mut scroll_position = state.scroll_position;
+        if let Some(spring) = state.x_spring.as_mut() {
+            scroll_position.x = spring.step(dt);
+            if spring.is_settled() {
+                state.x_spring = None;
+            }
+        }
+        if let Some(spring) = state.y_spring.as_mut() {
+            scroll_position.y = spring.step(dt);
+            if spring.is_settled() {
+                state.y_spring = None;
+            }
+        }
+
+        let changed = scroll_position != state.scroll_position;
+        if changed {
+            state.scroll_position = scroll_position;
+        }
+        changed
+    }
+
+    /// Advance any animated scroll to an end by `dt` seconds.
+    fn step_scroll_to(&mut self, dt: f32) {
+        let state = self.state_mut();
+        if let Some(tween) = state.x_tween.as_mut() {
+            state.scroll_position.x = tween.step(dt).round();
+            if tween.is_finished() {
+                state.x_tween = None;
+            }
+        }
+        if let Some(tween) = state.y_tween.as_mut() {
+            state.scroll_position.y = tween.step(dt).round();
+            if tween.is_finished() {
+                state.y_tween = None;
+            }
+        }
+    }
+
+    fn is_animating(&self) -> bool {
+        let state = self.state_ref();
+        state.x_spring.is_some()
+            || state.y_spring.is_some()
+            || state.x_tween.is_some()
+            || state.y_tween.is_some()
+    }
+
+    fn scroll_message(&self, previous: Point) -> Option<Message> {
+        let position = self.state_ref().scroll_position;
+        self.on_scroll
+            .as_ref()
+            .filter(|_| position != previous)
+            .map(|f| f(position))
+    }
+
+    /// The `on_scroll` message for a change of the scroll position from `previous`. When the
+    /// position is controlled, the change is undone, so that only the parent can make it.
+    fn scrolled(&mut self, previous: Point) -> Option<Message> {
+        let m = self.scroll_message(previous);
+        self.apply_scroll_offset();
+        m
+    }
+
+    /// Set the scroll position to the controlled `scroll_offset`, if there is one.
+    fn apply_scroll_offset(&mut self) {
+        if let (Some(offset), Some(state)) = (self.scroll_offset, self.state.as_mut()) {
+            state.scroll_position = offset.into();
         }
     }
 }
 
 #[state_component_impl(DivState)]
 impl Component for Div {
+    fn init(&mut self) {
+        self.apply_scroll_offset();
+    }
+
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.scroll_offset.map(Point::from).hash(hasher);
+    }
+
+    fn new_props(&mut self) {
+        self.apply_scroll_offset();
+    }
+
     fn render_hash(&self, hasher: &mut ComponentHasher) {
         if self.state.is_some() {
             self.state_ref().scroll_position.hash(hasher);
@@ -175,11 +783,51 @@ impl Component for Div {
         // Maybe TODO: Should hash scroll_descriptor
     }
 
+    fn on_tick(&mut self, event: &mut event::Event<event::Tick>) {
+        if self.state.is_none() {
+            return;
+        }
+        let previous = self.state_ref().scroll_position;
+        if let Some((request, animated)) = self.controller.as_ref().and_then(|c| c.take()) {
+            match request {
+                ScrollRequest::Edge(edge) => self.scroll_to_edge(edge, animated),
+                ScrollRequest::Offset(offset) => self.scroll_to(offset, animated),
+                ScrollRequest::Child(child) if child < self.state_ref().children.len() => {
+                    self.scroll_into_view(child, animated)
+                }
+                // Not laid out yet, e.g. just added, so wait for it
+                ScrollRequest::Child(_) => {
+                    self.controller.as_ref().unwrap().request(request, animated)
+                }
+            }
+        }
+
+        if self.is_animating() {
+            let now = Instant::now();
+            let dt = self
+                .state_ref()
+                .last_tick
+                .map(|t| now.duration_since(t).as_secs_f32())
+                .unwrap_or(0.0);
+            self.state_mut().last_tick = Some(now);
+            self.step_over_scroll(dt);
+            self.step_scroll_to(dt);
+        }
+
+        if let Some(m) = self.scrolled(previous) {
+            event.emit(m);
+        }
+    }
+
     fn on_scroll(&mut self, event: &mut event::Event<event::Scroll>) {
+        if self.scrollable() && self.snap_step(Point::new(event.input.x, event.input.y)) {
+            event.stop_bubbling();
+            return;
+        }
         if self.scrollable() {
             let mut scroll_position = self.state_ref().scroll_position;
             let mut scrolled = false;
-            let size = event.current_physical_aabb().size();
+            let size = self.viewport_size(event.current_physical_aabb().size());
             let inner_scale = event.current_inner_scale().unwrap();
 
             if self.y_scrollable() {
@@ -219,7 +867,13 @@ impl Component for Div {
             }
 
             if scrolled {
+                let previous = self.state_ref().scroll_position;
                 self.state_mut().scroll_position = scroll_position;
+                self.state_mut().x_tween = None;
+                self.state_mut().y_tween = None;
+                if let Some(m) = self.scrolled(previous) {
+                    event.emit(m);
+                }
                 event.stop_bubbling();
             }
         }
@@ -284,11 +938,15 @@ impl Component for Div {
             event.current_inner_scale(),
             event.physical_delta()
         );
+        let previous = self.state.as_ref().map(|s| s.scroll_position);
         self.handle_on_drag(
             event.current_physical_aabb(),
             event.current_inner_scale(),
             event.physical_delta(),
         );
+        if let Some(m) = previous.and_then(|p| self.scrolled(p)) {
+            event.emit(m);
+        }
     }
 
     fn on_touch_drag(&mut self, event: &mut event::Event<event::TouchDrag>) {
@@ -298,11 +956,41 @@ impl Component for Div {
             event.current_inner_scale(),
             event.physical_delta()
         );
+        let previous = self.state.as_ref().map(|s| s.scroll_position);
         self.handle_on_drag(
             event.current_physical_aabb(),
             event.current_inner_scale(),
             event.physical_delta(),
         );
+        if let Some(m) = previous.and_then(|p| self.scrolled(p)) {
+            event.emit(m);
+        }
+    }
+
+    // Only to learn where the children are, for `scroll_into_view` and snapping
+    fn full_control(&self) -> bool {
+        self.state.is_some() && self.scrollable()
+    }
+
+    fn set_aabb(
+        &mut self,
+        aabb: &mut AABB,
+        _parent_aabb: AABB,
+        children: Vec<(&mut AABB, Option<Scale>, Option<Point>)>,
+        _frame: AABB,
+        scale_factor: f32,
+    ) {
+        let viewport = self.viewport_size(aabb.size());
+        let children: Vec<AABB> = children.into_iter().map(|(c, _, _)| *c).collect();
+        let snap_padding = self.style_val("snap_padding").unwrap().f32() * scale_factor;
+        if self.state_ref().viewport != viewport
+            || self.state_ref().children != children
+            || self.state_ref().scaled_snap_padding != snap_padding
+        {
+            self.state_mut().viewport = viewport;
+            self.state_mut().children = children;
+            self.state_mut().scaled_snap_padding = snap_padding;
+        }
     }
 
     fn scroll_position(&self) -> Option<ScrollPosition> {
@@ -317,12 +1005,19 @@ impl Component for Div {
         }
     }
 
+    fn over_scrolled(&self) -> bool {
+        self.over_scroll
+            && self.state.as_ref().map_or(false, |state| {
+                state.dragging || state.x_spring.is_some() || state.y_spring.is_some()
+            })
+    }
+
     fn frame_bounds(&self, aabb: AABB, inner_scale: Option<Scale>) -> AABB {
         let mut aabb = aabb;
         if self.scrollable() {
             let inner_scale = inner_scale.unwrap();
             let scaled_width = self.state_ref().scaled_scroll_bar_width;
-            let size = aabb.size();
+            let size = self.viewport_size(aabb.size());
             let max_position = inner_scale - size;
 
             if self.y_scrollable() && max_position.height > 0.0 {
@@ -344,6 +1039,8 @@ impl Component for Div {
                     aabb.bottom_right.y -= scaled_width;
                 }
             }
+
+            aabb = aabb.inset(self.state_ref().scaled_content_padding);
         }
 
         aabb
@@ -394,8 +1091,13 @@ impl Component for Div {
             let size = context.aabb.size();
             let scaled_width = self.style_val("bar_width").unwrap().f32() * context.scale_factor;
             self.state_mut().scaled_scroll_bar_width = scaled_width;
+            self.state_mut().scaled_content_padding =
+                self.content_padding.unwrap_or_default() * context.scale_factor;
 
-            let max_position = inner_scale - size;
+            let viewport = self.viewport_size(size);
+            let max_position = inner_scale - viewport;
+            self.state_mut().max_scroll_position =
+                Point::new(max_position.width.max(0.0), max_position.height.max(0.0));
 
             if self.y_scrollable() {
                 if max_position.height > 0.0 {
@@ -432,7 +1134,7 @@ impl Component for Div {
                         self.style_val("bar_background_color").into(),
                     );
 
-                    let height = (bar_background_height * (size.height / inner_scale.height))
+                    let height = (bar_background_height * (viewport.height / inner_scale.height))
                         .max(MIN_BAR_SIZE);
                     let mut y = (bar_background_height - height)
                         * (scroll_position.y / max_position.height)
@@ -440,6 +1142,8 @@ impl Component for Div {
                     if height + y > bar_background_height {
                         y = bar_background_height - height;
                     }
+                    // Over-scrolled past the start
+                    y = y.max(bar_y_offset);
 
                     let bar_aabb = AABB::new(
                         Pos {
@@ -503,14 +1207,16 @@ impl Component for Div {
                         self.style_val("bar_background_color").into(),
                     );
 
-                    let width =
-                        (bar_background_width * (size.width / inner_scale.width)).max(MIN_BAR_SIZE);
+                    let width = (bar_background_width * (viewport.width / inner_scale.width))
+                        .max(MIN_BAR_SIZE);
                     let mut x = (bar_background_width - width)
                         * (scroll_position.x / max_position.width)
                         + bar_x_offset;
                     if width + x > bar_background_width {
                         x = bar_background_width - width;
                     }
+                    // Over-scrolled past the start
+                    x = x.max(bar_x_offset);
 
                     let bar_aabb = AABB::new(
                         Pos {
@@ -543,3 +1249,325 @@ impl Component for Div {
         Some(rs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_cache::FontCache;
+    use crate::node;
+    use cosmic_text::fontdb::Database;
+
+    /// Where the top of `div`'s 500px tall content is drawn, with `div` 100px tall.
+    fn rendered_content_y(div: Div) -> f32 {
+        let mut node = node!(div, [size: [100, 100]]).push(node!(Div::new(), [size: [100, 500]]));
+        node.view(None, &mut vec![]);
+        node.layout(
+            &node!(Div::new()),
+            &mut FontCache::new(Database::new()),
+            1.0,
+        );
+        node.children[0].aabb.pos.y
+    }
+
+    #[test]
+    fn test_content_padding_frame() {
+        let mut div = Div::new()
+            .scroll_y()
+            .content_padding(Padding::uniform(10.0));
+        div.state_mut().scaled_scroll_bar_width = 8.0;
+        div.state_mut().scaled_content_padding = div.content_padding.unwrap();
+
+        let aabb = AABB::new(Pos::default(), [100.0, 100.0].into());
+        let frame = div.frame_bounds(aabb, Some([100.0, 500.0].into()));
+
+        // Inset by 10 on every side, with the scroll bar outside of the padding on the right
+        assert_eq!(frame.pos, Pos::new(10.0, 10.0, 0.0));
+        assert_eq!(frame.bottom_right, Point::new(100.0 - 8.0 - 10.0, 90.0));
+        // The full 500px child can be scrolled through the padded viewport
+        let max_position = Scale::from([100.0, 500.0]) - div.viewport_size(aabb.size());
+        assert_eq!(max_position.height, 420.0);
+    }
+
+    #[test]
+    fn test_over_scroll_spring_back() {
+        let mut div = Div::new()
+            .scroll_y()
+            .over_scroll(true)
+            .on_scroll(Box::new(|p| crate::msg!(p)));
+        let aabb = AABB::new(Pos::default(), [100.0, 100.0].into());
+        let inner_scale = Some([100.0, 500.0].into());
+
+        // Flick 200px past the end of the content, which resists to the 30px limit
+        div.handle_drag_start();
+        div.handle_on_drag(aabb, inner_scale, Point::new(0.0, -600.0));
+        assert_eq!(div.state_ref().scroll_position.y, 430.0);
+        let m = div.scroll_message(Point::default()).unwrap();
+        assert_eq!(m.downcast_ref::<Point>(), Some(&Point::new(0.0, 430.0)));
+        assert!(div.over_scrolled());
+
+        // Released, it springs back within 500ms of 60fps ticks
+        div.handle_drag_end();
+        assert!(div.over_scrolled());
+        for _ in 0..30 {
+            div.step_over_scroll(1.0 / 60.0);
+        }
+        assert_eq!(div.state_ref().scroll_position.y, 400.0);
+        assert!(div.state_ref().y_spring.is_none());
+        assert!(!div.over_scrolled());
+    }
+
+    #[test]
+    fn test_over_scroll_rendered() {
+        let drag = || {
+            let mut div = Div::new().scroll_y().over_scroll(true);
+            div.handle_drag_start();
+            let aabb = AABB::new(Pos::default(), [100.0, 100.0].into());
+            div.handle_on_drag(aabb, Some([100.0, 500.0].into()), Point::new(0.0, -600.0));
+            div
+        };
+        // The content is drawn past its end while over-scrolled
+        assert_eq!(rendered_content_y(drag()), -430.0);
+
+        // But is otherwise kept from scrolling past its end
+        let mut div = drag();
+        div.handle_drag_end();
+        div.state_mut().y_spring = None;
+        assert_eq!(rendered_content_y(div), -400.0);
+    }
+
+    #[test]
+    fn test_without_over_scroll() {
+        let mut div = Div::new().scroll_y();
+        let aabb = AABB::new(Pos::default(), [100.0, 100.0].into());
+        div.handle_drag_start();
+        div.handle_on_drag(aabb, Some([100.0, 500.0].into()), Point::new(0.0, 50.0));
+        assert_eq!(div.state_ref().scroll_position.y, 0.0);
+        div.handle_drag_end();
+        assert!(div.state_ref().y_spring.is_none());
+    }
+
+    #[test]
+    fn test_scroll_to_ends() {
+        let mut div = Div::new().scroll_x().scroll_y();
+        div.state_mut().max_scroll_position = Point::new(300.0, 800.0);
+        div.state_mut().scroll_position = Point::new(100.0, 500.0);
+
+        div.scroll_to_top();
+        assert_eq!(div.state_ref().scroll_position, Point::new(100.0, 0.0));
+        div.scroll_to_bottom();
+        assert_eq!(div.state_ref().scroll_position, Point::new(100.0, 800.0));
+        div.scroll_to_end();
+        assert_eq!(div.state_ref().scroll_position, Point::new(300.0, 800.0));
+        div.scroll_to_start();
+        assert_eq!(div.state_ref().scroll_position, Point::new(0.0, 800.0));
+    }
+
+    #[test]
+    fn test_scroll_to_animated() {
+        let mut div = Div::new().scroll_y();
+        div.state_mut().max_scroll_position = Point::new(0.0, 800.0);
+        div.state_mut().scroll_position.y = 500.0;
+
+        div.scroll_to_top_animated();
+        div.step_scroll_to(1.0 / 60.0);
+        let y = div.state_ref().scroll_position.y;
+        assert!(y < 500.0 && y > 0.0);
+
+        // Done within 500ms of 60fps ticks
+        for _ in 0..30 {
+            div.step_scroll_to(1.0 / 60.0);
+        }
+        assert_eq!(div.state_ref().scroll_position.y, 0.0);
+        assert!(!div.is_animating());
+    }
+
+    #[test]
+    fn test_scroll_controller() {
+        let controller = ScrollController::new();
+        let mut div = Div::new().scroll_y().controller(controller.clone());
+        div.state_mut().max_scroll_position = Point::new(0.0, 800.0);
+
+        controller.scroll_to_bottom();
+        let (request, animated) = div.controller.as_ref().unwrap().take().unwrap();
+        assert_eq!(
+            (request, animated),
+            (ScrollRequest::Edge(ScrollEdge::Bottom), false)
+        );
+        div.scroll_to_edge(ScrollEdge::Bottom, animated);
+        assert_eq!(div.state_ref().scroll_position.y, 800.0);
+        assert!(controller.take().is_none());
+    }
+
+    #[test]
+    fn test_scroll_to_offset() {
+        let mut div = Div::new().scroll_y();
+        div.state_mut().max_scroll_position = Point::new(0.0, 800.0);
+
+        div.scroll_to(300.4, false);
+        assert_eq!(div.scroll_offset(), 300.0);
+        // Clamped to the content
+        div.scroll_to(1000.0, false);
+        assert_eq!(div.scroll_offset(), 800.0);
+
+        // Eased over the 250ms animation duration
+        div.scroll_to(200.0, true);
+        div.step_scroll_to(0.125);
+        let y = div.scroll_offset();
+        assert!(y < 800.0 && y > 200.0);
+        div.step_scroll_to(0.125);
+        assert_eq!(div.scroll_offset(), 200.0);
+
+        // A horizontal Div scrolls sideways
+        let mut div = Div::new().scroll_x();
+        div.state_mut().max_scroll_position = Point::new(500.0, 0.0);
+        div.scroll_to(120.0, false);
+        assert_eq!(div.state_ref().scroll_position, Point::new(120.0, 0.0));
+        assert_eq!(div.scroll_offset(), 120.0);
+    }
+
+    #[test]
+    fn test_scroll_into_view() {
+        let controller = ScrollController::new();
+        let mut div = Div::new().scroll_y().controller(controller.clone());
+        div.state_mut().max_scroll_position = Point::new(0.0, 900.0);
+        div.state_mut().viewport = [100.0, 100.0].into();
+        div.state_mut().children = (0..10)
+            .map(|i| AABB::new(Pos::new(0.0, i as f32 * 100.0, 0.0), [100.0, 100.0].into()))
+            .collect();
+
+        // Below the viewport, so its bottom is brought to the bottom
+        div.state_mut().scroll_position.y = 150.0;
+        div.scroll_into_view(3, false);
+        assert_eq!(div.scroll_offset(), 300.0);
+        // Above it, so its top is brought to the top
+        div.scroll_into_view(1, false);
+        assert_eq!(div.scroll_offset(), 100.0);
+        // Already in view
+        div.scroll_into_view(1, false);
+        assert_eq!(div.scroll_offset(), 100.0);
+        // Not a child
+        div.scroll_into_view(10, false);
+        assert_eq!(div.scroll_offset(), 100.0);
+
+        controller.scroll_into_view(6, true);
+        assert_eq!(controller.take(), Some((ScrollRequest::Child(6), true)));
+        controller.scroll_to(50.0, false);
+        assert_eq!(
+            controller.take(),
+            Some((ScrollRequest::Offset(50.0), false))
+        );
+    }
+
+    fn snapping_div(snap: ScrollSnap) -> Div {
+        let mut div = Div::new().scroll_x().snap(snap);
+        div.state_mut().max_scroll_position = Point::new(900.0, 0.0);
+        div.state_mut().viewport = [200.0, 100.0].into();
+        div.state_mut().children = (0..5)
+            .map(|i| AABB::new(Pos::new(i as f32 * 220.0, 0.0, 0.0), [200.0, 100.0].into()))
+            .collect();
+        div
+    }
+
+    #[test]
+    fn test_snap_points() {
+        let mut div = snapping_div(ScrollSnap::Mandatory);
+        assert_eq!(
+            div.snap_points(false),
+            vec![0.0, 220.0, 440.0, 660.0, 880.0]
+        );
+        // Offset by the padding, and limited to the content
+        div.state_mut().scaled_snap_padding = 20.0;
+        assert_eq!(
+            div.snap_points(false),
+            vec![0.0, 200.0, 420.0, 640.0, 860.0]
+        );
+
+        assert_eq!(Div::new().scroll_x().snap_mode(), ScrollSnap::None);
+        let div = Div::new().scroll_x().with_class("snap");
+        assert_eq!(div.snap_mode(), ScrollSnap::Mandatory);
+    }
+
+    #[test]
+    fn test_snap_on_release() {
+        let aabb = AABB::new(Pos::default(), [200.0, 100.0].into());
+        let inner_scale = Some([1100.0, 100.0].into());
+        let mut div = snapping_div(ScrollSnap::Mandatory);
+
+        // Released past the middle of the first child, it settles on the second
+        div.handle_drag_start();
+        div.handle_on_drag(aabb, inner_scale, Point::new(-150.0, 0.0));
+        div.handle_drag_end();
+        assert!(div.is_animating());
+        for _ in 0..30 {
+            div.step_scroll_to(1.0 / 60.0);
+        }
+        assert_eq!(div.scroll_offset(), 220.0);
+
+        // A new drag interrupts the snap
+        div.state_mut().scroll_position.x = 300.0;
+        div.handle_drag_end();
+        div.step_scroll_to(1.0 / 60.0);
+        div.handle_drag_start();
+        assert!(!div.is_animating());
+
+        // Proximity only snaps to points within a quarter of the viewport
+        let mut div = snapping_div(ScrollSnap::Proximity);
+        div.state_mut().scroll_position.x = 330.0;
+        div.handle_drag_end();
+        assert!(!div.is_animating());
+        div.state_mut().scroll_position.x = 400.0;
+        div.handle_drag_end();
+        assert!(div.is_animating());
+    }
+
+    #[test]
+    fn test_snap_step() {
+        let mut div = snapping_div(ScrollSnap::Always);
+        assert!(div.snap_step(Point::new(10.0, 0.0)));
+        // A second step before the first is done goes one further
+        assert!(div.snap_step(Point::new(10.0, 0.0)));
+        assert_eq!(div.state_ref().x_tween.as_ref().unwrap().to, 440.0);
+        assert!(div.snap_step(Point::new(-10.0, 0.0)));
+        assert_eq!(div.state_ref().x_tween.as_ref().unwrap().to, 220.0);
+
+        assert!(!snapping_div(ScrollSnap::Mandatory).snap_step(Point::new(10.0, 0.0)));
+    }
+
+    #[test]
+    fn test_controlled_scroll_offset() {
+        use crate::font_cache::FontCache;
+        use crate::{node, Node};
+        use cosmic_text::fontdb::Database;
+
+        let offset = ScrollOffset { x: 0.0, y: 100.0 };
+        let mut root: Node = node!(
+            Div::new().scroll_y().with_scroll_offset(offset),
+            [size: [100, 100], direction: Direction::Column]
+        )
+        .push(node!(Div::new(), [size: [100, 500]]));
+        root.view(None, &mut vec![]);
+        let prev = node!(Div::new());
+        root.layout(&prev, &mut FontCache::new(Database::new()), 1.0);
+
+        // The content is shifted up by the offset
+        assert_eq!(root.children[0].aabb.pos.y, -100.0);
+
+        // Scrolling only asks the parent to change the offset
+        let mut div = Div::new()
+            .scroll_y()
+            .with_scroll_offset(offset)
+            .on_scroll(Box::new(|p| crate::msg!(p)));
+        div.init();
+        assert_eq!(div.current_scroll_offset(), offset);
+        div.state_mut().scroll_position.y = 150.0;
+        let m = div.scrolled(Point::new(0.0, 100.0)).unwrap();
+        assert_eq!(m.downcast_ref::<Point>(), Some(&Point::new(0.0, 150.0)));
+        assert_eq!(div.current_scroll_offset(), offset);
+
+        // Uncontrolled, the Div keeps its own position
+        let mut div = Div::new().scroll_y();
+        div.state_mut().scroll_position.y = 150.0;
+        assert!(div.scrolled(Point::new(0.0, 100.0)).is_none());
+        assert_eq!(div.current_scroll_offset().y, 150.0);
+    }
+}
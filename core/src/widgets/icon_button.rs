@@ -1,11 +1,11 @@
 use std::hash::Hash;
 use std::time::Instant;
 
-// use super::ToolTip;
+use super::tool_tip::{with_tool_tip, ToolTip, TooltipPlacement};
 use crate::component::{Component, Message};
 use crate::font_cache::TextSegment;
 use crate::layout::Size;
-use crate::style::{HorizontalPosition, Styled};
+use crate::style::{ComponentState, CursorStyle, HorizontalPosition, Styled};
 use crate::{event, lay, rect};
 use crate::{node, node::Node};
 use crate::{size, size_pct, types::*};
@@ -31,7 +31,11 @@ pub struct IconButton {
     pub icon: String,
     pub icon_type: IconType,
     pub on_click: Option<Box<dyn Fn() -> Message + Send + Sync>>,
+    /// Shown next to the button after hovering over it for [`ToolTip::DELAY`].
+    pub tooltip: Option<String>,
+    #[deprecated(note = "Use `tooltip`, which takes priority")]
     pub tool_tip: Option<String>,
+    pub tooltip_placement: TooltipPlacement,
     pub on_press: Option<Box<dyn Fn() -> Message + Send + Sync>>,
     pub on_release: Option<Box<dyn Fn() -> Message + Send + Sync>>,
     pub disabled: bool,
@@ -46,12 +50,15 @@ impl std::fmt::Debug for IconButton {
 }
 
 impl IconButton {
+    #[allow(deprecated)]
     pub fn new<S: Into<String>>(icon: S) -> Self {
         Self {
             icon: icon.into(),
             icon_type: IconType::Svg,
             on_click: None,
+            tooltip: None,
             tool_tip: None,
+            tooltip_placement: TooltipPlacement::default(),
             on_press: None,
             on_release: None,
             disabled: false,
@@ -77,7 +84,17 @@ impl IconButton {
     }
 
     pub fn tool_tip(mut self, t: String) -> Self {
-        self.tool_tip = Some(t);
+        self.tooltip = Some(t);
+        self
+    }
+
+    pub fn tooltip<S: Into<String>>(mut self, t: S) -> Self {
+        self.tooltip = Some(t.into());
+        self
+    }
+
+    pub fn tooltip_placement(mut self, placement: TooltipPlacement) -> Self {
+        self.tooltip_placement = placement;
         self
     }
 
@@ -90,16 +107,47 @@ impl IconButton {
         self.disabled = d;
         self
     }
+
+    /// The `tooltip`, or else the deprecated `tool_tip`.
+    fn tooltip_text(&self) -> Option<&String> {
+        #[allow(deprecated)]
+        self.tooltip.as_ref().or(self.tool_tip.as_ref())
+    }
+
+    fn component_state(&self) -> ComponentState {
+        let mut state = ComponentState::empty();
+        state.set(ComponentState::HOVER, self.state_ref().hover);
+        state.set(ComponentState::ACTIVE, self.state_ref().pressed);
+        state.set(ComponentState::DISABLED, self.disabled);
+        state
+    }
+
+    /// The fill for the button's current state. `hover` and `active` entries for
+    /// `background_color` take priority over the `highlight_color` and `active_color`
+    /// parameters.
+    fn background_color(&self, state: ComponentState) -> Color {
+        let param = if state.contains(ComponentState::ACTIVE) {
+            "active_color"
+        } else if state.contains(ComponentState::HOVER) {
+            "highlight_color"
+        } else {
+            "background_color"
+        };
+        self.state_style_val("background_color", state)
+            .or_else(|| self.style_val(param))
+            .into()
+    }
 }
 
 #[state_component_impl(IconButtonState)]
 impl Component for IconButton {
+    fn cursor(&self) -> Option<CursorStyle> {
+        self.style_val("cursor").map(|v| v.cursor())
+    }
+
     fn view(&self) -> Option<Node> {
-        let radius: f32 = self.style_val("radius").unwrap().f32();
+        let radius = self.style_val("radius").unwrap().border_radius();
         let padding: f64 = self.style_val("padding").unwrap().into();
-        let active_color: Color = self.style_val("active_color").into();
-        let highlight_color: Color = self.style_val("highlight_color").into();
-        let background_color: Color = self.style_val("background_color").into();
         let border_color: Color = self.style_val("border_color").into();
         let border_width: f32 = self.style_val("border_width").unwrap().f32();
         let size: Size = self.style_val("size").unwrap().into();
@@ -122,16 +170,10 @@ impl Component for IconButton {
 
         let mut base = node!(
             super::RoundedRect {
-                background_color: if self.state_ref().pressed {
-                    active_color
-                } else if self.state_ref().hover {
-                    highlight_color
-                } else {
-                    background_color
-                },
+                background_color: self.background_color(self.component_state()),
                 border_color,
                 border_width: (border_width, border_width, border_width, border_width),
-                radius: (radius, radius, radius, radius),
+                radius: radius.into(),
                 ..Default::default()
             },
             lay!(
@@ -144,6 +186,10 @@ impl Component for IconButton {
         )
         .push(icon);
 
+        if let (Some(p), Some(tt)) = (self.state_ref().tool_tip_open, self.tooltip_text()) {
+            base = with_tool_tip(base, tt, self.tooltip_placement, p);
+        }
+
         Some(base)
     }
 
@@ -163,6 +209,8 @@ impl Component for IconButton {
     }
 
     fn on_mouse_leave(&mut self, _event: &mut event::Event<event::MouseLeave>) {
+        self.state_mut().hover_start = None;
+        self.state_mut().tool_tip_open = None;
         // *self.state_mut() = IconButtonState::default();
         // if let Some(w) = current_window() {
         //     w.unset_cursor();
@@ -170,16 +218,12 @@ impl Component for IconButton {
     }
 
     fn on_tick(&mut self, event: &mut event::Event<event::Tick>) {
-        // if self.state_ref().hover_start.is_some()
-        //     && self
-        //         .state_ref()
-        //         .hover_start
-        //         .map(|s| s.elapsed().as_millis() > ToolTip::DELAY)
-        //         .unwrap_or(false)
-        //     && self.state_ref().tool_tip_open.is_none()
-        // {
-        //     self.state_mut().tool_tip_open = Some(event.relative_logical_position());
-        // }
+        if self.tooltip_text().is_some()
+            && self.state_ref().tool_tip_open.is_none()
+            && ToolTip::is_due(self.state_ref().hover_start, Instant::now(), ToolTip::DELAY)
+        {
+            self.state_mut().tool_tip_open = Some(event.relative_logical_position());
+        }
     }
 
     fn on_touch_drag_start(&mut self, event: &mut event::Event<event::TouchDragStart>) {
@@ -262,3 +306,50 @@ impl Component for IconButton {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// `button`, with the mouse having hovered over it for `hovered_ms`, as of its next tick.
+    fn hovered(mut button: IconButton, hovered_ms: u64) -> Node {
+        button.state_mut().hover_start = Some(Instant::now() - Duration::from_millis(hovered_ms));
+        let mut tick = event::Event::new(event::Tick, &event::EventCache::new(1.0));
+        tick.current_aabb = Some(AABB::new(Pos::default(), Scale::new(32.0, 32.0)));
+        button.on_tick(&mut tick);
+        let mut node = node!(button);
+        node.view(None, &mut vec![]);
+        node
+    }
+
+    /// The text of the tool tip shown by `button`, if any.
+    fn tooltip_text(button: &Node) -> Option<String> {
+        // The tool tip is pushed after the icon, with its text in a rounded rect
+        let tooltip = button.children[0].children.get(1)?;
+        Some(format!("{:?}", tooltip.children[0].children[0].component))
+    }
+
+    #[test]
+    fn test_tooltip_shown_after_delay() {
+        let button = || IconButton {
+            tooltip: Some("Save".to_string()),
+            ..IconButton::new("save")
+        };
+        assert_eq!(tooltip_text(&hovered(button(), 500)), None);
+        let text = tooltip_text(&hovered(button(), ToolTip::DELAY as u64 + 100)).unwrap();
+        assert!(text.starts_with("Text"), "{}", text);
+        assert!(text.contains("\"Save\""), "{}", text);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_tool_tip() {
+        let button = IconButton {
+            tool_tip: Some("Open".to_string()),
+            ..IconButton::new("open")
+        };
+        let text = tooltip_text(&hovered(button, ToolTip::DELAY as u64 + 100)).unwrap();
+        assert!(text.contains("\"Open\""), "{}", text);
+    }
+}
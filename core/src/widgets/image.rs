@@ -1,20 +1,77 @@
-use std::hash::Hash;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use mctk_macros::component;
+use image::ImageFormat;
+
+use mctk_macros::{component, state_component_impl};
 
 use crate::component::{Component, ComponentHasher, RenderContext};
+use crate::font_cache::FontCache;
 
-use crate::renderables::image::InstanceBuilder as ImageInstanceBuilder;
+use crate::event;
+use crate::renderables::image::{
+    asset_name, CompressedImage, ImageFilter, ImageHold, Instance as ImageInstance,
+    InstanceBuilder as ImageInstanceBuilder,
+};
 use crate::renderables::types::{Point, Size};
 use crate::renderables::{self, Rect, Renderable};
 use crate::style::{self, Styled};
 use crate::types::*;
 
-#[component(Styled)]
+/// Where an [`Image`] loads its data from.
+#[derive(Debug, Clone)]
+struct ImageSource {
+    name: String,
+    dynamic_load_from: Option<String>,
+    compressed: Option<CompressedImage>,
+    filters: Vec<ImageFilter>,
+}
+
+impl ImageSource {
+    fn is_same_image(&self, other: &Self) -> bool {
+        self.name == other.name && self.dynamic_load_from == other.dynamic_load_from
+    }
+
+    /// A hold on the loaded image, when it is loaded on first use and so can be evicted.
+    fn hold(&self) -> Option<ImageHold> {
+        (self.compressed.is_some() || self.dynamic_load_from.is_some())
+            .then(|| ImageHold::new(asset_name(&self.name, &self.filters)))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ImageState {
+    /// What is currently shown.
+    source: Option<ImageSource>,
+    /// The image that is fading out, and when it started to.
+    crossfade_from: Option<(ImageSource, Instant)>,
+    /// How far the crossfade has progressed, from `0.0` to `1.0`.
+    crossfade_progress: f32,
+    /// Keep the images that are drawn from being evicted.
+    holds: Vec<ImageHold>,
+}
+
+#[component(State = "ImageState", Styled)]
 #[derive(Debug)]
 pub struct Image {
     pub name: String,
     pub dynamic_load_from: Option<String>,
+    /// The `(width, height)` of the image file, used to keep its aspect ratio during layout.
+    pub natural_size: Option<(f32, f32)>,
+    /// When the natural size is known and only one of the width or height is determined by layout, size the other to match the natural aspect ratio.
+    pub preserve_aspect_ratio: bool,
+    /// Encoded image data, decoded when the image is first rendered.
+    pub compressed: Option<CompressedImage>,
+    /// `(top_left, top_right, bottom_right, bottom_left)` radii to clip the corners to.
+    /// Overrides the `radius` style.
+    pub corner_radius: Option<(f32, f32, f32, f32)>,
+    /// Applied in order before the image is drawn. See [`ImageFilter`].
+    pub filters: Vec<ImageFilter>,
+    /// When the image changes, fade the previous one out and the new one in over this many
+    /// milliseconds. `0` switches immediately.
+    pub crossfade_duration_ms: u32,
 }
 
 impl Default for Image {
@@ -22,8 +79,16 @@ impl Default for Image {
         Self {
             name: "".to_string(),
             dynamic_load_from: None,
+            natural_size: None,
+            preserve_aspect_ratio: true,
+            compressed: None,
+            corner_radius: None,
+            filters: vec![],
+            crossfade_duration_ms: 0,
             class: Default::default(),
             style_overrides: Default::default(),
+            state: Some(ImageState::default()),
+            dirty: false,
         }
     }
 }
@@ -33,39 +98,377 @@ impl Image {
         Self {
             name: name.into(),
             dynamic_load_from: None,
+            natural_size: None,
+            preserve_aspect_ratio: true,
+            compressed: None,
+            corner_radius: None,
+            filters: vec![],
+            crossfade_duration_ms: 0,
             class: Default::default(),
             style_overrides: Default::default(),
+            state: Some(ImageState::default()),
+            dirty: false,
         }
     }
 
+    /// An image that is decoded from `data` the first time it is rendered, rather than now.
+    ///
+    /// The decoded image is cached under a name derived from a hash of the `data`, so the same
+    /// bytes are only decoded once, however many times they are viewed.
+    pub fn from_compressed_bytes(data: Arc<Vec<u8>>, format: ImageFormat) -> Self {
+        let mut hasher = DefaultHasher::new();
+        data.hash(&mut hasher);
+        Self {
+            name: format!("compressed:{:016x}", hasher.finish()),
+            compressed: Some(CompressedImage::new(data, format)),
+            ..Default::default()
+        }
+    }
+
+    /// Whether the compressed image data has been decoded. Always `false` for images loaded
+    /// by name.
+    pub fn is_decoded(&self) -> bool {
+        self.compressed
+            .as_ref()
+            .map(|c| c.is_decoded())
+            .unwrap_or(false)
+    }
+
+    /// Clip every corner of the image to `radius`. Layout is unaffected.
+    pub fn with_border_radius(self, radius: f32) -> Self {
+        self.style("radius", radius)
+    }
+
+    pub fn corner_radius(mut self, radius: (f32, f32, f32, f32)) -> Self {
+        self.corner_radius = Some(radius);
+        self
+    }
+
+    /// Add `filter` after the filters that are already applied, e.g.
+    /// `.with_filter(ImageFilter::Grayscale).with_filter(ImageFilter::Tint(Color::BLUE))`.
+    pub fn with_filter(mut self, filter: ImageFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    pub fn crossfade_duration_ms(mut self, duration_ms: u32) -> Self {
+        self.crossfade_duration_ms = duration_ms;
+        self
+    }
+
     pub fn dynamic_load_from(mut self, v: Option<String>) -> Self {
         self.dynamic_load_from = v;
         self
     }
+
+    pub fn natural_size(mut self, width: f32, height: f32) -> Self {
+        self.natural_size = Some((width, height));
+        self
+    }
+
+    pub fn preserve_aspect_ratio(mut self, v: bool) -> Self {
+        self.preserve_aspect_ratio = v;
+        self
+    }
+
+    /// `width / height` of the natural size, if it is known.
+    pub fn natural_aspect_ratio(&self) -> Option<f32> {
+        self.natural_size
+            .filter(|(_, h)| *h > 0.0)
+            .map(|(w, h)| w / h)
+    }
+
+    fn source(&self) -> ImageSource {
+        ImageSource {
+            name: self.name.clone(),
+            dynamic_load_from: self.dynamic_load_from.clone(),
+            compressed: self.compressed.clone(),
+            filters: self.filters.clone(),
+        }
+    }
+
+    /// Hold on to the images that are drawn, letting go of any others so that they can be
+    /// evicted.
+    fn hold_drawn_images(&mut self) {
+        let state = self.state_ref();
+        let holds = state
+            .source
+            .iter()
+            .chain(state.crossfade_from.as_ref().map(|(source, _)| source))
+            .filter_map(ImageSource::hold)
+            .collect();
+        // Replaced after the new holds are taken, so images that are still drawn stay held
+        self.state_mut().holds = holds;
+    }
+
+    /// Advance the crossfade to `now`, and forget the previous image once it has faded out.
+    fn step_crossfade(&mut self, now: Instant) {
+        if let Some((_, start)) = self.state_ref().crossfade_from {
+            let duration = Duration::from_millis(self.crossfade_duration_ms as u64);
+            let elapsed = now.duration_since(start);
+            if elapsed >= duration {
+                self.state_mut().crossfade_from = None;
+                self.state_mut().crossfade_progress = 1.0;
+                self.hold_drawn_images();
+            } else {
+                self.state_mut().crossfade_progress =
+                    elapsed.as_secs_f32() / duration.as_secs_f32();
+            }
+        }
+    }
+
+    fn instance(&self, source: ImageSource, aabb: AABB, opacity: f32) -> ImageInstance {
+        ImageInstanceBuilder::default()
+            .pos(aabb.pos)
+            .scale(aabb.size())
+            .name(source.name)
+            .corner_radius(Some(self.corner_radius.unwrap_or_else(|| {
+                self.style_val("radius").unwrap().border_radius().into()
+            })))
+            .dynamic_load_from(source.dynamic_load_from)
+            .compressed(source.compressed)
+            .filters(source.filters)
+            .opacity(opacity)
+            .build()
+            .unwrap()
+    }
 }
 
+#[state_component_impl(ImageState)]
 impl Component for Image {
+    fn init(&mut self) {
+        self.state_mut().source = Some(self.source());
+        self.hold_drawn_images();
+    }
+
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.name.hash(hasher);
+        self.dynamic_load_from.hash(hasher);
+        self.filters.hash(hasher);
+    }
+
+    fn new_props(&mut self) {
+        let source = self.source();
+        let previous = self.state_mut().source.replace(source.clone());
+        if let Some(previous) = previous {
+            if self.crossfade_duration_ms > 0 && !previous.is_same_image(&source) {
+                self.state_mut().crossfade_from = Some((previous, Instant::now()));
+                self.state_mut().crossfade_progress = 0.0;
+            }
+        }
+        self.hold_drawn_images();
+    }
+
+    fn on_tick(&mut self, _event: &mut event::Event<event::Tick>) {
+        if self.state_ref().crossfade_from.is_some() {
+            self.step_crossfade(Instant::now());
+        }
+    }
+
     fn render_hash(&self, hasher: &mut ComponentHasher) {
         self.name.hash(hasher);
+        self.natural_size
+            .map(|(w, h)| (w as u32, h as u32))
+            .hash(hasher);
+        self.preserve_aspect_ratio.hash(hasher);
+        self.style_val("radius")
+            .unwrap()
+            .border_radius()
+            .hash(hasher);
+        self.corner_radius
+            .map(|(a, b, c, d)| (a as i32, b as i32, c as i32, d as i32))
+            .hash(hasher);
+        self.filters.hash(hasher);
+        if let Some((previous, _)) = &self.state_ref().crossfade_from {
+            previous.name.hash(hasher);
+            ((self.state_ref().crossfade_progress * 1000.0) as u32).hash(hasher);
+        }
+    }
+
+    fn fill_bounds(
+        &mut self,
+        width: Option<f32>,
+        height: Option<f32>,
+        _max_width: Option<f32>,
+        _max_height: Option<f32>,
+        _font_cache: &mut FontCache,
+        _scale_factor: f32,
+    ) -> (Option<f32>, Option<f32>) {
+        match (self.natural_size, self.preserve_aspect_ratio) {
+            (Some((w, h)), true) if w > 0.0 && h > 0.0 => match (width, height) {
+                (Some(width), None) => (Some(width), Some(width * h / w)),
+                (None, Some(height)) => (Some(height * w / h), Some(height)),
+                _ => (width, height),
+            },
+            _ => (width, height),
+        }
     }
 
     fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
-        let width = context.aabb.width();
-        let height = context.aabb.height();
-        let AABB { pos, .. } = context.aabb;
-        let radius = self.style_val("radius").unwrap().f32();
+        let mut renderables = vec![];
+        let mut opacity = 1.0;
+        if let Some((previous, _)) = self.state_ref().crossfade_from.clone() {
+            opacity = self.state_ref().crossfade_progress;
+            let instance = self.instance(previous, context.aabb, 1.0 - opacity);
+            renderables.push(Renderable::Image(renderables::Image::from_instance_data(
+                instance,
+            )));
+        }
+
+        let instance = self.instance(self.source(), context.aabb, opacity);
+        renderables.push(Renderable::Image(renderables::Image::from_instance_data(
+            instance,
+        )));
+        Some(renderables)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Direction;
+    use crate::widgets::Div;
+    use crate::{lay, node, size};
+    use cosmic_text::fontdb::Database;
+
+    #[test]
+    fn test_preserve_aspect_ratio() {
+        let image = Image::new("video").natural_size(1600.0, 900.0);
+        assert_eq!(image.natural_aspect_ratio(), Some(16.0 / 9.0));
+
+        let mut row = node!(
+            Div::new(),
+            lay![size: [400, 400], direction: Direction::Row]
+        )
+        .push(node!(image, lay![size: [160, Auto]]));
+        row.calculate_layout(&mut FontCache::new(Database::new()), 1.0);
+        assert_eq!(row.children[0].layout_result.size, size!(160.0, 90.0));
+    }
+
+    #[test]
+    fn test_lazy_decoding() {
+        let mut png = vec![];
+        image::DynamicImage::new_rgba8(4, 2)
+            .write_to(&mut std::io::Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        let data = Arc::new(png);
+
+        let image = Image::from_compressed_bytes(data.clone(), ImageFormat::Png);
+        assert!(!image.is_decoded());
+        // Named by the bytes rather than where they are, which may later hold other bytes
+        let copy = Arc::new(data.as_ref().clone());
+        assert_eq!(
+            image.name,
+            Image::from_compressed_bytes(copy, ImageFormat::Png).name
+        );
+        let other = Arc::new(vec![0, 1, 2]);
+        assert_ne!(
+            image.name,
+            Image::from_compressed_bytes(other, ImageFormat::Png).name
+        );
+
+        // Drawing the renderable decodes the shared image
+        let decoded = image.compressed.clone().unwrap().decode().cloned().unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (4, 2));
+        assert!(image.is_decoded());
+
+        let invalid = CompressedImage::new(Arc::new(vec![0, 1, 2]), ImageFormat::Png);
+        assert!(invalid.decode().is_none());
+        assert!(!invalid.is_decoded());
+        assert!(!Image::new("video").is_decoded());
+    }
+
+    #[test]
+    fn test_border_radius() {
+        let image = Image::new("avatar").with_border_radius(50.0);
+        assert_eq!(image.style_val("radius").unwrap().f32(), 50.0);
 
         let instance = ImageInstanceBuilder::default()
-            .pos(pos)
-            .scale(Scale { width, height })
-            .name(self.name.clone())
-            .radius(radius)
-            .dynamic_load_from(self.dynamic_load_from.clone())
+            .pos(Pos::default())
+            .scale(Scale::new(100.0, 100.0))
+            .name(image.name.clone())
+            .radius(50.0)
             .build()
             .unwrap();
+        // A circle, clipping all four corners
+        assert_eq!(instance.corner_radii(), (50.0, 50.0, 50.0, 50.0));
+
+        // Per-corner radii win, and are limited to half of the shorter side
+        let instance = renderables::image::Instance {
+            corner_radius: Some((8.0, 0.0, 80.0, 0.0)),
+            ..instance
+        };
+        assert_eq!(instance.corner_radii(), (8.0, 0.0, 50.0, 0.0));
+    }
+
+    #[test]
+    fn test_crossfade() {
+        let mut first = Image::new("first").crossfade_duration_ms(200);
+        first.init();
+        let mut image = Image::new("second").crossfade_duration_ms(200);
+        image.replace_state(first.take_state().unwrap());
+        image.new_props();
+
+        let (_, start) = image.state_ref().crossfade_from.clone().unwrap();
+        image.step_crossfade(start + Duration::from_millis(100));
+        let context = RenderContext {
+            aabb: AABB::new(Pos::default(), Scale::new(100.0, 100.0)),
+            inner_scale: None,
+            caches: crate::renderer::Caches {
+                font: Arc::new(std::sync::RwLock::new(FontCache::new(Database::new()))),
+            },
+            prev_state: None,
+            scale_factor: 1.0,
+        };
+        let opacities = |renderables: Vec<Renderable>| -> Vec<(String, f32)> {
+            renderables
+                .into_iter()
+                .filter_map(|r| match r {
+                    Renderable::Image(i) => Some((i.instance_data.name, i.instance_data.opacity)),
+                    _ => None,
+                })
+                .collect()
+        };
+        let halfway = opacities(image.render(context.clone()).unwrap());
+        assert_eq!(halfway.len(), 2);
+        // The old image is drawn first, fading out under the new one
+        assert_eq!(halfway[0].0, "first");
+        assert_eq!(halfway[1].0, "second");
+        assert!((halfway[0].1 - 0.5).abs() < 0.01);
+        assert!((halfway[1].1 - 0.5).abs() < 0.01);
+
+        image.step_crossfade(start + Duration::from_millis(200));
+        assert!(image.state_ref().crossfade_from.is_none());
+        let done = opacities(image.render(context).unwrap());
+        assert_eq!(done, vec![("second".to_string(), 1.0)]);
+    }
+
+    #[test]
+    fn test_crossfade_lets_go() {
+        let from_file = |name: &str| {
+            Image::new(name)
+                .dynamic_load_from(Some(format!("{}.png", name)))
+                .crossfade_duration_ms(200)
+        };
+        let mut first = from_file("first");
+        first.init();
+        assert_eq!(first.state_ref().holds.len(), 1);
+        let mut image = from_file("second");
+        image.replace_state(first.take_state().unwrap());
+        image.new_props();
+        // Both are held while they are drawn
+        assert_eq!(image.state_ref().holds.len(), 2);
+
+        let (_, start) = image.state_ref().crossfade_from.clone().unwrap();
+        image.step_crossfade(start + Duration::from_millis(100));
+        assert_eq!(image.state_ref().holds.len(), 2);
+        // The image faded out is let go of, so that it can be evicted
+        image.step_crossfade(start + Duration::from_millis(200));
+        assert_eq!(image.state_ref().holds.len(), 1);
 
-        Some(vec![Renderable::Image(
-            renderables::Image::from_instance_data(instance),
-        )])
+        // Only images that can be loaded again are held
+        let mut named = Image::new("asset");
+        named.init();
+        assert!(named.state_ref().holds.is_empty());
     }
 }
@@ -7,13 +7,13 @@ mod icon_button;
 pub use icon_button::{IconButton, IconType};
 
 mod rounded_rect;
-pub use rounded_rect::RoundedRect;
+pub use rounded_rect::{GradientRoundedRect, RoundedRect};
 
 mod text;
 pub use text::Text;
 
 mod div;
-pub use div::Div;
+pub use div::{Div, ScrollController, ScrollEdge, ScrollOffset, ScrollSnap};
 
 mod image;
 pub use image::Image;
@@ -22,13 +22,18 @@ mod svg;
 pub use svg::Svg;
 
 mod slider;
-pub use slider::Slider;
+pub use slider::{Orientation, Slider};
+
+mod progress_bar;
+pub use progress_bar::ProgressBar;
 
 mod carousel;
 pub use carousel::{Carousel, TransitionPositions};
 
 mod textbox;
-pub use textbox::{TextBox, TextBoxAction, TextBoxVariant};
+pub use textbox::{
+    ResizeMode, TabBehavior, TextBox, TextBoxAction, TextBoxController, TextBoxVariant,
+};
 
 mod scrollable;
 pub use scrollable::Scrollable;
@@ -39,6 +44,27 @@ pub use scrollable::Scrollable;
 mod radio_buttons;
 pub use radio_buttons::RadioButtons;
 
+mod accordion;
+pub use accordion::{Accordion, AccordionItem};
+
+mod checkbox;
+pub use checkbox::Checkbox;
+
+mod modal;
+pub use modal::{Modal, ModalSize};
+
+mod number_input;
+pub use number_input::NumberInput;
+
+mod tabs;
+pub use tabs::{TabItem, Tabs};
+
+mod toast;
+pub use toast::{Toast, ToastId, ToastManager, ToastPosition, ToastVariant};
+
+mod virtual_scroll;
+pub use virtual_scroll::{ItemHeight, VirtualScroll};
+
 mod toggle;
 pub use toggle::{Toggle, ToggleType};
 
@@ -47,3 +73,9 @@ pub use h_divider::HDivider;
 
 mod slide_bar;
 pub use slide_bar::{SlideBar, SlideBarType};
+
+mod select;
+pub use select::{Select, SelectItem};
+
+mod tool_tip;
+pub use tool_tip::{ToolTip, TooltipPlacement};
@@ -0,0 +1,392 @@
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use mctk_macros::{component, state_component_impl};
+
+use crate::component::{Component, ComponentHasher, Message, RenderContext};
+use crate::input::Key;
+use crate::layout::{Alignment, Direction};
+use crate::renderables::{rect::InstanceBuilder as RectInstanceBuilder, Rect, Renderable};
+use crate::style::{BoxShadow, Styled};
+use crate::types::*;
+use crate::{event, lay, msg, node, rect, size, size_pct, txt, Node};
+
+use super::Text;
+
+#[derive(Debug)]
+enum ModalMsg {
+    Close,
+}
+
+/// How wide a [`Modal`]'s dialog is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ModalSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
+    /// Fills the modal, without rounded corners.
+    FullScreen,
+}
+
+impl ModalSize {
+    /// The width of the dialog, given the `max_width` style parameter, or `None` to fill the
+    /// modal.
+    fn width(self, max_width: f32) -> Option<f32> {
+        match self {
+            ModalSize::Small => Some(max_width * 0.6),
+            ModalSize::Medium => Some(max_width),
+            ModalSize::Large => Some(max_width * 1.5),
+            ModalSize::FullScreen => None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ModalState {
+    /// How far the modal has appeared, from 0 to 1.
+    openness: f32,
+    last_tick: Option<Instant>,
+}
+
+/// A dialog over a backdrop, which covers everything behind it while `visible`.
+///
+/// The modal covers the Node it is laid out in, so it is usually the last child of the root,
+/// with `size_pct: [100]` and `position_type: Absolute`. While visible, it traps the focus:
+/// Tab and Shift+Tab cycle through the focusable Components of its content, and Escape calls
+/// `on_close`, as does clicking the backdrop when `close_on_backdrop` is set. It fades and slides
+/// in and out over the `animation_duration_ms` style parameter.
+#[component(State = "ModalState", Styled, Internal)]
+pub struct Modal {
+    pub visible: bool,
+    pub title: Option<String>,
+    pub on_close: Option<Box<dyn Fn() -> Message + Send + Sync>>,
+    pub close_on_backdrop: bool,
+    pub size: ModalSize,
+    // Moved into the modal's view, which is only made once for each instance
+    content: Mutex<Option<Box<dyn Component + Send + Sync>>>,
+}
+
+impl fmt::Debug for Modal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Modal")
+            .field("visible", &self.visible)
+            .field("title", &self.title)
+            .field("close_on_backdrop", &self.close_on_backdrop)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl Modal {
+    pub fn new<C: Component + Send + Sync + 'static>(content: C) -> Self {
+        Self {
+            visible: false,
+            title: None,
+            on_close: None,
+            close_on_backdrop: true,
+            size: ModalSize::default(),
+            content: Mutex::new(Some(Box::new(content))),
+            state: Some(ModalState::default()),
+            dirty: false,
+            class: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    pub fn visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    pub fn title<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn on_close(mut self, close_fn: Box<dyn Fn() -> Message + Send + Sync>) -> Self {
+        self.on_close = Some(close_fn);
+        self
+    }
+
+    pub fn close_on_backdrop(mut self, close_on_backdrop: bool) -> Self {
+        self.close_on_backdrop = close_on_backdrop;
+        self
+    }
+
+    pub fn size(mut self, size: ModalSize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Move the openness towards `visible` for `dt` seconds.
+    fn step_animation(&mut self, dt: f32) {
+        let duration_ms = self
+            .style_val("animation_duration_ms")
+            .map(|v| v.f32())
+            .unwrap_or(0.0);
+        let delta = if duration_ms > 0.0 {
+            dt * 1000.0 / duration_ms
+        } else {
+            1.0
+        };
+        let openness = self.state_ref().openness;
+        self.state_mut().openness = if self.visible {
+            (openness + delta).min(1.0)
+        } else {
+            (openness - delta).max(0.0)
+        };
+    }
+
+    fn is_animating(&self) -> bool {
+        self.state_ref().openness != if self.visible { 1.0 } else { 0.0 }
+    }
+}
+
+#[state_component_impl(ModalState)]
+impl Component for Modal {
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.visible.hash(hasher);
+        self.title.hash(hasher);
+        self.close_on_backdrop.hash(hasher);
+        self.size.hash(hasher);
+    }
+
+    fn render_hash(&self, hasher: &mut ComponentHasher) {
+        ((self.state_ref().openness * 1000.0) as u32).hash(hasher);
+    }
+
+    fn traps_focus(&self) -> bool {
+        self.visible
+    }
+
+    fn on_key_down(&mut self, event: &mut event::Event<event::KeyDown>) {
+        if self.visible && event.input.0 == Key::Escape {
+            event.stop_bubbling();
+            if let Some(close_fn) = &self.on_close {
+                event.emit(close_fn());
+            }
+        }
+    }
+
+    fn on_tick(&mut self, _event: &mut event::Event<event::Tick>) {
+        if !self.is_animating() {
+            self.state_mut().last_tick = None;
+            return;
+        }
+        let now = Instant::now();
+        let dt = self
+            .state_ref()
+            .last_tick
+            .map(|t| now.duration_since(t).as_secs_f32())
+            .unwrap_or(0.0);
+        self.state_mut().last_tick = Some(now);
+        self.step_animation(dt);
+    }
+
+    fn update(&mut self, message: Message) -> Vec<Message> {
+        match message.downcast_ref::<ModalMsg>() {
+            Some(ModalMsg::Close) => self.on_close.as_ref().map(|f| f()).into_iter().collect(),
+            None => vec![message],
+        }
+    }
+
+    fn view(&self) -> Option<Node> {
+        let openness = self.state_ref().openness;
+        if !self.visible && openness == 0.0 {
+            return None;
+        }
+        let mut backdrop_color: Color = self.style_val("backdrop_color").into();
+        backdrop_color.a *= openness;
+        let padding: f32 = self.style_val("padding").unwrap().f32();
+        let max_width: f32 = self.style_val("max_width").unwrap().f32();
+        let slide: f32 = self.style_val("slide_distance").unwrap().f32() * (1.0 - openness);
+        let full_screen = self.size == ModalSize::FullScreen;
+
+        let dialog = ModalDialog {
+            background_color: self.style_val("background_color").into(),
+            radius: if full_screen {
+                0.0
+            } else {
+                self.style_val("border_radius").unwrap().f32()
+            },
+            shadow: self.style_val("shadow").map(|v| v.box_shadow()),
+        };
+        let dialog_layout = match self.size.width(max_width) {
+            Some(width) => lay![
+                direction: Direction::Column,
+                size: [width, Auto],
+                padding: [padding],
+                // Centered, so this moves the dialog down by `slide`
+                margin: [slide * 2.0, 0.0, 0.0, 0.0],
+                gap: (padding / 2.0).into()
+            ],
+            None => lay![
+                direction: Direction::Column,
+                size_pct: [100],
+                padding: [padding],
+                margin: [slide * 2.0, 0.0, 0.0, 0.0],
+                gap: (padding / 2.0).into()
+            ],
+        };
+        let mut dialog = node!(dialog, dialog_layout);
+        if let Some(title) = &self.title {
+            dialog = dialog.push(
+                node!(Text::new(txt!(title.clone()))
+                    .style("size", self.style_val("title_font_size").unwrap())
+                    .style("color", self.style_val("title_color").unwrap())
+                    .maybe_style("font", self.style_val("font")))
+                .key(0),
+            );
+        }
+        if let Some(content) = self.content.lock().unwrap().take() {
+            dialog = dialog.push(Node::new(content, 1, lay![size_pct: [100, Auto]]));
+        }
+
+        Some(
+            node!(
+                ModalBackdrop {
+                    color: backdrop_color,
+                    close_on_click: self.close_on_backdrop,
+                },
+                lay![
+                    size_pct: [100],
+                    direction: Direction::Column,
+                    axis_alignment: Alignment::Center,
+                    cross_alignment: Alignment::Center,
+                    z_index_increment: 1000.0
+                ]
+            )
+            .push(dialog),
+        )
+    }
+}
+
+/// Covers everything behind a [`Modal`], so that it can't be clicked.
+#[derive(Debug)]
+struct ModalBackdrop {
+    color: Color,
+    close_on_click: bool,
+}
+
+impl Component for ModalBackdrop {
+    fn render_hash(&self, hasher: &mut ComponentHasher) {
+        self.color.hash(hasher);
+    }
+
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        event.stop_bubbling();
+        if self.close_on_click {
+            event.emit(msg!(ModalMsg::Close));
+        }
+    }
+
+    fn on_double_click(&mut self, event: &mut event::Event<event::DoubleClick>) {
+        event.stop_bubbling();
+    }
+
+    fn on_mouse_down(&mut self, event: &mut event::Event<event::MouseDown>) {
+        event.stop_bubbling();
+    }
+
+    fn on_scroll(&mut self, event: &mut event::Event<event::Scroll>) {
+        event.stop_bubbling();
+    }
+
+    fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
+        Some(vec![Renderable::Rect(Rect::new(
+            context.aabb.pos,
+            context.aabb.size(),
+            self.color,
+        ))])
+    }
+}
+
+/// The box of a [`Modal`], which holds its title and content.
+#[derive(Debug)]
+struct ModalDialog {
+    background_color: Color,
+    radius: f32,
+    shadow: Option<BoxShadow>,
+}
+
+impl Component for ModalDialog {
+    fn render_hash(&self, hasher: &mut ComponentHasher) {
+        self.background_color.hash(hasher);
+        self.radius.to_bits().hash(hasher);
+        self.shadow.hash(hasher);
+    }
+
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        // Clicks in the dialog don't reach the backdrop
+        event.stop_bubbling();
+    }
+
+    fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
+        let radius = self.radius;
+        let instance_data = RectInstanceBuilder::default()
+            .pos(context.aabb.pos)
+            .scale(context.aabb.size())
+            .color(self.background_color)
+            .radius((radius, radius, radius, radius))
+            .shadow(self.shadow)
+            .build()
+            .unwrap();
+        Some(vec![Renderable::Rect(Rect::from_instance_data(
+            instance_data,
+        ))])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::Div;
+
+    fn modal(visible: bool) -> Modal {
+        Modal::new(Div::new())
+            .visible(visible)
+            .on_close(Box::new(|| msg!("closed")))
+            .style("animation_duration_ms", 200.0)
+    }
+
+    #[test]
+    fn test_width() {
+        assert_eq!(ModalSize::Small.width(500.0), Some(300.0));
+        assert_eq!(ModalSize::Medium.width(500.0), Some(500.0));
+        assert_eq!(ModalSize::Large.width(500.0), Some(750.0));
+        assert_eq!(ModalSize::FullScreen.width(500.0), None);
+    }
+
+    #[test]
+    fn test_animation() {
+        let mut modal = modal(true);
+        assert!(modal.is_animating());
+        modal.step_animation(0.05);
+        assert_eq!(modal.state_ref().openness, 0.25);
+        modal.step_animation(1.0);
+        assert_eq!(modal.state_ref().openness, 1.0);
+        assert!(!modal.is_animating());
+
+        // Hidden, it fades back out
+        modal.visible = false;
+        modal.step_animation(0.1);
+        assert_eq!(modal.state_ref().openness, 0.5);
+    }
+
+    #[test]
+    fn test_close() {
+        let mut modal = modal(true);
+        assert!(modal.traps_focus());
+        let m = modal.update(msg!(ModalMsg::Close));
+        assert_eq!(m[0].downcast_ref::<&str>(), Some(&"closed"));
+
+        // Nothing is shown or trapped once it has faded out
+        let mut modal = modal.visible(false);
+        assert!(!modal.traps_focus());
+        modal.step_animation(1.0);
+        assert!(modal.view().is_none());
+    }
+}
@@ -0,0 +1,382 @@
+use std::fmt;
+use std::hash::Hash;
+
+use mctk_macros::{component, state_component_impl};
+
+use crate::component::{Component, ComponentHasher, Message};
+use crate::input::Key;
+use crate::layout::{Alignment, Direction, PositionType};
+use crate::style::Styled;
+use crate::{event, lay, msg, node, rect, size, size_pct, txt, Color, Node};
+
+use super::{Button, Div, TextBox};
+
+#[derive(Debug)]
+enum NumberInputMsg {
+    Edited(String),
+    Committed(String),
+    Step(f64),
+}
+
+#[derive(Debug, Default)]
+struct NumberInputState {
+    /// The text given to the text box. It only changes when the value is set, and not while
+    /// typing, since a new text resets the text box.
+    shown: String,
+    /// What has been typed since the value was last set.
+    editing: Option<String>,
+    /// Bumped to replace the text box, when its typed text has to be thrown away.
+    generation: u64,
+}
+
+/// A [`TextBox`] for numbers, with buttons to step the value down and up.
+///
+/// The Up and Down arrow keys also step the value. Typed values are reported as they are entered,
+/// as long as they are within `min` and `max`. Otherwise the border is drawn in `error_color`,
+/// until the text box loses the focus and the value is clamped.
+///
+/// The style is that of [`TextBox`], with `button_width` for the width of each step button.
+#[component(State = "NumberInputState", Styled = "TextBox", Internal)]
+pub struct NumberInput {
+    pub value: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub step: f64,
+    /// The number of decimal places that values are rounded to.
+    pub precision: u8,
+    pub on_change: Option<Box<dyn Fn(f64) -> Message + Send + Sync>>,
+    /// Shows values in place of the default of `precision` decimal places, e.g. with a currency
+    /// or unit. Typed text is read by its digits, sign and decimal point, so values like `$1.50`
+    /// or `3 kg` can be edited in place.
+    pub format_value: Option<Box<dyn Fn(f64) -> String + Send + Sync>>,
+}
+
+impl fmt::Debug for NumberInput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NumberInput")
+            .field("value", &self.value)
+            .field("min", &self.min)
+            .field("max", &self.max)
+            .field("step", &self.step)
+            .field("precision", &self.precision)
+            .finish()
+    }
+}
+
+impl NumberInput {
+    pub fn new(value: f64) -> Self {
+        Self {
+            value,
+            min: None,
+            max: None,
+            step: 1.0,
+            precision: 0,
+            on_change: None,
+            format_value: None,
+            state: Some(NumberInputState::default()),
+            dirty: false,
+            class: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    pub fn min(mut self, min: f64) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = step;
+        self
+    }
+
+    pub fn precision(mut self, precision: u8) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    pub fn on_change(mut self, change_fn: Box<dyn Fn(f64) -> Message + Send + Sync>) -> Self {
+        self.on_change = Some(change_fn);
+        self
+    }
+
+    pub fn format_value(mut self, format_fn: Box<dyn Fn(f64) -> String + Send + Sync>) -> Self {
+        self.format_value = Some(format_fn);
+        self
+    }
+
+    fn format(&self, value: f64) -> String {
+        match &self.format_value {
+            Some(format_fn) => format_fn(value),
+            None => format!("{:.*}", self.precision as usize, value),
+        }
+    }
+
+    fn parse(text: &str) -> Option<f64> {
+        let number: String = text
+            .chars()
+            .filter(|c| c.is_ascii_digit() || *c == '.' || *c == '-')
+            .collect();
+        number.parse().ok()
+    }
+
+    fn in_range(&self, value: f64) -> bool {
+        self.min.map_or(true, |min| value >= min) && self.max.map_or(true, |max| value <= max)
+    }
+
+    /// Rounds `value` to the precision, and clamps it to the range.
+    fn constrain(&self, value: f64) -> f64 {
+        let scale = 10f64.powi(self.precision as i32);
+        let mut value = (value * scale).round() / scale;
+        if let Some(max) = self.max {
+            value = value.min(max);
+        }
+        if let Some(min) = self.min {
+            value = value.max(min);
+        }
+        value
+    }
+
+    /// Whether the typed text is not a number, or is out of range.
+    fn is_invalid(&self) -> bool {
+        match &self.state_ref().editing {
+            Some(text) => !Self::parse(text).map_or(false, |v| self.in_range(v)),
+            None => false,
+        }
+    }
+
+    /// Show `value`, discarding any typed text, and report it if it is a change.
+    fn set(&mut self, value: f64) -> Option<Message> {
+        let shown = self.format(value);
+        // The text box keeps what was typed unless it is replaced
+        if self
+            .state_mut()
+            .editing
+            .take()
+            .map_or(false, |text| text != shown)
+        {
+            self.state_mut().generation += 1;
+        }
+        self.state_mut().shown = shown;
+        if value == self.value {
+            return None;
+        }
+        self.on_change.as_ref().map(|f| f(value))
+    }
+
+    /// Step the value by `steps`, from what has been typed if it is a number.
+    fn step_by(&mut self, steps: f64) -> Option<Message> {
+        let from = self
+            .state_ref()
+            .editing
+            .as_deref()
+            .and_then(Self::parse)
+            .unwrap_or(self.value);
+        // Keep the text box, which may have the focus. Its new text resets it anyway.
+        self.state_mut().editing = None;
+        self.set(self.constrain(from + steps * self.step))
+    }
+}
+
+#[state_component_impl(NumberInputState)]
+impl Component for NumberInput {
+    fn init(&mut self) {
+        self.state_mut().shown = self.format(self.value);
+    }
+
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.value.to_bits().hash(hasher);
+        self.min.map(f64::to_bits).hash(hasher);
+        self.max.map(f64::to_bits).hash(hasher);
+        self.precision.hash(hasher);
+    }
+
+    fn new_props(&mut self) {
+        // Don't overwrite what is being typed with the value it was reported as
+        if self.state_ref().editing.is_none() {
+            self.state_mut().shown = self.format(self.value);
+        }
+    }
+
+    fn render_hash(&self, hasher: &mut ComponentHasher) {
+        self.state_ref().shown.hash(hasher);
+        self.state_ref().generation.hash(hasher);
+        self.is_invalid().hash(hasher);
+    }
+
+    fn on_key_down(&mut self, event: &mut event::Event<event::KeyDown>) {
+        let steps = match event.input.0 {
+            Key::Up => 1.0,
+            Key::Down => -1.0,
+            _ => return,
+        };
+        event.stop_bubbling();
+        if let Some(m) = self.step_by(steps) {
+            event.emit(m);
+        }
+    }
+
+    fn update(&mut self, message: Message) -> Vec<Message> {
+        let m = match message.downcast_ref::<NumberInputMsg>() {
+            Some(NumberInputMsg::Edited(text)) => {
+                self.state_mut().editing = Some(text.clone());
+                match Self::parse(text) {
+                    Some(value) if self.in_range(value) && value != self.value => {
+                        self.on_change.as_ref().map(|f| f(value))
+                    }
+                    _ => None,
+                }
+            }
+            Some(NumberInputMsg::Committed(text)) => {
+                let value = Self::parse(text).map_or(self.value, |v| self.constrain(v));
+                self.state_mut().editing = Some(text.clone());
+                self.set(value)
+            }
+            Some(NumberInputMsg::Step(steps)) => self.step_by(*steps),
+            None => return vec![message],
+        };
+        m.into_iter().collect()
+    }
+
+    fn view(&self) -> Option<Node> {
+        let button_width: f32 = self.style_val("button_width").unwrap().f32();
+        let mut text_box = TextBox::new(Some(self.state_ref().shown.clone()))
+            .on_change(Box::new(|s| msg!(NumberInputMsg::Edited(s.to_string()))))
+            .on_commit(Box::new(|s| msg!(NumberInputMsg::Committed(s.to_string()))));
+        *text_box.class_mut() = self.class;
+        *text_box.style_overrides_mut() = self.style_overrides.clone();
+        if self.is_invalid() {
+            let error_color: Color = self.style_val("error_color").into();
+            text_box = text_box.style("border_color", error_color);
+        }
+
+        let button = |label: &str, steps: f64, disabled: bool| {
+            node!(
+                Button::new(txt!(label))
+                    .on_click(Box::new(move || msg!(NumberInputMsg::Step(steps))))
+                    .disabled(disabled),
+                lay![size: [button_width, Auto]]
+            )
+        };
+        let at_min = self.min.map_or(false, |min| self.value <= min);
+        let at_max = self.max.map_or(false, |max| self.value >= max);
+
+        Some(
+            node!(
+                Div::new(),
+                lay![
+                    size_pct: [100],
+                    padding: [0.0, 0.0, 0.0, button_width * 2.0]
+                ]
+            )
+            .push(node!(text_box, lay![size_pct: [100]]).key(self.state_ref().generation))
+            .push(
+                node!(
+                    Div::new(),
+                    lay![
+                        direction: Direction::Row,
+                        cross_alignment: Alignment::Stretch,
+                        position_type: PositionType::Absolute,
+                        position: [0.0, Auto, 0.0, 0.0],
+                        size: [button_width * 2.0, Auto]
+                    ]
+                )
+                .push(button("-", -1.0, at_min).key(0))
+                .push(button("+", 1.0, at_max).key(1)),
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(value: f64) -> NumberInput {
+        let mut input = NumberInput::new(value)
+            .min(0.0)
+            .max(10.0)
+            .step(0.5)
+            .precision(1)
+            .on_change(Box::new(|value| msg!(value)));
+        input.init();
+        input
+    }
+
+    fn changed(m: Vec<Message>) -> Option<f64> {
+        m.first().and_then(|m| m.downcast_ref::<f64>().copied())
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(NumberInput::parse("$1.50"), Some(1.5));
+        assert_eq!(NumberInput::parse(" -3 kg"), Some(-3.0));
+        assert_eq!(NumberInput::parse("kg"), None);
+    }
+
+    #[test]
+    fn test_step() {
+        let mut input = input(9.8);
+        assert_eq!(input.state_ref().shown, "9.8");
+        assert_eq!(
+            changed(input.update(msg!(NumberInputMsg::Step(1.0)))),
+            Some(10.0)
+        );
+        assert_eq!(input.state_ref().shown, "10.0");
+        assert_eq!(
+            changed(input.update(msg!(NumberInputMsg::Step(-1.0)))),
+            Some(9.3)
+        );
+
+        // Steps from the typed value
+        input.update(msg!(NumberInputMsg::Edited("2".to_string())));
+        assert_eq!(
+            changed(input.update(msg!(NumberInputMsg::Step(1.0)))),
+            Some(2.5)
+        );
+        assert!(input.state_ref().editing.is_none());
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let mut input = input(5.0);
+        assert_eq!(
+            changed(input.update(msg!(NumberInputMsg::Edited("7".to_string())))),
+            Some(7.0)
+        );
+        assert!(!input.is_invalid());
+
+        // Not reported until committed, when it is clamped
+        assert!(input
+            .update(msg!(NumberInputMsg::Edited("70".to_string())))
+            .is_empty());
+        assert!(input.is_invalid());
+        let generation = input.state_ref().generation;
+        assert_eq!(
+            changed(input.update(msg!(NumberInputMsg::Committed("70".to_string())))),
+            Some(10.0)
+        );
+        assert!(!input.is_invalid());
+        assert_eq!(input.state_ref().generation, generation + 1);
+
+        // Text that isn't a number goes back to the value
+        input.update(msg!(NumberInputMsg::Edited("abc".to_string())));
+        assert!(input.is_invalid());
+        assert!(input
+            .update(msg!(NumberInputMsg::Committed("abc".to_string())))
+            .is_empty());
+        assert_eq!(input.state_ref().shown, "5.0");
+    }
+
+    #[test]
+    fn test_format_value() {
+        let input = input(2.5).format_value(Box::new(|v| format!("{v:.2} kg")));
+        assert_eq!(input.format(2.5), "2.50 kg");
+        assert_eq!(NumberInput::parse(&input.format(2.5)), Some(2.5));
+    }
+}
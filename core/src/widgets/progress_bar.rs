@@ -0,0 +1,227 @@
+use std::fmt;
+use std::hash::Hash;
+use std::time::Instant;
+
+use mctk_macros::{component, state_component_impl};
+
+use crate::component::{Component, ComponentHasher, RenderContext};
+use crate::event;
+use crate::renderables::{rect::InstanceBuilder as RectInstanceBuilder, Rect, Renderable};
+use crate::style::Styled;
+use crate::types::*;
+
+use super::Orientation;
+
+#[derive(Debug, Default)]
+struct ProgressBarState {
+    /// How far through its sweep an indeterminate bar is, from 0 to 1.
+    phase: f32,
+    last_tick: Option<Instant>,
+}
+
+/// Shows how far along a task is, as a fill between `min` and `max`.
+///
+/// Without a `value`, the progress is unknown, and a segment sweeps along the track instead,
+/// taking the `animation_duration_ms` style parameter to cross it.
+#[component(State = "ProgressBarState", Styled, Internal)]
+pub struct ProgressBar {
+    pub value: Option<f32>,
+    pub min: f32,
+    pub max: f32,
+    pub orientation: Orientation,
+}
+
+impl fmt::Debug for ProgressBar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ProgressBar")
+            .field("value", &self.value)
+            .field("min", &self.min)
+            .field("max", &self.max)
+            .field("orientation", &self.orientation)
+            .finish()
+    }
+}
+
+impl Default for ProgressBar {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl ProgressBar {
+    /// The length of the indeterminate segment, as a fraction of the track.
+    const SWEEP_LENGTH: f32 = 0.3;
+
+    /// A horizontal bar from 0 to 100.
+    pub fn new(value: Option<f32>) -> Self {
+        Self {
+            value,
+            min: 0.0,
+            max: 100.0,
+            orientation: Orientation::Horizontal,
+            state: Some(ProgressBarState::default()),
+            dirty: false,
+            class: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    pub fn range(mut self, min: f32, max: f32) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Advance the indeterminate sweep by `dt` seconds.
+    fn step_animation(&mut self, dt: f32) {
+        let duration_ms = self
+            .style_val("animation_duration_ms")
+            .map(|v| v.f32())
+            .unwrap_or(0.0);
+        if self.value.is_none() && duration_ms > 0.0 {
+            let phase = self.state_ref().phase + dt * 1000.0 / duration_ms;
+            self.state_mut().phase = phase.fract();
+        }
+    }
+
+    /// The part of the track that is filled, as fractions of its length from the start.
+    fn filled(&self) -> (f32, f32) {
+        match self.value {
+            Some(value) if self.max > self.min => (
+                0.0,
+                ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0),
+            ),
+            Some(_) => (0.0, 0.0),
+            None => {
+                let head = self.state_ref().phase * (1.0 + Self::SWEEP_LENGTH);
+                ((head - Self::SWEEP_LENGTH).max(0.0), head.min(1.0))
+            }
+        }
+    }
+
+    /// The track, `thickness` across and centered in `aabb`, and the part of it in `span`.
+    fn bars(&self, aabb: AABB, thickness: f32, span: (f32, f32)) -> (AABB, AABB) {
+        let AABB { pos, .. } = aabb;
+        let Scale { width, height } = aabb.size();
+        match self.orientation {
+            Orientation::Horizontal => {
+                let y = pos.y + (height - thickness) / 2.0;
+                (
+                    AABB::new(Pos::new(pos.x, y, pos.z), Scale::new(width, thickness)),
+                    AABB::new(
+                        Pos::new(pos.x + width * span.0, y, pos.z),
+                        Scale::new(width * (span.1 - span.0), thickness),
+                    ),
+                )
+            }
+            Orientation::Vertical => {
+                let x = pos.x + (width - thickness) / 2.0;
+                (
+                    AABB::new(Pos::new(x, pos.y, pos.z), Scale::new(thickness, height)),
+                    AABB::new(
+                        Pos::new(x, pos.y + height * (1.0 - span.1), pos.z),
+                        Scale::new(thickness, height * (span.1 - span.0)),
+                    ),
+                )
+            }
+        }
+    }
+}
+
+#[state_component_impl(ProgressBarState)]
+impl Component for ProgressBar {
+    fn render_hash(&self, hasher: &mut ComponentHasher) {
+        self.value.map(f32::to_bits).hash(hasher);
+        self.min.to_bits().hash(hasher);
+        self.max.to_bits().hash(hasher);
+        self.orientation.hash(hasher);
+        if self.value.is_none() {
+            ((self.state_ref().phase * 1000.0) as u32).hash(hasher);
+        }
+    }
+
+    fn on_tick(&mut self, _event: &mut event::Event<event::Tick>) {
+        if self.value.is_some() {
+            self.state_mut().last_tick = None;
+            return;
+        }
+        let now = Instant::now();
+        let dt = self
+            .state_ref()
+            .last_tick
+            .map(|t| now.duration_since(t).as_secs_f32())
+            .unwrap_or(0.0);
+        self.state_mut().last_tick = Some(now);
+        self.step_animation(dt);
+    }
+
+    fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
+        let thickness: f32 = self.style_val("height").unwrap().f32();
+        let radius: f32 = self.style_val("radius").unwrap().f32();
+        let (track, fill) = self.bars(context.aabb, thickness, self.filled());
+
+        let mut rs = vec![];
+        for (aabb, color) in [
+            (track, self.style_val("track_color").into()),
+            (fill, self.style_val("fill_color").into()),
+        ] {
+            if aabb.width() <= 0.0 || aabb.height() <= 0.0 {
+                continue;
+            }
+            let instance_data = RectInstanceBuilder::default()
+                .pos(aabb.pos)
+                .scale(aabb.size())
+                .color(color)
+                .radius((radius, radius, radius, radius))
+                .build()
+                .unwrap();
+            rs.push(Renderable::Rect(Rect::from_instance_data(instance_data)));
+        }
+        Some(rs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_determinate() {
+        let bar = ProgressBar::new(Some(15.0)).range(10.0, 30.0);
+        assert_eq!(bar.filled(), (0.0, 0.25));
+        assert_eq!(ProgressBar::new(Some(200.0)).filled(), (0.0, 1.0));
+
+        let aabb = AABB::new(Pos::new(10.0, 20.0, 0.0), Scale::new(40.0, 100.0));
+        let (track, fill) = bar
+            .orientation(Orientation::Vertical)
+            .bars(aabb, 4.0, (0.0, 0.25));
+        assert_eq!(track.pos, Pos::new(28.0, 20.0, 0.0));
+        // Fills up from the bottom
+        assert_eq!(fill.pos, Pos::new(28.0, 95.0, 0.0));
+        assert_eq!(fill.size(), Scale::new(4.0, 25.0));
+    }
+
+    #[test]
+    fn test_indeterminate_sweep() {
+        let mut bar = ProgressBar::new(None).style("animation_duration_ms", 1000.0);
+        assert_eq!(bar.filled(), (0.0, 0.0));
+
+        bar.step_animation(0.5);
+        let (start, end) = bar.filled();
+        assert!((start - 0.35).abs() < 1e-5 && (end - 0.65).abs() < 1e-5);
+
+        // Wraps around to the start
+        bar.step_animation(0.75);
+        assert!((bar.state_ref().phase - 0.25).abs() < 1e-5);
+
+        // A determinate bar does not move
+        let mut bar = ProgressBar::new(Some(1.0)).style("animation_duration_ms", 1000.0);
+        bar.step_animation(0.5);
+        assert_eq!(bar.state_ref().phase, 0.0);
+    }
+}
@@ -5,9 +5,9 @@ use std::hash::Hash;
 use crate::component::{Component, ComponentHasher, Message};
 use crate::font_cache::TextSegment;
 use crate::renderables::circle::InstanceBuilder as CircleInstanceBuilder;
-use crate::renderables::{Circle, Renderable};
-use crate::style::{FontWeight, HorizontalPosition, Styled};
-use crate::{event, lay, msg, rect, size, size_pct, txt, Point, Pos, AABB};
+use crate::renderables::{Circle, Rect, Renderable};
+use crate::style::{CursorStyle, FontWeight, HorizontalPosition, Styled};
+use crate::{event, lay, msg, rect, size, size_pct, txt, Key, Point, Pos, Scale, AABB};
 use crate::{layout::*, Color};
 use crate::{node, Node};
 use mctk_macros::{component, state_component_impl};
@@ -25,16 +25,23 @@ pub enum RadioButtonsType {
 #[derive(Debug, Default)]
 struct RadioButtonsState {
     selected: usize,
+    /// The button that arrow keys move from, while one of the buttons is focused.
+    focused: Option<usize>,
+    indeterminate: Option<usize>,
 }
 
 #[component(State = "RadioButtonsState", Styled = "RadioButton", Internal)]
 pub struct RadioButtons {
     buttons: Vec<(Vec<TextSegment>, Vec<TextSegment>)>,
     selected: Option<Vec<TextSegment>>,
+    /// A button that is shown as neither checked nor unchecked, until it is selected.
+    indeterminate: Option<usize>,
     direction: Direction,
     max_rows: Option<usize>,
     max_columns: Option<usize>,
     on_change: Option<Box<dyn Fn(String) -> Message + Send + Sync>>,
+    /// Like `on_change`, with the position of the selected button.
+    on_select: Option<Box<dyn Fn(usize) -> Message + Send + Sync>>,
     radio_buttons_type: RadioButtonsType,
 }
 
@@ -49,6 +56,8 @@ impl fmt::Debug for RadioButtons {
 
 enum RadioButtonMsg {
     Clicked(usize, String),
+    KeyDown(Key),
+    Blurred,
 }
 
 impl RadioButtons {
@@ -56,10 +65,12 @@ impl RadioButtons {
         Self {
             buttons,
             selected: None,
+            indeterminate: None,
             direction: Direction::Row,
             max_rows: None,
             max_columns: None,
             on_change: None,
+            on_select: None,
             class: Default::default(),
             style_overrides: Default::default(),
             radio_buttons_type: Default::default(),
@@ -73,6 +84,11 @@ impl RadioButtons {
         self
     }
 
+    pub fn indeterminate(mut self, position: usize) -> Self {
+        self.indeterminate = Some(position);
+        self
+    }
+
     pub fn direction(mut self, direction: Direction) -> Self {
         self.direction = direction;
         self
@@ -93,16 +109,63 @@ impl RadioButtons {
         self
     }
 
+    pub fn on_select(mut self, select_fn: Box<dyn Fn(usize) -> Message + Send + Sync>) -> Self {
+        self.on_select = Some(select_fn);
+        self
+    }
+
     pub fn radio_buttons_type(mut self, t: RadioButtonsType) -> Self {
         self.radio_buttons_type = t;
         self
     }
+
+    fn select(&mut self, position: usize) -> Vec<Message> {
+        self.state_mut().selected = position;
+        if self.state_ref().indeterminate == Some(position) {
+            self.state_mut().indeterminate = None;
+        }
+        let mut m: Vec<Message> = vec![];
+        if let Some(change_fn) = &self.on_change {
+            m.push(change_fn(
+                self.buttons[position].1.get(0).unwrap().text.clone(),
+            ));
+        }
+        if let Some(select_fn) = &self.on_select {
+            m.push(select_fn(position));
+        }
+        m
+    }
+
+    /// Follows the WAI-ARIA radio group pattern: the arrow keys move the focus and selection
+    /// together, wrapping around at the ends, and Space or Return selects the focused button.
+    fn handle_key(&mut self, key: Key) -> Vec<Message> {
+        let len = self.buttons.len();
+        let focused = match self.state_ref().focused {
+            Some(focused) if len > 0 => focused,
+            _ => return vec![],
+        };
+        let next = match key {
+            Key::Down | Key::Right => (focused + 1) % len,
+            Key::Up | Key::Left => (focused + len - 1) % len,
+            Key::Space | Key::Return => focused,
+            _ => return vec![],
+        };
+        self.state_mut().focused = Some(next);
+        if next == self.state_ref().selected && self.state_ref().indeterminate != Some(next) {
+            return vec![];
+        }
+        self.select(next)
+    }
 }
 
 #[state_component_impl(RadioButtonsState)]
 impl Component for RadioButtons {
     fn init(&mut self) {
-        if self.selected.is_none() || self.state.is_none() {
+        if self.state.is_none() {
+            return;
+        }
+        self.state_mut().indeterminate = self.indeterminate;
+        if self.selected.is_none() {
             return;
         }
 
@@ -120,10 +183,13 @@ impl Component for RadioButtons {
 
     fn props_hash(&self, hasher: &mut ComponentHasher) {
         self.selected.hash(hasher);
+        self.indeterminate.hash(hasher);
     }
 
     fn render_hash(&self, hasher: &mut ComponentHasher) {
         self.state_ref().selected.hash(hasher);
+        self.state_ref().focused.hash(hasher);
+        self.state_ref().indeterminate.hash(hasher);
     }
 
     fn view(&self) -> Option<Node> {
@@ -197,21 +263,31 @@ impl Component for RadioButtons {
             };
 
             let selected = self.state_ref().selected == position;
-            let radius: f32 = self.style_val("radius").unwrap().f32();
+            let focused = self.state_ref().focused == Some(position);
+            let indeterminate = self.state_ref().indeterminate == Some(position);
+            let radius = self.style_val("radius").unwrap().border_radius();
 
             let radio_button_radius = match self.radio_buttons_type {
                 RadioButtonsType::Basic => (14., 14., 14., 14.),
                 RadioButtonsType::Group => (10., 10., 10., 10.),
                 RadioButtonsType::Block => (
-                    if row == 0 && col == 0 { radius } else { 0.0 },
+                    if row == 0 && col == 0 {
+                        radius.top_left
+                    } else {
+                        0.0
+                    },
                     if row == 0 && (col + 1 == n_columns || position + 1 == len) {
-                        radius
+                        radius.top_right
+                    } else {
+                        0.0
+                    },
+                    if position + 1 == len {
+                        radius.bottom_right
                     } else {
                         0.0
                     },
-                    if position + 1 == len { radius } else { 0.0 },
                     if col == 0 && (row + 1 == n_rows || position + 1 == len) {
-                        radius
+                        radius.bottom_left
                     } else {
                         0.0
                     },
@@ -226,6 +302,8 @@ impl Component for RadioButtons {
                         radio_button_type: self.radio_buttons_type,
                         position,
                         selected,
+                        focused,
+                        indeterminate,
                         radius: radio_button_radius,
                         class: self.class,
                         style_overrides: self.style_overrides.clone(),
@@ -253,18 +331,18 @@ impl Component for RadioButtons {
     }
 
     fn update(&mut self, message: Message) -> Vec<Message> {
-        let mut m: Vec<Message> = vec![];
-
         match message.downcast_ref::<RadioButtonMsg>() {
-            Some(RadioButtonMsg::Clicked(position, label)) => {
-                self.state_mut().selected = *position;
-                if let Some(change_fn) = &self.on_change {
-                    m.push(change_fn(label.clone()));
-                }
+            Some(RadioButtonMsg::Clicked(position, _)) => {
+                self.state_mut().focused = Some(*position);
+                self.select(*position)
+            }
+            Some(RadioButtonMsg::KeyDown(key)) => self.handle_key(*key),
+            Some(RadioButtonMsg::Blurred) => {
+                self.state_mut().focused = None;
+                vec![]
             }
             None => panic!(),
         }
-        m
     }
 }
 
@@ -275,13 +353,63 @@ struct RadioButton {
     value: Vec<TextSegment>,
     position: usize,
     selected: bool,
+    /// Whether keyboard navigation of the group is at this button.
+    focused: bool,
+    /// Shown with a dash rather than a dot. An indeterminate button is never checked.
+    indeterminate: bool,
     radius: (f32, f32, f32, f32),
     radio_button_type: RadioButtonsType,
 }
 
+impl RadioButton {
+    fn is_checked(&self) -> bool {
+        self.selected && !self.indeterminate
+    }
+
+    /// The mark inside the circle centered on `origin`: a dot when checked, or a dash when
+    /// indeterminate.
+    fn indicator(
+        &self,
+        origin: Pos,
+        radius: f32,
+        active_color: Color,
+        indeterminate_color: Color,
+    ) -> Option<Renderable> {
+        if self.indeterminate {
+            let width = radius;
+            let height = 2.0;
+            Some(Renderable::Rect(Rect::new(
+                Pos::new(origin.x - width / 2.0, origin.y - height / 2.0, origin.z),
+                Scale { width, height },
+                indeterminate_color,
+            )))
+        } else if self.is_checked() {
+            let instance = CircleInstanceBuilder::default()
+                .origin(origin)
+                .radius(radius - 2.)
+                .color(Some(active_color))
+                .build()
+                .unwrap();
+            Some(Renderable::Circle(Circle::from_instance_data(instance)))
+        } else {
+            None
+        }
+    }
+}
+
 impl Component for RadioButton {
+    fn cursor(&self) -> Option<CursorStyle> {
+        self.style_val("cursor").map(|v| v.cursor())
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
     fn props_hash(&self, hasher: &mut ComponentHasher) {
         self.selected.hash(hasher);
+        self.focused.hash(hasher);
+        self.indeterminate.hash(hasher);
     }
 
     fn render(
@@ -290,6 +418,7 @@ impl Component for RadioButton {
     ) -> Option<Vec<crate::renderables::Renderable>> {
         let active_color: Color = self.style_val("active_color").into();
         let border_color: Color = self.style_val("border_color").into();
+        let indeterminate_color: Color = self.style_val("indeterminate_color").into();
 
         let width = context.aabb.width();
         let height = context.aabb.height();
@@ -305,7 +434,7 @@ impl Component for RadioButton {
             })
             .radius(radius)
             .border_width(2.)
-            .border_color(Some(if self.selected {
+            .border_color(Some(if self.is_checked() {
                 active_color
             } else {
                 border_color
@@ -316,21 +445,13 @@ impl Component for RadioButton {
             circle_instance_data,
         )));
 
-        let circle_instance_data_2 = CircleInstanceBuilder::default()
-            .origin(Pos {
-                x: pos.x + width - 10. - radius / 2.,
-                y: pos.y + height / 2.,
-                z: 0.,
-            })
-            .radius(radius - 2.)
-            .color(Some(active_color))
-            .build()
-            .unwrap();
-
-        if self.selected {
-            rs.push(Renderable::Circle(Circle::from_instance_data(
-                circle_instance_data_2,
-            )));
+        let origin = Pos {
+            x: pos.x + width - 10. - radius / 2.,
+            y: pos.y + height / 2.,
+            z: 0.,
+        };
+        if let Some(indicator) = self.indicator(origin, radius, active_color, indeterminate_color) {
+            rs.push(indicator);
         }
 
         Some(rs)
@@ -341,7 +462,11 @@ impl Component for RadioButton {
         let active_color: Color = self.style_val("active_color").into();
         let highlight_color: Color = self.style_val("highlight_color").into();
         let background_color: Color = self.style_val("background_color").into();
-        let border_color: Color = self.style_val("border_color").into();
+        let border_color: Color = if self.focused {
+            highlight_color
+        } else {
+            self.style_val("border_color").into()
+        };
         let border_width: f32 = self.style_val("border_width").unwrap().f32();
 
         match self.radio_button_type {
@@ -358,7 +483,7 @@ impl Component for RadioButton {
 
                 let circle = node!(
                     super::RoundedRect {
-                        background_color: if self.selected {
+                        background_color: if self.is_checked() {
                             active_color
                         } else {
                             background_color
@@ -367,7 +492,8 @@ impl Component for RadioButton {
                         border_width: (border_width, border_width, border_width, border_width),
                         radius: self.radius,
                         scissor: None,
-                        swipe: 0
+                        swipe: 0,
+                        shadow: None,
                     },
                     lay!(
                         size: size_pct!(100.0),
@@ -413,7 +539,7 @@ impl Component for RadioButton {
             RadioButtonsType::Block => {
                 let base = node!(
                     super::RoundedRect {
-                        background_color: if self.selected {
+                        background_color: if self.is_checked() {
                             active_color
                         } else {
                             background_color
@@ -422,7 +548,8 @@ impl Component for RadioButton {
                         border_width: (border_width, border_width, border_width, border_width),
                         radius: self.radius,
                         scissor: None,
-                        swipe: 0
+                        swipe: 0,
+                        shadow: None,
                     },
                     lay!(
                         size: size_pct!(100.0),
@@ -444,6 +571,8 @@ impl Component for RadioButton {
 
     fn on_click(&mut self, event: &mut event::Event<event::Click>) {
         event.stop_bubbling();
+        // The group handles key events from whichever of its buttons is focused
+        event.focus();
         event.emit(msg!(RadioButtonMsg::Clicked(
             self.position,
             self.value.get(0).unwrap().text.clone()
@@ -458,4 +587,94 @@ impl Component for RadioButton {
             self.value.get(0).unwrap().text.clone()
         )));
     }
+
+    fn on_key_down(&mut self, event: &mut event::Event<event::KeyDown>) {
+        if event.input.0 == Key::Tab {
+            // Tab leaves the group as a whole, rather than moving between its buttons
+            event.blur();
+        } else {
+            event.emit(msg!(RadioButtonMsg::KeyDown(event.input.0)));
+        }
+    }
+
+    fn on_blur(&mut self, event: &mut event::Event<event::Blur>) {
+        event.emit(msg!(RadioButtonMsg::Blurred));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group() -> RadioButtons {
+        RadioButtons::new(vec![
+            (txt!("One"), txt!("1")),
+            (txt!("Two"), txt!("2")),
+            (txt!("Three"), txt!("3")),
+        ])
+        .on_select(Box::new(|position| msg!(position)))
+    }
+
+    #[test]
+    fn test_arrow_keys() {
+        let mut group = group();
+        group.update(msg!(RadioButtonMsg::Clicked(0, "1".to_string())));
+
+        let m = group.update(msg!(RadioButtonMsg::KeyDown(Key::Down)));
+        assert_eq!(m.len(), 1);
+        assert_eq!(m[0].downcast_ref::<usize>(), Some(&1));
+        assert_eq!(group.state_ref().selected, 1);
+        assert_eq!(group.state_ref().focused, Some(1));
+
+        // Wraps around to the last button
+        group.update(msg!(RadioButtonMsg::KeyDown(Key::Up)));
+        group.update(msg!(RadioButtonMsg::KeyDown(Key::Up)));
+        assert_eq!(group.state_ref().selected, 2);
+
+        // Selecting the selected button is not a change
+        assert!(group
+            .update(msg!(RadioButtonMsg::KeyDown(Key::Space)))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_indeterminate() {
+        let button = RadioButton {
+            label: txt!("All"),
+            value: txt!("all"),
+            position: 0,
+            selected: true,
+            focused: false,
+            indeterminate: true,
+            radius: (0., 0., 0., 0.),
+            radio_button_type: RadioButtonsType::Group,
+            class: None,
+            style_overrides: Default::default(),
+        };
+        // Indeterminate takes precedence over checked
+        assert!(!button.is_checked());
+        let indicator = button.indicator(Pos::default(), 9.0, Color::BLUE, Color::MID_GREY);
+        assert!(matches!(indicator, Some(Renderable::Rect(_))));
+
+        let mut group = group().indeterminate(0);
+        group.init();
+        group.update(msg!(RadioButtonMsg::Clicked(0, "1".to_string())));
+        assert_eq!(group.state_ref().selected, 0);
+        assert_eq!(group.state_ref().indeterminate, None);
+    }
+
+    #[test]
+    fn test_keys_without_focus() {
+        let mut group = group();
+        assert!(group
+            .update(msg!(RadioButtonMsg::KeyDown(Key::Down)))
+            .is_empty());
+        assert_eq!(group.state_ref().selected, 0);
+
+        group.update(msg!(RadioButtonMsg::Clicked(1, "2".to_string())));
+        group.update(msg!(RadioButtonMsg::Blurred));
+        assert!(group
+            .update(msg!(RadioButtonMsg::KeyDown(Key::Down)))
+            .is_empty());
+    }
 }
@@ -1,8 +1,9 @@
 use crate::component::{Component, ComponentHasher, RenderContext};
 
-use crate::renderables::rect::InstanceBuilder;
+use crate::renderables::rect::{Gradient, InstanceBuilder};
 use crate::renderables::types::{Point, Size};
 use crate::renderables::{Rect, Renderable};
+use crate::style::BoxShadow;
 use crate::types::*;
 use std::hash::Hash;
 
@@ -14,6 +15,8 @@ pub struct RoundedRect {
     pub radius: (f32, f32, f32, f32),
     pub scissor: Option<bool>,
     pub swipe: i32,
+    /// Drawn behind the rect.
+    pub shadow: Option<BoxShadow>,
 }
 
 impl Default for RoundedRect {
@@ -25,6 +28,7 @@ impl Default for RoundedRect {
             radius: (3.0, 3.0, 3.0, 3.0),
             scissor: None,
             swipe: 0,
+            shadow: None,
         }
     }
 }
@@ -38,6 +42,7 @@ impl RoundedRect {
             radius: (radius, radius, radius, radius),
             scissor: None,
             swipe: 0,
+            shadow: None,
         }
     }
 
@@ -46,10 +51,37 @@ impl RoundedRect {
         self
     }
 
+    /// Fill the background with `g` instead of the `background_color`.
+    pub fn background_gradient(self, g: Gradient) -> GradientRoundedRect {
+        GradientRoundedRect {
+            rect: self,
+            gradient: g,
+        }
+    }
+
     pub fn swipe(mut self, s: i32) -> Self {
         self.swipe = s;
         self
     }
+
+    pub fn shadow(mut self, shadow: BoxShadow) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+
+    fn instance_builder(&self, aabb: AABB) -> InstanceBuilder {
+        let mut builder = InstanceBuilder::default();
+        builder
+            .pos(aabb.pos)
+            .scale(aabb.size())
+            .color(self.background_color)
+            .border_color(self.border_color)
+            .border_size(self.border_width)
+            .scissor(self.scissor)
+            .radius(self.radius)
+            .shadow(self.shadow);
+        builder
+    }
 }
 
 impl Component for RoundedRect {
@@ -62,22 +94,38 @@ impl Component for RoundedRect {
         (self.radius.2 as i32).hash(hasher);
         (self.radius.3 as i32).hash(hasher);
         (self.swipe.hash(hasher));
+        self.shadow.hash(hasher);
     }
 
     fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
         // println!("Rounded rect render {:?}", self.scissor);
-        let width = context.aabb.width();
-        let height = context.aabb.height();
-        let AABB { pos, .. } = context.aabb;
+        let instance_data = self.instance_builder(context.aabb).build().unwrap();
 
-        let instance_data = InstanceBuilder::default()
-            .pos(pos)
-            .scale(Scale { width, height })
-            .color(self.background_color)
-            .border_color(self.border_color)
-            .border_size(self.border_width)
-            .scissor(self.scissor)
-            .radius(self.radius)
+        Some(vec![Renderable::Rect(Rect::from_instance_data(
+            instance_data,
+        ))])
+    }
+}
+
+/// A [`RoundedRect`] with its background filled with a gradient. Made with
+/// [`RoundedRect::background_gradient`].
+#[derive(Debug)]
+pub struct GradientRoundedRect {
+    pub rect: RoundedRect,
+    pub gradient: Gradient,
+}
+
+impl Component for GradientRoundedRect {
+    fn render_hash(&self, hasher: &mut ComponentHasher) {
+        self.rect.render_hash(hasher);
+        self.gradient.hash(hasher);
+    }
+
+    fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
+        let instance_data = self
+            .rect
+            .instance_builder(context.aabb)
+            .gradient(Some(self.gradient.clone()))
             .build()
             .unwrap();
 
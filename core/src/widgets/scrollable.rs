@@ -143,7 +143,8 @@ impl Component for Scrollable {
                     border_color: Color::TRANSPARENT,
                     border_width: (0., 0., 0., 0.),
                     radius: (0., 0., 0., 0.),
-                    swipe: 0
+                    swipe: 0,
+                    shadow: None,
                 },
                 lay![
                     size: [size.width, size.height],
@@ -164,7 +165,8 @@ impl Component for Scrollable {
                     border_color: Color::TRANSPARENT,
                     border_width: (0., 0., 0., 0.),
                     radius: (0., 0., 0., 0.),
-                    swipe: 0
+                    swipe: 0,
+                    shadow: None,
                 },
                 lay![
                     size: [size.width, size.height],
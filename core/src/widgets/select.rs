@@ -0,0 +1,801 @@
+use std::fmt;
+use std::hash::Hash;
+
+use crate::component::{Component, ComponentHasher, Message};
+use crate::input::Key;
+use crate::layout::{Alignment, Dimension, Direction, PositionType, Rect, Size};
+use crate::style::{BorderRadius, BoxShadow, CursorStyle, FontWeight, HorizontalPosition, Styled};
+use crate::{event, lay, msg, node, rect, size_pct, txt, Color, Node};
+use mctk_macros::{component, state_component_impl};
+
+use super::{Div, HDivider, RoundedRect, Text};
+
+/// An entry of a [`Select`]: either a selectable option, or a labelled group of entries.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SelectItem<T> {
+    Option {
+        value: T,
+        label: String,
+        disabled: bool,
+    },
+    Group {
+        label: String,
+        items: Vec<SelectItem<T>>,
+    },
+}
+
+impl<T> SelectItem<T> {
+    pub fn option<S: Into<String>>(value: T, label: S) -> Self {
+        Self::Option {
+            value,
+            label: label.into(),
+            disabled: false,
+        }
+    }
+
+    pub fn group<S: Into<String>>(label: S, items: Vec<SelectItem<T>>) -> Self {
+        Self::Group {
+            label: label.into(),
+            items,
+        }
+    }
+}
+
+/// A row of the flattened dropdown list.
+#[derive(Debug, Clone, PartialEq)]
+enum SelectRow<'a, T> {
+    Header {
+        label: &'a str,
+    },
+    Option {
+        value: &'a T,
+        label: &'a str,
+        disabled: bool,
+        /// How many groups the option is in.
+        depth: usize,
+    },
+}
+
+impl<'a, T> SelectRow<'a, T> {
+    fn selectable(&self) -> bool {
+        matches!(
+            self,
+            SelectRow::Option {
+                disabled: false,
+                ..
+            }
+        )
+    }
+}
+
+fn flatten<'a, T>(items: &'a [SelectItem<T>], rows: &mut Vec<SelectRow<'a, T>>) {
+    flatten_at(items, 0, rows);
+}
+
+fn flatten_at<'a, T>(items: &'a [SelectItem<T>], depth: usize, rows: &mut Vec<SelectRow<'a, T>>) {
+    for item in items.iter() {
+        match item {
+            SelectItem::Option {
+                value,
+                label,
+                disabled,
+            } => rows.push(SelectRow::Option {
+                value,
+                label,
+                disabled: *disabled,
+                depth,
+            }),
+            SelectItem::Group { label, items } => {
+                rows.push(SelectRow::Header { label });
+                flatten_at(items, depth + 1, rows);
+            }
+        }
+    }
+}
+
+/// The next selectable row after `from` (or before it, if `forward` is false), skipping group headers and disabled options.
+fn next_selectable<T>(rows: &[SelectRow<T>], from: Option<usize>, forward: bool) -> Option<usize> {
+    if forward {
+        let start = from.map(|i| i + 1).unwrap_or(0);
+        (start..rows.len()).find(|&i| rows[i].selectable())
+    } else {
+        let end = from.unwrap_or(rows.len());
+        (0..end).rev().find(|&i| rows[i].selectable())
+    }
+}
+
+/// The index of the option at `row`, counting only options.
+fn option_index<T>(rows: &[SelectRow<T>], row: usize) -> usize {
+    rows[..row]
+        .iter()
+        .filter(|r| matches!(r, SelectRow::Option { .. }))
+        .count()
+}
+
+#[derive(Debug, Default)]
+struct SelectState {
+    open: bool,
+    selected: Option<usize>,
+    highlighted: Option<usize>,
+    /// The rows selected in `multi` mode, in the order they were selected.
+    checked: Vec<usize>,
+}
+
+enum SelectMessage {
+    Clicked(usize),
+    Remove(usize),
+}
+
+/// A dropdown list of [`SelectItem`]s.
+///
+/// With `multi`, any number of options (up to `max_selected`) can be checked in the dropdown,
+/// which stays open, and are shown as removable chips. `on_change_multi` is then called with the
+/// indices of the checked options, counting only options, in the order they were checked.
+#[component(State = "SelectState", Styled, Internal)]
+pub struct Select<T: Clone + PartialEq + fmt::Debug + 'static> {
+    pub items: Vec<SelectItem<T>>,
+    pub selected: Option<T>,
+    pub placeholder: Option<String>,
+    pub on_change: Option<Box<dyn Fn(T) -> Message + Send + Sync>>,
+    pub multi: bool,
+    /// The values that start out checked, in `multi` mode.
+    pub selected_values: Vec<T>,
+    pub max_selected: Option<usize>,
+    pub on_change_multi: Option<Box<dyn Fn(Vec<usize>) -> Message + Send + Sync>>,
+}
+
+impl<T: Clone + PartialEq + fmt::Debug + 'static> fmt::Debug for Select<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Select")
+            .field("items", &self.items)
+            .field("selected", &self.selected)
+            .field("multi", &self.multi)
+            .field("selected_values", &self.selected_values)
+            .field("max_selected", &self.max_selected)
+            .finish()
+    }
+}
+
+impl<T: Clone + PartialEq + fmt::Debug + 'static> Select<T> {
+    pub fn new(items: Vec<SelectItem<T>>) -> Self {
+        Self {
+            items,
+            selected: None,
+            placeholder: None,
+            on_change: None,
+            multi: false,
+            selected_values: vec![],
+            max_selected: None,
+            on_change_multi: None,
+            state: Some(SelectState::default()),
+            dirty: false,
+            class: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    pub fn selected(mut self, selected: T) -> Self {
+        self.selected = Some(selected);
+        self
+    }
+
+    pub fn placeholder<S: Into<String>>(mut self, placeholder: S) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+
+    pub fn on_change(mut self, change_fn: Box<dyn Fn(T) -> Message + Send + Sync>) -> Self {
+        self.on_change = Some(change_fn);
+        self
+    }
+
+    pub fn multi(mut self, multi: bool) -> Self {
+        self.multi = multi;
+        self
+    }
+
+    pub fn selected_values(mut self, selected_values: Vec<T>) -> Self {
+        self.selected_values = selected_values;
+        self
+    }
+
+    pub fn max_selected(mut self, max_selected: usize) -> Self {
+        self.max_selected = Some(max_selected);
+        self
+    }
+
+    pub fn on_change_multi(
+        mut self,
+        change_fn: Box<dyn Fn(Vec<usize>) -> Message + Send + Sync>,
+    ) -> Self {
+        self.on_change_multi = Some(change_fn);
+        self
+    }
+
+    fn rows(&self) -> Vec<SelectRow<T>> {
+        let mut rows = vec![];
+        flatten(&self.items, &mut rows);
+        rows
+    }
+
+    fn open(&mut self) {
+        let highlighted = self.state_ref().selected;
+        self.state_mut().open = true;
+        self.state_mut().highlighted = highlighted;
+    }
+
+    fn close(&mut self) {
+        self.state_mut().open = false;
+        self.state_mut().highlighted = None;
+    }
+
+    fn move_highlight(&mut self, forward: bool) {
+        let current = self.state_ref().highlighted;
+        let next = next_selectable(&self.rows(), current, forward);
+        if next.is_some() {
+            self.state_mut().highlighted = next;
+        }
+    }
+
+    fn select(&mut self, row: usize) -> Option<Message> {
+        if self.multi {
+            return self.toggle(row);
+        }
+        let value = match self.rows().get(row) {
+            Some(r @ SelectRow::Option { value, .. }) if r.selectable() => (*value).clone(),
+            _ => return None,
+        };
+        self.state_mut().selected = Some(row);
+        self.close();
+        self.on_change.as_ref().map(|f| f(value))
+    }
+
+    /// Whether no more options can be checked.
+    fn at_limit(&self) -> bool {
+        self.max_selected
+            .map_or(false, |max| self.state_ref().checked.len() >= max)
+    }
+
+    /// Check or uncheck the option at `row`, in `multi` mode.
+    fn toggle(&mut self, row: usize) -> Option<Message> {
+        if !self.rows().get(row).map_or(false, |r| r.selectable()) {
+            return None;
+        }
+        if self.state_ref().checked.contains(&row) {
+            return self.remove(row);
+        }
+        if self.at_limit() {
+            return None;
+        }
+        self.state_mut().checked.push(row);
+        self.multi_change_message()
+    }
+
+    fn remove(&mut self, row: usize) -> Option<Message> {
+        self.state_mut().checked.retain(|r| *r != row);
+        self.multi_change_message()
+    }
+
+    fn multi_change_message(&self) -> Option<Message> {
+        let rows = self.rows();
+        let indices = self
+            .state_ref()
+            .checked
+            .iter()
+            .map(|row| option_index(&rows, *row))
+            .collect();
+        self.on_change_multi.as_ref().map(|f| f(indices))
+    }
+
+    /// The chips of the checked options, in `multi` mode, or `None` if there are none.
+    fn chips(&self, rows: &[SelectRow<T>]) -> Option<Node> {
+        if self.state_ref().checked.is_empty() {
+            return None;
+        }
+        let padding: f64 = self.style_val("padding").unwrap().into();
+        let chip_radius = self.style_val("chip_radius").unwrap().f32();
+        let mut chips = node!(
+            Div::new(),
+            lay![
+                size_pct: [100, Auto],
+                direction: Direction::Row,
+                wrap: true,
+                gap: padding,
+            ]
+        );
+        for &row in self.state_ref().checked.iter() {
+            let label = match rows.get(row) {
+                Some(SelectRow::Option { label, .. }) => label.to_string(),
+                _ => continue,
+            };
+            chips = chips.push(
+                node!(
+                    RoundedRect {
+                        background_color: self.style_val("chip_background").into(),
+                        radius: (chip_radius, chip_radius, chip_radius, chip_radius),
+                        ..Default::default()
+                    },
+                    lay![
+                        padding: rect!(padding),
+                        direction: Direction::Row,
+                        cross_alignment: Alignment::Center,
+                        gap: padding,
+                    ]
+                )
+                .key(row as u64)
+                .push(node!(Text::new(txt!(label))
+                    .style("size", self.style_val("font_size").unwrap())
+                    .style("color", self.style_val("chip_color").unwrap())
+                    .maybe_style("font", self.style_val("font"))))
+                .push(node!(SelectChipRemove { row }).push(
+                    node!(Text::new(txt!("×"))
+                        .style("size", self.style_val("font_size").unwrap())
+                        .style("color", self.style_val("chip_color").unwrap())),
+                )),
+            );
+        }
+        Some(chips)
+    }
+}
+
+/// Plain options, whose values are their labels.
+impl From<Vec<String>> for Select<String> {
+    fn from(options: Vec<String>) -> Self {
+        Select::new(
+            options
+                .into_iter()
+                .map(|o| SelectItem::option(o.clone(), o))
+                .collect(),
+        )
+    }
+}
+
+#[state_component_impl(SelectState)]
+impl<T: Clone + PartialEq + fmt::Debug + 'static> Component for Select<T> {
+    fn cursor(&self) -> Option<CursorStyle> {
+        self.style_val("cursor").map(|v| v.cursor())
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn init(&mut self) {
+        let row_of = |selected: &T| {
+            self.rows()
+                .iter()
+                .position(|r| matches!(r, SelectRow::Option { value, .. } if *value == selected))
+        };
+        let row = self.selected.as_ref().and_then(row_of);
+        let checked: Vec<usize> = self.selected_values.iter().filter_map(row_of).collect();
+        if row.is_some() {
+            self.state_mut().selected = row;
+        }
+        if !checked.is_empty() {
+            self.state_mut().checked = checked;
+        }
+    }
+
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        format!("{:?}", self.items).hash(hasher);
+        format!("{:?}", self.selected).hash(hasher);
+        format!("{:?}", self.selected_values).hash(hasher);
+        self.multi.hash(hasher);
+        self.max_selected.hash(hasher);
+    }
+
+    fn render_hash(&self, hasher: &mut ComponentHasher) {
+        self.state_ref().open.hash(hasher);
+        self.state_ref().selected.hash(hasher);
+        self.state_ref().highlighted.hash(hasher);
+        self.state_ref().checked.hash(hasher);
+    }
+
+    fn view(&self) -> Option<Node> {
+        let radius = self.style_val("radius").unwrap().border_radius();
+        let padding: f64 = self.style_val("padding").unwrap().into();
+        let background_color: Color = self.style_val("background_color").into();
+        let highlight_color: Color = self.style_val("highlight_color").into();
+        let border_color: Color = self.style_val("border_color").into();
+        let border_width: f32 = self.style_val("border_width").unwrap().f32();
+        let shadow = self.style_val("shadow").map(|v| v.box_shadow());
+        let group_label_background: Color = self.style_val("group_label_background").into();
+        let group_separator_color: Color = self.style_val("group_separator_color").into();
+        let group_label_padding: f64 = self.style_val("group_label_padding").unwrap().into();
+        let group_indent: f64 = self.style_val("group_indent").unwrap().into();
+
+        let rows = self.rows();
+        let label = match self.state_ref().selected.and_then(|i| rows.get(i)) {
+            Some(SelectRow::Option { label, .. }) if !self.multi => label.to_string(),
+            _ => self.placeholder.clone().unwrap_or_default(),
+        };
+        let chips = if self.multi { self.chips(&rows) } else { None };
+        let label = match chips {
+            Some(chips) => chips,
+            None => node!(Text::new(txt!(label))
+                .style("size", self.style_val("font_size").unwrap())
+                .style("color", self.style_val("text_color").unwrap())
+                .maybe_style("font", self.style_val("font"))),
+        };
+        let at_limit = self.multi && self.at_limit();
+
+        let mut base = node!(
+            Div::new(),
+            lay![size_pct: [100, Auto], direction: Direction::Column]
+        )
+        .push(
+            node!(
+                RoundedRect {
+                    background_color,
+                    border_color,
+                    border_width: (border_width, border_width, border_width, border_width),
+                    radius: radius.into(),
+                    shadow,
+                    ..Default::default()
+                },
+                lay![
+                    size_pct: [100, Auto],
+                    padding: rect!(padding),
+                    direction: Direction::Row,
+                    cross_alignment: Alignment::Center,
+                    axis_alignment: Alignment::Stretch,
+                ]
+            )
+            .push(label)
+            .push(node!(Text::new(txt!("▾"))
+                .style("size", self.style_val("font_size").unwrap())
+                .style("color", self.style_val("caret_color").unwrap())
+                .style("h_alignment", HorizontalPosition::Right))),
+        );
+
+        if self.state_ref().open {
+            let mut list = dropdown(
+                background_color,
+                border_color,
+                border_width,
+                radius,
+                shadow,
+                Dimension::Px(self.style_val("max_height").unwrap().f64()),
+            );
+
+            for (i, row) in rows.iter().enumerate() {
+                list = match row {
+                    SelectRow::Header { label } => {
+                        if i > 0 {
+                            list = list.push(
+                                node!(HDivider {
+                                    size: 1.0,
+                                    color: group_separator_color,
+                                })
+                                .key(2 * i as u64),
+                            );
+                        }
+                        list.push(
+                            node!(
+                                Div::new().bg(group_label_background),
+                                lay![size_pct: [100, Auto], padding: rect!(group_label_padding)]
+                            )
+                            .key(2 * i as u64 + 1)
+                            .push(node!(Text::new(txt!(
+                                label.to_string()
+                            ))
+                            .style("size", self.style_val("group_label_font_size").unwrap())
+                            .style("color", self.style_val("group_label_color").unwrap())
+                            .style("font_weight", FontWeight::Bold)
+                            .maybe_style("font", self.style_val("font")))),
+                        )
+                    }
+                    SelectRow::Option {
+                        label,
+                        disabled,
+                        depth,
+                        ..
+                    } => {
+                        // Grouped options are indented under their group's label
+                        let indent = padding + group_indent * *depth as f64;
+                        let checked = self.state_ref().checked.contains(&i);
+                        let disabled = *disabled || (at_limit && !checked);
+                        let mut option = node!(
+                            Div::new().bg(if self.state_ref().highlighted == Some(i) {
+                                highlight_color
+                            } else {
+                                Color::TRANSPARENT
+                            }),
+                            lay![
+                                size_pct: [100, Auto],
+                                padding: [padding, indent, padding, padding],
+                                direction: Direction::Row,
+                                gap: padding,
+                            ]
+                        );
+                        if self.multi {
+                            option = option.push(node!(Text::new(txt!(if checked {
+                                "✓"
+                            } else {
+                                " "
+                            }))
+                            .style("size", self.style_val("font_size").unwrap())
+                            .style("color", self.style_val("caret_color").unwrap())));
+                        }
+                        option = option.push(node!(Text::new(txt!(label.to_string()))
+                            .style("size", self.style_val("font_size").unwrap())
+                            .style(
+                                "color",
+                                if disabled {
+                                    Color::MID_GREY.into()
+                                } else {
+                                    self.style_val("text_color").unwrap()
+                                }
+                            )
+                            .maybe_style("font", self.style_val("font"))));
+                        list.push(
+                            node!(
+                                SelectOption { row: i, disabled },
+                                lay![size_pct: [100, Auto]]
+                            )
+                            .key(2 * i as u64 + 1)
+                            .push(option),
+                        )
+                    }
+                };
+            }
+
+            base = base.push(list);
+        }
+
+        Some(base)
+    }
+
+    fn update(&mut self, message: Message) -> Vec<Message> {
+        let mut m: Vec<Message> = vec![];
+        match message.downcast_ref::<SelectMessage>() {
+            Some(SelectMessage::Clicked(row)) => {
+                if let Some(msg) = self.select(*row) {
+                    m.push(msg);
+                }
+            }
+            Some(SelectMessage::Remove(row)) => {
+                if let Some(msg) = self.remove(*row) {
+                    m.push(msg);
+                }
+            }
+            None => m.push(message),
+        }
+        m
+    }
+
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        if self.state_ref().open {
+            self.close();
+        } else {
+            self.open();
+        }
+        event.stop_bubbling();
+        event.focus();
+    }
+
+    fn on_blur(&mut self, _event: &mut event::Event<event::Blur>) {
+        self.close();
+    }
+
+    fn on_key_down(&mut self, event: &mut event::Event<event::KeyDown>) {
+        match event.input.0 {
+            Key::Down | Key::Up => {
+                if !self.state_ref().open {
+                    self.open();
+                }
+                self.move_highlight(event.input.0 == Key::Down);
+            }
+            Key::Return | Key::Space => {
+                if let Some(row) = self
+                    .state_ref()
+                    .highlighted
+                    .filter(|_| self.state_ref().open)
+                {
+                    if let Some(msg) = self.select(row) {
+                        event.emit(msg);
+                    }
+                } else {
+                    self.open();
+                }
+            }
+            Key::Escape => self.close(),
+            Key::Backspace if self.multi => {
+                if let Some(&row) = self.state_ref().checked.last() {
+                    if let Some(msg) = self.remove(row) {
+                        event.emit(msg);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// The box of a dropdown list, which hangs below its parent, in front of whatever follows it. The
+/// rows of the list are pushed onto it.
+pub(crate) fn dropdown(
+    background_color: Color,
+    border_color: Color,
+    border_width: f32,
+    radius: BorderRadius,
+    shadow: Option<BoxShadow>,
+    max_height: Dimension,
+) -> Node {
+    node!(
+        RoundedRect {
+            background_color,
+            border_color,
+            border_width: (border_width, border_width, border_width, border_width),
+            radius: radius.into(),
+            shadow,
+            ..Default::default()
+        },
+        lay![
+            position_type: PositionType::Absolute,
+            position: Rect {
+                top: Dimension::Pct(100.0),
+                left: Dimension::Px(0.0),
+                right: Dimension::Px(0.0),
+                bottom: Dimension::Auto,
+            },
+            z_index_increment: 1000.0,
+            direction: Direction::Column,
+            max_size: Size {
+                width: Dimension::Auto,
+                height: max_height,
+            },
+        ]
+    )
+}
+
+#[derive(Debug)]
+struct SelectOption {
+    row: usize,
+    disabled: bool,
+}
+
+impl Component for SelectOption {
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.row.hash(hasher);
+        self.disabled.hash(hasher);
+    }
+
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        event.stop_bubbling();
+        if !self.disabled {
+            event.emit(msg!(SelectMessage::Clicked(self.row)));
+        }
+    }
+}
+
+/// The "×" of a chip of a `multi` [`Select`], which unchecks its option.
+#[derive(Debug)]
+struct SelectChipRemove {
+    row: usize,
+}
+
+impl Component for SelectChipRemove {
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.row.hash(hasher);
+    }
+
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        // Without opening or closing the dropdown
+        event.stop_bubbling();
+        event.emit(msg!(SelectMessage::Remove(self.row)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_navigation_skips_group_headers() {
+        let items = vec![
+            SelectItem::group(
+                "Fruit",
+                vec![
+                    SelectItem::option(1, "Apple"),
+                    SelectItem::option(2, "Pear"),
+                ],
+            ),
+            SelectItem::group(
+                "Vegetables",
+                vec![SelectItem::option(3, "Leek"), SelectItem::option(4, "Kale")],
+            ),
+        ];
+        let mut rows = vec![];
+        flatten(&items, &mut rows);
+
+        // Headers at 0 and 3; "Pear" is the last option of the first group
+        assert_eq!(
+            rows[3],
+            SelectRow::Header {
+                label: "Vegetables"
+            }
+        );
+        let next = next_selectable(&rows, Some(2), true).unwrap();
+        assert_eq!(next, 4);
+        assert!(matches!(
+            rows[next],
+            SelectRow::Option { label: "Leek", .. }
+        ));
+
+        assert_eq!(next_selectable(&rows, Some(4), false), Some(2));
+        assert_eq!(next_selectable(&rows, None, true), Some(1));
+        assert!(matches!(rows[1], SelectRow::Option { depth: 1, .. }));
+    }
+
+    #[test]
+    fn test_from_strings() {
+        let select: Select<String> = vec!["One".to_string(), "Two".to_string()].into();
+        let rows = select.rows();
+        assert_eq!(rows.len(), 2);
+        assert!(matches!(
+            rows[1],
+            SelectRow::Option {
+                value,
+                label: "Two",
+                depth: 0,
+                ..
+            } if value == "Two"
+        ));
+    }
+
+    #[test]
+    fn test_navigation_skips_disabled() {
+        let items = vec![
+            SelectItem::option(1, "One"),
+            SelectItem::Option {
+                value: 2,
+                label: "Two".to_string(),
+                disabled: true,
+            },
+            SelectItem::option(3, "Three"),
+        ];
+        let mut rows = vec![];
+        flatten(&items, &mut rows);
+        assert_eq!(next_selectable(&rows, Some(0), true), Some(2));
+        assert_eq!(next_selectable(&rows, Some(2), true), None);
+    }
+
+    #[test]
+    fn test_multi() {
+        let items = vec![
+            SelectItem::group(
+                "Fruit",
+                vec![
+                    SelectItem::option(1, "Apple"),
+                    SelectItem::option(2, "Pear"),
+                ],
+            ),
+            SelectItem::group("Vegetables", vec![SelectItem::option(3, "Leek")]),
+        ];
+        let mut select = Select::new(items)
+            .multi(true)
+            .selected_values(vec![3])
+            .max_selected(2)
+            .on_change_multi(Box::new(|indices| msg!(indices)));
+        select.init();
+        assert_eq!(select.state_ref().checked, vec![4]);
+
+        // Indices count only options, in the order they were checked
+        let m = select.select(1).unwrap();
+        assert_eq!(m.downcast_ref::<Vec<usize>>(), Some(&vec![2, 0]));
+        // At the limit, unchecked options can't be checked
+        assert!(select.at_limit());
+        assert!(select.select(2).is_none());
+        // Checked ones can be unchecked, and the dropdown stays open
+        select.open();
+        let m = select.select(4).unwrap();
+        assert_eq!(m.downcast_ref::<Vec<usize>>(), Some(&vec![0]));
+        assert!(select.state_ref().open);
+        // Headers can't be checked
+        assert!(select.select(0).is_none());
+
+        let m = select.update(msg!(SelectMessage::Remove(1)));
+        assert_eq!(m[0].downcast_ref::<Vec<usize>>(), Some(&vec![]));
+    }
+}
@@ -3,40 +3,47 @@ use mctk_macros::component;
 use crate::component::{Component, ComponentHasher, Message, RenderContext};
 
 use crate::event::{self, Event};
-use crate::renderables::types::{Point, Size};
-use crate::renderables::{
-    circle::InstanceBuilder as CircleInstanceBuilder, line::InstanceBuilder as LineInstanceBuilder,
-    rect::InstanceBuilder as RectInstanceBuilder,
-};
-use crate::renderables::{Circle, Line, Rect, Renderable};
-use crate::{lay, msg, node, size, size_pct, types::*, Node};
+use crate::input::Key;
+use crate::renderables::circle::InstanceBuilder as CircleInstanceBuilder;
+use crate::renderables::line::InstanceBuilder as LineInstanceBuilder;
+use crate::renderables::{Circle, Line, Renderable};
+use crate::style::{CursorStyle, Styled};
+use crate::types::*;
 use std::hash::Hash;
-use std::ops::Neg;
+
+/// The direction of the track of a [`Slider`] or [`ProgressBar`](super::ProgressBar). The
+/// minimum of a vertical track is at the bottom.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    #[default]
+    Horizontal,
+    Vertical,
+}
 
 #[derive(Debug, Default)]
 struct SliderState {}
 
+/// A thumb that is dragged along a track to pick a value between `min` and `max`.
+///
+/// Clicking or dragging moves the thumb to the pointer. Once clicked, the slider has the focus,
+/// and the arrow keys move the value by `step`, while Home and End go to either end.
 #[component(State = "SliderState", Styled, Internal)]
 pub struct Slider {
-    pub value: i32,
-    pub on_slide: Option<Box<dyn Fn(i32) -> Message + Send + Sync>>,
-}
-
-#[derive(Debug)]
-enum SliderMsg {
-    ValueChanged(i32),
+    pub value: f32,
+    pub min: f32,
+    pub max: f32,
+    /// Values are snapped to multiples of `step` from `min`. Without a step, any value can be
+    /// picked with the pointer, and the arrow keys move by a hundredth of the range.
+    pub step: Option<f32>,
+    pub orientation: Orientation,
+    /// A disabled slider ignores input, and is drawn in its `disabled_color`.
+    pub disabled: bool,
+    pub on_change: Option<Box<dyn Fn(f32) -> Message + Send + Sync>>,
 }
 
 impl Default for Slider {
     fn default() -> Self {
-        Self {
-            value: 0,
-            on_slide: None,
-            state: Some(SliderState::default()),
-            dirty: false,
-            class: Default::default(),
-            style_overrides: Default::default(),
-        }
+        Self::new(0.0)
     }
 }
 
@@ -44,15 +51,26 @@ impl std::fmt::Debug for Slider {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.debug_struct("Slider")
             .field("value", &self.value)
+            .field("min", &self.min)
+            .field("max", &self.max)
+            .field("step", &self.step)
+            .field("orientation", &self.orientation)
+            .field("disabled", &self.disabled)
             .finish()
     }
 }
 
 impl Slider {
-    pub fn new(value: i32) -> Self {
+    /// A horizontal slider from 0 to 100.
+    pub fn new(value: f32) -> Self {
         Self {
             value,
-            on_slide: None,
+            min: 0.0,
+            max: 100.0,
+            step: None,
+            orientation: Orientation::Horizontal,
+            disabled: false,
+            on_change: None,
             state: Some(SliderState::default()),
             dirty: false,
             class: Default::default(),
@@ -60,39 +78,141 @@ impl Slider {
         }
     }
 
-    pub fn on_slide(mut self, f: Box<dyn Fn(i32) -> Message + Send + Sync>) -> Self {
-        self.on_slide = Some(f);
+    pub fn range(mut self, min: f32, max: f32) -> Self {
+        self.min = min;
+        self.max = max;
         self
     }
+
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = Some(step);
+        self
+    }
+
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    pub fn on_change(mut self, change_fn: Box<dyn Fn(f32) -> Message + Send + Sync>) -> Self {
+        self.on_change = Some(change_fn);
+        self
+    }
+
+    /// Snaps `value` to the nearest step, and clamps it to the range.
+    fn constrain(&self, value: f32) -> f32 {
+        let value = match self.step {
+            Some(step) if step > 0.0 => self.min + ((value - self.min) / step).round() * step,
+            _ => value,
+        };
+        value.min(self.max).max(self.min)
+    }
+
+    /// How far along the track `value` is, from 0 to 1.
+    fn fraction(&self, value: f32) -> f32 {
+        if self.max > self.min {
+            ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// The ends of the track in a box of the given size, inset so the thumb stays within it.
+    /// The first end is the minimum.
+    fn track(&self, size: Scale, inset: f32) -> (Point, Point) {
+        let Scale { width, height } = size;
+        match self.orientation {
+            Orientation::Horizontal => (
+                Point::new(inset, height / 2.0),
+                Point::new(width - inset, height / 2.0),
+            ),
+            Orientation::Vertical => (
+                Point::new(width / 2.0, height - inset),
+                Point::new(width / 2.0, inset),
+            ),
+        }
+    }
+
+    /// The value under `position`, relative to a box of the given size.
+    fn value_at(&self, position: Point, size: Scale, inset: f32) -> f32 {
+        let (start, end) = self.track(size, inset);
+        let (dx, dy) = (end.x - start.x, end.y - start.y);
+        let length = dx * dx + dy * dy;
+        let fraction = if length > 0.0 {
+            (((position.x - start.x) * dx + (position.y - start.y) * dy) / length).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self.constrain(self.min + fraction * (self.max - self.min))
+    }
+
+    /// The value that `key` moves to, if it is one that the slider handles.
+    fn value_for_key(&self, key: Key) -> Option<f32> {
+        let step = match self.step {
+            Some(step) if step > 0.0 => step,
+            _ => (self.max - self.min) / 100.0,
+        };
+        let value = match key {
+            Key::Right | Key::Up => self.value + step,
+            Key::Left | Key::Down => self.value - step,
+            Key::Home => self.min,
+            Key::End => self.max,
+            _ => return None,
+        };
+        Some(self.constrain(value))
+    }
+
+    fn thumb_size(&self) -> f32 {
+        self.style_val("thumb_size").map(|v| v.f32()).unwrap_or(0.0)
+    }
+
+    /// The message for a change to `value`, unless it is the current value.
+    fn change(&self, value: f32) -> Option<Message> {
+        if self.disabled || value == self.value {
+            return None;
+        }
+        self.on_change.as_ref().map(|f| f(value))
+    }
+
+    fn slide_to<T: event::EventInput>(&self, event: &mut Event<T>, position: Point) {
+        let size = event.current_aabb.unwrap().size();
+        let value = self.value_at(position, size, self.thumb_size() / 2.0);
+        if let Some(m) = self.change(value) {
+            event.emit(m);
+        }
+    }
 }
 
 impl Component for Slider {
     fn render_hash(&self, hasher: &mut ComponentHasher) {
-        (self.value as i32).hash(hasher);
-        // (self.state).hash(hasher);
-    }
-
-    fn update(&mut self, msg: Message) -> Vec<Message> {
-        let mut m: Vec<Message> = vec![];
-        match msg.downcast_ref::<SliderMsg>() {
-            Some(SliderMsg::ValueChanged(value)) => {
-                //println!("slider update value {:?}", value);
-                if let Some(slide_fn) = &self.on_slide {
-                    m.push(slide_fn(*value));
-                }
-            }
-            _ => (),
+        self.value.to_bits().hash(hasher);
+        self.min.to_bits().hash(hasher);
+        self.max.to_bits().hash(hasher);
+        self.orientation.hash(hasher);
+        self.disabled.hash(hasher);
+    }
+
+    fn cursor(&self) -> Option<CursorStyle> {
+        if self.disabled {
+            Some(CursorStyle::NotAllowed)
+        } else {
+            self.style_val("cursor").map(|v| v.cursor())
         }
-        m
     }
-    fn on_drag(&mut self, event: &mut Event<event::Drag>) {
-        let slider_position = event.relative_logical_position();
-        let slider_width = event.current_aabb.unwrap().width();
 
-        let value_changed = slider_position.x / slider_width * 100.;
+    fn focusable(&self) -> bool {
+        !self.disabled
+    }
 
-        if let Some(slide_fn) = &self.on_slide {
-            event.emit(slide_fn(value_changed.min(100.).max(0.) as i32));
+    fn on_drag(&mut self, event: &mut Event<event::Drag>) {
+        if !self.disabled {
+            let position = event.relative_logical_position();
+            self.slide_to(event, position);
         }
     }
 
@@ -105,186 +225,164 @@ impl Component for Slider {
     }
 
     fn on_touch_drag(&mut self, event: &mut Event<event::TouchDrag>) {
-        println!("Slider::on_touch_drag()");
-
-        let slider_position = event.relative_logical_position_touch();
-        let slider_width = event.current_aabb.unwrap().width();
-
-        let value_changed = slider_position.x / slider_width * 100.;
-
-        println!(
-            "value_changed {:?} slider position {:?}",
-            value_changed as i32, slider_position
-        );
-        if let Some(slide_fn) = &self.on_slide {
-            event.emit(slide_fn(value_changed.min(100.).max(0.) as i32));
+        if !self.disabled {
+            let position = event.relative_logical_position_touch();
+            self.slide_to(event, position);
         }
     }
 
     fn on_touch_drag_start(&mut self, event: &mut Event<event::TouchDragStart>) {
-        println!("Slider::on_touch_drag_start()");
         event.stop_bubbling();
     }
 
     fn on_touch_drag_end(&mut self, event: &mut Event<event::TouchDragEnd>) {
-        println!("Slider::on_touch_drag_end()");
         event.stop_bubbling();
     }
 
     fn on_mouse_down(&mut self, event: &mut Event<event::MouseDown>) {
         event.stop_bubbling();
-        let click_position = event.relative_logical_position();
-        println!("mouse down postion is {:?}", click_position);
-
-        let slider_width = event.current_aabb.unwrap().width();
-        let value_changed = click_position.x / slider_width * 100.;
-        if let Some(slide_fn) = &self.on_slide {
-            event.emit(slide_fn(value_changed.min(100.).max(0.) as i32));
+        if !self.disabled {
+            // Take the focus, so the arrow keys move the thumb
+            event.focus();
+            let position = event.relative_logical_position();
+            self.slide_to(event, position);
         }
     }
 
     fn on_touch_down(&mut self, event: &mut Event<event::TouchDown>) {
         event.stop_bubbling();
-        let click_position = event.relative_logical_position_touch();
-        println!("touch down postion is {:?}", click_position);
+        if !self.disabled {
+            let position = event.relative_logical_position_touch();
+            self.slide_to(event, position);
+        }
+    }
 
-        let slider_width = event.current_aabb.unwrap().width();
-        let value_changed = click_position.x / slider_width * 100.;
-        if let Some(slide_fn) = &self.on_slide {
-            event.emit(slide_fn(value_changed.min(100.).max(0.) as i32));
+    fn on_key_down(&mut self, event: &mut Event<event::KeyDown>) {
+        if let Some(value) = self.value_for_key(event.input.0) {
+            event.stop_bubbling();
+            if let Some(m) = self.change(value) {
+                event.emit(m);
+            }
         }
     }
 
     fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
-        let width = context.aabb.width();
-        let height = context.aabb.height();
         let AABB { pos, .. } = context.aabb;
-
-        let mut rs = vec![];
-
-        //Outer box
-        let rect_instance_data = RectInstanceBuilder::default()
-            .pos(pos)
-            .scale(Scale { width, height })
-            .color(Color::TRANSPARENT)
-            .build()
-            .unwrap();
-        rs.push(Renderable::Rect(Rect::from_instance_data(
-            rect_instance_data,
-        )));
-
-        let start = Pos {
-            x: pos.x,
-            y: pos.y + height / 2.,
-            z: 0.,
-        };
-
-        let end = Pos {
-            x: pos.x + width,
-            y: pos.y + height / 2.,
-            z: 0.,
+        let thumb_size = self.thumb_size();
+        let track_height: f32 = self.style_val("track_height").unwrap().f32();
+        let track_color: Color = self.style_val("track_color").into();
+        let (active_color, thumb_color): (Color, Color) = if self.disabled {
+            let color = self.style_val("disabled_color").into();
+            (color, color)
+        } else {
+            (
+                self.style_val("active_color").into(),
+                self.style_val("thumb_color").into(),
+            )
         };
 
-        //Horizontal BG
-        let line_instance_data = LineInstanceBuilder::default()
-            .from(start)
-            .to(end)
-            .color(Color::rgb(64., 64., 68.))
-            .width(4.0)
-            .build()
-            .unwrap();
-        rs.push(Renderable::Line(Line::from_instance_data(
-            line_instance_data,
-        )));
+        let (start, end) = self.track(context.aabb.size(), thumb_size / 2.0);
+        let fraction = self.fraction(self.value);
+        let to_pos = |p: Point| Pos::new(pos.x + p.x, pos.y + p.y, 0.0);
+        let (start, end) = (to_pos(start), to_pos(end));
+        let thumb = Pos::new(
+            start.x + (end.x - start.x) * fraction,
+            start.y + (end.y - start.y) * fraction,
+            0.0,
+        );
 
-        let filled_end = Pos {
-            x: pos.x + width * self.value as f32 / 100.,
-            y: pos.y + height / 2.,
-            z: 0.,
-        };
+        let mut rs = vec![];
+        for (from, to, color) in [(start, end, track_color), (start, thumb, active_color)] {
+            let line_instance_data = LineInstanceBuilder::default()
+                .from(from)
+                .to(to)
+                .color(color)
+                .width(track_height)
+                .build()
+                .unwrap();
+            rs.push(Renderable::Line(Line::from_instance_data(
+                line_instance_data,
+            )));
+        }
 
-        //Horizontal Line
-        let line_instance_data = LineInstanceBuilder::default()
-            .from(start)
-            .to(filled_end)
-            .color(Color::WHITE)
-            .width(4.0)
+        let circle_instance_data = CircleInstanceBuilder::default()
+            .origin(thumb)
+            .radius(thumb_size / 2.0)
+            .color(Some(thumb_color))
             .build()
             .unwrap();
-        rs.push(Renderable::Line(Line::from_instance_data(
-            line_instance_data,
+        rs.push(Renderable::Circle(Circle::from_instance_data(
+            circle_instance_data,
         )));
 
-        //Circle
-        // let radius = 10.;
-        // let circle_instance_data = CircleInstanceBuilder::default()
-        //     .origin(Pos {
-        //         x: pos.x + radius,
-        //         y: pos.y + height / 2.,
-        //         z: 0.,
-        //     })
-        //     .radius(radius)
-        //     .build()
-        //     .unwrap();
-        // rs.push(Renderable::Circle(Circle::from_instance_data(
-        //     circle_instance_data,
-        // )));
-
-        // let mut pointer = Pointer {};
-        // let x = pointer.render(context).unwrap();
-
         Some(rs)
     }
-
-    fn view(&self) -> Option<Node> {
-        //println!("Slider view {}", self.value);
-
-        Some(node!(Pointer {
-            value: self.value,
-        }, [ size_pct: [96, 100] ]))
-    }
 }
 
-pub struct Pointer {
-    pub value: i32,
-}
-
-impl std::fmt::Debug for Pointer {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.debug_struct("Pointer")
-            .field("value", &self.value)
-            .finish()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constrain() {
+        let slider = Slider::new(0.0).range(-1.0, 1.0).step(0.25);
+        assert_eq!(slider.constrain(0.3), 0.25);
+        assert_eq!(slider.constrain(-0.9), -1.0);
+        assert_eq!(slider.constrain(4.0), 1.0);
+
+        // Steps count from the minimum
+        let slider = Slider::new(1.0).range(1.0, 10.0).step(2.0);
+        assert_eq!(slider.constrain(4.2), 5.0);
+        assert_eq!(slider.constrain(10.0), 10.0);
     }
-}
 
-impl Component for Pointer {
-    fn render_hash(&self, hasher: &mut ComponentHasher) {
-        (self.value as i32).hash(hasher);
-        // (self.state).hash(hasher);
+    #[test]
+    fn test_value_at() {
+        let size = Scale {
+            width: 120.0,
+            height: 20.0,
+        };
+        let slider = Slider::new(0.0);
+        assert_eq!(slider.value_at(Point::new(60.0, 3.0), size, 10.0), 50.0);
+        assert_eq!(slider.value_at(Point::new(5.0, 10.0), size, 10.0), 0.0);
+        assert_eq!(slider.value_at(Point::new(200.0, 10.0), size, 10.0), 100.0);
+
+        let size = Scale {
+            width: 20.0,
+            height: 120.0,
+        };
+        let slider = slider.orientation(Orientation::Vertical).step(10.0);
+        assert_eq!(slider.value_at(Point::new(10.0, 35.0), size, 10.0), 80.0);
     }
 
-    fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
-        //println!("Pointer render {}", self.value);
-
-        let width = context.aabb.width();
-        let height = context.aabb.height();
-        let AABB { pos, .. } = context.aabb;
-        let mut rs = vec![];
-
-        let radius = 9.;
-        let circle_instance_data = CircleInstanceBuilder::default()
-            .origin(Pos {
-                x: pos.x + radius / 2. + self.value as f32 * width / 100.,
-                y: pos.y + height / 2.,
-                z: 0.,
-            })
-            .radius(radius)
-            .build()
-            .unwrap();
-        rs.push(Renderable::Circle(Circle::from_instance_data(
-            circle_instance_data,
-        )));
+    #[test]
+    fn test_arrow_keys() {
+        let slider = Slider::new(0.5).range(0.0, 1.0).step(0.5);
+        assert_eq!(slider.value_for_key(Key::Right), Some(1.0));
+        assert_eq!(slider.value_for_key(Key::Up), Some(1.0));
+        assert_eq!(slider.value_for_key(Key::Down), Some(0.0));
+        assert_eq!(slider.value_for_key(Key::End), Some(1.0));
+        assert_eq!(slider.value_for_key(Key::Tab), None);
+
+        // Without a step, keys move by a hundredth of the range
+        let slider = Slider::new(100.0).range(0.0, 200.0);
+        assert_eq!(slider.value_for_key(Key::Left), Some(98.0));
+        assert_eq!(slider.value_for_key(Key::Right), Some(102.0));
+    }
 
-        Some(rs)
+    #[test]
+    fn test_change() {
+        let slider = Slider::new(1.0)
+            .range(0.0, 1.0)
+            .step(0.5)
+            .on_change(Box::new(|value| crate::msg!(value)));
+        // Already at the maximum
+        assert!(slider
+            .change(slider.value_for_key(Key::Up).unwrap())
+            .is_none());
+        let m = slider.change(0.5).unwrap();
+        assert_eq!(m.downcast_ref::<f32>(), Some(&0.5));
+
+        assert!(slider.disabled(true).change(0.5).is_none());
     }
 }
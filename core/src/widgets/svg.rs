@@ -7,7 +7,7 @@ use crate::renderables::types::{Point, Size};
 use crate::renderables::{self, Rect, Renderable};
 use crate::types::*;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Svg {
     pub name: String,
     pub dynamic_load_from: Option<String>,
@@ -0,0 +1,365 @@
+use std::fmt;
+use std::hash::Hash;
+
+use mctk_macros::component;
+
+use crate::component::{Component, ComponentHasher, Message, RenderContext};
+use crate::layout::{Alignment, Direction};
+use crate::renderables::{rect::InstanceBuilder as RectInstanceBuilder, Rect, Renderable};
+use crate::style::{CursorStyle, Styled};
+use crate::types::*;
+use crate::{event, lay, msg, node, rect, size, txt, Node};
+
+use super::{Div, Orientation, Svg, Text};
+
+#[derive(Debug)]
+enum TabsMsg {
+    Select(usize),
+    Close(usize),
+}
+
+/// A tab of [`Tabs`].
+#[derive(Debug, Clone)]
+pub struct TabItem {
+    pub label: String,
+    /// Shown before the label.
+    pub icon: Option<Svg>,
+    /// Shows a × after the label, which calls [`Tabs`]'s `on_close`.
+    pub closable: bool,
+}
+
+impl TabItem {
+    pub fn new<S: Into<String>>(label: S) -> Self {
+        Self {
+            label: label.into(),
+            icon: None,
+            closable: false,
+        }
+    }
+
+    pub fn icon(mut self, icon: Svg) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    pub fn closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+}
+
+/// A bar of tabs, of which the `active` one is marked with an underline.
+///
+/// With the `tabs-pills` class, the active tab is marked with a pill instead. Clicking a tab
+/// calls `on_change` with its position, unless it is already active.
+#[component(Styled, Internal)]
+pub struct Tabs {
+    pub tabs: Vec<TabItem>,
+    pub active: usize,
+    pub orientation: Orientation,
+    pub on_change: Option<Box<dyn Fn(usize) -> Message + Send + Sync>>,
+    pub on_close: Option<Box<dyn Fn(usize) -> Message + Send + Sync>>,
+}
+
+impl fmt::Debug for Tabs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Tabs")
+            .field("tabs", &self.tabs)
+            .field("active", &self.active)
+            .field("orientation", &self.orientation)
+            .finish()
+    }
+}
+
+impl Tabs {
+    pub fn new(tabs: Vec<TabItem>) -> Self {
+        Self {
+            tabs,
+            active: 0,
+            orientation: Orientation::Horizontal,
+            on_change: None,
+            on_close: None,
+            class: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    pub fn active(mut self, active: usize) -> Self {
+        self.active = active;
+        self
+    }
+
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn on_change(mut self, change_fn: Box<dyn Fn(usize) -> Message + Send + Sync>) -> Self {
+        self.on_change = Some(change_fn);
+        self
+    }
+
+    pub fn on_close(mut self, close_fn: Box<dyn Fn(usize) -> Message + Send + Sync>) -> Self {
+        self.on_close = Some(close_fn);
+        self
+    }
+}
+
+impl Component for Tabs {
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        for tab in self.tabs.iter() {
+            tab.label.hash(hasher);
+            tab.icon.as_ref().map(|icon| &icon.name).hash(hasher);
+            tab.closable.hash(hasher);
+        }
+        self.active.hash(hasher);
+        self.orientation.hash(hasher);
+    }
+
+    fn view(&self) -> Option<Node> {
+        let mut base = node!(
+            Div::new(),
+            lay![
+                direction: match self.orientation {
+                    Orientation::Horizontal => Direction::Row,
+                    Orientation::Vertical => Direction::Column,
+                },
+                cross_alignment: Alignment::Stretch
+            ]
+        );
+        for (index, item) in self.tabs.iter().enumerate() {
+            base = base.push(
+                node!(
+                    Tab {
+                        item: item.clone(),
+                        index,
+                        active: index == self.active,
+                        orientation: self.orientation,
+                        class: self.class,
+                        style_overrides: self.style_overrides.clone(),
+                    },
+                    lay![]
+                )
+                .key(index as u64),
+            );
+        }
+        Some(base)
+    }
+
+    fn update(&mut self, message: Message) -> Vec<Message> {
+        let m = match message.downcast_ref::<TabsMsg>() {
+            Some(TabsMsg::Select(index)) if *index != self.active => {
+                self.on_change.as_ref().map(|f| f(*index))
+            }
+            Some(TabsMsg::Select(_)) => None,
+            Some(TabsMsg::Close(index)) => self.on_close.as_ref().map(|f| f(*index)),
+            None => return vec![message],
+        };
+        m.into_iter().collect()
+    }
+}
+
+#[component(Styled = "Tabs", Internal)]
+#[derive(Debug)]
+struct Tab {
+    item: TabItem,
+    index: usize,
+    active: bool,
+    orientation: Orientation,
+}
+
+impl Tab {
+    /// What marks the active tab in `aabb`: a pill behind the label, or else a line of `width`
+    /// along the bottom of a horizontal tab, or the right of a vertical one.
+    fn indicator(&self, aabb: AABB, color: Color, width: f32, pill: bool) -> Rect {
+        let AABB { pos, .. } = aabb;
+        let size = aabb.size();
+        let (pos, scale, radius) = if pill {
+            (pos, size, size.width.min(size.height) / 2.0)
+        } else {
+            match self.orientation {
+                Orientation::Horizontal => (
+                    Pos::new(pos.x, pos.y + size.height - width, pos.z),
+                    Scale::new(size.width, width),
+                    0.0,
+                ),
+                Orientation::Vertical => (
+                    Pos::new(pos.x + size.width - width, pos.y, pos.z),
+                    Scale::new(width, size.height),
+                    0.0,
+                ),
+            }
+        };
+        Rect::from_instance_data(
+            RectInstanceBuilder::default()
+                .pos(pos)
+                .scale(scale)
+                .color(color)
+                .radius((radius, radius, radius, radius))
+                .build()
+                .unwrap(),
+        )
+    }
+}
+
+impl Component for Tab {
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.item.label.hash(hasher);
+        self.item.closable.hash(hasher);
+        self.active.hash(hasher);
+    }
+
+    fn cursor(&self) -> Option<CursorStyle> {
+        self.style_val("cursor").map(|v| v.cursor())
+    }
+
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        event.stop_bubbling();
+        event.emit(msg!(TabsMsg::Select(self.index)));
+    }
+
+    fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
+        let background: Color = if self.active {
+            self.style_val("active_tab_background").into()
+        } else {
+            self.style_val("tab_background").into()
+        };
+        let mut rs = vec![Renderable::Rect(Rect::new(
+            context.aabb.pos,
+            context.aabb.size(),
+            background,
+        ))];
+        if self.active {
+            rs.push(Renderable::Rect(self.indicator(
+                context.aabb,
+                self.style_val("indicator_color").into(),
+                self.style_val("indicator_width").unwrap().f32(),
+                self.style_val("pill").map(|v| v.bool()).unwrap_or(false),
+            )));
+        }
+        Some(rs)
+    }
+
+    fn view(&self) -> Option<Node> {
+        let padding: f32 = self.style_val("tab_padding").unwrap().f32();
+        let font_size: f32 = self.style_val("tab_font_size").unwrap().f32();
+        let text_color: Color = if self.active {
+            self.style_val("active_text_color").into()
+        } else {
+            self.style_val("text_color").into()
+        };
+
+        let mut base = node!(
+            Div::new(),
+            lay![
+                direction: Direction::Row,
+                cross_alignment: Alignment::Center,
+                padding: [padding],
+                gap: (font_size / 2.0).into()
+            ]
+        );
+        if let Some(icon) = &self.item.icon {
+            base = base.push(node!(
+                icon.clone(),
+                lay![size: [font_size * 1.25, font_size * 1.25]]
+            ));
+        }
+        base = base.push(node!(Text::new(txt!(self.item.label.clone()))
+            .style("size", font_size)
+            .style("color", text_color)));
+        if self.item.closable {
+            base = base.push(node!(TabClose {
+                index: self.index,
+                font_size,
+                color: text_color,
+            }));
+        }
+        Some(base)
+    }
+}
+
+/// The × of a closable [`Tab`].
+#[derive(Debug)]
+struct TabClose {
+    index: usize,
+    font_size: f32,
+    color: Color,
+}
+
+impl Component for TabClose {
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.index.hash(hasher);
+        self.color.hash(hasher);
+    }
+
+    fn cursor(&self) -> Option<CursorStyle> {
+        Some(CursorStyle::Pointer)
+    }
+
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        // Closing the tab doesn't select it
+        event.stop_bubbling();
+        event.emit(msg!(TabsMsg::Close(self.index)));
+    }
+
+    fn view(&self) -> Option<Node> {
+        Some(node!(Text::new(txt!("×"))
+            .style("size", self.font_size)
+            .style("color", self.color)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tabs() -> Tabs {
+        Tabs::new(vec![
+            TabItem::new("One"),
+            TabItem::new("Two").closable(true),
+        ])
+        .active(1)
+        .on_change(Box::new(|index| msg!(("change", index))))
+        .on_close(Box::new(|index| msg!(("close", index))))
+    }
+
+    #[test]
+    fn test_messages() {
+        let mut tabs = tabs();
+        let m = tabs.update(msg!(TabsMsg::Select(0)));
+        assert_eq!(m[0].downcast_ref::<(&str, usize)>(), Some(&("change", 0)));
+        // Already active
+        assert!(tabs.update(msg!(TabsMsg::Select(1))).is_empty());
+
+        let m = tabs.update(msg!(TabsMsg::Close(1)));
+        assert_eq!(m[0].downcast_ref::<(&str, usize)>(), Some(&("close", 1)));
+    }
+
+    #[test]
+    fn test_indicator() {
+        let tab = Tab {
+            item: TabItem::new("One"),
+            index: 0,
+            active: true,
+            orientation: Orientation::Horizontal,
+            class: None,
+            style_overrides: Default::default(),
+        };
+        let aabb = AABB::new(Pos::new(10.0, 10.0, 0.0), Scale::new(80.0, 30.0));
+
+        let underline = tab.indicator(aabb, Color::BLUE, 2.0, false).instance_data;
+        assert_eq!(underline.pos, Pos::new(10.0, 38.0, 0.0));
+        assert_eq!(underline.scale, Scale::new(80.0, 2.0));
+
+        let pill = tab.indicator(aabb, Color::BLUE, 2.0, true).instance_data;
+        assert_eq!(pill.scale, Scale::new(80.0, 30.0));
+        assert_eq!(pill.radius, (15.0, 15.0, 15.0, 15.0));
+
+        let tab = Tab {
+            orientation: Orientation::Vertical,
+            ..tab
+        };
+        let side = tab.indicator(aabb, Color::BLUE, 2.0, false).instance_data;
+        assert_eq!(side.pos, Pos::new(88.0, 10.0, 0.0));
+    }
+}
@@ -2,9 +2,12 @@ use std::hash::Hash;
 
 use crate::component::{Component, ComponentHasher, RenderContext};
 use crate::font_cache::{FontCache, TextSegment};
-use crate::renderables::text::InstanceBuilder;
+use crate::renderables::text::{InstanceBuilder, TextDirection};
 use crate::renderables::{text, Renderable};
-use crate::style::{FontWeight, HorizontalPosition, Styled};
+use crate::style::{
+    FontStyle, FontWeight, HorizontalPosition, OverflowWrap, Styled, TextDecoration,
+    TextOverflow, TextShadow, WordBreak,
+};
 use crate::types::*;
 use cosmic_text::LayoutGlyph;
 use femtovg::Align;
@@ -19,6 +22,25 @@ struct BoundsCache {
     output: Option<(Option<f32>, Option<f32>)>,
 }
 
+/// Part of a segment's text that is laid out on one line.
+#[derive(Debug, PartialEq)]
+struct SegmentRun {
+    /// The index of the segment.
+    segment: usize,
+    text: String,
+    /// Where the run starts, relative to the start of the line.
+    x: f32,
+    width: f32,
+}
+
+/// A line of segment runs.
+#[derive(Debug, Default, PartialEq)]
+struct SegmentLine {
+    runs: Vec<SegmentRun>,
+    /// The width of the line, without the spaces at its end.
+    width: f32,
+}
+
 #[derive(Debug, Default)]
 pub struct TextState {
     bounds_cache: BoundsCache,
@@ -28,18 +50,222 @@ pub struct TextState {
 #[derive(Debug)]
 pub struct Text {
     pub text: Vec<TextSegment>,
+    /// Right-to-left text is right aligned by default, and the `Left` and `Right` `h_alignment`s swap.
+    pub direction: TextDirection,
+    /// Drawn in front of any shadows from the `text_shadow` style.
+    pub text_shadow: Option<TextShadow>,
 }
 
 impl Text {
     pub fn new(text: Vec<TextSegment>) -> Self {
         Self {
             text,
+            direction: TextDirection::Ltr,
+            text_shadow: None,
             class: Default::default(),
             style_overrides: Default::default(),
             state: Some(TextState::default()),
             dirty: false,
         }
     }
+
+    pub fn direction(mut self, direction: TextDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub fn text_shadow(mut self, text_shadow: TextShadow) -> Self {
+        self.text_shadow = Some(text_shadow);
+        self
+    }
+
+    /// The shadows to draw, back to front.
+    fn text_shadows(&self) -> Vec<TextShadow> {
+        let mut shadows = self
+            .style_val("text_shadow")
+            .map(|v| v.text_shadow())
+            .unwrap_or_default();
+        shadows.extend(self.text_shadow);
+        shadows
+    }
+
+    /// Precede the text `renderables` with their shadows.
+    fn with_shadows(&self, renderables: Vec<Renderable>) -> Vec<Renderable> {
+        let mut shadowed = vec![];
+        for shadow in self.text_shadows().iter() {
+            shadowed.extend(renderables.iter().filter_map(|r| match r {
+                Renderable::Text(t) => Some(Renderable::Text(t.shadow(shadow))),
+                _ => None,
+            }));
+        }
+        shadowed.extend(renderables);
+        shadowed
+    }
+
+    fn word_break(&self) -> WordBreak {
+        self.style_val("word_break")
+            .map(|v| v.word_break())
+            .unwrap_or_default()
+    }
+
+    fn overflow_wrap(&self) -> OverflowWrap {
+        self.style_val("overflow_wrap")
+            .map(|v| v.overflow_wrap())
+            .unwrap_or_default()
+    }
+
+    /// Extra space after every glyph, in logical pixels.
+    fn letter_spacing(&self) -> f32 {
+        self.style_val("letter_spacing").map_or(0.0, |v| v.f32())
+    }
+
+    /// Lay out each segment after the previous one, and break them onto more lines at newlines,
+    /// and where `word_break` and `overflow_wrap` allow when they are wider than `max_width`.
+    /// `measure` is the width of some text in a segment.
+    fn segment_lines(
+        &self,
+        max_width: f32,
+        mut measure: impl FnMut(&TextSegment, &str) -> f32,
+    ) -> Vec<SegmentLine> {
+        let word_break = self.word_break();
+        let overflow_wrap = self.overflow_wrap();
+        let wraps = word_break != WordBreak::Normal || overflow_wrap != OverflowWrap::Normal;
+        let breaks_words =
+            word_break == WordBreak::BreakAll || overflow_wrap != OverflowWrap::Normal;
+
+        let mut lines = vec![SegmentLine::default()];
+        let mut x = 0.0;
+        for (i, segment) in self.text.iter().enumerate() {
+            for (n, text) in segment.text.split('\n').enumerate() {
+                if n > 0 {
+                    lines.push(SegmentLine::default());
+                    x = 0.0;
+                }
+                let words: Vec<&str> = if !wraps {
+                    vec![text]
+                } else if word_break == WordBreak::BreakAll {
+                    text.split_inclusive(|_: char| true).collect()
+                } else {
+                    text.split_inclusive(' ').collect()
+                };
+                for word in words {
+                    let width = measure(segment, word);
+                    // A word that is longer than a line is broken between characters, if allowed
+                    let pieces: Vec<(&str, f32)> = if breaks_words && width > max_width {
+                        word.split_inclusive(|_: char| true)
+                            .map(|c| (c, measure(segment, c)))
+                            .collect()
+                    } else {
+                        vec![(word, width)]
+                    };
+                    for (piece, width) in pieces {
+                        // Spaces at the end of a line may overflow it
+                        let visible = if piece.ends_with(' ') {
+                            measure(segment, piece.trim_end())
+                        } else {
+                            width
+                        };
+                        let line = lines.last_mut().unwrap();
+                        if wraps && !line.runs.is_empty() && x + visible > max_width {
+                            lines.push(SegmentLine::default());
+                            x = 0.0;
+                        }
+                        let line = lines.last_mut().unwrap();
+                        match line.runs.last_mut() {
+                            Some(run) if run.segment == i => {
+                                run.text.push_str(piece);
+                                run.width += width;
+                            }
+                            _ => line.runs.push(SegmentRun {
+                                segment: i,
+                                text: piece.to_string(),
+                                x,
+                                width,
+                            }),
+                        }
+                        line.width = x + visible;
+                        x += width;
+                    }
+                }
+            }
+        }
+        lines
+    }
+
+    /// Lay out the segments on lines from [`#segment_lines`][Text#method.segment_lines], with
+    /// each segment on the baseline of its line, shifted by its `vertical_offset`.
+    fn render_segments(
+        &self,
+        context: &RenderContext,
+        align: Align,
+        font: Option<String>,
+        color: Color,
+        size: f32,
+        line_height: f32,
+        font_weight: FontWeight,
+        font_style: FontStyle,
+        decoration: TextDecoration,
+    ) -> Vec<Renderable> {
+        let AABB { pos, .. } = context.aabb;
+        let scale = context.aabb.size();
+        let letter_spacing = self.letter_spacing();
+        let lines = {
+            let mut font_cache = context.caches.font.write().unwrap();
+            self.segment_lines(scale.width, |segment, text| {
+                let segment_size = segment.font_size(size);
+                font_cache
+                    .measure_text(
+                        text.to_string(),
+                        segment.font.clone().or_else(|| font.clone()),
+                        segment_size,
+                        1.0,
+                        line_height * segment_size / size,
+                        HorizontalPosition::Left,
+                        (scale.width, scale.height),
+                        letter_spacing,
+                    )
+                    .0
+                    .unwrap_or(0.0)
+            })
+        };
+        let text: String = self.text.iter().map(|s| s.text.as_str()).collect();
+        let align = self.direction.physical_align(align, &text);
+
+        let mut renderables = vec![];
+        for (row, line) in lines.iter().enumerate() {
+            let left = pos.x
+                + match align {
+                    Align::Left => 0.0,
+                    Align::Center => (scale.width - line.width) / 2.0,
+                    Align::Right => scale.width - line.width,
+                };
+            let top = pos.y + row as f32 * line_height;
+            for run in line.runs.iter() {
+                let segment = &self.text[run.segment];
+                let segment_size = segment.font_size(size);
+                let text_instance = InstanceBuilder::default()
+                    .align(Align::Left)
+                    .direction(self.direction)
+                    .pos(Pos::new(left + run.x, segment.top(top, size), pos.z))
+                    .scale(Scale::new(run.width, scale.height))
+                    .text(run.text.clone())
+                    .color(color)
+                    .font(segment.font.clone().or_else(|| font.clone()))
+                    .weight(font_weight)
+                    .font_style(font_style)
+                    .decoration(decoration)
+                    .letter_spacing(letter_spacing)
+                    .line_height(line_height * segment_size / size)
+                    .font_size(segment_size)
+                    .build()
+                    .unwrap();
+                renderables.push(Renderable::Text(text::Text::from_instance_data(
+                    text_instance,
+                )));
+            }
+        }
+        renderables
+    }
 }
 
 #[state_component_impl(TextState)]
@@ -54,10 +280,14 @@ impl Component for Text {
 
     fn render_hash(&self, hasher: &mut ComponentHasher) {
         self.text.hash(hasher);
+        self.direction.hash(hasher);
         (self.style_val("size").unwrap().f32() as u32).hash(hasher);
         (self.style_val("color").unwrap().color()).hash(hasher);
         (self.style_val("font").map(|p| p.str().to_string())).hash(hasher);
         (self.style_val("h_alignment").map(|v| v.horizontal_position())).hash(hasher);
+        self.word_break().hash(hasher);
+        self.overflow_wrap().hash(hasher);
+        self.text_shadows().hash(hasher);
     }
 
     fn fill_bounds(
@@ -91,20 +321,64 @@ impl Component for Text {
             line_height = self.style_val("line_height").unwrap().f32();
         }
 
-        let (t_w, t_h, ..) = font_cache.measure_text(
-            text.clone(),
-            font,
-            size,
-            scale_factor,
-            line_height,
-            HorizontalPosition::Left,
-            (
-                width.or(max_width).unwrap_or(std::f32::MAX) * scale_factor,
-                height.or(max_height).unwrap_or(std::f32::MAX) * scale_factor,
-            ),
+        let bounds = (
+            width.or(max_width).unwrap_or(std::f32::MAX) * scale_factor,
+            height.or(max_height).unwrap_or(std::f32::MAX) * scale_factor,
         );
-
-        let output = (t_w, t_h);
+        let output = if self.text.len() > 1 || self.text[0].vertical_offset.is_some() {
+            // Segments are laid out in rows, and offset segments can extend their line
+            let letter_spacing = self.letter_spacing();
+            let lines = self.segment_lines(bounds.0, |segment, text| {
+                let segment_size = segment.font_size(size);
+                font_cache
+                    .measure_text(
+                        text.to_string(),
+                        segment.font.clone().or_else(|| font.clone()),
+                        segment_size,
+                        scale_factor,
+                        line_height * segment_size / size,
+                        HorizontalPosition::Left,
+                        bounds,
+                        letter_spacing,
+                    )
+                    .0
+                    .unwrap_or(0.0)
+            });
+            let t_w = lines.iter().map(|line| line.width).fold(0.0, f32::max);
+            let t_h: f32 = lines
+                .iter()
+                .map(|line| {
+                    line.runs.iter().fold(line_height, |h, run| {
+                        let segment = &self.text[run.segment];
+                        let offset = (segment.top(0.0, size) * scale_factor).abs();
+                        h.max(line_height * segment.font_size(size) / size + offset)
+                    })
+                })
+                .sum();
+            (Some(t_w), Some(t_h))
+        } else {
+            let (t_w, t_h, ..) = font_cache.measure_wrapped_text(
+                text.clone(),
+                font,
+                size,
+                scale_factor,
+                line_height,
+                HorizontalPosition::Left,
+                bounds,
+                self.letter_spacing(),
+                self.word_break(),
+                self.overflow_wrap(),
+            );
+            (t_w, t_h)
+        };
+        // Lines past `max_lines` are not drawn, so they take up no space
+        let output = match self.style_val("max_lines") {
+            Some(max_lines) => {
+                let max_height = max_lines.u32().max(1) as f32 * line_height;
+                (output.0, output.1.map(|h| h.min(max_height)))
+            }
+            None => output,
+        };
         self.state_mut().bounds_cache = BoundsCache {
             width,
             height,
@@ -136,6 +410,12 @@ impl Component for Text {
         } else {
             FontWeight::Normal
         };
+        let font_style = self
+            .style_val("font_style")
+            .map_or(FontStyle::Normal, |s| s.font_style());
+        let decoration = self
+            .style_val("text_decoration")
+            .map_or(TextDecoration::NONE, |d| d.text_decoration());
         // line height as 1.3 of font_size
         let line_height = if let Some(line_height) = self.style_val("line_height") {
             line_height.f32()
@@ -161,25 +441,268 @@ impl Component for Text {
         //         z: 0.,
         //     };
 
+        if self.text.len() > 1 || self.text[0].vertical_offset.is_some() {
+            let segments = self.render_segments(
+                &context,
+                match h_alignment {
+                    HorizontalPosition::Left => Align::Left,
+                    HorizontalPosition::Center => Align::Center,
+                    HorizontalPosition::Right => Align::Right,
+                },
+                font,
+                color,
+                size,
+                line_height,
+                font_weight,
+                font_style,
+                decoration,
+            );
+            return Some(self.with_shadows(segments));
+        }
+
         let text_instance = InstanceBuilder::default()
             .align(match h_alignment {
                 HorizontalPosition::Left => Align::Left,
                 HorizontalPosition::Center => Align::Center,
                 HorizontalPosition::Right => Align::Right,
             })
+            .direction(self.direction)
             .pos(pos)
             .scale(scale)
             .text(self.text.get(0).unwrap().text.clone())
             .color(color)
             .font(font)
             .weight(font_weight)
+            .font_style(font_style)
+            .decoration(decoration)
+            .letter_spacing(self.letter_spacing())
+            .overflow(
+                self.style_val("text_overflow")
+                    .map_or(TextOverflow::Clip, |o| o.text_overflow()),
+            )
+            .max_lines(self.style_val("max_lines").map(|m| m.u32()))
             .line_height(line_height)
             .font_size(size)
+            .word_break(self.word_break())
+            .overflow_wrap(self.overflow_wrap())
             .build()
             .unwrap();
 
-        Some(vec![Renderable::Text(text::Text::from_instance_data(
-            text_instance,
-        ))])
+        Some(self.with_shadows(vec![Renderable::Text(
+            text::Text::from_instance_data(text_instance),
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::txt;
+    use cosmic_text::fontdb::Database;
+
+    fn font_cache() -> FontCache {
+        let mut fonts = Database::new();
+        fonts.load_font_data(
+            include_bytes!("../../../examples/kitchen/src/assets/fonts/SpaceGrotesk-Regular.ttf")
+                .to_vec(),
+        );
+        FontCache::new(fonts)
+    }
+
+    #[test]
+    fn test_word_break_all() {
+        let mut font_cache = font_cache();
+        let long_word = "abcdefghij".repeat(5);
+
+        let mut text = Text::new(txt!(long_word.clone())).style("font", "Space Grotesk");
+        let (width, height) = text.fill_bounds(None, None, Some(100.0), None, &mut font_cache, 1.0);
+        assert!(width.unwrap() > 100.0);
+        let line_height = height.unwrap();
+
+        let mut text = Text::new(txt!(long_word))
+            .style("font", "Space Grotesk")
+            .with_class("word-break-all");
+        let (width, height) = text.fill_bounds(None, None, Some(100.0), None, &mut font_cache, 1.0);
+        assert!(width.unwrap() <= 100.0);
+        assert!(height.unwrap() > line_height);
+    }
+
+    #[test]
+    fn test_max_lines() {
+        let mut font_cache = font_cache();
+        let text = "The quick brown fox jumps over the lazy dog ".repeat(3);
+
+        let mut wrapped = Text::new(txt!(text.clone()))
+            .style("font", "Space Grotesk")
+            .style("line_height", 20.0)
+            .with_class("break-word");
+        let (_, height) = wrapped.fill_bounds(None, None, Some(100.0), None, &mut font_cache, 1.0);
+        assert!(height.unwrap() > 40.0);
+
+        let mut clamped = Text::new(txt!(text))
+            .style("font", "Space Grotesk")
+            .style("line_height", 20.0)
+            .with_class("break-word lines-2");
+        let (_, height) = clamped.fill_bounds(None, None, Some(100.0), None, &mut font_cache, 1.0);
+        assert_eq!(height, Some(40.0));
+
+        // Text with fewer lines keeps its own height
+        let mut short = Text::new(txt!("Fox"))
+            .style("font", "Space Grotesk")
+            .style("line_height", 20.0)
+            .with_class("lines-2");
+        let (_, height) = short.fill_bounds(None, None, Some(100.0), None, &mut font_cache, 1.0);
+        assert_eq!(height, Some(20.0));
+    }
+
+    /// The text renderables of `text`, rendered in a box of `width`.
+    fn render_texts(text: &mut Text, width: f32) -> Vec<text::Instance> {
+        let context = RenderContext {
+            aabb: AABB::new(Pos::new(0.0, 0.0, 0.0), Scale::new(width, 100.0)),
+            inner_scale: None,
+            caches: crate::renderer::Caches {
+                font: std::sync::Arc::new(std::sync::RwLock::new(font_cache())),
+            },
+            prev_state: None,
+            scale_factor: 1.0,
+        };
+        text.render(context)
+            .unwrap()
+            .into_iter()
+            .map(|r| match r {
+                Renderable::Text(t) => t.instance_data,
+                r => panic!("Expected a text renderable, got {r:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_segments_aligned() {
+        let mut text = Text::new(vec![
+            "H".into(),
+            TextSegment::subscript("2", 16.0),
+            "O".into(),
+        ])
+        .style("font", "Space Grotesk")
+        .style("h_alignment", HorizontalPosition::Right);
+        let runs = render_texts(&mut text, 200.0);
+        assert_eq!(runs.len(), 3);
+        // The segments follow each other, and the last one ends at the right
+        assert_eq!(runs[1].pos.x, runs[0].pos.x + runs[0].scale.width);
+        let last = &runs[2];
+        assert!((last.pos.x + last.scale.width - 200.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_segments_wrapped() {
+        let segments = || {
+            vec![
+                "The quick brown fox jumps over the lazy dog".into(),
+                TextSegment::superscript("2", 16.0),
+            ]
+        };
+        let mut text = Text::new(segments()).style("font", "Space Grotesk");
+        let runs = render_texts(&mut text, 100.0);
+        // Without wrapping, the segments stay on one line
+        assert_eq!(runs.len(), 2);
+
+        let mut text = Text::new(segments())
+            .style("font", "Space Grotesk")
+            .style("line_height", 20.0)
+            .with_class("break-word");
+        let runs = render_texts(&mut text, 100.0);
+        assert!(runs.len() > 2);
+        for run in runs.iter() {
+            assert!(run.pos.x >= 0.0);
+            assert!(run.pos.x + run.scale.width <= 100.0 + run.font_size);
+        }
+        // Each line starts at the left, a line height below the last
+        let tops: Vec<f32> = runs
+            .iter()
+            .filter(|r| r.pos.x == 0.0)
+            .map(|r| r.pos.y)
+            .collect();
+        assert!(tops.len() > 1);
+        assert_eq!(tops[1] - tops[0], 20.0);
+
+        let (width, height) =
+            text.fill_bounds(None, None, Some(100.0), None, &mut font_cache(), 1.0);
+        assert!(width.unwrap() <= 100.0);
+        assert!(height.unwrap() >= 20.0 * tops.len() as f32);
+    }
+
+    #[test]
+    fn test_text_shadow() {
+        let mut text = Text::new(txt!("Shadow"))
+            .style("font", "Space Grotesk")
+            .text_shadow(TextShadow::new(0.0, 2.0, 0.0, Color::RED));
+        let context = RenderContext {
+            aabb: AABB::new(Pos::new(10.0, 20.0, 0.0), Scale::new(100.0, 20.0)),
+            inner_scale: None,
+            caches: crate::renderer::Caches {
+                font: std::sync::Arc::new(std::sync::RwLock::new(font_cache())),
+            },
+            prev_state: None,
+            scale_factor: 1.0,
+        };
+        let renderables = text.render(context).unwrap();
+        assert_eq!(renderables.len(), 2);
+        let (shadow, main) = match (&renderables[0], &renderables[1]) {
+            (Renderable::Text(shadow), Renderable::Text(main)) => (shadow, main),
+            _ => panic!("Expected text renderables"),
+        };
+        // The shadow is drawn first, below the text
+        assert_eq!(shadow.instance_data.color, Color::RED);
+        assert_eq!(shadow.instance_data.pos.x, main.instance_data.pos.x);
+        assert_eq!(shadow.instance_data.pos.y, main.instance_data.pos.y + 2.0);
+        assert_eq!(shadow.instance_data.text, main.instance_data.text);
+    }
+
+    #[test]
+    fn test_font_style() {
+        let text = Text::new(txt!("Italic")).with_class("font-italic");
+        assert_eq!(
+            text.style_val("font_style").unwrap().font_style(),
+            FontStyle::Italic
+        );
+
+        let mut text = text.style("font", "Space Grotesk");
+        let context = RenderContext {
+            aabb: AABB::new(Pos::new(0.0, 0.0, 0.0), Scale::new(100.0, 20.0)),
+            inner_scale: None,
+            caches: crate::renderer::Caches {
+                font: std::sync::Arc::new(std::sync::RwLock::new(font_cache())),
+            },
+            prev_state: None,
+            scale_factor: 1.0,
+        };
+        match &text.render(context).unwrap()[0] {
+            Renderable::Text(t) => assert_eq!(t.instance_data.font_style, FontStyle::Italic),
+            r => panic!("Expected a text renderable, got {r:?}"),
+        }
+    }
+
+    #[test]
+    fn test_truncate() {
+        let mut text = Text::new(txt!("A long line of text"))
+            .style("font", "Space Grotesk")
+            .style("max_lines", 2u32)
+            .with_class("truncate");
+        let context = RenderContext {
+            aabb: AABB::new(Pos::new(0.0, 0.0, 0.0), Scale::new(50.0, 20.0)),
+            inner_scale: None,
+            caches: crate::renderer::Caches {
+                font: std::sync::Arc::new(std::sync::RwLock::new(font_cache())),
+            },
+            prev_state: None,
+            scale_factor: 1.0,
+        };
+        match &text.render(context).unwrap()[0] {
+            Renderable::Text(t) => {
+                assert_eq!(t.instance_data.overflow, TextOverflow::Ellipsis);
+                assert_eq!(t.instance_data.max_lines, Some(2));
+            }
+            r => panic!("Expected a text renderable, got {r:?}"),
+        }
     }
 }
@@ -1,26 +1,41 @@
 use std::cmp::Ordering;
+use std::collections::VecDeque;
 use std::default;
 use std::hash::Hash;
 use std::ops::Add;
+use std::sync::{Arc, RwLock};
 use std::time::Instant;
 
 use crate::component::{Component, ComponentHasher, Message, RenderContext};
 use crate::font_cache::{FontCache, TextSegment};
 use crate::input::Key;
-use crate::layout::{Alignment, ScrollPosition};
+use crate::layout::{Alignment, Dimension, Direction, ScrollPosition, Size};
 use crate::renderables::{
     rect::InstanceBuilder as RectInstanceBuilder, text::InstanceBuilder as TextInstanceBuilder,
+    text::TextDirection,
 };
 use crate::renderables::{Rect, Renderable, Text};
-use crate::style::{BorderWidth, HorizontalPosition, Styled};
-use crate::{event, lay, msg, node, rect, size, size_pct, types::*, Node};
+use crate::style::{
+    current_style, BorderRadius, BorderWidth, BoxShadow, CursorStyle, FontStyle,
+    HorizontalPosition, StyleVal, Styled, TextDecoration, TextShadow,
+};
+use crate::{event, lay, msg, node, rect, size, size_pct, txt, types::*, Node};
 use cosmic_text::LayoutGlyph;
 use femtovg::Align;
 use mctk_macros::{component, state_component_impl};
 
-use super::IconButton;
+use super::select::dropdown;
+use super::{Div, IconButton};
 
 const CURSOR_BLINK_PERIOD: u128 = 500; // millis
+const UNDO_COALESCE_PERIOD: u128 = 500; // millis
+const DEFAULT_UNDO_DEPTH: usize = 100;
+/// How long the text must be left alone before suggestions are asked for.
+const SUGGESTION_DEBOUNCE: u128 = 150; // millis
+/// The size of the square in the bottom right corner that resizes a [`TextBox`], in logical pixels.
+const RESIZE_HANDLE_SIZE: f32 = 12.0;
+/// How few characters may be left before the counter warns of the `max_length`.
+const COUNTER_WARNING_REMAINING: usize = 10;
 
 #[derive(Debug)]
 enum TextBoxMessage {
@@ -29,6 +44,20 @@ enum TextBoxMessage {
     Change(String),
     Commit(String),
     ToggleHidden,
+    Resize(Option<f32>, Option<f32>),
+    Undo,
+    Redo,
+    CompositionStart,
+    CompositionUpdate(String),
+    CompositionEnd(String),
+    /// Highlight the next suggestion, or the previous one if `false`.
+    SuggestionMove(bool),
+    /// Accept the highlighted suggestion, or the first.
+    SuggestionAccept,
+    SuggestionDismiss,
+    SuggestionHover(usize),
+    SuggestionClick(usize),
+    Find(Vec<(usize, usize)>),
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -36,6 +65,8 @@ pub enum TextBoxAction {
     Cut,
     Copy,
     Paste,
+    Undo,
+    Redo,
 }
 
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -45,11 +76,205 @@ pub enum TextBoxVariant {
     Hidden,
 }
 
+/// What pressing Tab in a [`TextBox`] does.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TabBehavior {
+    /// Move the focus on. Since there is no focus order, this leaves the text box, as does
+    /// Shift+Tab.
+    #[default]
+    FocusNext,
+    /// Insert a tab character.
+    InsertTab,
+    /// Insert this many spaces.
+    InsertSpaces(u8),
+}
+
+/// Which ways a [`TextBox`] can be resized by dragging its bottom right corner, like the CSS
+/// `resize` property. Set with the `resize` style parameter, which the `textarea` class sets to
+/// `"vertical"`.
+///
+/// A resized text box sets its own size, so the axes that are resized should be left `Auto` in
+/// its layout.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ResizeMode {
+    #[default]
+    None,
+    Vertical,
+    Horizontal,
+    Both,
+}
+
+impl ResizeMode {
+    fn from_style(name: &str) -> Self {
+        match name {
+            "vertical" => ResizeMode::Vertical,
+            "horizontal" => ResizeMode::Horizontal,
+            "both" => ResizeMode::Both,
+            _ => ResizeMode::None,
+        }
+    }
+
+    fn horizontal(self) -> bool {
+        matches!(self, ResizeMode::Horizontal | ResizeMode::Both)
+    }
+
+    fn vertical(self) -> bool {
+        matches!(self, ResizeMode::Vertical | ResizeMode::Both)
+    }
+}
+
 #[derive(Debug, Default)]
 struct TextBoxState {
     focused: bool,
     hidden: bool,
     has_text_value: bool,
+    /// The size the text box has been resized to, in logical pixels.
+    resized: (Option<f32>, Option<f32>),
+    /// The text to suggest completions of, and when it was typed.
+    query: Option<(String, Instant)>,
+    suggestions: Vec<String>,
+    highlighted: Option<usize>,
+    /// The suggestion last accepted, until the text is next edited.
+    accepted: Option<String>,
+    accepted_count: u32,
+    /// How many characters long the text is, once it has changed.
+    char_count: Option<usize>,
+}
+
+/// The text and cursor of a [`TextBox`] at some point in its history.
+#[derive(Debug, Clone, PartialEq)]
+struct TextBoxSnapshot {
+    text: String,
+    cursor_pos: usize,
+}
+
+/// Bounded undo/redo history. Entries are the states *before* each edit.
+#[derive(Debug)]
+struct UndoStack<T> {
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+    max_depth: usize,
+    // When the last coalescable edit was pushed
+    coalesce_from: Option<Instant>,
+}
+
+impl<T> UndoStack<T> {
+    fn new(max_depth: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: vec![],
+            max_depth,
+            coalesce_from: None,
+        }
+    }
+
+    /// Record `before`, the state prior to an edit. Consecutive coalescable edits that happen
+    /// within [`UNDO_COALESCE_PERIOD`] of each other are merged into a single entry.
+    fn push(&mut self, before: T, coalesce: bool, now: Instant) {
+        self.redo.clear();
+        if coalesce {
+            let merge = self
+                .coalesce_from
+                .map(|t| now.duration_since(t).as_millis() < UNDO_COALESCE_PERIOD)
+                .unwrap_or(false);
+            self.coalesce_from = Some(now);
+            if merge && !self.undo.is_empty() {
+                return;
+            }
+        } else {
+            self.coalesce_from = None;
+        }
+
+        if self.max_depth == 0 {
+            return;
+        }
+        while self.undo.len() >= self.max_depth {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(before);
+    }
+
+    fn undo(&mut self, current: T) -> Option<T> {
+        let prev = self.undo.pop_back()?;
+        self.redo.push(current);
+        self.coalesce_from = None;
+        Some(prev)
+    }
+
+    fn redo(&mut self, current: T) -> Option<T> {
+        let next = self.redo.pop()?;
+        self.undo.push_back(current);
+        self.coalesce_from = None;
+        Some(next)
+    }
+}
+
+/// What a [`TextBoxController`] was asked to do.
+#[derive(Debug, Clone, PartialEq)]
+enum TextBoxRequest {
+    Undo,
+    Redo,
+    Find(String, bool),
+    Replace((usize, usize), String),
+    ReplaceAll(String, String, bool),
+}
+
+/// A handle for editing a [`TextBox`] from outside of it, e.g. from its parent's `update`.
+/// Keep one in the parent's state and give the `TextBox` a clone with [`TextBox::controller`].
+/// Requests are applied in order on the text's next tick, and edits call `on_change` as typing
+/// does.
+#[derive(Debug, Clone, Default)]
+pub struct TextBoxController(Arc<RwLock<Vec<TextBoxRequest>>>);
+
+impl TextBoxController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Revert the last edit, as `Ctrl+Z` does.
+    pub fn undo(&self) {
+        self.request(TextBoxRequest::Undo);
+    }
+
+    /// Re-apply the last undone edit, as `Ctrl+Shift+Z` does.
+    pub fn redo(&self) {
+        self.request(TextBoxRequest::Redo);
+    }
+
+    /// Highlight every match of `query` with the `find_highlight_color`, and move the cursor to
+    /// the first one. The byte-offset spans of the matches are passed to `on_find`. An empty
+    /// `query` clears the highlights.
+    pub fn find<S: Into<String>>(&self, query: S, case_sensitive: bool) {
+        self.request(TextBoxRequest::Find(query.into(), case_sensitive));
+    }
+
+    /// Replace the text in the byte-offset `span`, e.g. one passed to `on_find`, as a single
+    /// undoable edit.
+    pub fn replace<S: Into<String>>(&self, span: (usize, usize), replacement: S) {
+        self.request(TextBoxRequest::Replace(span, replacement.into()));
+    }
+
+    /// Replace every match of `query`, as a single undoable edit.
+    pub fn replace_all<S: Into<String>, R: Into<String>>(
+        &self,
+        query: S,
+        replacement: R,
+        case_sensitive: bool,
+    ) {
+        self.request(TextBoxRequest::ReplaceAll(
+            query.into(),
+            replacement.into(),
+            case_sensitive,
+        ));
+    }
+
+    fn request(&self, request: TextBoxRequest) {
+        self.0.write().unwrap().push(request);
+    }
+
+    fn take(&self) -> Vec<TextBoxRequest> {
+        std::mem::take(&mut *self.0.write().unwrap())
+    }
 }
 
 #[component(State = "TextBoxState", Styled, Internal)]
@@ -62,6 +287,25 @@ pub struct TextBox {
     on_change: Option<Box<dyn Fn(&str) -> Message + Send + Sync>>,
     on_commit: Option<Box<dyn Fn(&str) -> Message + Send + Sync>>,
     on_focus: Option<Box<dyn Fn() -> Message + Send + Sync>>,
+    on_undo: Option<Box<dyn Fn() -> Message + Send + Sync>>,
+    on_redo: Option<Box<dyn Fn() -> Message + Send + Sync>>,
+    on_composition_start: Option<Box<dyn Fn() -> Message + Send + Sync>>,
+    on_composition_update: Option<Box<dyn Fn(&str) -> Message + Send + Sync>>,
+    on_composition_end: Option<Box<dyn Fn(&str) -> Message + Send + Sync>>,
+    on_find: Option<Box<dyn Fn(&[(usize, usize)]) -> Message + Send + Sync>>,
+    autocomplete: Option<Box<dyn Fn(&str) -> Vec<String> + Send + Sync>>,
+    controller: Option<TextBoxController>,
+    undo_depth: usize,
+    tab_behavior: TabBehavior,
+    text_shadow: Option<TextShadow>,
+    multiline: bool,
+    min_rows: u32,
+    max_rows: Option<u32>,
+    shift_enter_submits: bool,
+    max_length: Option<usize>,
+    show_counter: bool,
+    direction: TextDirection,
+    bidi: bool,
 }
 
 impl std::fmt::Debug for TextBox {
@@ -79,8 +323,27 @@ impl TextBox {
             on_change: None,
             on_commit: None,
             on_focus: None,
+            on_undo: None,
+            on_redo: None,
+            on_composition_start: None,
+            on_composition_update: None,
+            on_composition_end: None,
+            on_find: None,
+            autocomplete: None,
+            controller: None,
             show_icon: None,
             hide_icon: None,
+            undo_depth: DEFAULT_UNDO_DEPTH,
+            tab_behavior: TabBehavior::default(),
+            text_shadow: None,
+            multiline: false,
+            min_rows: 1,
+            max_rows: None,
+            shift_enter_submits: false,
+            max_length: None,
+            show_counter: false,
+            direction: TextDirection::Ltr,
+            bidi: false,
             state: Some(TextBoxState::default()),
             dirty: false,
             class: Default::default(),
@@ -103,6 +366,88 @@ impl TextBox {
         self
     }
 
+    /// Called after an edit is undone, once `on_change` has been.
+    pub fn on_undo(mut self, undo_fn: Box<dyn Fn() -> Message + Send + Sync>) -> Self {
+        self.on_undo = Some(undo_fn);
+        self
+    }
+
+    /// Called after an undone edit is redone, once `on_change` has been.
+    pub fn on_redo(mut self, redo_fn: Box<dyn Fn() -> Message + Send + Sync>) -> Self {
+        self.on_redo = Some(redo_fn);
+        self
+    }
+
+    /// Called when an input method starts composing text.
+    pub fn on_composition_start(
+        mut self,
+        start_fn: Box<dyn Fn() -> Message + Send + Sync>,
+    ) -> Self {
+        self.on_composition_start = Some(start_fn);
+        self
+    }
+
+    /// Called with the text composed so far, each time it changes.
+    pub fn on_composition_update(
+        mut self,
+        update_fn: Box<dyn Fn(&str) -> Message + Send + Sync>,
+    ) -> Self {
+        self.on_composition_update = Some(update_fn);
+        self
+    }
+
+    /// Called with the committed text when a composition ends, or with an empty string when it is
+    /// cancelled.
+    pub fn on_composition_end(
+        mut self,
+        end_fn: Box<dyn Fn(&str) -> Message + Send + Sync>,
+    ) -> Self {
+        self.on_composition_end = Some(end_fn);
+        self
+    }
+
+    /// Called with the byte-offset spans of the matches of a [`TextBoxController::find`].
+    pub fn on_find(
+        mut self,
+        find_fn: Box<dyn Fn(&[(usize, usize)]) -> Message + Send + Sync>,
+    ) -> Self {
+        self.on_find = Some(find_fn);
+        self
+    }
+
+    pub fn controller(mut self, controller: TextBoxController) -> Self {
+        self.controller = Some(controller);
+        self
+    }
+
+    /// The direction of the text's paragraph. The Left and Right arrow keys move forward and back
+    /// through right-to-left text, but the text is still laid out from the left of the box.
+    pub fn direction(mut self, direction: TextDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Move the cursor with the Left and Right arrow keys to the nearest position on that side,
+    /// across runs of mixed-direction text, instead of back and forward through the text.
+    pub fn bidi(mut self, bidi: bool) -> Self {
+        self.bidi = bidi;
+        self
+    }
+
+    /// Suggest completions of the text, from those that `autocomplete_fn` returns for it, in a
+    /// dropdown below the text box. Up and Down move through them, Enter accepts the highlighted
+    /// one, Tab the highlighted one or the first, and Escape hides them.
+    ///
+    /// Suggestions are only asked for once the text has been left alone for a moment, and has at
+    /// least `suggestion_min_chars` characters. At most `suggestion_max_items` are shown.
+    pub fn autocomplete(
+        mut self,
+        autocomplete_fn: Box<dyn Fn(&str) -> Vec<String> + Send + Sync>,
+    ) -> Self {
+        self.autocomplete = Some(autocomplete_fn);
+        self
+    }
+
     pub fn placeholder<S: Into<String>>(mut self, placeholder: S) -> Self {
         self.placeholder = Some(placeholder.into());
         self
@@ -120,24 +465,299 @@ impl TextBox {
         self.hide_icon = Some(icon.into());
         self
     }
+
+    /// Maximum number of edits that can be undone with `Ctrl+Z`. Defaults to 100.
+    pub fn undo_depth(mut self, max_depth: usize) -> Self {
+        self.undo_depth = max_depth;
+        self
+    }
+
+    pub fn tab_behavior(mut self, tab_behavior: TabBehavior) -> Self {
+        self.tab_behavior = tab_behavior;
+        self
+    }
+
+    pub fn text_shadow(mut self, text_shadow: TextShadow) -> Self {
+        self.text_shadow = Some(text_shadow);
+        self
+    }
+
+    /// Wrap the text to the width of the text box, and insert a newline on Enter rather than
+    /// committing. Also turned on by the `multiline` style parameter, which the `textarea` class
+    /// sets. Hidden text boxes are always single-line.
+    pub fn multiline(mut self, multiline: bool) -> Self {
+        self.multiline = multiline;
+        self
+    }
+
+    /// The fewest rows a multi-line text box is tall enough for. Defaults to 1.
+    pub fn min_rows(mut self, min_rows: u32) -> Self {
+        self.min_rows = min_rows;
+        self
+    }
+
+    /// The most rows a multi-line text box grows to fit, after which it scrolls. By default, it
+    /// grows to fit all of its text.
+    pub fn max_rows(mut self, max_rows: u32) -> Self {
+        self.max_rows = Some(max_rows);
+        self
+    }
+
+    /// Commit a multi-line text box with Shift+Enter, as Enter does for a single-line one.
+    pub fn shift_enter_submits(mut self, shift_enter_submits: bool) -> Self {
+        self.shift_enter_submits = shift_enter_submits;
+        self
+    }
+
+    /// The most characters the text can have. Typed or pasted text is cut short to fit.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Show how many characters long the text is in the bottom right corner, out of the
+    /// `max_length` if there is one. It is drawn in `counter_color`, or `counter_warning_color`
+    /// once there are 10 or fewer characters left.
+    pub fn show_counter(mut self, show_counter: bool) -> Self {
+        self.show_counter = show_counter;
+        self
+    }
+
+    fn is_multiline(&self) -> bool {
+        (self.multiline || self.style_val("multiline").map_or(false, |v| v.bool()))
+            && self.variant != Some(TextBoxVariant::Hidden)
+    }
+
+    fn resize_mode(&self) -> ResizeMode {
+        self.style_val("resize")
+            .map_or(ResizeMode::None, |v| ResizeMode::from_style(v.str()))
+    }
+
+    fn line_height(&self) -> f32 {
+        row_height(self)
+    }
+
+    /// A scroll bar parameter, which falls back to that of `Scroll`.
+    fn scroll_bar_val(&self, param: &'static str) -> Option<StyleVal> {
+        self.style_val(param)
+            .or_else(|| current_style("Scroll", param))
+    }
+
+    /// The logical height of `rows` rows of text, and the padding and borders around them.
+    fn rows_height(&self, rows: u32) -> f32 {
+        let padding: f32 = self.style_val("padding").unwrap().f32();
+        let border_width: BorderWidth = self.style_val("border_width").unwrap().into();
+        rows as f32 * self.line_height() + padding * 2.0 + border_width.left * 2.0
+    }
+
+    fn char_count(&self) -> usize {
+        self.state_ref()
+            .char_count
+            .unwrap_or_else(|| self.text.as_ref().map_or(0, |t| t.chars().count()))
+    }
+
+    /// The text of the counter, and its color, if it is shown.
+    fn counter(&self) -> Option<(String, Color)> {
+        if !self.show_counter {
+            return None;
+        }
+        let count = self.char_count();
+        let color: Color = self.style_val("counter_color").into();
+        Some(match self.max_length {
+            Some(max_length) => {
+                let color = if max_length.saturating_sub(count) <= COUNTER_WARNING_REMAINING {
+                    self.style_val("counter_warning_color").into()
+                } else {
+                    color
+                };
+                (format!("{}/{}", count, max_length), color)
+            }
+            None => (count.to_string(), color),
+        })
+    }
+
+    /// Replace the suggestions with those for `text`.
+    fn suggest(&mut self, text: &str) {
+        let min_chars = self
+            .style_val("suggestion_min_chars")
+            .map_or(1, |v| v.u32()) as usize;
+        let max_items = self
+            .style_val("suggestion_max_items")
+            .map_or(8, |v| v.u32()) as usize;
+        let suggestions = match &self.autocomplete {
+            Some(autocomplete_fn) if text.chars().count() >= min_chars => {
+                let mut suggestions = autocomplete_fn(text);
+                suggestions.truncate(max_items);
+                suggestions
+            }
+            _ => vec![],
+        };
+        self.state_mut().suggestions = suggestions;
+        self.state_mut().highlighted = None;
+    }
+
+    fn dismiss_suggestions(&mut self) {
+        if self.state_ref().query.is_some() || !self.state_ref().suggestions.is_empty() {
+            self.state_mut().query = None;
+            self.state_mut().suggestions.clear();
+            self.state_mut().highlighted = None;
+        }
+    }
+
+    /// Highlight the next suggestion, or the previous one if not `down`, wrapping around.
+    fn move_highlight(&mut self, down: bool) {
+        let count = self.state_ref().suggestions.len();
+        if count == 0 {
+            return;
+        }
+        let highlighted = match (self.state_ref().highlighted, down) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(i), true) => (i + 1) % count,
+            (Some(i), false) => (i + count - 1) % count,
+        };
+        self.state_mut().highlighted = Some(highlighted);
+    }
+
+    /// Replace the text with the suggestion `index`, which the [`TextBoxText`] picks up when it is
+    /// next viewed.
+    fn accept_suggestion(&mut self, index: usize) -> Option<Message> {
+        let text = self.state_ref().suggestions.get(index)?.clone();
+        self.dismiss_suggestions();
+        self.state_mut().accepted_count = self.state_ref().accepted_count.wrapping_add(1);
+        self.state_mut().accepted = Some(text.clone());
+        self.state_mut().has_text_value = !text.is_empty();
+        // The text box cuts it short to the max length
+        let char_count = text.chars().count().min(self.max_length.unwrap_or(usize::MAX));
+        self.state_mut().char_count = Some(char_count);
+        self.on_change.as_ref().map(|change_fn| change_fn(&text))
+    }
+
+    /// The dropdown of suggestions.
+    fn suggestion_list(&self) -> Node {
+        let padding: f64 = self.style_val("padding").unwrap().into();
+        let border_width: BorderWidth = self.style_val("border_width").unwrap().into();
+        let hover_color: Color = self.style_val("suggestion_hover_color").into();
+        let mut list = dropdown(
+            self.style_val("suggestion_background").into(),
+            self.style_val("border_color").into(),
+            border_width.left,
+            BorderRadius::default(),
+            self.style_val("shadow").map(|v| v.box_shadow()),
+            Dimension::Auto,
+        );
+        for (i, suggestion) in self.state_ref().suggestions.iter().enumerate() {
+            let background = if self.state_ref().highlighted == Some(i) {
+                hover_color
+            } else {
+                Color::TRANSPARENT
+            };
+            list = list.push(
+                node!(SuggestionOption { index: i }, lay![size_pct: [100, Auto]])
+                    .key(i as u64)
+                    .push(
+                        node!(
+                            Div::new().bg(background),
+                            lay![size_pct: [100, Auto], padding: rect!(padding)]
+                        )
+                        .push(node!(super::Text::new(txt!(suggestion.clone()))
+                            .style("size", self.style_val("font_size").unwrap())
+                            .style("color", self.style_val("text_color").unwrap())
+                            .maybe_style("font", self.style_val("font")))),
+                    ),
+            );
+        }
+        list
+    }
 }
 
 #[state_component_impl(TextBoxState)]
 impl Component for TextBox {
+    fn cursor(&self) -> Option<CursorStyle> {
+        self.style_val("cursor").map(|v| v.cursor())
+    }
+
     fn view(&self) -> Option<Node> {
         let background_color: Color = self.style_val("background_color").into();
         let border_color: Color = self.style_val("border_color").into();
         let border_width: BorderWidth = self.style_val("border_width").unwrap().into();
+        let multiline = self.is_multiline();
+        let (resized_width, resized_height) = self.state_ref().resized;
+
+        let mut container = TextBoxContainer::new(
+            background_color,
+            border_color,
+            (border_width.top, border_width.left, border_width.bottom, border_width.right),
+            self.style_val("shadow").map(|v| v.box_shadow()),
+        );
+        container.resize = self.resize_mode();
+        if multiline {
+            container.line_height = Some(self.line_height());
+            container.scroll_bar = Some(TextBoxScrollBar {
+                width: self.scroll_bar_val("bar_width").map_or(0.0, |v| v.f32()),
+                background_color: self.scroll_bar_val("bar_background_color").into(),
+                color: self.scroll_bar_val("bar_color").into(),
+            });
+        }
+        if let Some((text, color)) = self.counter() {
+            container.counter = Some(TextBoxCounter {
+                text,
+                color,
+                font_size: self.style_val("counter_font_size").unwrap().f32(),
+                font: self.style_val("font").map(|p| p.str().to_string()),
+                padding: self.style_val("padding").unwrap().f32(),
+            });
+        }
+        let fill = |resized: Option<f32>, otherwise: Dimension| {
+            resized.map_or(otherwise, |px| Dimension::Px(px as f64))
+        };
+        let size = Size {
+            width: fill(resized_width, Dimension::Pct(100.0)),
+            height: fill(
+                resized_height,
+                if multiline {
+                    Dimension::Auto
+                } else {
+                    Dimension::Pct(100.0)
+                },
+            ),
+        };
+        let max_size = Size {
+            width: Dimension::Auto,
+            height: match self.max_rows {
+                Some(rows) if multiline && resized_height.is_none() => {
+                    Dimension::Px(self.rows_height(rows) as f64)
+                }
+                _ => Dimension::Auto,
+            },
+        };
+        let cross_alignment = if multiline {
+            Alignment::Start
+        } else {
+            Alignment::Center
+        };
+
+        // With suggestions, the text box is wrapped so that they can hang below it, which fills
+        // the wrapper instead
+        let wrapped = self.autocomplete.is_some();
+        let container_size = if wrapped {
+            Size {
+                width: Dimension::Pct(100.0),
+                height: match size.height {
+                    Dimension::Auto => Dimension::Auto,
+                    _ => Dimension::Pct(100.0),
+                },
+            }
+        } else {
+            size
+        };
 
         let mut textbox_node = node!(
-            TextBoxContainer::new(
-                background_color,
-                border_color,
-                (border_width.top, border_width.left, border_width.bottom, border_width.right)
-            ),
+            container,
             lay![
-                size: size_pct!(100.0),
-                cross_alignment: crate::layout::Alignment::Center,
+                size: container_size,
+                max_size: max_size,
+                cross_alignment: cross_alignment,
                 // padding: [10.]
             ]
         )
@@ -147,12 +767,33 @@ impl Component for TextBox {
                 default_text: self.text.clone().unwrap_or_default(),
                 variant: self.variant.clone().unwrap_or_default(),
                 hidden: self.state_ref().hidden,
+                undo_depth: self.undo_depth,
+                tab_behavior: self.tab_behavior,
+                text_shadow: self.text_shadow,
+                multiline,
+                min_rows: self.min_rows,
+                shift_enter_submits: self.shift_enter_submits,
+                suggesting: !self.state_ref().suggestions.is_empty(),
+                suggestion_highlighted: self.state_ref().highlighted.is_some(),
+                accepted: self.state_ref().accepted.clone(),
+                accepted_count: self.state_ref().accepted_count,
+                max_length: self.max_length,
+                controller: self.controller.clone(),
+                direction: self.direction,
+                bidi: self.bidi,
                 style_overrides: self.style_overrides.clone(),
                 class: self.class,
                 state: None,
                 dirty: false,
             },
-            lay![size_pct: [90.0],]
+            if multiline {
+                lay![size: Size {
+                    width: Dimension::Pct(90.0),
+                    height: Dimension::Auto,
+                }]
+            } else {
+                lay![size_pct: [90.0],]
+            }
         ));
 
         if self.variant == Some(TextBoxVariant::Hidden) && self.state_ref().has_text_value {
@@ -175,7 +816,31 @@ impl Component for TextBox {
             }
         }
 
-        Some(textbox_node)
+        if !wrapped {
+            return Some(textbox_node);
+        }
+        let mut wrapper = node!(
+            Div::new(),
+            lay![size: size, direction: Direction::Column]
+        )
+        .push(textbox_node);
+        if !self.state_ref().suggestions.is_empty() {
+            wrapper = wrapper.push(self.suggestion_list());
+        }
+        Some(wrapper)
+    }
+
+    fn on_tick(&mut self, _event: &mut event::Event<event::Tick>) {
+        let due = self.state_ref().query.as_ref().map_or(false, |(_, at)| {
+            at.elapsed().as_millis() >= SUGGESTION_DEBOUNCE
+        });
+        if due {
+            if let Some((text, _)) = self.state_mut().query.take() {
+                if self.state_ref().focused {
+                    self.suggest(&text);
+                }
+            }
+        }
     }
 
     fn update(&mut self, message: Message) -> Vec<Message> {
@@ -187,33 +852,152 @@ impl Component for TextBox {
                     m.push(focus_fn())
                 }
             }
-            Some(TextBoxMessage::Close) => self.state_mut().focused = false,
+            Some(TextBoxMessage::Close) => {
+                self.state_mut().focused = false;
+                self.dismiss_suggestions();
+            }
             Some(TextBoxMessage::Change(s)) => {
                 self.state_mut().has_text_value = !s.is_empty();
+                self.state_mut().char_count = Some(s.chars().count());
+                self.state_mut().accepted = None;
+                if self.autocomplete.is_some() {
+                    self.state_mut().query = Some((s.clone(), Instant::now()));
+                }
                 if let Some(change_fn) = &self.on_change {
                     m.push(change_fn(s))
                 }
             }
             Some(TextBoxMessage::Commit(s)) => {
+                // A suggestion clicked on is committed as the text box loses the focus, before
+                // the text has been replaced with it
+                let s = self.state_ref().accepted.as_ref().unwrap_or(s);
                 if let Some(commit_fn) = &self.on_commit {
                     m.push(commit_fn(s))
                 }
             }
+            Some(TextBoxMessage::SuggestionMove(down)) => self.move_highlight(*down),
+            Some(TextBoxMessage::SuggestionAccept) => {
+                let index = self.state_ref().highlighted.unwrap_or(0);
+                m.extend(self.accept_suggestion(index));
+            }
+            Some(TextBoxMessage::SuggestionDismiss) => self.dismiss_suggestions(),
+            Some(TextBoxMessage::SuggestionHover(index)) => {
+                if self.state_ref().highlighted != Some(*index) {
+                    self.state_mut().highlighted = Some(*index);
+                }
+            }
+            Some(TextBoxMessage::SuggestionClick(index)) => {
+                m.extend(self.accept_suggestion(*index));
+            }
             Some(TextBoxMessage::ToggleHidden) => {
                 self.state_mut().hidden = !self.state_ref().hidden;
             }
+            Some(TextBoxMessage::Undo) => {
+                if let Some(undo_fn) = &self.on_undo {
+                    m.push(undo_fn())
+                }
+            }
+            Some(TextBoxMessage::Redo) => {
+                if let Some(redo_fn) = &self.on_redo {
+                    m.push(redo_fn())
+                }
+            }
+            Some(TextBoxMessage::CompositionStart) => {
+                if let Some(start_fn) = &self.on_composition_start {
+                    m.push(start_fn())
+                }
+            }
+            Some(TextBoxMessage::CompositionUpdate(s)) => {
+                if let Some(update_fn) = &self.on_composition_update {
+                    m.push(update_fn(s))
+                }
+            }
+            Some(TextBoxMessage::CompositionEnd(s)) => {
+                if let Some(end_fn) = &self.on_composition_end {
+                    m.push(end_fn(s))
+                }
+            }
+            Some(TextBoxMessage::Find(spans)) => {
+                if let Some(find_fn) = &self.on_find {
+                    m.push(find_fn(spans))
+                }
+            }
+            Some(TextBoxMessage::Resize(width, height)) => {
+                let resized = (
+                    width.or(self.state_ref().resized.0),
+                    height.or(self.state_ref().resized.1),
+                );
+                self.state_mut().resized = resized;
+            }
             _ => m.push(message),
         }
         m
     }
 }
 
+/// A row of the suggestions of a [`TextBox`], which is highlighted when hovered over.
+#[derive(Debug)]
+struct SuggestionOption {
+    index: usize,
+}
+
+impl Component for SuggestionOption {
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.index.hash(hasher);
+    }
+
+    fn cursor(&self) -> Option<CursorStyle> {
+        Some(CursorStyle::Pointer)
+    }
+
+    fn on_mouse_motion(&mut self, event: &mut event::Event<event::MouseMotion>) {
+        // So that this row is entered, rather than the text box
+        event.stop_bubbling();
+    }
+
+    fn on_mouse_enter(&mut self, event: &mut event::Event<event::MouseEnter>) {
+        event.emit(msg!(TextBoxMessage::SuggestionHover(self.index)));
+    }
+
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        event.stop_bubbling();
+        event.emit(msg!(TextBoxMessage::SuggestionClick(self.index)));
+    }
+}
+
 #[derive(Debug, Default)]
 #[allow(dead_code)]
 struct TextBoxContainerState {
     scroll_position: f32,
     border_width_px: f32,
     width_px: f32,
+    /// How far multi-line text is scrolled up.
+    scroll_y: f32,
+    /// Where the cursor was at the last layout, so that it is only scrolled to when it moves.
+    focus_y: Option<f32>,
+    /// The height of the text, and of the space inside the borders, as of the last layout.
+    content_height_px: f32,
+    inner_height_px: f32,
+    /// The logical size when a resize drag started.
+    resize_from: Option<Scale>,
+}
+
+/// The vertical scroll bar of a multi-line [`TextBox`].
+#[derive(Debug, Clone, Copy)]
+struct TextBoxScrollBar {
+    width: f32,
+    background_color: Color,
+    color: Color,
+}
+
+/// The character counter of a [`TextBox`], drawn in the bottom right corner, inside the padding.
+#[derive(Debug, Clone)]
+struct TextBoxCounter {
+    text: String,
+    color: Color,
+    font_size: f32,
+    font: Option<String>,
+    padding: f32,
 }
 
 #[component(State = "TextBoxContainerState", Internal)]
@@ -222,14 +1006,30 @@ struct TextBoxContainer {
     background_color: Color,
     border_color: Color,
     border_width: (f32, f32, f32, f32),
+    shadow: Option<BoxShadow>,
+    /// The logical height of a row, when the text is multi-line and scrolls vertically.
+    line_height: Option<f32>,
+    scroll_bar: Option<TextBoxScrollBar>,
+    resize: ResizeMode,
+    counter: Option<TextBoxCounter>,
 }
 
 impl TextBoxContainer {
-    fn new<C: Into<Color>>(background_color: C, border_color: C, border_width: (f32, f32, f32, f32)) -> Self {
+    fn new<C: Into<Color>>(
+        background_color: C,
+        border_color: C,
+        border_width: (f32, f32, f32, f32),
+        shadow: Option<BoxShadow>,
+    ) -> Self {
         Self {
             background_color: background_color.into(),
             border_color: border_color.into(),
             border_width,
+            shadow,
+            line_height: None,
+            scroll_bar: None,
+            resize: ResizeMode::None,
+            counter: None,
             state: Some(Default::default()),
             dirty: false,
         }
@@ -238,6 +1038,25 @@ impl TextBoxContainer {
     fn border_width_px(&self, scale_factor: f32) -> f32 {
         (self.border_width.0 * scale_factor.floor()).round()
     }
+
+    fn max_scroll_y(&self) -> f32 {
+        (self.state_ref().content_height_px - self.state_ref().inner_height_px).max(0.0)
+    }
+
+    /// Scroll the multi-line text to `scroll_y`, within bounds.
+    fn scroll_y_to(&mut self, scroll_y: f32) {
+        let scroll_y = scroll_y.min(self.max_scroll_y()).max(0.0);
+        if scroll_y != self.state_ref().scroll_y {
+            self.state_mut().scroll_y = scroll_y;
+        }
+    }
+
+    /// Whether `position`, relative to a text box of `size`, is over its resize handle.
+    fn over_resize_handle(&self, position: Point, size: Scale) -> bool {
+        self.resize != ResizeMode::None
+            && position.x >= size.width - RESIZE_HANDLE_SIZE
+            && position.y >= size.height - RESIZE_HANDLE_SIZE
+    }
 }
 
 #[state_component_impl(TextBoxContainerState)]
@@ -257,6 +1076,32 @@ impl Component for TextBoxContainer {
         if let Some((child_aabb, _, Some(focus))) = children.first_mut() {
             let width = aabb.width();
             let border_width_px = self.border_width_px(scale_factor);
+            if let Some(line_height) = self.line_height {
+                // Wrapped text fits the width, so it only scrolls up and down
+                let inner_height = aabb.height() - border_width_px * 2.0;
+                let content_height = child_aabb.height();
+                if self.state_ref().inner_height_px != inner_height
+                    || self.state_ref().content_height_px != content_height
+                {
+                    self.state_mut().inner_height_px = inner_height;
+                    self.state_mut().content_height_px = content_height;
+                }
+                let line_height_px = line_height * scale_factor;
+                let scroll_y = self.state_ref().scroll_y;
+                let moved = self.state_ref().focus_y != Some(focus.y);
+                if moved {
+                    self.state_mut().focus_y = Some(focus.y);
+                }
+                if !moved {
+                    // Keep where the wheel scrolled to, within the content as it is now
+                    self.scroll_y_to(scroll_y);
+                } else if focus.y + line_height_px > inner_height + scroll_y {
+                    self.scroll_y_to(focus.y + line_height_px - inner_height);
+                } else if focus.y < scroll_y {
+                    self.scroll_y_to(focus.y);
+                }
+                return;
+            }
             // We need to expand our child's AABB width if it's not as big as this AABB
             if child_aabb.bottom_right.x < aabb.bottom_right.x {
                 child_aabb.bottom_right.x = aabb.bottom_right.x - border_width_px;
@@ -287,13 +1132,72 @@ impl Component for TextBoxContainer {
         self.background_color.hash(hasher);
         self.border_color.hash(hasher);
         (self.border_width.0 as u32).hash(hasher);
+        self.shadow.hash(hasher);
+        self.resize.hash(hasher);
+        if let Some(bar) = self.scroll_bar {
+            (bar.width as u32).hash(hasher);
+            bar.background_color.hash(hasher);
+            bar.color.hash(hasher);
+        }
+        if let Some(counter) = &self.counter {
+            counter.text.hash(hasher);
+            counter.color.hash(hasher);
+            (counter.font_size as u32).hash(hasher);
+        }
+        (self.state_ref().scroll_y as u32).hash(hasher);
+        (self.state_ref().content_height_px as u32).hash(hasher);
+        (self.state_ref().inner_height_px as u32).hash(hasher);
     }
 
     fn scroll_position(&self) -> Option<ScrollPosition> {
-        Some(ScrollPosition {
-            x: Some(self.state_ref().scroll_position),
-            y: None,
-        })
+        if self.line_height.is_some() {
+            Some(ScrollPosition {
+                x: None,
+                y: Some(self.state_ref().scroll_y),
+            })
+        } else {
+            Some(ScrollPosition {
+                x: Some(self.state_ref().scroll_position),
+                y: None,
+            })
+        }
+    }
+
+    fn on_scroll(&mut self, event: &mut event::Event<event::Scroll>) {
+        if self.line_height.is_some() && self.max_scroll_y() > 0.0 {
+            let scroll_y = self.state_ref().scroll_y + event.input.y;
+            self.scroll_y_to(scroll_y);
+            event.stop_bubbling();
+        }
+    }
+
+    fn on_drag_start(&mut self, event: &mut event::Event<event::DragStart>) {
+        let size = event.current_logical_aabb().size();
+        if self.over_resize_handle(event.relative_logical_position(), size) {
+            self.state_mut().resize_from = Some(size);
+            event.stop_bubbling();
+        }
+    }
+
+    fn on_drag(&mut self, event: &mut event::Event<event::Drag>) {
+        if let Some(from) = self.state_ref().resize_from {
+            let delta = event.logical_delta();
+            let min = RESIZE_HANDLE_SIZE * 2.0;
+            event.emit(msg!(TextBoxMessage::Resize(
+                self.resize
+                    .horizontal()
+                    .then(|| (from.width + delta.x).max(min)),
+                self.resize
+                    .vertical()
+                    .then(|| (from.height + delta.y).max(min)),
+            )));
+        }
+    }
+
+    fn on_drag_end(&mut self, _event: &mut event::Event<event::DragEnd>) {
+        if self.state_ref().resize_from.is_some() {
+            self.state_mut().resize_from = None;
+        }
     }
 
     fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
@@ -311,11 +1215,80 @@ impl Component for TextBoxContainer {
                 .border_size(self.border_width)
                 .border_color(self.border_color)
                 .color(self.background_color)
+                .shadow(self.shadow)
                 .build()
                 .unwrap(),
         ));
+        let mut renderables = vec![background];
+
+        let inner_height = self.state_ref().inner_height_px;
+        let content_height = self.state_ref().content_height_px;
+        // The counter keeps clear of the scroll bar and the resize grip
+        let mut right_inset = 0.0;
+        if let Some(bar) = self.scroll_bar.filter(|_| content_height > inner_height) {
+            // Drawn like the vertical bar of a scrolling `Div`
+            let width = bar.width * context.scale_factor;
+            let x = context.aabb.size().width - border_width - width;
+            let bar_pos = context.aabb.pos.add(Pos::new(x, border_width, 0.6));
+            renderables.push(Renderable::Rect(Rect::new(
+                bar_pos,
+                Scale::new(width, inner_height),
+                bar.background_color,
+            )));
+            let height = inner_height * inner_height / content_height;
+            let y = (inner_height - height) * self.state_ref().scroll_y / self.max_scroll_y();
+            renderables.push(Renderable::Rect(Rect::new(
+                bar_pos.add(Pos::new(2.0, y, 0.1)),
+                Scale::new(width - 4.0, height),
+                bar.color,
+            )));
+            right_inset = width;
+        }
 
-        Some(vec![background])
+        if self.resize != ResizeMode::None {
+            right_inset = f32::max(right_inset, RESIZE_HANDLE_SIZE * context.scale_factor);
+            // A grip of dots along the diagonal of the corner
+            let dot = context.scale_factor.round().max(1.0) * 2.0;
+            let corner = context.aabb.bottom_right;
+            for i in 0..3 {
+                for j in 0..=i {
+                    let step = (RESIZE_HANDLE_SIZE / 4.0) * context.scale_factor;
+                    let (dx, dy) = ((i - j + 1) as f32 * step, (j + 1) as f32 * step);
+                    renderables.push(Renderable::Rect(Rect::new(
+                        Pos::new(corner.x - dx - dot / 2.0, corner.y - dy - dot / 2.0, 0.7),
+                        Scale::new(dot, dot),
+                        self.border_color,
+                    )));
+                }
+            }
+        }
+
+        if let Some(counter) = &self.counter {
+            let padding = counter.padding * context.scale_factor;
+            let height = counter.font_size * context.scale_factor;
+            let size = context.aabb.size();
+            let text_instance = TextInstanceBuilder::default()
+                .pos(context.aabb.pos.add(Pos {
+                    x: border_width + padding,
+                    y: size.height - border_width - padding - height,
+                    z: 0.7,
+                }))
+                .scale(Scale::new(
+                    (size.width - (border_width + padding) * 2.0 - right_inset).max(0.0),
+                    height,
+                ))
+                .text(counter.text.clone())
+                .color(counter.color)
+                .font(counter.font.clone())
+                .font_size(counter.font_size)
+                .line_height(counter.font_size)
+                .align(Align::Right)
+                .build()
+                .unwrap();
+            renderables.push(Renderable::Text(Text::from_instance_data(text_instance)));
+        }
+
+        Some(renderables)
     }
 }
 
@@ -329,9 +1302,17 @@ struct TextBoxTextState {
     activated_at: Instant,
     cursor_visible: bool,
     glyphs: Vec<LayoutGlyph>,
-    glyph_widths: Vec<f32>,
+    /// Where the cursor goes before each byte of multi-line text, and after the last.
+    carets: Vec<Caret>,
+    row_height_px: f32,
     padding_offset_px: f32,
     dirty: bool,
+    history: UndoStack<TextBoxSnapshot>,
+    find_matches: Vec<(usize, usize)>,
+    /// The text an input method is composing, shown at the cursor until it is committed.
+    composition_text: Option<String>,
+    /// How many suggestions had been accepted when the text was last set.
+    accepted_count: u32,
     menu: Option<wx_rs::Menu<TextBoxAction>>,
 }
 #[derive(Debug)]
@@ -345,10 +1326,18 @@ struct TextBoxTextState {
     activated_at: Instant,
     cursor_visible: bool,
     glyphs: Vec<LayoutGlyph>,
-    glyph_widths: Vec<f32>,
+    /// Where the cursor goes before each byte of multi-line text, and after the last.
+    carets: Vec<Caret>,
+    row_height_px: f32,
     padding_offset_px: f32,
     dirty: bool,
     variant: TextBoxVariant,
+    history: UndoStack<TextBoxSnapshot>,
+    find_matches: Vec<(usize, usize)>,
+    /// The text an input method is composing, shown at the cursor until it is committed.
+    composition_text: Option<String>,
+    /// How many suggestions had been accepted when the text was last set.
+    accepted_count: u32,
 }
 
 #[component(State = "TextBoxTextState", Styled = "TextBox", Internal)]
@@ -358,9 +1347,49 @@ pub struct TextBoxText {
     pub placeholder: Option<String>,
     pub variant: TextBoxVariant,
     pub hidden: bool,
+    pub undo_depth: usize,
+    pub tab_behavior: TabBehavior,
+    /// Drawn in front of any shadows from the `text_shadow` style.
+    pub text_shadow: Option<TextShadow>,
+    pub multiline: bool,
+    pub min_rows: u32,
+    pub shift_enter_submits: bool,
+    /// Whether suggestions are shown, and one of them is highlighted, in which case the keys that
+    /// pick from them are passed on to the [`TextBox`].
+    pub suggesting: bool,
+    pub suggestion_highlighted: bool,
+    /// The last suggestion accepted, which replaces the text when `accepted_count` goes up.
+    pub accepted: Option<String>,
+    pub accepted_count: u32,
+    pub max_length: Option<usize>,
+    pub controller: Option<TextBoxController>,
+    pub direction: TextDirection,
+    /// Whether the arrow keys move the cursor by where it is drawn, rather than through the text.
+    pub bidi: bool,
 }
 
 impl TextBoxText {
+    /// The shadows to draw, back to front.
+    fn text_shadows(&self) -> Vec<TextShadow> {
+        let mut shadows = self
+            .style_val("text_shadow")
+            .map(|v| v.text_shadow())
+            .unwrap_or_default();
+        shadows.extend(self.text_shadow);
+        shadows
+    }
+
+    /// `text`, preceded by its shadows.
+    fn with_shadows(&self, text: Text) -> Vec<Renderable> {
+        let mut renderables: Vec<Renderable> = self
+            .text_shadows()
+            .iter()
+            .map(|shadow| Renderable::Text(text.shadow(shadow)))
+            .collect();
+        renderables.push(Renderable::Text(text));
+        renderables
+    }
+
     fn reset_state(&mut self) {
         self.state = Some(TextBoxTextState {
             focused: false,
@@ -371,10 +1400,15 @@ impl TextBoxText {
             activated_at: Instant::now(),
             cursor_visible: false,
             glyphs: vec![],
-            glyph_widths: vec![],
+            carets: vec![],
+            row_height_px: 0.0,
             padding_offset_px: 0.0,
             dirty: true,
             variant: self.variant.clone(),
+            history: UndoStack::new(self.undo_depth),
+            find_matches: vec![],
+            composition_text: None,
+            accepted_count: self.accepted_count,
             #[cfg(feature = "backend_wx_rs")]
             menu: None,
         });
@@ -391,33 +1425,45 @@ impl TextBoxText {
             })
     }
 
-    fn position(&self, x: f32) -> usize {
-        if let Some(i) = self.state_ref().glyphs.iter().position(|g| x < g.x + 4.0)
-        // This should really be checking against the glyph center
-        {
-            i
-        } else {
-            self.state_ref().text.len()
+    fn position(&self, point: Point) -> usize {
+        let x = point.x;
+        if self.multiline {
+            let offset = self.state_ref().padding_offset_px;
+            let row_height = self.state_ref().row_height_px.max(1.0);
+            let row = ((point.y - offset) / row_height).floor().max(0.0) as usize;
+            return nearest_caret(
+                &self.state_ref().text,
+                &self.state_ref().carets,
+                x - offset,
+                row,
+            );
         }
+        let text = &self.state_ref().text;
+        boundaries(text)
+            .min_by(|a, b| {
+                let a = (self.cursor_position_px(*a) - x).abs();
+                let b = (self.cursor_position_px(*b) - x).abs();
+                a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+            })
+            .unwrap_or(text.len())
     }
 
     // Returns whether or not there was a word to select
     fn select_word(&mut self) -> bool {
-        let pos = self.state_ref().cursor_pos;
-        let text = &self.state_ref().text;
-        let end_pos = pos
-            + text
-                .chars()
-                .skip(pos)
-                .position(|x| !x.is_alphanumeric())
-                .unwrap_or(text.len() - pos);
-        let start_pos = pos
-            - text
-                .chars()
-                .rev()
-                .skip(text.len() - pos)
-                .position(|x| !x.is_alphanumeric())
-                .unwrap_or(pos);
+        let (start_pos, end_pos) = word_bounds(&self.state_ref().text, self.state_ref().cursor_pos);
+
+        if start_pos != end_pos {
+            self.state_mut().selection_from = Some(start_pos);
+            self.state_mut().cursor_pos = end_pos;
+            true
+        } else {
+            false
+        }
+    }
+
+    // Returns whether or not there was a line to select
+    fn select_line(&mut self) -> bool {
+        let (start_pos, end_pos) = line_bounds(&self.state_ref().text, self.state_ref().cursor_pos);
 
         if start_pos != end_pos {
             self.state_mut().selection_from = Some(start_pos);
@@ -428,13 +1474,126 @@ impl TextBoxText {
         }
     }
 
+    fn snapshot(&self) -> TextBoxSnapshot {
+        TextBoxSnapshot {
+            text: self.state_ref().text.clone(),
+            cursor_pos: self.state_ref().cursor_pos,
+        }
+    }
+
+    fn restore(&mut self, snapshot: TextBoxSnapshot) {
+        self.state_mut().masked_text = get_masked_text(snapshot.text.clone());
+        self.state_mut().text = snapshot.text;
+        self.state_mut().cursor_pos = snapshot.cursor_pos;
+        self.state_mut().selection_from = None;
+        self.state_mut().dirty = true;
+    }
+
+    /// Push the current text onto the undo history, ahead of an edit.
+    fn record_edit(&mut self, coalesce: bool) {
+        let snapshot = self.snapshot();
+        self.state_mut().history.push(snapshot, coalesce, Instant::now());
+    }
+
+    /// Revert the last edit. Returns whether there was anything to undo.
+    fn undo(&mut self) -> bool {
+        let current = self.snapshot();
+        if let Some(prev) = self.state_mut().history.undo(current) {
+            self.restore(prev);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Re-apply the last undone edit. Returns whether there was anything to redo.
+    fn redo(&mut self) -> bool {
+        let current = self.snapshot();
+        if let Some(next) = self.state_mut().history.redo(current) {
+            self.restore(next);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Highlight every match of `query`, and move the cursor to the first one.
+    /// Returns the byte-offset spans of the matches.
+    fn find(&mut self, query: &str, case_sensitive: bool) -> Vec<(usize, usize)> {
+        let spans = find_spans(&self.state_ref().text, query, case_sensitive);
+        if let Some((start, _)) = spans.first() {
+            self.state_mut().cursor_pos = *start;
+            self.state_mut().selection_from = None;
+        }
+        self.state_mut().find_matches = spans.clone();
+        spans
+    }
+
+    /// As much of `text` as fits within the `max_length`, in place of the byte-offset `span`.
+    fn fit<'a>(&self, text: &'a str, span: (usize, usize)) -> &'a str {
+        match self.max_length {
+            Some(max_length) => {
+                let current = &self.state_ref().text;
+                let kept = current.chars().count() - current[span.0..span.1].chars().count();
+                truncate_chars(text, max_length.saturating_sub(kept))
+            }
+            None => text,
+        }
+    }
+
+    /// Replace the text in the byte-offset `span` with `replacement`, as a single undoable edit.
+    fn replace(&mut self, span: (usize, usize), replacement: &str) {
+        let replacement = self.fit(replacement, span);
+        self.record_edit(false);
+        self.state_mut().text.replace_range(span.0..span.1, replacement);
+        self.state_mut().masked_text = get_masked_text(self.state_ref().text.clone());
+        self.state_mut().cursor_pos = span.0 + replacement.len();
+        self.state_mut().selection_from = None;
+        self.state_mut().find_matches.clear();
+        self.state_mut().dirty = true;
+    }
+
+    /// Replace every match of `query` with `replacement`, as a single undoable edit.
+    /// Returns the number of replacements made.
+    fn replace_all(&mut self, query: &str, replacement: &str, case_sensitive: bool) -> usize {
+        let current = &self.state_ref().text;
+        let spans = find_spans(current, query, case_sensitive);
+        if spans.is_empty() {
+            return 0;
+        }
+        // As with `fit`, the replacements only get the room that the max_length leaves
+        let room = self.max_length.map(|max_length| {
+            let matched: usize = spans
+                .iter()
+                .map(|(a, b)| current[*a..*b].chars().count())
+                .sum();
+            max_length.saturating_sub(current.chars().count() - matched)
+        });
+        let (text, cursor_pos) = replace_spans(
+            current,
+            &spans,
+            replacement,
+            room,
+            self.state_ref().cursor_pos,
+        );
+        self.record_edit(false);
+        self.state_mut().masked_text = get_masked_text(text.clone());
+        self.state_mut().text = text;
+        self.state_mut().cursor_pos = cursor_pos;
+        self.state_mut().selection_from = None;
+        self.state_mut().find_matches.clear();
+        self.state_mut().dirty = true;
+        spans.len()
+    }
+
     fn insert_text(&mut self, text: &str) {
+        let pos = self.state_ref().cursor_pos;
+        let text = self.fit(text, self.selection().unwrap_or((pos, pos)));
         if let Some((a, b)) = self.selection() {
             self.state_mut().text.replace_range(a..b, text);
             self.state_mut().cursor_pos = a + text.len();
             self.state_mut().selection_from = None;
         } else {
-            let pos = self.state_ref().cursor_pos;
             self.state_mut().text.insert_str(pos, text);
             self.state_mut().masked_text = get_masked_text(self.state_ref().text.clone());
             self.state_mut().cursor_pos += text.len();
@@ -442,25 +1601,253 @@ impl TextBoxText {
         self.state_mut().dirty = true;
     }
 
+    /// Insert the text for a Tab key press, as a single undoable edit. Returns `false` when Tab
+    /// moves the focus instead.
+    fn insert_tab(&mut self) -> bool {
+        let text = match self.tab_behavior {
+            TabBehavior::FocusNext => return false,
+            TabBehavior::InsertTab => "\t".to_string(),
+            TabBehavior::InsertSpaces(n) => " ".repeat(n as usize),
+        };
+        self.record_edit(false);
+        self.insert_text(&text);
+        true
+    }
+
     fn activate(&mut self) {
         self.state_mut().activated_at = Instant::now();
         self.state_mut().cursor_visible = true;
         self.state_mut().selection_from = None;
     }
 
+    /// The text being composed, if it is shown. Hidden text boxes don't show it.
+    fn composition(&self) -> Option<&String> {
+        let masked = self.state_ref().variant == TextBoxVariant::Hidden && self.hidden;
+        self.state_ref()
+            .composition_text
+            .as_ref()
+            .filter(|_| !masked)
+    }
+
+    /// The text as it is shown, with any composition at the cursor.
+    fn display_text(&self) -> String {
+        let mut text = self.state_ref().text.clone();
+        if let Some(composition) = self.composition() {
+            text.insert_str(self.state_ref().cursor_pos, composition);
+        }
+        text
+    }
+
+    /// The position in the shown text of `pos`, which is after any composition.
+    fn to_display(&self, pos: usize) -> usize {
+        match self.composition() {
+            Some(composition) if pos > self.state_ref().cursor_pos => pos + composition.len(),
+            _ => pos,
+        }
+    }
+
+    /// The byte-offset span of the composition in the shown text.
+    fn composition_span(&self) -> Option<(usize, usize)> {
+        let pos = self.state_ref().cursor_pos;
+        self.composition().map(|c| (pos, pos + c.len()))
+    }
+
+    fn shows_placeholder(&self) -> bool {
+        self.state_ref().text.is_empty()
+            && self.state_ref().composition_text.is_none()
+            && self.placeholder.is_some()
+    }
+
+    /// Where the cursor goes at `pos` in multi-line text.
+    fn caret(&self, pos: usize) -> Caret {
+        self.display_caret(self.to_display(pos))
+    }
+
+    /// Where the cursor goes at `pos` in the shown multi-line text.
+    fn display_caret(&self, pos: usize) -> Caret {
+        let carets = &self.state_ref().carets;
+        carets
+            .get(pos)
+            .or_else(|| carets.last())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The top left of the cursor at `pos` in multi-line text, in physical pixels.
+    fn caret_px(&self, pos: usize) -> Point {
+        self.display_caret_px(self.to_display(pos))
+    }
+
+    /// The top left of the cursor at `pos` in the shown multi-line text, in physical pixels.
+    fn display_caret_px(&self, pos: usize) -> Point {
+        let caret = self.display_caret(pos);
+        let offset = self.state_ref().padding_offset_px;
+        Point {
+            x: caret.x + offset,
+            y: caret.row as f32 * self.state_ref().row_height_px + offset,
+        }
+    }
+
+    /// Move the cursor to `pos`, extending the selection to it if `extend`.
+    fn move_cursor(&mut self, pos: usize, extend: bool) {
+        if extend {
+            if self.state_ref().selection_from.is_none() {
+                let from = self.state_ref().cursor_pos;
+                self.state_mut().selection_from = Some(from);
+            }
+        } else if self.state_ref().selection_from.is_some() {
+            self.state_mut().selection_from = None;
+        }
+        if pos != self.state_ref().cursor_pos {
+            self.state_mut().cursor_pos = pos;
+        }
+    }
+
+    /// The position on the row above (`up`) or below the cursor, nearest to it across.
+    fn row_step(&self, up: bool) -> usize {
+        let caret = self.caret(self.state_ref().cursor_pos);
+        let text = &self.state_ref().text;
+        let last_row = self.state_ref().carets.last().map_or(0, |c| c.row);
+        if up && caret.row == 0 {
+            0
+        } else if !up && caret.row >= last_row {
+            text.len()
+        } else {
+            let row = if up { caret.row - 1 } else { caret.row + 1 };
+            nearest_caret(text, &self.state_ref().carets, caret.x, row)
+        }
+    }
+
+    /// Where the Left (`left`) or Right arrow key moves the cursor to. Left moves back through
+    /// left-to-right text, and forward through right-to-left text. With `bidi`, the cursor moves
+    /// to the nearest position on that side of it on its row, or else onto the next row.
+    fn horizontal_step(&self, left: bool) -> usize {
+        let text = &self.state_ref().text;
+        let pos = self.state_ref().cursor_pos;
+        let logical = if left == self.direction.is_rtl(text) {
+            next_boundary(text, pos)
+        } else {
+            prev_boundary(text, pos)
+        };
+        if !self.bidi {
+            return logical;
+        }
+        let x = self.cursor_position_px(pos);
+        let row = self.multiline.then(|| self.caret(pos).row);
+        let nearest = boundaries(text)
+            .filter(|p| row.map_or(true, |row| self.caret(*p).row == row))
+            .map(|p| (p, self.cursor_position_px(p) - x))
+            .filter(|(_, dx)| if left { *dx < 0.0 } else { *dx > 0.0 })
+            .min_by(|a, b| a.1.abs().partial_cmp(&b.1.abs()).unwrap_or(Ordering::Equal));
+        match nearest {
+            Some((p, _)) => p,
+            None if row.map_or(false, |row| self.caret(logical).row != row) => logical,
+            None => pos,
+        }
+    }
+
+    /// Lay out multi-line text, wrapped to `width`, and return its size.
+    fn fill_rows(
+        &mut self,
+        width: Option<f32>,
+        font_cache: &mut FontCache,
+        scale_factor: f32,
+    ) -> (Option<f32>, Option<f32>) {
+        let padding: f32 = self.style_val("padding").unwrap().f32();
+        let font_size: f32 = self.style_val("font_size").unwrap().f32();
+        let border_width: BorderWidth = self.style_val("border_width").unwrap().into();
+        let font = self.style_val("font").map(|p| p.str().to_string());
+        let letter_spacing = self
+            .style_val("letter_spacing")
+            .map_or(0.0, |v| v.f32());
+        let row_height = row_height(self);
+        let offset = ((padding + border_width.left) * scale_factor).round();
+        let is_placeholder = self.shows_placeholder();
+        let text = if is_placeholder {
+            self.placeholder.clone().unwrap()
+        } else {
+            self.display_text()
+        };
+        let wrap_width = width.map_or(f32::MAX, |w| (w * scale_factor - offset * 2.0).max(0.0));
+        let (t_w, _, glyphs) = font_cache.measure_text(
+            text.clone(),
+            font,
+            font_size,
+            scale_factor,
+            row_height,
+            HorizontalPosition::Left,
+            (wrap_width, f32::MAX),
+            letter_spacing,
+        );
+
+        let boxes: Vec<GlyphBox> = glyphs
+            .iter()
+            .map(|g| GlyphBox {
+                start: g.start,
+                end: g.end,
+                x: g.x,
+                w: g.w,
+                row: (g.y / row_height).round().max(0.0) as usize,
+                rtl: g.level.is_rtl(),
+            })
+            .collect();
+        let carets = carets(&text, &boxes);
+        let rows = carets.last().map_or(1, |c| c.row + 1);
+        if !is_placeholder {
+            self.state_mut().glyphs = glyphs;
+            self.state_mut().carets = carets;
+        } else {
+            self.state_mut().carets = vec![Caret::default()];
+        }
+        self.state_mut().row_height_px = row_height * scale_factor;
+        self.state_mut().padding_offset_px = offset;
+        self.state_mut().dirty = false;
+
+        (
+            width.or(t_w.map(|w| (w + offset * 2.0) / scale_factor)),
+            Some(
+                rows.max(self.min_rows as usize) as f32 * row_height
+                    + padding * 2.0
+                    + border_width.left * 2.0,
+            ),
+        )
+    }
+
     fn cursor_position_px(&self, pos: usize) -> f32 {
-        let len = self.state_ref().text.len();
+        self.display_position_px(self.to_display(pos))
+    }
+
+    /// How far across the cursor at `pos` in the shown text is, in physical pixels.
+    fn display_position_px(&self, pos: usize) -> f32 {
+        if self.multiline {
+            return self.display_caret_px(pos).x;
+        }
+        // Masked text has a glyph for each character
+        let pos = if self.state_ref().variant == TextBoxVariant::Hidden && self.hidden {
+            let chars = self.state_ref().text.get(..pos).map_or(0, |t| t.chars().count());
+            chars * get_masked_text("a").len()
+        } else {
+            pos
+        };
+        // The leading edge of the glyph after the cursor, or the trailing edge of the one before
+        // it. Those are the right edges of right-to-left glyphs.
         let glyphs = &self.state_ref().glyphs;
-        (if pos < len {
-            let g = &glyphs[pos];
-            g.x
-        } else if pos == 0 {
-            0.0
+        let x = if let Some(g) = glyphs.iter().find(|g| g.start == pos) {
+            if g.level.is_rtl() {
+                g.x + g.w
+            } else {
+                g.x
+            }
+        } else if let Some(g) = glyphs.iter().find(|g| g.end == pos) {
+            if g.level.is_rtl() {
+                g.x
+            } else {
+                g.x + g.w
+            }
         } else {
-            // Last glyph, need to add the advance
-            let g = &glyphs[pos - 1];
-            g.x + self.state_ref().glyph_widths.last().map_or(0.0, |w| *w)
-        }) + self.state_ref().padding_offset_px
+            0.0
+        };
+        x + self.state_ref().padding_offset_px
     }
 
     fn cut(&mut self) -> bool {
@@ -497,26 +1884,84 @@ impl TextBoxText {
         // } else {
         //     false
         // }
-        true
+        // Nothing is pasted until there is clipboard access
+        false
+    }
+
+    /// Apply a request from the [`TextBoxController`], returning the messages for the `TextBox`.
+    fn handle_request(&mut self, request: TextBoxRequest) -> Vec<Message> {
+        let changed = match request {
+            TextBoxRequest::Undo => return self.handle_action(TextBoxAction::Undo),
+            TextBoxRequest::Redo => return self.handle_action(TextBoxAction::Redo),
+            TextBoxRequest::Find(query, case_sensitive) => {
+                let spans = self.find(&query, case_sensitive);
+                return vec![Box::new(TextBoxMessage::Find(spans))];
+            }
+            TextBoxRequest::Replace((a, b), replacement) => {
+                let text = &self.state_ref().text;
+                // The span may be stale, if the text was edited since it was found
+                let valid = a <= b && text.is_char_boundary(a) && text.is_char_boundary(b);
+                if valid {
+                    self.replace((a, b), &replacement);
+                }
+                valid
+            }
+            TextBoxRequest::ReplaceAll(query, replacement, case_sensitive) => {
+                self.replace_all(&query, &replacement, case_sensitive) > 0
+            }
+        };
+        if changed {
+            vec![Box::new(TextBoxMessage::Change(
+                self.state_ref().text.clone(),
+            ))]
+        } else {
+            vec![]
+        }
     }
 
     fn handle_action(&mut self, action: TextBoxAction) -> Vec<Message> {
         match action {
             TextBoxAction::Cut => {
-                self.cut();
-                vec![Box::new(TextBoxMessage::Change(
-                    self.state_ref().text.clone(),
-                ))]
+                if self.cut() {
+                    vec![Box::new(TextBoxMessage::Change(
+                        self.state_ref().text.clone(),
+                    ))]
+                } else {
+                    vec![]
+                }
             }
             TextBoxAction::Copy => {
                 self.copy();
                 vec![]
             }
             TextBoxAction::Paste => {
-                self.paste();
-                vec![Box::new(TextBoxMessage::Change(
-                    self.state_ref().text.clone(),
-                ))]
+                if self.paste() {
+                    vec![Box::new(TextBoxMessage::Change(
+                        self.state_ref().text.clone(),
+                    ))]
+                } else {
+                    vec![]
+                }
+            }
+            TextBoxAction::Undo => {
+                if self.undo() {
+                    vec![
+                        Box::new(TextBoxMessage::Change(self.state_ref().text.clone())),
+                        Box::new(TextBoxMessage::Undo),
+                    ]
+                } else {
+                    vec![]
+                }
+            }
+            TextBoxAction::Redo => {
+                if self.redo() {
+                    vec![
+                        Box::new(TextBoxMessage::Change(self.state_ref().text.clone())),
+                        Box::new(TextBoxMessage::Redo),
+                    ]
+                } else {
+                    vec![]
+                }
             }
         }
     }
@@ -530,10 +1975,24 @@ impl Component for TextBoxText {
 
     fn props_hash(&self, hasher: &mut ComponentHasher) {
         self.default_text.hash(hasher);
+        self.accepted_count.hash(hasher);
+    }
+
+    fn focusable(&self) -> bool {
+        true
     }
 
     fn new_props(&mut self) {
-        self.reset_state();
+        if self.accepted_count != self.state_ref().accepted_count {
+            // An accepted suggestion replaces the text as an edit, which can be undone
+            self.state_mut().accepted_count = self.accepted_count;
+            if let Some(text) = self.accepted.clone() {
+                let len = self.state_ref().text.len();
+                self.replace((0, len), &text);
+            }
+        } else {
+            self.reset_state();
+        }
     }
 
     fn update(&mut self, message: Message) -> Vec<Message> {
@@ -560,7 +2019,14 @@ impl Component for TextBoxText {
         // }
     }
 
-    fn on_tick(&mut self, _event: &mut event::Event<event::Tick>) {
+    fn on_tick(&mut self, event: &mut event::Event<event::Tick>) {
+        if let Some(controller) = self.controller.clone() {
+            for request in controller.take() {
+                for message in self.handle_request(request) {
+                    event.emit(message);
+                }
+            }
+        }
         if self.state_ref().focused {
             let visible =
                 (self.state_ref().activated_at.elapsed().as_millis() / CURSOR_BLINK_PERIOD) % 2
@@ -574,8 +2040,18 @@ impl Component for TextBoxText {
     fn on_click(&mut self, event: &mut event::Event<event::Click>) {
         match event.input.0 {
             crate::input::MouseButton::Left => {
-                self.activate();
-                let new_pos = self.position(event.relative_physical_position().x);
+                let new_pos = self.position(event.relative_physical_position());
+                if event.modifiers_held.shift {
+                    // Extend the current selection, or start one from the cursor
+                    let anchor = self
+                        .state_ref()
+                        .selection_from
+                        .unwrap_or(self.state_ref().cursor_pos);
+                    self.activate();
+                    self.state_mut().selection_from = Some(anchor);
+                } else {
+                    self.activate();
+                }
                 if new_pos != self.state_ref().cursor_pos {
                     self.state_mut().cursor_pos = new_pos;
                 }
@@ -621,7 +2097,13 @@ impl Component for TextBoxText {
     fn on_double_click(&mut self, event: &mut event::Event<event::DoubleClick>) {
         event.stop_bubbling();
         event.focus();
-        self.select_word();
+        self.activate();
+        self.state_mut().cursor_pos = self.position(event.relative_physical_position());
+        if event.click_count() >= 3 {
+            self.select_line();
+        } else {
+            self.select_word();
+        }
     }
 
     fn on_focus(&mut self, event: &mut event::Event<event::Focus>) {
@@ -635,6 +2117,10 @@ impl Component for TextBoxText {
         self.state_mut().cursor_visible = false;
         self.state_mut().selection_from = None;
         self.state_mut().cursor_pos = 0;
+        if self.state_mut().composition_text.take().is_some() {
+            self.state_mut().dirty = true;
+            event.emit(Box::new(TextBoxMessage::CompositionEnd(String::new())));
+        }
         event.emit(Box::new(TextBoxMessage::Close));
         event.emit(Box::new(TextBoxMessage::Commit(
             self.state_ref().text.clone(),
@@ -642,64 +2128,84 @@ impl Component for TextBoxText {
     }
 
     fn on_key_down(&mut self, event: &mut event::Event<event::KeyDown>) {
+        // Keys belong to the input method while it is composing
+        if self.state_ref().composition_text.is_some() {
+            return;
+        }
         let pos = self.state_ref().cursor_pos;
         let len = self.state_ref().text.len();
         let mut changed = false;
+        // Sent after the change, when the key undid or redid an edit
+        let mut history_message = None;
         match event.input.0 {
+            // The keys that pick from the suggestions
+            Key::Up | Key::Down if self.suggesting => {
+                event.emit(Box::new(TextBoxMessage::SuggestionMove(
+                    event.input.0 == Key::Down,
+                )));
+            }
+            Key::Return if self.suggestion_highlighted => {
+                event.emit(Box::new(TextBoxMessage::SuggestionAccept));
+            }
+            Key::Tab if self.suggesting => {
+                // Keep a focus trap from also moving the focus
+                event.stop_bubbling();
+                event.emit(Box::new(TextBoxMessage::SuggestionAccept));
+            }
+            Key::Escape if self.suggesting => {
+                event.emit(Box::new(TextBoxMessage::SuggestionDismiss));
+            }
             Key::Backspace => {
+                if self.selection().is_some() || pos > 0 {
+                    self.record_edit(false);
+                }
                 if let Some((a, b)) = self.selection() {
                     self.state_mut().text.replace_range(a..b, "");
                     self.state_mut().cursor_pos = a;
                     self.state_mut().selection_from = None;
                     changed = true;
                 } else if pos > 0 {
-                    self.state_mut().text.remove(pos - 1);
-                    self.state_mut().cursor_pos -= 1;
+                    let prev = prev_boundary(&self.state_ref().text, pos);
+                    self.state_mut().text.remove(prev);
+                    self.state_mut().cursor_pos = prev;
                     changed = true;
                 }
             }
-            Key::Left => {
-                // TODO more modifiers
-                if pos > 0 {
-                    if event.modifiers_held.shift {
-                        if let Some(s) = self.state_ref().selection_from {
-                            if pos == s + 1 {
-                                self.state_mut().selection_from = None;
-                            }
-                        } else {
-                            self.state_mut().selection_from = Some(pos);
-                        }
-                        self.state_mut().cursor_pos -= 1;
-                    } else if self.state_ref().selection_from.is_some() {
-                        self.state_mut().selection_from = None;
-                    } else {
-                        self.state_mut().cursor_pos -= 1;
-                    }
-                } else if !event.modifiers_held.shift && self.state_ref().selection_from.is_some() {
+            Key::Delete => {
+                if self.selection().is_some() || pos < len {
+                    self.record_edit(false);
+                }
+                if let Some((a, b)) = self.selection() {
+                    self.state_mut().text.replace_range(a..b, "");
+                    self.state_mut().cursor_pos = a;
                     self.state_mut().selection_from = None;
+                    changed = true;
+                } else if pos < len {
+                    self.state_mut().text.remove(pos);
+                    changed = true;
                 }
             }
-            Key::Right => {
+            Key::Left | Key::Right => {
                 // TODO more modifiers
-                if pos < len {
-                    if event.modifiers_held.shift {
-                        if let Some(s) = self.state_ref().selection_from {
-                            if pos + 1 == s {
-                                self.state_mut().selection_from = None;
-                            }
-                        } else {
-                            self.state_mut().selection_from = Some(pos);
-                        }
-                        self.state_mut().cursor_pos += 1;
-                    } else if self.state_ref().selection_from.is_some() {
+                let shift = event.modifiers_held.shift;
+                if !shift && self.state_ref().selection_from.is_some() {
+                    self.state_mut().selection_from = None;
+                } else {
+                    let target = self.horizontal_step(event.input.0 == Key::Left);
+                    self.move_cursor(target, shift);
+                    if self.state_ref().selection_from == Some(target) {
                         self.state_mut().selection_from = None;
-                    } else {
-                        self.state_mut().cursor_pos += 1;
                     }
-                } else if !event.modifiers_held.shift && self.state_ref().selection_from.is_some() {
-                    self.state_mut().selection_from = None;
                 }
             }
+            Key::Up if self.multiline => {
+                let target = self.row_step(true);
+                self.move_cursor(target, event.modifiers_held.shift);
+            }
+            Key::Down if self.multiline => {
+                let target = self.row_step(false);
+                self.move_cursor(target, event.modifiers_held.shift);
+            }
             Key::Up => {
                 // TODO more modifiers
                 if event.modifiers_held.shift {
@@ -725,7 +2231,23 @@ impl Component for TextBoxText {
                 }
             }
             Key::Return => {
-                event.blur();
+                if self.multiline && !(event.modifiers_held.shift && self.shift_enter_submits) {
+                    event.stop_bubbling();
+                    self.record_edit(false);
+                    self.insert_text("\n");
+                    changed = true;
+                } else {
+                    event.blur();
+                }
+            }
+            Key::Tab => {
+                if self.tab_behavior == TabBehavior::FocusNext {
+                    event.blur();
+                } else if !event.modifiers_held.shift {
+                    // Keep a focus trap from also moving the focus
+                    event.stop_bubbling();
+                    changed = self.insert_tab();
+                }
             }
             Key::X => {
                 if event.modifiers_held.ctrl {
@@ -742,6 +2264,23 @@ impl Component for TextBoxText {
                     changed = self.paste();
                 }
             }
+            Key::Z => {
+                if event.modifiers_held.ctrl {
+                    if event.modifiers_held.shift {
+                        changed = self.redo();
+                        history_message = Some(TextBoxMessage::Redo);
+                    } else {
+                        changed = self.undo();
+                        history_message = Some(TextBoxMessage::Undo);
+                    }
+                }
+            }
+            Key::Y => {
+                if event.modifiers_held.ctrl {
+                    changed = self.redo();
+                    history_message = Some(TextBoxMessage::Redo);
+                }
+            }
             _ => (),
         }
 
@@ -749,11 +2288,29 @@ impl Component for TextBoxText {
             self.state_mut().dirty = true;
             event.emit(Box::new(TextBoxMessage::Change(
                 self.state_ref().text.clone(),
-            )))
+            )));
+            if let Some(message) = history_message {
+                event.emit(Box::new(message));
+            }
         }
     }
 
     fn on_text_entry(&mut self, event: &mut event::Event<event::TextEntry>) {
+        // Tab is handled as a key press, according to the tab behavior, as is Enter in
+        // multi-line text
+        if event.input.0 == "\t"
+            || (self.multiline && (event.input.0 == "\n" || event.input.0 == "\r"))
+            || self.state_ref().composition_text.is_some()
+        {
+            return;
+        }
+        let pos = self.state_ref().cursor_pos;
+        if self.fit(&event.input.0, (pos, pos)).is_empty() && self.selection().is_none() {
+            // At the max length
+            return;
+        }
+        let coalesce = event.input.0.chars().count() == 1 && self.selection().is_none();
+        self.record_edit(coalesce);
         self.insert_text(&event.input.0);
         self.state_mut().dirty = true;
         event.stop_bubbling();
@@ -762,9 +2319,48 @@ impl Component for TextBoxText {
         )));
     }
 
+    fn on_ime_compose(&mut self, event: &mut event::Event<event::ImeCompose>) {
+        event.stop_bubbling();
+        let composing = self.state_ref().composition_text.is_some();
+        if event.input.0.is_empty() {
+            // The composition was cancelled
+            if composing {
+                self.state_mut().composition_text = None;
+                self.state_mut().dirty = true;
+                event.emit(Box::new(TextBoxMessage::CompositionEnd(String::new())));
+            }
+            return;
+        }
+        if !composing {
+            event.emit(Box::new(TextBoxMessage::CompositionStart));
+        }
+        self.state_mut().composition_text = Some(event.input.0.clone());
+        self.state_mut().dirty = true;
+        event.emit(Box::new(TextBoxMessage::CompositionUpdate(
+            event.input.0.clone(),
+        )));
+    }
+
+    fn on_ime_commit(&mut self, event: &mut event::Event<event::ImeCommit>) {
+        event.stop_bubbling();
+        let composing = self.state_mut().composition_text.take().is_some();
+        if !composing {
+            event.emit(Box::new(TextBoxMessage::CompositionStart));
+        }
+        self.record_edit(false);
+        self.insert_text(&event.input.0);
+        self.state_mut().dirty = true;
+        event.emit(Box::new(TextBoxMessage::Change(
+            self.state_ref().text.clone(),
+        )));
+        event.emit(Box::new(TextBoxMessage::CompositionEnd(
+            event.input.0.clone(),
+        )));
+    }
+
     fn on_drag_start(&mut self, event: &mut event::Event<event::DragStart>) {
         self.activate();
-        self.state_mut().selection_from = Some(self.position(event.relative_physical_position().x));
+        self.state_mut().selection_from = Some(self.position(event.relative_physical_position()));
         event.focus();
         event.stop_bubbling();
     }
@@ -776,7 +2372,7 @@ impl Component for TextBoxText {
     }
 
     fn on_drag(&mut self, event: &mut event::Event<event::Drag>) {
-        let new_pos = self.position(event.relative_physical_position().x);
+        let new_pos = self.position(event.relative_physical_position());
         if new_pos != self.state_ref().cursor_pos {
             self.state_mut().cursor_pos = new_pos;
         }
@@ -795,9 +2391,16 @@ impl Component for TextBoxText {
         self.state_ref().cursor_pos.hash(hasher);
         self.state_ref().cursor_visible.hash(hasher);
         self.state_ref().variant.hash(hasher);
+        self.state_ref().find_matches.hash(hasher);
+        self.state_ref().composition_text.hash(hasher);
+        self.text_shadows().hash(hasher);
+        self.multiline.hash(hasher);
     }
 
     fn focus(&self) -> Option<Point> {
+        if self.multiline {
+            return Some(self.caret_px(self.state_ref().cursor_pos));
+        }
         Some(Point {
             x: self.cursor_position_px(self.state_ref().cursor_pos),
             y: 0.0,
@@ -806,25 +2409,31 @@ impl Component for TextBoxText {
 
     fn fill_bounds(
         &mut self,
-        _width: Option<f32>,
+        width: Option<f32>,
         _height: Option<f32>,
-        _max_width: Option<f32>,
+        max_width: Option<f32>,
         _max_height: Option<f32>,
         font_cache: &mut FontCache,
         scale_factor: f32,
     ) -> (Option<f32>, Option<f32>) {
+        if self.multiline {
+            return self.fill_rows(width.or(max_width), font_cache, scale_factor);
+        }
         let padding: f32 = self.style_val("padding").unwrap().f32();
         let font_size: f32 = self.style_val("font_size").unwrap().f32();
         let border_width: BorderWidth = self.style_val("border_width").unwrap().into();
         let font = self.style_val("font").map(|p| p.str().to_string());
-        let is_placeholder = self.state_ref().text.len() == 0 && self.placeholder.is_some();
+        let letter_spacing = self
+            .style_val("letter_spacing")
+            .map_or(0.0, |v| v.f32());
+        let is_placeholder = self.shows_placeholder();
         let text = if is_placeholder {
             self.placeholder.clone().unwrap()
         } else {
             if self.state_ref().variant == TextBoxVariant::Hidden && self.hidden {
                 get_masked_text(self.state_ref().text.clone())
             } else {
-                self.state_ref().text.clone()
+                self.display_text()
             }
         };
         let (t_w, t_h, glyphs) = font_cache.measure_text(
@@ -835,15 +2444,13 @@ impl Component for TextBoxText {
             font_size * 1.3,
             HorizontalPosition::Left,
             (f32::MAX, f32::MAX),
+            letter_spacing,
         );
 
         //Temporary removed this check due to cursor not getting correct position in variant hidden - Akshay
         //self.state_ref().dirty &&
 
         if !is_placeholder {
-            let glyph_widths = glyphs.iter().map(|g| g.w).collect();
-            // println!("glyph_widths are {:?}", glyph_widths);
-            self.state_mut().glyph_widths = glyph_widths;
             self.state_mut().glyphs = glyphs;
             self.state_mut().padding_offset_px = ((padding + border_width.left) * scale_factor).round();
             self.state_mut().dirty = false;
@@ -866,6 +2473,15 @@ impl Component for TextBoxText {
         let font = self.style_val("font").map(|p| p.str().to_string());
         let font_size: f32 = self.style_val("font_size").unwrap().f32();
         let font_weight = self.style_val("font_weight").unwrap().font_weight();
+        let font_style = self
+            .style_val("font_style")
+            .map_or(FontStyle::Normal, |s| s.font_style());
+        let decoration = self
+            .style_val("text_decoration")
+            .map_or(TextDecoration::NONE, |d| d.text_decoration());
+        let letter_spacing = self
+            .style_val("letter_spacing")
+            .map_or(0.0, |v| v.f32());
         let mut line_height = font_size * 1.3; // line height as 1.3 of font_size
 
         if self.style_val("line_height").is_some() {
@@ -875,6 +2491,7 @@ impl Component for TextBoxText {
 
         let cursor_color: Color = self.style_val("cursor_color").into();
         let selection_color: Color = self.style_val("selection_color").into();
+        let find_highlight_color: Color = self.style_val("find_highlight_color").into();
         let pos = self.state_ref().cursor_pos;
         let offset = self.state_ref().padding_offset_px;
         let font_size_px = font_size * context.scale_factor;
@@ -884,13 +2501,19 @@ impl Component for TextBoxText {
             .selection_from
             .map(|pos| self.cursor_position_px(pos));
 
-        let is_placeholder = self.state_ref().text.len() == 0 && self.placeholder.is_some();
+        let is_placeholder = self.shows_placeholder();
+        // Multi-line text wraps inside the padding, as it was measured to
+        let text_scale = if self.multiline {
+            context.aabb.size() - Scale::new(offset * 2.0, 0.0)
+        } else {
+            context.aabb.size()
+        };
         let text_color: Color = self.style_val("text_color").into();
         // println!("self.state_ref().hidden {:?}", self.hidden);
         let text = if self.state_ref().variant == TextBoxVariant::Hidden && self.hidden {
             self.state_ref().masked_text.clone()
         } else {
-            self.state_ref().text.clone()
+            self.display_text()
         };
 
         let mut renderables = vec![];
@@ -902,19 +2525,20 @@ impl Component for TextBoxText {
                     y: offset * 2.0,
                     z: 0.,
                 }))
-                .scale(context.aabb.size())
+                .scale(text_scale)
                 .text(text)
                 .color(text_color)
                 .font(font.clone())
                 .weight(font_weight)
+                .font_style(font_style)
+                .decoration(decoration)
+                .letter_spacing(letter_spacing)
                 .line_height(line_height)
                 .font_size(font_size)
                 .build()
                 .unwrap();
 
-            let text = Renderable::Text(Text::from_instance_data(text_instance));
-
-            renderables.push(text);
+            renderables.extend(self.with_shadows(Text::from_instance_data(text_instance)));
         }
         let placeholder_color: Color = self.style_val("placeholder_color").into();
         if is_placeholder {
@@ -924,27 +2548,94 @@ impl Component for TextBoxText {
                     y: offset * 2.0,
                     z: 0.,
                 }))
-                .scale(context.aabb.size())
+                .scale(text_scale)
                 .text(self.placeholder.clone().unwrap())
                 .color(placeholder_color)
                 .font(font.clone())
                 .weight(font_weight)
+                .font_style(font_style)
+                .decoration(decoration)
+                .letter_spacing(letter_spacing)
                 .line_height(line_height)
                 .font_size(font_size)
                 .build()
                 .unwrap();
 
-            let text = Renderable::Text(Text::from_instance_data(text_instance));
+            renderables.extend(self.with_shadows(Text::from_instance_data(text_instance)));
+        }
 
-            renderables.push(text);
+        if !is_placeholder && self.multiline {
+            let row_height = self.state_ref().row_height_px;
+            for (a, b) in self.state_ref().find_matches.iter() {
+                for (row, x1, x2) in row_spans(&self.state_ref().carets, *a, *b) {
+                    let y = offset + 6.0 + row as f32 * row_height;
+                    renderables.push(Renderable::Rect(Rect::new(
+                        context.aabb.pos.add(Pos::new(x1 + offset, y, 1.0)),
+                        Scale::new(x2 - x1, font_size_px - offset),
+                        find_highlight_color,
+                    )));
+                }
+            }
+        } else if !is_placeholder {
+            for (a, b) in self.state_ref().find_matches.iter() {
+                let (x1, x2) = (self.cursor_position_px(*a), self.cursor_position_px(*b));
+                renderables.push(Renderable::Rect(Rect::new(
+                    context.aabb.pos.add(Pos::new(x1, offset + 6.0, 1.0)),
+                    Scale::new(x2 - x1, font_size_px - offset),
+                    find_highlight_color,
+                )));
+            }
         }
 
-        if self.state_ref().cursor_visible && self.selection().is_none() {
+        // The composition is underlined, and the cursor sits at its end until it is committed
+        if let Some((a, b)) = self.composition_span() {
+            let thickness = context.scale_factor.round().max(1.0);
+            if self.multiline {
+                let row_height = self.state_ref().row_height_px;
+                for (row, x1, x2) in row_spans(&self.state_ref().carets, a, b) {
+                    let y = 6.0 + row as f32 * row_height + font_size_px;
+                    renderables.push(Renderable::Rect(Rect::new(
+                        context.aabb.pos.add(Pos::new(x1 + offset, y, cursor_z)),
+                        Scale::new(x2 - x1, thickness),
+                        text_color,
+                    )));
+                }
+            } else {
+                let (x1, x2) = (self.display_position_px(a), self.display_position_px(b));
+                renderables.push(Renderable::Rect(Rect::new(
+                    context
+                        .aabb
+                        .pos
+                        .add(Pos::new(x1, 6.0 + font_size_px, cursor_z)),
+                    Scale::new(x2 - x1, thickness),
+                    text_color,
+                )));
+            }
+            let (cursor_x, cursor_y) = if self.multiline {
+                let caret = self.display_caret_px(b);
+                (caret.x, caret.y + 6.0)
+            } else {
+                (self.display_position_px(b), offset + 6.0)
+            };
+            renderables.push(Renderable::Rect(Rect::new(
+                context
+                    .aabb
+                    .pos
+                    .add(Pos::new(cursor_x, cursor_y, cursor_z)),
+                Scale::new(1.0, font_size_px - offset),
+                cursor_color,
+            )));
+        } else if self.state_ref().cursor_visible && self.selection().is_none() {
+            let cursor_y = if self.multiline {
+                self.caret_px(pos).y + 6.0
+            } else {
+                offset + 6.0
+            };
             let cursor_rect = Renderable::Rect(Rect::new(
                 context
                     .aabb
                     .pos
-                    .add(Pos::new(cursor_x, offset + 6.0, cursor_z)),
+                    .add(Pos::new(cursor_x, cursor_y, cursor_z)),
                 Scale::new(1.0, font_size_px - offset),
                 cursor_color,
             ));
@@ -973,3 +2664,923 @@ impl Component for TextBoxText {
 fn get_masked_text<S: Into<String>>(text: S) -> String {
     text.into().chars().into_iter().map(|_| "•").collect()
 }
+
+/// The logical height of a row of text in `styled`: its `line_height`, or 1.3 times its
+/// `font_size`.
+fn row_height(styled: &impl Styled) -> f32 {
+    styled
+        .style_val("line_height")
+        .map(|v| v.f32())
+        .unwrap_or_else(|| styled.style_val("font_size").unwrap().f32() * 1.3)
+}
+
+/// The first `n` characters of `text`.
+fn truncate_chars(text: &str, n: usize) -> &str {
+    text.char_indices().nth(n).map_or(text, |(i, _)| &text[..i])
+}
+
+/// A place for the cursor in multi-line text: how far across it is, in physical pixels, and
+/// which row it is on.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+struct Caret {
+    x: f32,
+    row: usize,
+}
+
+/// A glyph of multi-line text: the byte-offset span of the text it shows, where it is across
+/// and how wide, in physical pixels, and which row it is on.
+#[derive(Debug)]
+struct GlyphBox {
+    start: usize,
+    end: usize,
+    x: f32,
+    w: f32,
+    row: usize,
+    /// Whether the glyph is laid out right-to-left, so that it starts on its right.
+    rtl: bool,
+}
+
+/// Where the cursor goes before each byte of `text`, and after the last, given its `glyphs`.
+/// Newlines have no glyphs, so the cursor goes to the start of the next row after each one.
+fn carets(text: &str, glyphs: &[GlyphBox]) -> Vec<Caret> {
+    let len = text.len();
+    let mut starts: Vec<Option<&GlyphBox>> = vec![None; len + 1];
+    let mut ends: Vec<Option<&GlyphBox>> = vec![None; len + 1];
+    for glyph in glyphs.iter().filter(|g| g.end <= len) {
+        starts[glyph.start].get_or_insert(glyph);
+        ends[glyph.end] = Some(glyph);
+    }
+
+    let mut carets: Vec<Caret> = Vec::with_capacity(len + 1);
+    for pos in 0..=len {
+        let caret = if let Some(glyph) = starts[pos] {
+            Caret {
+                x: if glyph.rtl { glyph.x + glyph.w } else { glyph.x },
+                row: glyph.row,
+            }
+        } else if pos == 0 {
+            Caret::default()
+        } else if text.as_bytes()[pos - 1] == b'\n' {
+            Caret {
+                x: 0.0,
+                row: carets[pos - 1].row + 1,
+            }
+        } else if let Some(glyph) = ends[pos] {
+            Caret {
+                x: if glyph.rtl { glyph.x } else { glyph.x + glyph.w },
+                row: glyph.row,
+            }
+        } else {
+            carets[pos - 1]
+        };
+        carets.push(caret);
+    }
+    carets
+}
+
+/// The byte offset of each character of `text`, and of its end.
+fn boundaries(text: &str) -> impl Iterator<Item = usize> + '_ {
+    text.char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(text.len()))
+}
+
+/// The byte offset of the character before `pos` in `text`, or `0` at the start.
+fn prev_boundary(text: &str, pos: usize) -> usize {
+    text.get(..pos)
+        .and_then(|t| t.char_indices().next_back())
+        .map_or(0, |(i, _)| i)
+}
+
+/// The byte offset of the character after the one at `pos` in `text`, or `pos` at the end.
+fn next_boundary(text: &str, pos: usize) -> usize {
+    text.get(pos..)
+        .and_then(|t| t.chars().next())
+        .map_or(pos, |c| pos + c.len_utf8())
+}
+
+/// The character position on `row` (or the last row) of `text` whose caret is nearest to `x`.
+fn nearest_caret(text: &str, carets: &[Caret], x: f32, row: usize) -> usize {
+    if carets.len() != text.len() + 1 {
+        return text.len();
+    }
+    let row = row.min(carets[text.len()].row);
+    boundaries(text)
+        .filter(|i| carets[*i].row == row)
+        .min_by(|a, b| {
+            let (a, b) = ((carets[*a].x - x).abs(), (carets[*b].x - x).abs());
+            a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+        })
+        .unwrap_or(text.len())
+}
+
+/// The rows that the byte-offset span from `a` to `b` covers, with where it starts and ends
+/// across each of them.
+fn row_spans(carets: &[Caret], a: usize, b: usize) -> Vec<(usize, f32, f32)> {
+    let mut spans: Vec<(usize, f32, f32)> = vec![];
+    for caret in carets.iter().take(b + 1).skip(a) {
+        match spans.last_mut() {
+            Some((row, x1, x2)) if *row == caret.row => {
+                *x1 = x1.min(caret.x);
+                *x2 = x2.max(caret.x);
+            }
+            _ => spans.push((caret.row, caret.x, caret.x)),
+        }
+    }
+    spans
+}
+
+/// Byte-offset spans of the non-overlapping matches of `query` in `text`.
+fn find_spans(text: &str, query: &str, case_sensitive: bool) -> Vec<(usize, usize)> {
+    let query: Vec<char> = query.chars().collect();
+    let matches = |a: char, b: char| {
+        if case_sensitive {
+            a == b
+        } else {
+            a.to_lowercase().eq(b.to_lowercase())
+        }
+    };
+
+    let mut spans = vec![];
+    if query.is_empty() {
+        return spans;
+    }
+    let mut start = 0;
+    while let Some(first) = text[start..].chars().next() {
+        let mut q = 0;
+        let mut end = None;
+        for (i, c) in text[start..].char_indices() {
+            if !matches(c, query[q]) {
+                break;
+            }
+            q += 1;
+            if q == query.len() {
+                end = Some(start + i + c.len_utf8());
+                break;
+            }
+        }
+        match end {
+            Some(end) => {
+                spans.push((start, end));
+                start = end;
+            }
+            None => start += first.len_utf8(),
+        }
+    }
+    spans
+}
+
+/// `text` with each of the byte-offset `spans` replaced with `replacement`, and where the byte
+/// offset `cursor_pos` ends up: after the replacement, when it was inside a span. With `room`,
+/// the replacements are cut short to add at most that many characters in all.
+fn replace_spans(
+    text: &str,
+    spans: &[(usize, usize)],
+    replacement: &str,
+    mut room: Option<usize>,
+    cursor_pos: usize,
+) -> (String, usize) {
+    let cursor_pos = cursor_pos.min(text.len());
+    let mut new_cursor_pos = None;
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for (a, b) in spans.iter() {
+        result.push_str(&text[last..*a]);
+        if (last..=*a).contains(&cursor_pos) {
+            new_cursor_pos = Some(result.len() - (a - cursor_pos));
+        }
+        let replacement = match room.as_mut() {
+            Some(room) => {
+                let replacement = truncate_chars(replacement, *room);
+                *room -= replacement.chars().count();
+                replacement
+            }
+            None => replacement,
+        };
+        result.push_str(replacement);
+        if (a + 1..*b).contains(&cursor_pos) {
+            new_cursor_pos = Some(result.len());
+        }
+        last = *b;
+    }
+    result.push_str(&text[last..]);
+    let new_cursor_pos =
+        new_cursor_pos.unwrap_or_else(|| result.len() - (text.len() - cursor_pos.max(last)));
+    (result, new_cursor_pos)
+}
+
+/// The whitespace-delimited word around `pos`, as `(start, end)` byte offsets.
+fn word_bounds(text: &str, pos: usize) -> (usize, usize) {
+    bounds_between(text, pos, char::is_whitespace)
+}
+
+/// The logical line around `pos`, as `(start, end)` byte offsets.
+fn line_bounds(text: &str, pos: usize) -> (usize, usize) {
+    bounds_between(text, pos, |c| c == '\n')
+}
+
+/// The span around the byte offset `pos` of `text` that has no character that `is_break`
+/// accepts, as `(start, end)` byte offsets.
+fn bounds_between(text: &str, pos: usize, is_break: impl Fn(char) -> bool) -> (usize, usize) {
+    let mut pos = pos.min(text.len());
+    while !text.is_char_boundary(pos) {
+        pos -= 1;
+    }
+    let end_pos = text[pos..]
+        .char_indices()
+        .find(|(_, c)| is_break(*c))
+        .map_or(text.len(), |(i, _)| pos + i);
+    let start_pos = text[..pos]
+        .char_indices()
+        .rev()
+        .find(|(_, c)| is_break(*c))
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    (start_pos, end_pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn type_char(history: &mut UndoStack<String>, text: &mut String, c: char, at: Instant) {
+        history.push(text.clone(), true, at);
+        text.push(c);
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut history = UndoStack::new(DEFAULT_UNDO_DEPTH);
+        let mut text = String::new();
+        let start = Instant::now();
+        for (i, c) in "abc".chars().enumerate() {
+            let at = start + Duration::from_millis(i as u64 * 1000);
+            type_char(&mut history, &mut text, c, at);
+        }
+        assert_eq!(text, "abc");
+
+        text = history.undo(text).unwrap();
+        assert_eq!(text, "ab");
+        text = history.undo(text).unwrap();
+        assert_eq!(text, "a");
+        text = history.redo(text).unwrap();
+        assert_eq!(text, "ab");
+        text = history.redo(text).unwrap();
+        assert_eq!(text, "abc");
+        assert_eq!(history.redo(text.clone()), None);
+    }
+
+    #[test]
+    fn test_undo_coalesces_typing() {
+        let mut history = UndoStack::new(DEFAULT_UNDO_DEPTH);
+        let mut text = String::new();
+        let start = Instant::now();
+        for (i, c) in "abc".chars().enumerate() {
+            let at = start + Duration::from_millis(i as u64 * 100);
+            type_char(&mut history, &mut text, c, at);
+        }
+
+        text = history.undo(text).unwrap();
+        assert_eq!(text, "");
+        assert_eq!(history.undo(text.clone()), None);
+    }
+
+    #[test]
+    fn test_word_bounds() {
+        let text = "say hello world";
+        for pos in 4..=9 {
+            let (start, end) = word_bounds(text, pos);
+            assert_eq!((start, end), (4, 9));
+            assert_eq!(&text[start..end], "hello");
+        }
+        assert_eq!(word_bounds(text, 0), (0, 3));
+        assert_eq!(word_bounds(text, 15), (10, 15));
+    }
+
+    #[test]
+    fn test_line_bounds() {
+        assert_eq!(line_bounds("say hello world", 5), (0, 15));
+        assert_eq!(line_bounds("first\nsecond line", 8), (6, 17));
+    }
+
+    #[test]
+    fn test_bounds_multi_byte() {
+        let text = "éé éé";
+        assert_eq!(word_bounds(text, 0), (0, 4));
+        assert_eq!(word_bounds(text, 7), (5, 9));
+        assert_eq!(&text[5..9], "éé");
+
+        let text = "été\nœuf";
+        assert_eq!(line_bounds(text, 2), (0, 5));
+        assert_eq!(line_bounds(text, 8), (6, 10));
+        assert_eq!(&text[6..10], "œuf");
+
+        // A double click selects the word, which typing then replaces
+        let mut text = text_box_text(TabBehavior::FocusNext);
+        text.state_mut().text = "éé éé".to_string();
+        text.state_mut().cursor_pos = 7;
+        assert!(text.select_word());
+        assert_eq!(text.selection(), Some((5, 9)));
+        text.insert_text("x");
+        assert_eq!(text.state_ref().text, "éé x");
+    }
+
+    #[test]
+    fn test_find_replace() {
+        assert_eq!(find_spans("AbAb", "ab", false), vec![(0, 2), (2, 4)]);
+        assert_eq!(find_spans("AbAb", "ab", true), vec![]);
+        assert_eq!(find_spans("aaa", "aa", true), vec![(0, 2)]);
+        fn replace_all_in(
+            text: &str,
+            query: &str,
+            replacement: &str,
+            room: Option<usize>,
+            cursor_pos: usize,
+        ) -> (String, usize) {
+            let spans = find_spans(text, query, false);
+            replace_spans(text, &spans, replacement, room, cursor_pos)
+        }
+        assert_eq!(
+            replace_all_in("AbAb", "ab", "xy", None, 4),
+            ("xyxy".to_string(), 4)
+        );
+        assert_eq!(
+            replace_all_in("one two one", "one", "1", None, 5),
+            ("1 two 1".to_string(), 3)
+        );
+        // A cursor inside a match moves past its replacement
+        assert_eq!(
+            replace_all_in("one two one", "one", "1", None, 9),
+            ("1 two 1".to_string(), 7)
+        );
+        // The cursor stays on a char boundary when a replacement is longer in bytes
+        assert_eq!(
+            replace_all_in("xa", "x", "é", None, 1),
+            ("éa".to_string(), 2)
+        );
+        // Replacements are cut short once the room runs out
+        assert_eq!(
+            replace_all_in("xax", "x", "yy", Some(3), 3),
+            ("yyay".to_string(), 4)
+        );
+
+        // Replacing all keeps within the max_length, like other edits
+        let mut text = text_box_text(TabBehavior::FocusNext);
+        text.max_length = Some(3);
+        assert_eq!(text.replace_all("b", "xyz", true), 1);
+        assert_eq!(text.state_ref().text, "axy");
+        assert_eq!(text.state_ref().cursor_pos, 1);
+    }
+
+    fn text_box_text(tab_behavior: TabBehavior) -> TextBoxText {
+        let mut text = TextBoxText {
+            default_text: "ab".to_string(),
+            placeholder: None,
+            variant: TextBoxVariant::Normal,
+            hidden: false,
+            undo_depth: DEFAULT_UNDO_DEPTH,
+            tab_behavior,
+            text_shadow: None,
+            multiline: false,
+            min_rows: 1,
+            shift_enter_submits: false,
+            suggesting: false,
+            suggestion_highlighted: false,
+            accepted: None,
+            accepted_count: 0,
+            max_length: None,
+            controller: None,
+            direction: TextDirection::Ltr,
+            bidi: false,
+            style_overrides: Default::default(),
+            class: Default::default(),
+            state: None,
+            dirty: false,
+        };
+        text.init();
+        text.state_mut().cursor_pos = 1;
+        text
+    }
+
+    #[test]
+    fn test_tab_behavior() {
+        let mut text = text_box_text(TabBehavior::FocusNext);
+        assert!(!text.insert_tab());
+        assert_eq!(text.state_ref().text, "ab");
+
+        let mut text = text_box_text(TabBehavior::InsertSpaces(4));
+        assert!(text.insert_tab());
+        assert_eq!(text.state_ref().text, "a    b");
+        assert_eq!(text.state_ref().cursor_pos, 5);
+
+        let mut text = text_box_text(TabBehavior::InsertTab);
+        assert!(text.insert_tab());
+        assert_eq!(text.state_ref().text, "a\u{9}b");
+        assert!(text.undo());
+        assert_eq!(text.state_ref().text, "ab");
+    }
+
+    /// Glyphs 10px wide, for each character of the `rows` of text.
+    fn glyph_boxes(rows: &[&str]) -> (String, Vec<GlyphBox>) {
+        let mut glyphs = vec![];
+        let mut start = 0;
+        for (row, line) in rows.iter().enumerate() {
+            for (i, c) in line.chars().filter(|c| *c != '\n').enumerate() {
+                glyphs.push(GlyphBox {
+                    start,
+                    end: start + c.len_utf8(),
+                    x: i as f32 * 10.0,
+                    w: 10.0,
+                    row,
+                    rtl: false,
+                });
+                start += c.len_utf8();
+            }
+            if line.ends_with('\n') {
+                start += 1;
+            }
+        }
+        (rows.concat(), glyphs)
+    }
+
+    #[test]
+    fn test_carets() {
+        // "one two" wraps after the space, and an empty line follows
+        let (text, glyphs) = glyph_boxes(&["one ", "two\n", "\n", "x"]);
+        let carets = carets(&text, &glyphs);
+        assert_eq!(carets.len(), text.len() + 1);
+        assert_eq!(carets[3], Caret { x: 30.0, row: 0 });
+        // The start of a wrapped row is on that row
+        assert_eq!(carets[4], Caret { x: 0.0, row: 1 });
+        // Before and after a newline
+        assert_eq!(carets[7], Caret { x: 30.0, row: 1 });
+        assert_eq!(carets[8], Caret { x: 0.0, row: 2 });
+        assert_eq!(carets[9], Caret { x: 0.0, row: 3 });
+        assert_eq!(carets[10], Caret { x: 10.0, row: 3 });
+
+        let (text, glyphs) = glyph_boxes(&["ab\n", ""]);
+        assert_eq!(carets(&text, &glyphs)[3], Caret { x: 0.0, row: 1 });
+    }
+
+    #[test]
+    fn test_rtl_carets() {
+        // Hebrew is laid out from the right, so its carets are on the right of its glyphs
+        let text = "abאב";
+        let glyph = |start, len, x, rtl| GlyphBox {
+            start,
+            end: start + len,
+            x,
+            w: 10.0,
+            row: 0,
+            rtl,
+        };
+        let glyphs = vec![
+            glyph(0, 1, 0.0, false),
+            glyph(1, 1, 10.0, false),
+            glyph(2, 2, 30.0, true),
+            glyph(4, 2, 20.0, true),
+        ];
+        let xs: Vec<f32> = carets(text, &glyphs).iter().map(|c| c.x).collect();
+        assert_eq!((xs[0], xs[1], xs[2], xs[4], xs[6]), (0.0, 10.0, 40.0, 30.0, 20.0));
+    }
+
+    #[test]
+    fn test_rtl_cursor() {
+        let mut text = text_box_text(TabBehavior::FocusNext);
+        text.state_mut().text = "שלום".to_string();
+        text.state_mut().cursor_pos = 0;
+        // Left moves forward through right-to-left text, a character at a time
+        text.direction = TextDirection::Rtl;
+        assert_eq!(text.horizontal_step(true), 2);
+        text.state_mut().cursor_pos = 2;
+        assert_eq!(text.horizontal_step(false), 0);
+
+        text.direction = TextDirection::Auto;
+        assert_eq!(text.horizontal_step(true), 4);
+        text.direction = TextDirection::Ltr;
+        assert_eq!(text.horizontal_step(true), 0);
+        assert_eq!(text.horizontal_step(false), 4);
+
+        assert_eq!(prev_boundary("aש", 3), 1);
+        assert_eq!(next_boundary("aש", 3), 3);
+    }
+
+    #[test]
+    fn test_bidi_cursor() {
+        let mut text = text_box_text(TabBehavior::FocusNext);
+        text.multiline = true;
+        text.bidi = true;
+        // "ab" then "אב", which is drawn from the right: a b ב א
+        let (ab, mut glyphs) = glyph_boxes(&["ab"]);
+        glyphs.push(GlyphBox {
+            start: 2,
+            end: 4,
+            x: 30.0,
+            w: 10.0,
+            row: 0,
+            rtl: true,
+        });
+        glyphs.push(GlyphBox {
+            start: 4,
+            end: 6,
+            x: 20.0,
+            w: 10.0,
+            row: 0,
+            rtl: true,
+        });
+        let content = ab + "אב";
+        text.state_mut().carets = carets(&content, &glyphs);
+        text.state_mut().text = content;
+
+        // The cursor moves across where it is drawn, through the Hebrew from its end
+        let mut steps = vec![];
+        for _ in 0..4 {
+            let pos = text.horizontal_step(false);
+            text.state_mut().cursor_pos = pos;
+            steps.push(pos);
+        }
+        assert_eq!(steps, vec![6, 4, 2, 2]);
+        for _ in 0..4 {
+            let pos = text.horizontal_step(true);
+            text.state_mut().cursor_pos = pos;
+            steps.push(pos);
+        }
+        assert_eq!(&steps[4..], &[4, 6, 1, 0]);
+    }
+
+    #[test]
+    fn test_nearest_caret() {
+        let (text, glyphs) = glyph_boxes(&["hello\n", "hi\n", "there"]);
+        let carets = carets(&text, &glyphs);
+        assert_eq!(nearest_caret(&text, &carets, 32.0, 0), 3);
+        // Past the end of a short row
+        assert_eq!(nearest_caret(&text, &carets, 40.0, 1), 8);
+        // Rows past the last are the last
+        assert_eq!(nearest_caret(&text, &carets, 0.0, 7), 9);
+        assert_eq!(nearest_caret(&text, &carets[1..], 0.0, 0), text.len());
+    }
+
+    #[test]
+    fn test_row_spans() {
+        let (text, glyphs) = glyph_boxes(&["hello\n", "there"]);
+        let carets = carets(&text, &glyphs);
+        assert_eq!(row_spans(&carets, 1, 3), vec![(0, 10.0, 30.0)]);
+        assert_eq!(
+            row_spans(&carets, 3, 8),
+            vec![(0, 30.0, 50.0), (1, 0.0, 20.0)]
+        );
+    }
+
+    #[test]
+    fn test_resize_mode() {
+        assert_eq!(ResizeMode::from_style("both"), ResizeMode::Both);
+        assert_eq!(ResizeMode::from_style("auto"), ResizeMode::None);
+        assert!(ResizeMode::Vertical.vertical() && !ResizeMode::Vertical.horizontal());
+    }
+
+    #[test]
+    fn test_redo_branch() {
+        let mut history = UndoStack::new(DEFAULT_UNDO_DEPTH);
+        let start = Instant::now();
+        history.push("a".to_string(), false, start);
+        history.push("ab".to_string(), false, start);
+        let text = history.undo("abc".to_string()).unwrap();
+        assert_eq!(text, "ab");
+
+        // A new edit after an undo starts a new branch, and the undone one can't be redone
+        history.push(text, false, start);
+        assert_eq!(history.redo("abd".to_string()), None);
+        assert_eq!(history.undo("abd".to_string()), Some("ab".to_string()));
+        assert_eq!(history.undo("ab".to_string()), Some("a".to_string()));
+        assert_eq!(history.redo("a".to_string()), Some("ab".to_string()));
+    }
+
+    #[test]
+    fn test_text_undo() {
+        let mut text = text_box_text(TabBehavior::FocusNext);
+        // Typing quickly is undone in one step, back to where the cursor was
+        for c in ["x", "y", "z"] {
+            text.record_edit(true);
+            text.insert_text(c);
+        }
+        assert_eq!(text.state_ref().text, "axyzb");
+        assert_eq!(text.state_ref().cursor_pos, 4);
+        assert!(text.undo());
+        assert_eq!(text.state_ref().text, "ab");
+        assert_eq!(text.state_ref().cursor_pos, 1);
+
+        assert!(text.redo());
+        assert_eq!(text.state_ref().text, "axyzb");
+        assert_eq!(text.state_ref().cursor_pos, 4);
+        assert!(!text.redo());
+
+        // An undo followed by an edit leaves nothing to redo
+        assert!(text.undo());
+        text.record_edit(false);
+        text.insert_text("!");
+        assert_eq!(text.state_ref().text, "a!b");
+        assert!(!text.redo());
+        assert!(text.undo());
+        assert_eq!(text.state_ref().text, "ab");
+        assert!(!text.undo());
+    }
+
+    #[test]
+    fn test_composition_display() {
+        let mut text = text_box_text(TabBehavior::FocusNext);
+        text.state_mut().composition_text = Some("xy".to_string());
+        assert_eq!(text.display_text(), "axyb");
+        assert_eq!(text.composition_span(), Some((1, 3)));
+        // Positions after the cursor are pushed past the composition
+        assert_eq!(text.to_display(1), 1);
+        assert_eq!(text.to_display(2), 4);
+        assert_eq!(text.state_ref().text, "ab");
+
+        // Hidden text doesn't show what is being composed
+        text.state_mut().variant = TextBoxVariant::Hidden;
+        text.hidden = true;
+        assert_eq!(text.display_text(), "ab");
+        assert_eq!(text.composition_span(), None);
+    }
+
+    fn messages(messages: &[Message]) -> Vec<String> {
+        messages
+            .iter()
+            .map(|m| format!("{:?}", m.downcast_ref::<TextBoxMessage>().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_ime_events() {
+        let mut text = text_box_text(TabBehavior::FocusNext);
+        let cache = event::EventCache::new(1.0);
+        for preedit in ["n", "ni"] {
+            let mut event = event::Event::new(event::ImeCompose(preedit.to_string()), &cache);
+            text.on_ime_compose(&mut event);
+            assert!(!event.bubbles);
+            if preedit == "n" {
+                assert_eq!(
+                    messages(&event.messages),
+                    vec!["CompositionStart", "CompositionUpdate(\"n\")"]
+                );
+            }
+        }
+        assert_eq!(text.display_text(), "anib");
+
+        // Keys go to the input method while composing
+        let mut event = event::Event::new(event::TextEntry("q".to_string()), &cache);
+        text.on_text_entry(&mut event);
+        assert_eq!(text.state_ref().text, "ab");
+
+        let mut event = event::Event::new(event::ImeCommit("你".to_string()), &cache);
+        text.on_ime_commit(&mut event);
+        assert_eq!(
+            messages(&event.messages),
+            vec!["Change(\"a你b\")", "CompositionEnd(\"你\")"]
+        );
+        assert_eq!(text.state_ref().composition_text, None);
+        assert_eq!(text.state_ref().cursor_pos, 4);
+        assert!(text.undo());
+        assert_eq!(text.state_ref().text, "ab");
+
+        // An empty composition cancels it, leaving the text as it was
+        let mut event = event::Event::new(event::ImeCompose("x".to_string()), &cache);
+        text.on_ime_compose(&mut event);
+        let mut event = event::Event::new(event::ImeCompose(String::new()), &cache);
+        text.on_ime_compose(&mut event);
+        assert_eq!(messages(&event.messages), vec!["CompositionEnd(\"\")"]);
+        assert_eq!(text.display_text(), "ab");
+    }
+
+    fn fruit_box() -> TextBox {
+        TextBox::new(None)
+            .autocomplete(Box::new(|text| {
+                ["apple", "apricot", "banana"]
+                    .iter()
+                    .filter(|fruit| fruit.starts_with(text))
+                    .map(|fruit| fruit.to_string())
+                    .collect()
+            }))
+            .on_commit(Box::new(|s| msg!(s.to_string())))
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let mut text_box = fruit_box();
+        let cache = event::EventCache::new(1.0);
+        text_box.update(msg!(TextBoxMessage::Open));
+        text_box.update(msg!(TextBoxMessage::Change("ap".to_string())));
+        // Nothing is suggested until the text has been left alone
+        text_box.on_tick(&mut event::Event::new(event::Tick, &cache));
+        assert!(text_box.state_ref().suggestions.is_empty());
+        let typed_at = Instant::now() - Duration::from_millis(SUGGESTION_DEBOUNCE as u64);
+        text_box.state_mut().query = Some(("ap".to_string(), typed_at));
+        text_box.on_tick(&mut event::Event::new(event::Tick, &cache));
+        assert_eq!(text_box.state_ref().suggestions, vec!["apple", "apricot"]);
+
+        // Up from nothing highlighted goes to the last, and down from the last to the first
+        text_box.update(msg!(TextBoxMessage::SuggestionMove(false)));
+        assert_eq!(text_box.state_ref().highlighted, Some(1));
+        text_box.update(msg!(TextBoxMessage::SuggestionMove(true)));
+        assert_eq!(text_box.state_ref().highlighted, Some(0));
+        text_box.update(msg!(TextBoxMessage::SuggestionHover(1)));
+        assert_eq!(text_box.state_ref().highlighted, Some(1));
+
+        text_box.update(msg!(TextBoxMessage::SuggestionAccept));
+        assert!(text_box.state_ref().suggestions.is_empty());
+        assert_eq!(text_box.state_ref().accepted_count, 1);
+        // Committed before the text box has picked up the suggestion
+        let m = text_box.update(msg!(TextBoxMessage::Commit("ap".to_string())));
+        assert_eq!(m[0].downcast_ref::<String>().unwrap(), "apricot");
+
+        text_box.update(msg!(TextBoxMessage::Change("b".to_string())));
+        assert_eq!(text_box.state_ref().accepted, None);
+        text_box.suggest("b");
+        assert_eq!(text_box.state_ref().suggestions, vec!["banana"]);
+        text_box.update(msg!(TextBoxMessage::SuggestionDismiss));
+        assert!(text_box.state_ref().suggestions.is_empty());
+        assert!(text_box.state_ref().query.is_none());
+    }
+
+    #[test]
+    fn test_suggestion_limits() {
+        let mut text_box = fruit_box()
+            .style("suggestion_min_chars", 2u32)
+            .style("suggestion_max_items", 1u32);
+        text_box.suggest("a");
+        assert!(text_box.state_ref().suggestions.is_empty());
+        text_box.suggest("ap");
+        assert_eq!(text_box.state_ref().suggestions, vec!["apple"]);
+    }
+
+    #[test]
+    fn test_accepted_suggestion() {
+        let mut text = text_box_text(TabBehavior::FocusNext);
+        text.accepted = Some("apple".to_string());
+        text.accepted_count = 1;
+        text.new_props();
+        assert_eq!(text.state_ref().text, "apple");
+        assert_eq!(text.state_ref().cursor_pos, 5);
+        assert!(text.undo());
+        assert_eq!(text.state_ref().text, "ab");
+
+        // Other new props reset the text
+        text.default_text = "cd".to_string();
+        text.new_props();
+        assert_eq!(text.state_ref().text, "cd");
+        assert_eq!(text.state_ref().accepted_count, 1);
+    }
+
+    #[test]
+    fn test_controller() {
+        let controller = TextBoxController::new();
+        let mut text = text_box_text(TabBehavior::FocusNext);
+        text.controller = Some(controller.clone());
+        text.record_edit(false);
+        text.insert_text("x");
+        let cache = event::EventCache::new(1.0);
+
+        controller.undo();
+        let mut event = event::Event::new(event::Tick, &cache);
+        text.on_tick(&mut event);
+        assert_eq!(text.state_ref().text, "ab");
+        assert_eq!(messages(&event.messages), vec!["Change(\"ab\")", "Undo"]);
+
+        controller.redo();
+        controller.redo();
+        let mut event = event::Event::new(event::Tick, &cache);
+        text.on_tick(&mut event);
+        assert_eq!(text.state_ref().text, "axb");
+        assert_eq!(messages(&event.messages), vec!["Change(\"axb\")", "Redo"]);
+        assert!(controller.take().is_empty());
+    }
+
+    #[test]
+    fn test_controller_find_replace() {
+        let controller = TextBoxController::new();
+        let mut text = text_box_text(TabBehavior::FocusNext);
+        text.controller = Some(controller.clone());
+        let cache = event::EventCache::new(1.0);
+
+        controller.replace_all("b", "cbc", true);
+        controller.find("C", false);
+        let mut event = event::Event::new(event::Tick, &cache);
+        text.on_tick(&mut event);
+        assert_eq!(text.state_ref().text, "acbc");
+        assert_eq!(text.state_ref().find_matches, vec![(1, 2), (3, 4)]);
+        assert_eq!(
+            messages(&event.messages),
+            vec!["Change(\"acbc\")", "Find([(1, 2), (3, 4)])"]
+        );
+
+        controller.replace((3, 4), "d");
+        controller.replace((9, 10), "out of bounds");
+        let mut event = event::Event::new(event::Tick, &cache);
+        text.on_tick(&mut event);
+        assert_eq!(text.state_ref().text, "acbd");
+        assert!(text.state_ref().find_matches.is_empty());
+        assert_eq!(messages(&event.messages), vec!["Change(\"acbd\")"]);
+
+        controller.undo();
+        controller.undo();
+        controller.redo();
+        let mut event = event::Event::new(event::Tick, &cache);
+        text.on_tick(&mut event);
+        assert_eq!(text.state_ref().text, "acbc");
+        assert_eq!(
+            messages(&event.messages),
+            vec![
+                "Change(\"acbc\")",
+                "Undo",
+                "Change(\"ab\")",
+                "Undo",
+                "Change(\"acbc\")",
+                "Redo"
+            ]
+        );
+        assert!(controller.take().is_empty());
+    }
+
+    #[test]
+    fn test_on_find() {
+        let mut text_box = TextBox::new(None).on_find(Box::new(|spans| msg!(spans.len())));
+        let m = text_box.update(msg!(TextBoxMessage::Find(vec![(0, 1), (2, 3)])));
+        assert_eq!(m[0].downcast_ref::<usize>(), Some(&2));
+    }
+
+    #[test]
+    fn test_max_length_paste() {
+        let mut text = text_box_text(TabBehavior::FocusNext);
+        text.max_length = Some(5);
+        let cache = event::EventCache::new(1.0);
+        // Pasted text is cut short to fit
+        let mut event = event::Event::new(event::TextEntry("xyzw".to_string()), &cache);
+        text.on_text_entry(&mut event);
+        assert_eq!(text.state_ref().text, "axyzb");
+        assert_eq!(text.state_ref().cursor_pos, 4);
+        assert_eq!(
+            messages(&event.messages),
+            vec!["Change(\"axyzb\")"]
+        );
+
+        // Nothing more fits
+        let mut event = event::Event::new(event::TextEntry("!".to_string()), &cache);
+        text.on_text_entry(&mut event);
+        assert_eq!(text.state_ref().text, "axyzb");
+        assert!(event.messages.is_empty());
+
+        // Unless it replaces the selection, counting characters rather than bytes
+        text.state_mut().selection_from = Some(1);
+        text.insert_text("你好世界");
+        assert_eq!(text.state_ref().text, "a你好世b");
+        assert_eq!(text.state_ref().cursor_pos, 10);
+    }
+
+    #[test]
+    fn test_counter() {
+        let text_box = TextBox::new(Some("hello".to_string())).max_length(100);
+        assert_eq!(text_box.counter(), None);
+
+        let mut text_box = text_box.show_counter(true);
+        let (counter, _) = text_box.counter().unwrap();
+        assert_eq!(counter, "5/100");
+        text_box.update(msg!(TextBoxMessage::Change("hello!".to_string())));
+        assert_eq!(text_box.counter().unwrap().0, "6/100");
+
+        let text_box = TextBox::new(None).show_counter(true);
+        assert_eq!(text_box.counter().unwrap().0, "0");
+    }
+
+    #[test]
+    fn test_counter_warning() {
+        let counter_color: Color = current_style("TextBox", "counter_color").into();
+        let warning_color: Color = current_style("TextBox", "counter_warning_color").into();
+        assert_ne!(counter_color, warning_color);
+        let text_box = |max_length| {
+            TextBox::new(Some("hello".to_string()))
+                .max_length(max_length)
+                .show_counter(true)
+        };
+        // Eleven left, then ten
+        assert_eq!(text_box(16).counter().unwrap().1, counter_color);
+        assert_eq!(text_box(15).counter().unwrap().1, warning_color);
+        assert_eq!(text_box(5).counter().unwrap().1, warning_color);
+    }
+
+    #[test]
+    fn test_undo_max_depth() {
+        let mut history = UndoStack::new(2);
+        let mut text = String::new();
+        let start = Instant::now();
+        for (i, c) in "abcd".chars().enumerate() {
+            let at = start + Duration::from_millis(i as u64 * 1000);
+            type_char(&mut history, &mut text, c, at);
+        }
+        assert_eq!(history.undo.len(), 2);
+
+        text = history.undo(text).unwrap();
+        text = history.undo(text).unwrap();
+        assert_eq!(text, "ab");
+        assert_eq!(history.undo(text.clone()), None);
+    }
+}
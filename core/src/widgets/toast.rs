@@ -0,0 +1,571 @@
+use std::fmt;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use mctk_macros::{component, state_component_impl};
+
+use crate::component::{Component, ComponentHasher, Message, RenderContext};
+use crate::layout::{Alignment, Dimension, Direction, PositionType, Rect};
+use crate::renderables::{rect::InstanceBuilder as RectInstanceBuilder, Renderable};
+use crate::style::{CursorStyle, FontWeight, Styled};
+use crate::types::*;
+use crate::{event, lay, msg, node, rect, size, size_pct, txt, Node};
+
+use super::{Div, Text};
+
+/// Identifies a [`Toast`] that has been pushed to a [`ToastManager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ToastId(u64);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ToastVariant {
+    #[default]
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastVariant {
+    /// The style parameter of the background color of toasts of this variant.
+    fn background_color_param(self) -> &'static str {
+        match self {
+            ToastVariant::Info => "info_background_color",
+            ToastVariant::Success => "success_background_color",
+            ToastVariant::Warning => "warning_background_color",
+            ToastVariant::Error => "error_background_color",
+        }
+    }
+}
+
+/// The corner of the screen that a [`ToastManager`] stacks its toasts in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ToastPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+impl ToastPosition {
+    /// The position of the stack, `margin` away from the corner.
+    fn rect(self, margin: f32) -> Rect {
+        let px = Dimension::Px(margin as f64);
+        match self {
+            ToastPosition::TopLeft => Rect {
+                top: px,
+                left: px,
+                ..rect!(Auto)
+            },
+            ToastPosition::TopRight => Rect {
+                top: px,
+                right: px,
+                ..rect!(Auto)
+            },
+            ToastPosition::BottomLeft => Rect {
+                bottom: px,
+                left: px,
+                ..rect!(Auto)
+            },
+            ToastPosition::BottomRight => Rect {
+                bottom: px,
+                right: px,
+                ..rect!(Auto)
+            },
+        }
+    }
+
+    fn is_top(self) -> bool {
+        matches!(self, ToastPosition::TopLeft | ToastPosition::TopRight)
+    }
+
+    fn is_right(self) -> bool {
+        matches!(self, ToastPosition::TopRight | ToastPosition::BottomRight)
+    }
+}
+
+/// A short message, shown by a [`ToastManager`].
+pub struct Toast {
+    pub message: String,
+    pub variant: ToastVariant,
+    /// How long the toast is shown for, or until it is dismissed when `None`.
+    pub duration_ms: Option<u32>,
+    /// The label of a button on the toast, and what it does. Clicking it also dismisses the toast.
+    pub action: Option<(String, Box<dyn Fn() + Send + Sync>)>,
+}
+
+impl fmt::Debug for Toast {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Toast")
+            .field("message", &self.message)
+            .field("variant", &self.variant)
+            .field("duration_ms", &self.duration_ms)
+            .field("action", &self.action.as_ref().map(|(label, _)| label))
+            .finish()
+    }
+}
+
+impl Toast {
+    /// An info toast, shown for four seconds.
+    pub fn new<S: Into<String>>(message: S) -> Self {
+        Self {
+            message: message.into(),
+            variant: ToastVariant::default(),
+            duration_ms: Some(4000),
+            action: None,
+        }
+    }
+
+    pub fn variant(mut self, variant: ToastVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    pub fn duration_ms(mut self, duration_ms: Option<u32>) -> Self {
+        self.duration_ms = duration_ms;
+        self
+    }
+
+    pub fn action<S: Into<String>>(
+        mut self,
+        label: S,
+        action_fn: Box<dyn Fn() + Send + Sync>,
+    ) -> Self {
+        self.action = Some((label.into(), action_fn));
+        self
+    }
+}
+
+#[derive(Debug, Default)]
+struct ToastQueue {
+    /// The shown toasts, oldest first, with when they were pushed.
+    toasts: Vec<(ToastId, Toast, Instant)>,
+    next_id: u64,
+    /// Bumped whenever a toast is pushed or dismissed.
+    generation: u64,
+}
+
+impl ToastQueue {
+    fn push(&mut self, toast: Toast, now: Instant) -> ToastId {
+        let id = ToastId(self.next_id);
+        self.next_id += 1;
+        self.toasts.push((id, toast, now));
+        self.generation += 1;
+        id
+    }
+
+    fn dismiss(&mut self, id: ToastId) -> Option<Toast> {
+        let i = self.toasts.iter().position(|(t, _, _)| *t == id)?;
+        self.generation += 1;
+        Some(self.toasts.remove(i).1)
+    }
+
+    /// Dismiss the toasts that have been shown for their duration by `now`.
+    fn expire(&mut self, now: Instant) {
+        let len = self.toasts.len();
+        self.toasts.retain(|(_, toast, pushed)| {
+            toast.duration_ms.map_or(true, |ms| {
+                now.duration_since(*pushed) < Duration::from_millis(ms as u64)
+            })
+        });
+        if self.toasts.len() != len {
+            self.generation += 1;
+        }
+    }
+}
+
+#[derive(Debug)]
+enum ToastMsg {
+    Action(ToastId),
+    Dismiss(ToastId),
+    Swipe(ToastId, f32),
+    SwipeEnd(ToastId, f32),
+}
+
+#[derive(Debug, Default)]
+struct ToastManagerState {
+    /// The generation of the queue when it was last shown.
+    generation: u64,
+    /// The toast being swiped, and how far.
+    swipe: Option<(ToastId, f32)>,
+}
+
+/// Shows a stack of [`Toast`]s in a corner of the screen, newest nearest the corner.
+///
+/// Toasts are pushed with [`#push`][ToastManager#method.push], and are dismissed after their
+/// duration, by [`#dismiss`][ToastManager#method.dismiss], or by being swiped sideways past the
+/// `swipe_distance` style parameter. Clones of a manager share its toasts, as do all the managers
+/// from [`ToastManager::global`]. The manager covers the Node it is laid out in, so it is usually
+/// the last child of the root, with `size_pct: [100]` and `position_type: Absolute`.
+#[component(State = "ToastManagerState", Styled = "Toast", Internal)]
+pub struct ToastManager {
+    pub position: ToastPosition,
+    queue: Arc<Mutex<ToastQueue>>,
+}
+
+impl fmt::Debug for ToastManager {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ToastManager")
+            .field("position", &self.position)
+            .field("queue", &self.queue)
+            .finish()
+    }
+}
+
+impl Clone for ToastManager {
+    fn clone(&self) -> Self {
+        Self {
+            position: self.position,
+            queue: self.queue.clone(),
+            state: Some(ToastManagerState::default()),
+            dirty: false,
+            class: self.class,
+            style_overrides: self.style_overrides.clone(),
+        }
+    }
+}
+
+impl Default for ToastManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToastManager {
+    /// A manager with toasts of its own.
+    pub fn new() -> Self {
+        Self::with_queue(Default::default())
+    }
+
+    /// A manager of the toasts shared by the whole application.
+    pub fn global() -> Self {
+        static QUEUE: OnceLock<Arc<Mutex<ToastQueue>>> = OnceLock::new();
+        Self::with_queue(QUEUE.get_or_init(Default::default).clone())
+    }
+
+    fn with_queue(queue: Arc<Mutex<ToastQueue>>) -> Self {
+        Self {
+            position: ToastPosition::default(),
+            queue,
+            state: Some(ToastManagerState::default()),
+            dirty: false,
+            class: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    pub fn position(mut self, position: ToastPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Show `toast`, until it is dismissed.
+    pub fn push(&self, toast: Toast) -> ToastId {
+        self.queue.lock().unwrap().push(toast, Instant::now())
+    }
+
+    /// Stop showing the toast `id`, if it still is.
+    pub fn dismiss(&self, id: ToastId) {
+        self.queue.lock().unwrap().dismiss(id);
+    }
+
+    fn swipe_end(&mut self, id: ToastId, distance: f32) {
+        self.state_mut().swipe = None;
+        let swipe_distance = self.style_val("swipe_distance").unwrap().f32();
+        if distance.abs() >= swipe_distance {
+            self.dismiss(id);
+        }
+    }
+}
+
+#[state_component_impl(ToastManagerState)]
+impl Component for ToastManager {
+    fn init(&mut self) {
+        self.state_mut().generation = self.queue.lock().unwrap().generation;
+    }
+
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.position.hash(hasher);
+    }
+
+    fn on_tick(&mut self, _event: &mut event::Event<event::Tick>) {
+        let generation = {
+            let mut queue = self.queue.lock().unwrap();
+            queue.expire(Instant::now());
+            queue.generation
+        };
+        // Show the toasts again when they have changed
+        if self.state_ref().generation != generation {
+            self.state_mut().generation = generation;
+        }
+    }
+
+    fn update(&mut self, message: Message) -> Vec<Message> {
+        match message.downcast_ref::<ToastMsg>() {
+            Some(ToastMsg::Action(id)) => {
+                let toast = self.queue.lock().unwrap().dismiss(*id);
+                if let Some((_, action_fn)) = toast.and_then(|t| t.action) {
+                    action_fn();
+                }
+            }
+            Some(ToastMsg::Dismiss(id)) => self.dismiss(*id),
+            Some(ToastMsg::Swipe(id, distance)) => {
+                self.state_mut().swipe = Some((*id, *distance));
+            }
+            Some(ToastMsg::SwipeEnd(id, distance)) => self.swipe_end(*id, *distance),
+            None => return vec![message],
+        }
+        vec![]
+    }
+
+    fn view(&self) -> Option<Node> {
+        let margin: f32 = self.style_val("margin").unwrap().f32();
+        let gap: f32 = self.style_val("gap").unwrap().f32();
+        let max_width: f32 = self.style_val("max_width").unwrap().f32();
+
+        let mut stack = node!(
+            Div::new(),
+            lay![
+                direction: Direction::Column,
+                cross_alignment: if self.position.is_right() {
+                    Alignment::End
+                } else {
+                    Alignment::Start
+                },
+                position_type: PositionType::Absolute,
+                position: self.position.rect(margin),
+                gap: gap.into(),
+                z_index_increment: 1000.0
+            ]
+        );
+        let queue = self.queue.lock().unwrap();
+        let mut toasts: Vec<_> = queue.toasts.iter().collect();
+        if self.position.is_top() {
+            toasts.reverse();
+        }
+        for (id, toast, _) in toasts {
+            let offset = match self.state_ref().swipe {
+                Some((swiped, offset)) if swiped == *id => offset,
+                _ => 0.0,
+            };
+            stack = stack.push(
+                node!(
+                    ToastItem {
+                        id: *id,
+                        message: toast.message.clone(),
+                        variant: toast.variant,
+                        action: toast.action.as_ref().map(|(label, _)| label.clone()),
+                        class: self.class,
+                        style_overrides: self.style_overrides.clone(),
+                    },
+                    lay![
+                        max_size: [max_width, Auto],
+                        margin: [0.0, offset, 0.0, -offset]
+                    ]
+                )
+                .key(id.0),
+            );
+        }
+        Some(node!(Div::new(), lay![size_pct: [100]]).push(stack))
+    }
+}
+
+#[component(Styled = "Toast", Internal)]
+#[derive(Debug)]
+struct ToastItem {
+    id: ToastId,
+    message: String,
+    variant: ToastVariant,
+    action: Option<String>,
+}
+
+impl Component for ToastItem {
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.message.hash(hasher);
+        self.variant.hash(hasher);
+        self.action.hash(hasher);
+    }
+
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        event.stop_bubbling();
+    }
+
+    fn on_drag_start(&mut self, event: &mut event::Event<event::DragStart>) {
+        event.stop_bubbling();
+    }
+
+    fn on_drag(&mut self, event: &mut event::Event<event::Drag>) {
+        event.emit(msg!(ToastMsg::Swipe(self.id, event.logical_delta().x)));
+    }
+
+    fn on_drag_end(&mut self, event: &mut event::Event<event::DragEnd>) {
+        event.stop_bubbling();
+        event.emit(msg!(ToastMsg::SwipeEnd(self.id, event.logical_delta().x)));
+    }
+
+    fn on_touch_drag_start(&mut self, event: &mut event::Event<event::TouchDragStart>) {
+        event.stop_bubbling();
+    }
+
+    fn on_touch_drag(&mut self, event: &mut event::Event<event::TouchDrag>) {
+        event.emit(msg!(ToastMsg::Swipe(self.id, event.logical_delta().x)));
+    }
+
+    fn on_touch_drag_end(&mut self, event: &mut event::Event<event::TouchDragEnd>) {
+        event.stop_bubbling();
+        event.emit(msg!(ToastMsg::SwipeEnd(self.id, event.logical_delta().x)));
+    }
+
+    fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
+        let radius: f32 = self.style_val("radius").unwrap().f32();
+        let instance_data = RectInstanceBuilder::default()
+            .pos(context.aabb.pos)
+            .scale(context.aabb.size())
+            .color(self.style_val(self.variant.background_color_param()).into())
+            .radius((radius, radius, radius, radius))
+            .shadow(self.style_val("shadow").map(|v| v.box_shadow()))
+            .build()
+            .unwrap();
+        Some(vec![Renderable::Rect(
+            crate::renderables::Rect::from_instance_data(instance_data),
+        )])
+    }
+
+    fn view(&self) -> Option<Node> {
+        let padding: f32 = self.style_val("padding").unwrap().f32();
+        let font_size = self.style_val("font_size").unwrap();
+        let mut base = node!(
+            Div::new(),
+            lay![
+                direction: Direction::Row,
+                cross_alignment: Alignment::Center,
+                padding: [padding],
+                gap: padding.into()
+            ]
+        )
+        .push(node!(Text::new(txt!(self.message.clone()))
+            .style("size", font_size.clone())
+            .style("color", self.style_val("text_color").unwrap())
+            .maybe_style("font", self.style_val("font"))));
+        if let Some(label) = &self.action {
+            base = base.push(node!(ToastAction {
+                id: self.id,
+                label: label.clone(),
+                font_size: font_size.f32(),
+                color: self.style_val("action_color").into(),
+            }));
+        }
+        Some(base)
+    }
+}
+
+/// The action button of a [`ToastItem`].
+#[derive(Debug)]
+struct ToastAction {
+    id: ToastId,
+    label: String,
+    font_size: f32,
+    color: Color,
+}
+
+impl Component for ToastAction {
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.label.hash(hasher);
+        self.color.hash(hasher);
+    }
+
+    fn cursor(&self) -> Option<CursorStyle> {
+        Some(CursorStyle::Pointer)
+    }
+
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        event.stop_bubbling();
+        event.emit(msg!(ToastMsg::Action(self.id)));
+    }
+
+    fn view(&self) -> Option<Node> {
+        Some(node!(Text::new(txt!(self.label.clone()))
+            .style("size", self.font_size)
+            .style("color", self.color)
+            .style("font_weight", FontWeight::Bold)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    fn ids(manager: &ToastManager) -> Vec<ToastId> {
+        let queue = manager.queue.lock().unwrap();
+        queue.toasts.iter().map(|(id, _, _)| *id).collect()
+    }
+
+    #[test]
+    fn test_queue() {
+        let mut queue = ToastQueue::default();
+        let start = Instant::now();
+        let short = queue.push(Toast::new("Saved"), start);
+        let persistent = queue.push(Toast::new("Offline").duration_ms(None), start);
+        let long = queue.push(Toast::new("Synced").duration_ms(Some(10_000)), start);
+        assert_ne!(short, persistent);
+
+        queue.expire(start + Duration::from_secs(5));
+        let ids: Vec<_> = queue.toasts.iter().map(|(id, _, _)| *id).collect();
+        assert_eq!(ids, vec![persistent, long]);
+
+        let generation = queue.generation;
+        assert!(queue.dismiss(persistent).is_some());
+        assert!(queue.dismiss(persistent).is_none());
+        assert_eq!(queue.generation, generation + 1);
+    }
+
+    #[test]
+    fn test_global() {
+        let id = ToastManager::global().push(Toast::new("Hello"));
+        assert!(ids(&ToastManager::global()).contains(&id));
+        ToastManager::global().dismiss(id);
+        assert!(!ids(&ToastManager::global()).contains(&id));
+        // Other managers have their own toasts
+        assert!(ids(&ToastManager::new()).is_empty());
+    }
+
+    #[test]
+    fn test_swipe() {
+        let mut manager = ToastManager::new().style("swipe_distance", 80.0);
+        let id = manager.push(Toast::new("Hello"));
+        manager.update(msg!(ToastMsg::Swipe(id, -30.0)));
+        assert_eq!(manager.state_ref().swipe, Some((id, -30.0)));
+
+        // Not far enough, so it springs back
+        manager.update(msg!(ToastMsg::SwipeEnd(id, -30.0)));
+        assert_eq!(manager.state_ref().swipe, None);
+        assert_eq!(ids(&manager), vec![id]);
+
+        manager.update(msg!(ToastMsg::SwipeEnd(id, 100.0)));
+        assert!(ids(&manager).is_empty());
+    }
+
+    #[test]
+    fn test_action() {
+        static CALLED: AtomicBool = AtomicBool::new(false);
+        let mut manager = ToastManager::new();
+        let id = manager.push(
+            Toast::new("Deleted").action("Undo", Box::new(|| CALLED.store(true, Ordering::SeqCst))),
+        );
+        assert!(manager.update(msg!(ToastMsg::Action(id))).is_empty());
+        assert!(CALLED.load(Ordering::SeqCst));
+        assert!(ids(&manager).is_empty());
+    }
+
+    #[test]
+    fn test_position() {
+        let rect = ToastPosition::TopRight.rect(16.0);
+        assert_eq!(rect.top, Dimension::Px(16.0));
+        assert_eq!(rect.right, Dimension::Px(16.0));
+        assert_eq!(rect.left, Dimension::Auto);
+        assert!(!ToastPosition::BottomLeft.is_right());
+    }
+}
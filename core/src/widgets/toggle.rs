@@ -1,11 +1,13 @@
 use std::fmt;
 use std::hash::Hash;
+use std::time::Instant;
 
+use crate::animation::SpringAnimator;
 use crate::component::{Component, ComponentHasher, Message};
 use crate::layout::{Alignment, PositionType};
 use crate::{event, lay, node, rect, size, txt, Color};
 
-use crate::style::{FontWeight, Styled};
+use crate::style::{CursorStyle, FontWeight, Styled};
 use mctk_macros::{component, state_component_impl};
 
 use super::{Div, Text};
@@ -13,6 +15,10 @@ use super::{Div, Text};
 #[derive(Debug, Default)]
 struct ToggleState {
     pressed: bool,
+    /// The left of the thumb while it slides. `None` when it is at rest.
+    thumb_x: Option<f32>,
+    thumb_spring: Option<SpringAnimator>,
+    last_tick: Option<Instant>,
 }
 
 #[derive(Debug)]
@@ -26,12 +32,15 @@ pub struct Toggle {
     active: bool,
     toggle_type: ToggleType,
     on_change: Option<Box<dyn Fn(bool) -> Message + Send + Sync>>,
+    /// Slide the thumb between positions, rather than moving it straight to the new one.
+    pub animate: bool,
 }
 
 impl fmt::Debug for Toggle {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Toggle")
             .field("active", &self.active)
+            .field("animate", &self.animate)
             .finish()
     }
 }
@@ -42,7 +51,11 @@ impl Toggle {
             active,
             toggle_type: ToggleType::Type2,
             on_change: None,
-            state: Some(ToggleState { pressed: active }),
+            animate: true,
+            state: Some(ToggleState {
+                pressed: active,
+                ..Default::default()
+            }),
             dirty: false,
             class: Default::default(),
             style_overrides: Default::default(),
@@ -59,6 +72,57 @@ impl Toggle {
         self
     }
 
+    pub fn animate(mut self, animate: bool) -> Self {
+        self.animate = animate;
+        self
+    }
+
+    /// The left of the thumb when it is at rest in the `active` position.
+    fn thumb_rest_x(&self, active: bool) -> f32 {
+        match (&self.toggle_type, active) {
+            (ToggleType::Type1, false) => 2.0,
+            (ToggleType::Type1, true) => 25.0,
+            (ToggleType::Type2, false) => 0.0,
+            (ToggleType::Type2, true) => 42.0,
+        }
+    }
+
+    /// The current left of the thumb.
+    fn thumb_x(&self) -> f32 {
+        self.state_ref()
+            .thumb_x
+            .unwrap_or_else(|| self.thumb_rest_x(self.state_ref().pressed))
+    }
+
+    fn set_pressed(&mut self, pressed: bool) {
+        if pressed == self.state_ref().pressed {
+            return;
+        }
+        let from = self.thumb_x();
+        let to = self.thumb_rest_x(pressed);
+        self.state_mut().pressed = pressed;
+        if self.animate {
+            self.state_mut().thumb_x = Some(from);
+            self.state_mut().thumb_spring = Some(SpringAnimator::preset_snappy().start(from, to));
+            self.state_mut().last_tick = None;
+        } else {
+            self.state_mut().thumb_x = None;
+            self.state_mut().thumb_spring = None;
+        }
+    }
+
+    /// Slide the thumb for `dt` seconds.
+    fn step_animation(&mut self, dt: f32) {
+        let state = self.state_mut();
+        if let Some(spring) = state.thumb_spring.as_mut() {
+            state.thumb_x = Some(spring.step(dt));
+            if spring.is_settled() {
+                state.thumb_x = None;
+                state.thumb_spring = None;
+            }
+        }
+    }
+
     fn toggle_type_2(&self) -> Option<crate::Node> {
         let background_color: Color = self.style_val("background_color").into();
         let active_color: Color = self.style_val("active_color").into();
@@ -113,7 +177,7 @@ impl Toggle {
             }),
             lay![
                 position_type: PositionType::Absolute,
-                position:  if active {rect!(0., Auto, 0., 0.)} else {rect!(0., 0., 0., 0.)},
+                position: rect!(0., self.thumb_x(), 0., Auto),
                 size: [38., 38.]
             ]
         );
@@ -183,8 +247,8 @@ impl Toggle {
                 .border(Color::TRANSPARENT, 1., (50., 50., 50., 50.)),
             lay![
                 position_type: PositionType::Absolute,
-                position:  if active {rect!(0., Auto, 0., 0.)} else {rect!(0., 0., 0., 0.)},
-                margin: if active  {rect![5., 0., 0., 5.]} else {rect![5., 2., 0., 0.]},
+                position: rect!(0., self.thumb_x(), 0., Auto),
+                margin: [5., 0., 0., 0.],
                 size: [28., 28.]
             ]
         );
@@ -198,16 +262,34 @@ impl Toggle {
 
 #[state_component_impl(ToggleState)]
 impl Component for Toggle {
+    fn cursor(&self) -> Option<CursorStyle> {
+        self.style_val("cursor").map(|v| v.cursor())
+    }
+
     // fn on_mouse_leave(&mut self, _event: &mut event::Event<event::MouseLeave>) {
     //     self.state_mut().pressed = false;
     // }
 
     fn on_mouse_down(&mut self, _event: &mut event::Event<event::MouseDown>) {
-        self.state_mut().pressed = !self.state_ref().pressed;
+        self.set_pressed(!self.state_ref().pressed);
     }
 
     fn on_touch_down(&mut self, _event: &mut event::Event<event::TouchDown>) {
-        self.state_mut().pressed = !self.state_ref().pressed;
+        self.set_pressed(!self.state_ref().pressed);
+    }
+
+    fn on_tick(&mut self, _event: &mut event::Event<event::Tick>) {
+        if self.state_ref().thumb_spring.is_none() {
+            return;
+        }
+        let now = Instant::now();
+        let dt = self
+            .state_ref()
+            .last_tick
+            .map(|t| now.duration_since(t).as_secs_f32())
+            .unwrap_or(0.0);
+        self.state_mut().last_tick = Some(now);
+        self.step_animation(dt);
     }
 
     // fn on_mouse_up(&mut self, _event: &mut event::Event<event::MouseUp>) {
@@ -230,6 +312,7 @@ impl Component for Toggle {
     fn render_hash(&self, hasher: &mut ComponentHasher) {
         self.active.hash(hasher);
         self.state_ref().pressed.hash(hasher);
+        (self.thumb_x().round() as i32).hash(hasher);
     }
 
     fn props_hash(&self, hasher: &mut ComponentHasher) {
@@ -237,7 +320,7 @@ impl Component for Toggle {
     }
 
     fn new_props(&mut self) {
-        self.state_mut().pressed = self.active;
+        self.set_pressed(self.active);
     }
 
     fn view(&self) -> Option<crate::Node> {
@@ -248,3 +331,32 @@ impl Component for Toggle {
         base
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thumb_animation() {
+        let mut toggle = Toggle::new(false);
+        assert_eq!(toggle.thumb_x(), 0.0);
+
+        toggle.set_pressed(true);
+        toggle.step_animation(1.0 / 60.0);
+        let x = toggle.thumb_x();
+        assert!(x > 0.0 && x < 42.0);
+
+        toggle.step_animation(0.2 - 1.0 / 60.0);
+        assert!((toggle.thumb_x() - 42.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_without_animation() {
+        let mut toggle = Toggle::new(true)
+            .toggle_type(ToggleType::Type1)
+            .animate(false);
+        toggle.set_pressed(false);
+        assert_eq!(toggle.thumb_x(), 2.0);
+        assert!(toggle.state_ref().thumb_spring.is_none());
+    }
+}
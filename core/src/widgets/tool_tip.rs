@@ -0,0 +1,250 @@
+use std::hash::Hash;
+use std::time::Instant;
+
+use crate::component::{Component, ComponentHasher, RenderContext};
+use crate::layout::{Dimension, PositionType, Rect};
+use crate::renderables::{Polygon, Renderable};
+use crate::style::Styled;
+use crate::{lay, node, rect, txt, Color, Node, Point, AABB};
+use mctk_macros::component;
+
+use super::{RoundedRect, Text};
+
+/// Where a [`ToolTip`] is placed relative to the component it describes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TooltipPlacement {
+    /// Next to the mouse cursor.
+    #[default]
+    Auto,
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// A short text label, shown over other content. Styled by the `ToolTip` style keys.
+///
+/// An arrow of `arrow_size` points from the tool tip towards the component it describes.
+#[component(Styled, Internal)]
+#[derive(Debug)]
+pub struct ToolTip {
+    pub tool_tip: String,
+    pub placement: TooltipPlacement,
+}
+
+impl ToolTip {
+    /// How long the mouse has to hover before a tool tip is shown, in milliseconds.
+    pub const DELAY: u128 = 1000;
+    /// Offset from the mouse position for [`TooltipPlacement::Auto`].
+    pub const MOUSE_OFFSET: Point = Point { x: 1.0, y: 14.0 };
+
+    pub fn new<S: Into<String>>(tool_tip: S) -> Self {
+        Self {
+            tool_tip: tool_tip.into(),
+            placement: TooltipPlacement::default(),
+            class: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    pub fn placement(mut self, placement: TooltipPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// The corners of the arrow of a tool tip that covers `aabb`, tip first. The arrow is an
+    /// equilateral triangle with sides of `size`, on the edge that faces the described component.
+    pub fn arrow(placement: TooltipPlacement, aabb: AABB, size: f32) -> [Point; 3] {
+        let height = size * 3.0_f32.sqrt() / 2.0;
+        let half = size / 2.0;
+        let AABB {
+            pos, bottom_right, ..
+        } = aabb;
+        let center = Point::new(
+            (pos.x + bottom_right.x) / 2.0,
+            (pos.y + bottom_right.y) / 2.0,
+        );
+        match placement {
+            // Next to the mouse, which is above the top left corner
+            TooltipPlacement::Auto => {
+                let x = pos.x + size;
+                [
+                    Point::new(x, pos.y - height),
+                    Point::new(x - half, pos.y),
+                    Point::new(x + half, pos.y),
+                ]
+            }
+            TooltipPlacement::Top => [
+                Point::new(center.x, bottom_right.y + height),
+                Point::new(center.x - half, bottom_right.y),
+                Point::new(center.x + half, bottom_right.y),
+            ],
+            TooltipPlacement::Bottom => [
+                Point::new(center.x, pos.y - height),
+                Point::new(center.x - half, pos.y),
+                Point::new(center.x + half, pos.y),
+            ],
+            TooltipPlacement::Left => [
+                Point::new(bottom_right.x + height, center.y),
+                Point::new(bottom_right.x, center.y - half),
+                Point::new(bottom_right.x, center.y + half),
+            ],
+            TooltipPlacement::Right => [
+                Point::new(pos.x - height, center.y),
+                Point::new(pos.x, center.y - half),
+                Point::new(pos.x, center.y + half),
+            ],
+        }
+    }
+
+    /// Whether a tool tip should be open, given when hovering started.
+    pub fn is_due(hover_start: Option<Instant>, now: Instant, delay_ms: u128) -> bool {
+        hover_start
+            .map(|s| now.duration_since(s).as_millis() > delay_ms)
+            .unwrap_or(false)
+    }
+
+    /// The absolute position of a tool tip, relative to the component that it describes.
+    /// `mouse` is the relative logical position of the mouse when the tool tip was opened.
+    pub fn position(placement: TooltipPlacement, mouse: Point) -> Rect {
+        let px = |v: f32| Dimension::Px(v as f64);
+        let full = Dimension::Pct(100.0);
+        match placement {
+            TooltipPlacement::Auto => (mouse + Self::MOUSE_OFFSET).into(),
+            TooltipPlacement::Top => Rect {
+                left: px(0.0),
+                bottom: full,
+                ..rect!(Auto)
+            },
+            TooltipPlacement::Bottom => Rect {
+                left: px(0.0),
+                top: full,
+                ..rect!(Auto)
+            },
+            TooltipPlacement::Left => Rect {
+                right: full,
+                top: px(0.0),
+                ..rect!(Auto)
+            },
+            TooltipPlacement::Right => Rect {
+                left: full,
+                top: px(0.0),
+                ..rect!(Auto)
+            },
+        }
+    }
+}
+
+impl Component for ToolTip {
+    fn render_hash(&self, hasher: &mut ComponentHasher) {
+        self.placement.hash(hasher);
+        let arrow_size: f32 = self.style_val("arrow_size").unwrap().f32();
+        ((arrow_size * 100.0) as i32).hash(hasher);
+        Color::from(self.style_val("background_color")).hash(hasher);
+        Color::from(self.style_val("border_color")).hash(hasher);
+    }
+
+    fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
+        let arrow_size = self.style_val("arrow_size").unwrap().f32() * context.scale_factor;
+        if arrow_size <= 0.0 {
+            return None;
+        }
+        let border_width = self.style_val("border_width").unwrap().f32() * context.scale_factor;
+        let mut arrow = Polygon::new(
+            Self::arrow(self.placement, context.aabb, arrow_size).to_vec(),
+            self.style_val("background_color").into(),
+        );
+        arrow.instance_data.border_color = Some(self.style_val("border_color").into());
+        arrow.instance_data.border_width = border_width;
+        Some(vec![Renderable::Polygon(arrow)])
+    }
+
+    fn view(&self) -> Option<Node> {
+        let background_color: Color = self.style_val("background_color").into();
+        let border_color: Color = self.style_val("border_color").into();
+        let border_width: f32 = self.style_val("border_width").unwrap().f32();
+        let padding: f64 = self.style_val("padding").unwrap().into();
+
+        Some(
+            node!(
+                RoundedRect {
+                    background_color,
+                    border_color,
+                    border_width: (border_width, border_width, border_width, border_width),
+                    ..Default::default()
+                },
+                lay![padding: rect!(padding)]
+            )
+            .push(node!(Text::new(txt!(self.tool_tip.clone()))
+                .style("size", self.style_val("font_size").unwrap())
+                .style("color", self.style_val("text_color").unwrap())
+                .maybe_style("font", self.style_val("font")))),
+        )
+    }
+}
+
+/// Wrap `node` so that `tool_tip` is shown next to it, at `placement`.
+pub(crate) fn with_tool_tip(
+    node: Node,
+    tool_tip: &str,
+    placement: TooltipPlacement,
+    mouse: Point,
+) -> Node {
+    node.push(node!(
+        ToolTip::new(tool_tip).placement(placement),
+        lay![
+            position_type: PositionType::Absolute,
+            z_index_increment: 1000.0,
+            position: ToolTip::position(placement, mouse),
+        ]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_is_due() {
+        let start = Instant::now();
+        let later = |ms| start + Duration::from_millis(ms);
+        assert!(!ToolTip::is_due(None, later(5000), ToolTip::DELAY));
+        assert!(!ToolTip::is_due(Some(start), later(500), ToolTip::DELAY));
+        assert!(ToolTip::is_due(Some(start), later(1500), ToolTip::DELAY));
+    }
+
+    #[test]
+    fn test_position() {
+        let mouse = Point::new(10.0, 10.0);
+        let auto: Rect = Point::new(11.0, 24.0).into();
+        assert_eq!(ToolTip::position(TooltipPlacement::Auto, mouse), auto);
+        let top = ToolTip::position(TooltipPlacement::Top, mouse);
+        assert_eq!(top.bottom, Dimension::Pct(100.0));
+        assert_eq!(top.top, Dimension::Auto);
+    }
+
+    #[test]
+    fn test_arrow() {
+        let aabb = AABB::new(crate::Pos::new(10.0, 10.0, 0.0), crate::Scale::new(100.0, 40.0));
+        let height = 8.0 * 3.0_f32.sqrt() / 2.0;
+
+        // Above the component: on the bottom edge, pointing down
+        let [tip, a, b] = ToolTip::arrow(TooltipPlacement::Top, aabb, 8.0);
+        assert_eq!((a.y, b.y), (50.0, 50.0));
+        assert_eq!(tip, Point::new(60.0, 50.0 + height));
+        assert_eq!(a.dist(b), 8.0);
+
+        // Flipped below the component: on the top edge, pointing up
+        let [tip, a, b] = ToolTip::arrow(TooltipPlacement::Bottom, aabb, 8.0);
+        assert_eq!((a.y, b.y), (10.0, 10.0));
+        assert_eq!(tip, Point::new(60.0, 10.0 - height));
+
+        let [tip, a, _] = ToolTip::arrow(TooltipPlacement::Left, aabb, 8.0);
+        assert_eq!(a.x, 110.0);
+        assert!(tip.x > a.x);
+        let [tip, a, _] = ToolTip::arrow(TooltipPlacement::Right, aabb, 8.0);
+        assert_eq!(a.x, 10.0);
+        assert!(tip.x < a.x);
+    }
+}
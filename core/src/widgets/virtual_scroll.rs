@@ -0,0 +1,335 @@
+use std::fmt;
+use std::hash::Hash;
+use std::ops::Range;
+
+use mctk_macros::{component, state_component_impl};
+
+use crate::component::{Component, ComponentHasher, Message};
+use crate::layout::{Dimension, PositionType, Size};
+use crate::style::Styled;
+use crate::types::*;
+use crate::{lay, msg, node, rect, size, size_pct, Node};
+
+use super::{Div, ScrollOffset};
+
+/// The heights of the items of a [`VirtualScroll`].
+pub enum ItemHeight {
+    Fixed(f32),
+    /// The height of each item, by its index.
+    Variable(Box<dyn Fn(usize) -> f32 + Send + Sync>),
+}
+
+impl fmt::Debug for ItemHeight {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ItemHeight::Fixed(height) => f.debug_tuple("Fixed").field(height).finish(),
+            ItemHeight::Variable(_) => f.write_str("Variable"),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum VirtualScrollMsg {
+    Scrolled(Point),
+}
+
+#[derive(Debug)]
+struct VirtualScrollState {
+    /// How far the items are scrolled, in logical pixels.
+    offset: f32,
+    /// The logical height of the viewport, once it has been laid out.
+    viewport_height: Option<f32>,
+    scale_factor: f32,
+}
+
+impl Default for VirtualScrollState {
+    fn default() -> Self {
+        Self {
+            offset: 0.0,
+            viewport_height: None,
+            scale_factor: 1.0,
+        }
+    }
+}
+
+/// A vertically scrolled list of `item_count` items, of which only the ones in view, and
+/// `overscan` more on either side, are made.
+///
+/// Items are made by `render_item` from their index, and are as tall as `item_height` says, so
+/// that the list can be measured without making them. The list is scrolled by a [`Div`], so the
+/// style is that of `Scroll`.
+#[component(State = "VirtualScrollState", Styled = "Scroll", Internal)]
+pub struct VirtualScroll {
+    pub item_count: usize,
+    pub item_height: ItemHeight,
+    pub render_item: Box<dyn Fn(usize) -> Box<dyn Component + Send + Sync> + Send + Sync>,
+    pub overscan: usize,
+}
+
+impl fmt::Debug for VirtualScroll {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("VirtualScroll")
+            .field("item_count", &self.item_count)
+            .field("item_height", &self.item_height)
+            .field("overscan", &self.overscan)
+            .finish()
+    }
+}
+
+impl VirtualScroll {
+    pub fn new(
+        item_count: usize,
+        item_height: ItemHeight,
+        render_item: Box<dyn Fn(usize) -> Box<dyn Component + Send + Sync> + Send + Sync>,
+    ) -> Self {
+        Self {
+            item_count,
+            item_height,
+            render_item,
+            overscan: 3,
+            state: Some(VirtualScrollState::default()),
+            dirty: false,
+            class: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    pub fn overscan(mut self, overscan: usize) -> Self {
+        self.overscan = overscan;
+        self
+    }
+
+    /// How far the items are scrolled, in logical pixels.
+    pub fn offset(&self) -> f32 {
+        self.state_ref().offset
+    }
+
+    /// Scroll `px` logical pixels from the top.
+    pub fn scroll_to_offset(&mut self, px: f32) {
+        let offset = self.constrain(px);
+        if offset != self.state_ref().offset {
+            self.state_mut().offset = offset;
+        }
+    }
+
+    /// Scroll the item `index` to the top, or as near as the end of the list allows.
+    pub fn scroll_to_index(&mut self, index: usize) {
+        let offsets = self.offsets();
+        let index = index.min(self.item_count);
+        self.scroll_to_offset(offsets[index]);
+    }
+
+    /// The top of each item, and the bottom of the last one, which is the height of the list.
+    fn offsets(&self) -> Vec<f32> {
+        let mut offsets = Vec::with_capacity(self.item_count + 1);
+        let mut top = 0.0;
+        offsets.push(top);
+        for i in 0..self.item_count {
+            top += self.height_of(i);
+            offsets.push(top);
+        }
+        offsets
+    }
+
+    fn height_of(&self, index: usize) -> f32 {
+        match &self.item_height {
+            ItemHeight::Fixed(height) => *height,
+            ItemHeight::Variable(height_fn) => height_fn(index),
+        }
+    }
+
+    fn total_height(&self) -> f32 {
+        match &self.item_height {
+            ItemHeight::Fixed(height) => height * self.item_count as f32,
+            ItemHeight::Variable(_) => self.offsets()[self.item_count],
+        }
+    }
+
+    /// `offset` clamped to the scrollable range, once the viewport is known.
+    fn constrain(&self, offset: f32) -> f32 {
+        let max = match self.state_ref().viewport_height {
+            Some(height) => (self.total_height() - height).max(0.0),
+            None => self.total_height(),
+        };
+        offset.min(max).max(0.0)
+    }
+
+    /// The items to make, from `offsets`: those overlapping the viewport, and the overscan.
+    fn visible_range(&self, offsets: &[f32]) -> Range<usize> {
+        let top = self.state_ref().offset;
+        // Until it is laid out, assume the viewport is as tall as the window could be
+        let bottom = top + self.state_ref().viewport_height.unwrap_or(2000.0);
+        // The first item that ends below the top, and the first that starts at or below the bottom
+        let first = offsets[1..].partition_point(|end| *end <= top);
+        let last = offsets[..self.item_count].partition_point(|start| *start < bottom);
+        first.saturating_sub(self.overscan)..(last + self.overscan).min(self.item_count)
+    }
+}
+
+#[state_component_impl(VirtualScrollState)]
+impl Component for VirtualScroll {
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.item_count.hash(hasher);
+        self.overscan.hash(hasher);
+        if let ItemHeight::Fixed(height) = self.item_height {
+            height.to_bits().hash(hasher);
+        }
+    }
+
+    fn new_props(&mut self) {
+        // Fewer items may not reach as far
+        let offset = self.constrain(self.state_ref().offset);
+        self.state_mut().offset = offset;
+    }
+
+    fn full_control(&self) -> bool {
+        true
+    }
+
+    fn set_aabb(
+        &mut self,
+        aabb: &mut AABB,
+        _parent_aabb: AABB,
+        _children: Vec<(&mut AABB, Option<Scale>, Option<Point>)>,
+        _frame: AABB,
+        scale_factor: f32,
+    ) {
+        let viewport_height = Some(aabb.height() / scale_factor);
+        if self.state_ref().viewport_height != viewport_height
+            || self.state_ref().scale_factor != scale_factor
+        {
+            self.state_mut().viewport_height = viewport_height;
+            self.state_mut().scale_factor = scale_factor;
+        }
+    }
+
+    fn update(&mut self, message: Message) -> Vec<Message> {
+        match message.downcast_ref::<VirtualScrollMsg>() {
+            Some(VirtualScrollMsg::Scrolled(position)) => {
+                let offset = position.y / self.state_ref().scale_factor;
+                self.scroll_to_offset(offset);
+                vec![]
+            }
+            None => vec![message],
+        }
+    }
+
+    fn view(&self) -> Option<Node> {
+        let offsets = self.offsets();
+        let scale_factor = self.state_ref().scale_factor;
+        let mut scroller = Div::new();
+        *scroller.class_mut() = self.class;
+        *scroller.style_overrides_mut() = self.style_overrides.clone();
+        let scroller = scroller
+            .scroll_y()
+            .with_scroll_offset(ScrollOffset {
+                x: 0.0,
+                y: (self.state_ref().offset * scale_factor).round(),
+            })
+            .on_scroll(Box::new(|p| msg!(VirtualScrollMsg::Scrolled(p))));
+
+        let mut items = node!(
+            Div::new(),
+            lay![size: Size {
+                width: Dimension::Pct(100.0),
+                height: Dimension::Px(offsets[self.item_count] as f64),
+            }]
+        );
+        for i in self.visible_range(&offsets) {
+            items = items.push(Node::new(
+                (self.render_item)(i),
+                i as u64,
+                lay![
+                    position_type: PositionType::Absolute,
+                    position: [offsets[i], 0.0, Auto, Auto],
+                    size: Size {
+                        width: Dimension::Pct(100.0),
+                        height: Dimension::Px((offsets[i + 1] - offsets[i]) as f64),
+                    }
+                ],
+            ));
+        }
+        Some(node!(scroller, lay![size_pct: [100]]).push(items))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn list(item_count: usize, item_height: ItemHeight) -> VirtualScroll {
+        let mut list =
+            VirtualScroll::new(item_count, item_height, Box::new(|_| Box::new(Div::new())))
+                .overscan(2);
+        list.state_mut().viewport_height = Some(100.0);
+        list
+    }
+
+    #[test]
+    fn test_visible_range() {
+        let mut list = list(100, ItemHeight::Fixed(20.0));
+        let offsets = list.offsets();
+        // Five items fill the viewport, and two more follow
+        assert_eq!(list.visible_range(&offsets), 0..7);
+
+        list.scroll_to_offset(430.0);
+        assert_eq!(list.visible_range(&offsets), 19..29);
+        list.scroll_to_index(99);
+        assert_eq!(list.offset(), 1900.0);
+        assert_eq!(list.visible_range(&offsets), 93..100);
+
+        list.scroll_to_offset(-10.0);
+        assert_eq!(list.offset(), 0.0);
+    }
+
+    #[test]
+    fn test_variable_heights() {
+        // Even items are 10px, and odd ones 30px
+        let mut list = list(
+            10,
+            ItemHeight::Variable(Box::new(|i| if i % 2 == 0 { 10.0 } else { 30.0 })),
+        )
+        .overscan(0);
+        assert_eq!(list.total_height(), 200.0);
+
+        list.scroll_to_index(3);
+        assert_eq!(list.offset(), 50.0);
+        let offsets = list.offsets();
+        assert_eq!(list.visible_range(&offsets), 3..8);
+    }
+
+    #[test]
+    fn test_scrolled() {
+        let mut list = list(100, ItemHeight::Fixed(20.0));
+        list.state_mut().scale_factor = 2.0;
+        assert!(list
+            .update(msg!(VirtualScrollMsg::Scrolled(Point::new(0.0, 300.0))))
+            .is_empty());
+        assert_eq!(list.offset(), 150.0);
+    }
+
+    #[test]
+    fn test_10_000_items() {
+        let made = Arc::new(AtomicUsize::new(0));
+        let counter = made.clone();
+        let mut list = VirtualScroll::new(
+            10_000,
+            ItemHeight::Fixed(24.0),
+            Box::new(move |_| {
+                counter.fetch_add(1, Ordering::Relaxed);
+                Box::new(Div::new())
+            }),
+        );
+        list.state_mut().viewport_height = Some(600.0);
+
+        for i in 0..100 {
+            list.scroll_to_index(i * 100);
+            let node = list.view().unwrap();
+            // 25 rows in view, and 3 of overscan on either side
+            assert!(node.children[0].children.len() <= 32);
+        }
+        assert!(made.load(Ordering::Relaxed) <= 100 * 32);
+    }
+}
@@ -40,6 +40,10 @@ pub trait Window: HasRawWindowHandle + HasRawDisplayHandle + Send + Sync + Any {
     /// - "SizeNS"
     /// - "SizeNESW"
     /// - "SizeWE"
+    /// - "Progress"
+    /// - "Wait"
+    ///
+    /// These are the [`CursorStyle#name`][crate::style::CursorStyle#method.name]s.
     fn set_cursor(&self, _cursor_type: &str) {}
 
     /// Reset the cursor to the default pointer.
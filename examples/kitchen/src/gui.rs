@@ -102,7 +102,7 @@ impl Component for Kitchen {
             Pages::Toggles => node!(Toggles {}, lay![size_pct:[100]]),
             Pages::Textboxes => node!(TextBoxes {}, lay![size_pct:[100]]),
             Pages::SlideBars => node!(SlideBars {}, lay![size_pct:[100]]),
-            Pages::Scrollables => node!(Scrollables {}, lay![size_pct:[100]]),
+            Pages::Scrollables => node!(Scrollables::default(), lay![size_pct:[100]]),
         };
         Some(screen)
     }
@@ -1,15 +1,57 @@
+use std::hash::Hash;
+
+use mctk_core::component::{self, ComponentHasher};
 use mctk_core::layout::Alignment;
 use mctk_core::style::Styled;
-use mctk_core::widgets::{Button, IconButton, IconType, Scrollable, Text};
+use mctk_core::widgets::{Button, IconButton, IconType, ScrollController, Scrollable, Text};
 use mctk_core::{component::Component, node, widgets::Div, Color};
 use mctk_core::{lay, msg, rect, size, size_pct, txt};
+use mctk_macros::{component, state_component_impl};
 
 use crate::gui::Message;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+enum ChatMessage {
+    Send,
+    ScrollToTop,
+}
+
+#[derive(Debug, Default)]
+pub struct ScrollablesState {
+    messages: usize,
+    chat: ScrollController,
+}
+
+#[component(State = "ScrollablesState")]
+#[derive(Debug, Default)]
 pub struct Scrollables {}
 
+#[state_component_impl(ScrollablesState)]
 impl Component for Scrollables {
+    fn init(&mut self) {
+        self.state = Some(ScrollablesState::default());
+    }
+
+    fn render_hash(&self, hasher: &mut ComponentHasher) {
+        self.state_ref().messages.hash(hasher);
+    }
+
+    fn update(&mut self, msg: component::Message) -> Vec<component::Message> {
+        match msg.downcast_ref::<ChatMessage>() {
+            Some(ChatMessage::Send) => {
+                let messages = self.state_ref().messages + 1;
+                self.state_mut().messages = messages;
+                // Like a chat window, keep the newest message in view
+                self.state_ref().chat.scroll_into_view(messages - 1, true);
+            }
+            Some(ChatMessage::ScrollToTop) => {
+                self.state_ref().chat.scroll_to(0.0, true);
+            }
+            None => return vec![msg],
+        }
+        vec![]
+    }
+
     fn view(&self) -> Option<mctk_core::Node> {
         let mut start = node!(
             Div::new().bg(Color::LIGHT_GREY),
@@ -86,6 +128,54 @@ impl Component for Scrollables {
                 )),
             ),
         );
+
+        let mut chat = node!(
+            Div::new()
+                .scroll_y()
+                .controller(self.state_ref().chat.clone())
+                .border(Color::rgba(5., 5., 5., 0.06), 1., (8., 8., 8., 8.)),
+            lay![
+                size: [300, 120],
+                direction: mctk_core::layout::Direction::Column,
+            ]
+        );
+        for i in 0..self.state_ref().messages {
+            chat = chat.push(node!(
+                Text::new(txt!(format!("Message {}", i + 1)))
+                    .with_class("text-md font-space-grotesk text-black"),
+                lay![size: [Auto, 30], margin: [4., 8., 4., 8.]],
+                i as u64
+            ));
+        }
+        let send = node!(
+            Button::new(txt!("Send"))
+                .on_click(Box::new(|| msg!(ChatMessage::Send)))
+                .with_class("text-md rounded-sm p-2 text-white font-space-grotesk")
+                .style("background_color", Color::rgb(22., 119., 255.)),
+            lay![size: [100, 40], margin: [0., 0., 0., 8.]]
+        );
+        let top = node!(
+            Button::new(txt!("Top"))
+                .on_click(Box::new(|| msg!(ChatMessage::ScrollToTop)))
+                .with_class("text-md rounded-sm p-2 text-white font-space-grotesk")
+                .style("background_color", Color::rgb(22., 119., 255.)),
+            lay![size: [100, 40]]
+        );
+        start = start.push(
+            node!(
+                Div::new(),
+                lay![
+                    margin: [14., 0., 0., 0.],
+                    direction: mctk_core::layout::Direction::Column,
+                ]
+            )
+            .push(chat)
+            .push(
+                node!(Div::new(), lay![margin: [8., 0., 0., 0.]])
+                    .push(send)
+                    .push(top),
+            ),
+        );
         Some(start)
     }
 }
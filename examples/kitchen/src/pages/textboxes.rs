@@ -108,6 +108,51 @@ impl Component for TextBoxes {
                         size: [410, 40],
                         margin: [0., 0., 8., 0.]
                     ]
+                ))
+                .push(node!(
+                    TextBox::new(Some("".to_string()))
+                        .with_class("text-md")
+                        .placeholder("Autocomplete")
+                        .autocomplete(Box::new(|s| {
+                            let s = s.to_lowercase();
+                            [
+                                "Apple",
+                                "Apricot",
+                                "Avocado",
+                                "Banana",
+                                "Blueberry",
+                                "Cherry",
+                            ]
+                            .iter()
+                            .filter(|fruit| fruit.to_lowercase().starts_with(&s))
+                            .map(|fruit| fruit.to_string())
+                            .collect()
+                        }))
+                        .on_change(Box::new(|s| msg!(Message::Textbox {
+                            textbox_type: "Autocomplete".to_string(),
+                            text: s.to_string()
+                        }))),
+                    lay![
+                        size: [410, 40],
+                        margin: [0., 0., 8., 0.]
+                    ]
+                ))
+                .push(node!(
+                    TextBox::new(Some("".to_string()))
+                        .with_class("text-md textarea")
+                        .placeholder("Multi-line")
+                        .min_rows(3)
+                        .max_rows(6)
+                        .max_length(200)
+                        .show_counter(true)
+                        .on_change(Box::new(|s| msg!(Message::Textbox {
+                            textbox_type: "Multi-line".to_string(),
+                            text: s.to_string()
+                        }))),
+                    lay![
+                        size: [410, Auto],
+                        margin: [0., 0., 8., 0.]
+                    ]
                 )),
             ),
         );
@@ -0,0 +1,18 @@
+//! A style inspector for development builds: a floating panel that shows the Component under the
+//! mouse cursor, its layout and style, and lets the style be edited live.
+//!
+//! Everything is behind the `debug-inspector` feature, so that release builds can leave it out:
+//!
+//! ```ignore
+//! fn view(&self) -> Option<Node> {
+//!     let root = node!(Div::new(), lay![size_pct: [100]]);
+//!     #[cfg(feature = "debug-inspector")]
+//!     let root = InspectorOverlay::mount(root);
+//!     Some(root)
+//! }
+//! ```
+
+#[cfg(feature = "debug-inspector")]
+mod overlay;
+#[cfg(feature = "debug-inspector")]
+pub use overlay::InspectorOverlay;
@@ -0,0 +1,341 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::fmt::Write;
+use std::hash::{Hash, Hasher};
+
+use mctk_core::component::{Component, Message};
+use mctk_core::event::{self, Event};
+use mctk_core::inspector::{hovered, Inspected};
+use mctk_core::layout::{Alignment, Dimension, Direction, PositionType, Rect};
+use mctk_core::style::{
+    current_style_for_component, set_current_style_val, FontWeight, StyleKey, StyleVal, Styled,
+};
+use mctk_core::widgets::{Button, Div, Text, TextBox};
+use mctk_core::{lay, msg, node, rect, size, size_pct, txt, Color, Key, Node, AABB};
+use mctk_macros::{component, state_component_impl};
+
+/// The width of the panel, in logical pixels.
+const PANEL_WIDTH: f32 = 320.0;
+/// The distance between the panel and the top right corner of the window.
+const PANEL_MARGIN: f64 = 8.0;
+const FONT_SIZE: f32 = 12.0;
+
+#[derive(Debug)]
+enum InspectorMessage {
+    Edit {
+        component: &'static str,
+        parameter: &'static str,
+        text: String,
+    },
+    Copy,
+}
+
+#[derive(Debug, Default)]
+pub struct InspectorState {
+    open: bool,
+    target: Option<Inspected>,
+    /// The edits made so far, as `(component, parameter, value)`, in the order they were made.
+    overrides: Vec<(&'static str, &'static str, StyleVal)>,
+}
+
+/// A floating panel that shows the Component under the mouse cursor, its layout rect and its
+/// resolved style values, and lets those values be edited live. Toggled with `Ctrl+Shift+I`.
+///
+/// Edits are made to the current style (see [`set_current_style_val`]), so they apply to every
+/// Component of the same type, and can be copied as `style!` code with the "Copy overrides" button
+/// (see [`InspectorOverlay::on_copy`]).
+#[component(State = "InspectorState", New)]
+pub struct InspectorOverlay {
+    on_copy: Option<Box<dyn Fn(String) -> Message + Send + Sync>>,
+}
+
+impl fmt::Debug for InspectorOverlay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InspectorOverlay")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl InspectorOverlay {
+    /// Add an inspector to `root`, usually the Node returned by the root Component's `view`.
+    pub fn mount(root: Node) -> Node {
+        Self::new().attach(root)
+    }
+
+    /// Add this inspector to `root`. Like [`InspectorOverlay::mount`], for a configured inspector.
+    pub fn attach(self, root: Node) -> Node {
+        root.push(node!(
+            self,
+            lay![
+                position_type: PositionType::Absolute,
+                z_index_increment: 10000.0,
+                position: Rect {
+                    top: Dimension::Px(PANEL_MARGIN),
+                    right: Dimension::Px(PANEL_MARGIN),
+                    ..rect!(Auto)
+                },
+            ]
+        ))
+    }
+
+    /// Called with the `style!` code of the overrides when "Copy overrides" is clicked, e.g. to
+    /// put it on the clipboard. Without it, the button logs the code with `tracing`, at the
+    /// `info` level.
+    pub fn on_copy(mut self, f: Box<dyn Fn(String) -> Message + Send + Sync>) -> Self {
+        self.on_copy = Some(f);
+        self
+    }
+
+    fn label(text: String) -> Text {
+        Text::new(txt!(text))
+            .style("size", FONT_SIZE)
+            .style("color", Color::BLACK)
+    }
+
+    fn style_row(component: &'static str, parameter: &'static str, val: &StyleVal) -> Node {
+        let row = node!(
+            Div::new(),
+            lay![
+                size_pct: [100, Auto],
+                cross_alignment: Alignment::Center,
+                margin: [0., 0., 4., 0.],
+            ]
+        )
+        .key(row_key(component, parameter))
+        .push(node!(
+            Self::label(parameter.to_string()),
+            lay![size: [140, Auto]]
+        ));
+
+        if val.value_type().is_some() {
+            row.push(node!(
+                TextBox::new(Some(val.to_string()))
+                    .style("font_size", FONT_SIZE)
+                    .on_commit(Box::new(move |s| {
+                        msg!(InspectorMessage::Edit {
+                            component,
+                            parameter,
+                            text: s.to_string(),
+                        })
+                    })),
+                lay![size: [150, 24]]
+            ))
+        } else {
+            row.push(node!(Self::label(val.to_string())))
+        }
+    }
+}
+
+#[state_component_impl(InspectorState)]
+impl Component for InspectorOverlay {
+    fn init(&mut self) {
+        self.state = Some(InspectorState::default());
+    }
+
+    fn register(&mut self) -> Vec<event::Register> {
+        vec![event::Register::KeyDown]
+    }
+
+    fn on_key_down(&mut self, event: &mut Event<event::KeyDown>) {
+        let held = event.modifiers_held;
+        if event.input.0 == Key::I && held.ctrl && held.shift {
+            let open = !self.state_ref().open;
+            self.state_mut().open = open;
+        }
+    }
+
+    fn on_tick(&mut self, event: &mut Event<event::Tick>) {
+        if self.state.is_none() || !self.state_ref().open {
+            return;
+        }
+        // Keep the current target while the mouse is over the panel itself
+        let panel = event.current_physical_aabb();
+        if let Some(target) = hovered().filter(|h| !contains(panel, h.aabb)) {
+            if self.state_ref().target != Some(target) {
+                self.state_mut().target = Some(target);
+            }
+        }
+    }
+
+    fn update(&mut self, msg: Message) -> Vec<Message> {
+        match msg.downcast_ref::<InspectorMessage>() {
+            Some(InspectorMessage::Edit {
+                component,
+                parameter,
+                text,
+            }) => {
+                let (component, parameter) = (*component, *parameter);
+                let val = current_style_for_component(component)
+                    .get(parameter, None)
+                    .and_then(|current| parse_value(current, text));
+                if let Some(val) = val {
+                    set_current_style_val(StyleKey::new(component, parameter, None), val.clone());
+                    let overrides = &mut self.state_mut().overrides;
+                    overrides.retain(|(c, p, _)| (*c, *p) != (component, parameter));
+                    overrides.push((component, parameter, val));
+                }
+                vec![]
+            }
+            Some(InspectorMessage::Copy) => {
+                let code = style_code(&self.state_ref().overrides);
+                match &self.on_copy {
+                    Some(f) => vec![f(code)],
+                    None => {
+                        tracing::info!("Style overrides:\n{code}");
+                        vec![]
+                    }
+                }
+            }
+            None => vec![msg],
+        }
+    }
+
+    fn view(&self) -> Option<Node> {
+        if !self.state_ref().open {
+            return None;
+        }
+
+        let mut panel = node!(
+            Div::new()
+                .bg(Color::WHITE)
+                .border(Color::MID_GREY, 1.0, (8.0, 8.0, 8.0, 8.0)),
+            lay![
+                size: [PANEL_WIDTH, Auto],
+                direction: Direction::Column,
+                padding: [12],
+            ]
+        )
+        .push(node!(
+            Text::new(txt!("Inspector"))
+                .style("size", 14.0)
+                .style("color", Color::BLACK)
+                .style("font_weight", FontWeight::Bold),
+            lay![margin: [0., 0., 8., 0.]]
+        ));
+
+        match self.state_ref().target {
+            Some(Inspected { name, aabb }) => {
+                panel = panel.push(node!(Self::label(name.to_string()))).push(node!(
+                    Self::label(format!(
+                        "{}, {}  {} × {} px",
+                        aabb.pos.x,
+                        aabb.pos.y,
+                        aabb.width(),
+                        aabb.height()
+                    )),
+                    lay![margin: [0., 0., 8., 0.]]
+                ));
+
+                let snapshot = current_style_for_component(name);
+                let mut entries: Vec<_> = snapshot
+                    .iter()
+                    .filter(|((_, class), _)| class.is_none())
+                    .map(|((parameter, _), val)| (parameter, val))
+                    .collect();
+                entries.sort_by_key(|(parameter, _)| *parameter);
+                for (parameter, val) in entries {
+                    panel = panel.push(Self::style_row(name, parameter, val));
+                }
+            }
+            None => {
+                panel = panel.push(node!(Self::label(
+                    "Move the mouse over a component".to_string()
+                )));
+            }
+        }
+
+        Some(panel.push(node!(
+            Button::new(txt!("Copy overrides")).on_click(Box::new(|| msg!(InspectorMessage::Copy))),
+            lay![size: [PANEL_WIDTH - 24.0, 32], margin: [8., 0., 0., 0.]]
+        )))
+    }
+}
+
+/// Whether `inner` is entirely within `outer`.
+fn contains(outer: AABB, inner: AABB) -> bool {
+    inner.pos.x >= outer.pos.x
+        && inner.pos.y >= outer.pos.y
+        && inner.bottom_right.x <= outer.bottom_right.x
+        && inner.bottom_right.y <= outer.bottom_right.y
+}
+
+/// A Node key for the style row of `parameter`, so that its `TextBox` is recreated when the
+/// target changes.
+fn row_key(component: &'static str, parameter: &'static str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (component, parameter).hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Parse `text` as the same variant as `current`, if that variant can be parsed.
+fn parse_value(current: &StyleVal, text: &str) -> Option<StyleVal> {
+    let t = current.value_type()?;
+    StyleVal::from_str(t, text)
+        .or_else(|_| StyleVal::from_str(t, &text.to_lowercase()))
+        .ok()
+}
+
+/// Rust code for `val`, as it would be written in a `style!` entry.
+fn value_code(val: &StyleVal) -> String {
+    match val {
+        StyleVal::Color(c) => format!("Color::rgba({:?}, {:?}, {:?}, {:?})", c.r, c.g, c.b, c.a),
+        StyleVal::Float(f) => format!("{f:?}"),
+        StyleVal::Int(i) => format!("{i}u32"),
+        StyleVal::Bool(b) => b.to_string(),
+        StyleVal::FontWeight(w) => format!("FontWeight::{w:?}"),
+        StyleVal::HorizontalPosition(p) => format!("HorizontalPosition::{p:?}"),
+        StyleVal::VerticalPosition(p) => format!("VerticalPosition::{p:?}"),
+        // Only the variants above can be edited
+        _ => val.to_string(),
+    }
+}
+
+/// `overrides` as a `style!` macro invocation.
+fn style_code(overrides: &[(&'static str, &'static str, StyleVal)]) -> String {
+    let mut code = String::from("style!(\n");
+    for (component, parameter, val) in overrides {
+        let _ = writeln!(code, "    {component}.{parameter} = {};", value_code(val));
+    }
+    code.push(')');
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mctk_core::Pos;
+
+    #[test]
+    fn test_parse_value() {
+        let red = parse_value(&Color::WHITE.into(), "#ff0000");
+        assert_eq!(red, Some(StyleVal::Color(Color::RED)));
+        let weight = parse_value(&FontWeight::Normal.into(), "Bold");
+        assert_eq!(weight, Some(StyleVal::FontWeight(FontWeight::Bold)));
+        assert_eq!(parse_value(&4.0.into(), "abc"), None);
+        // Sizes can't be edited
+        assert_eq!(parse_value(&size!(10.0).into(), "10"), None);
+    }
+
+    #[test]
+    fn test_style_code() {
+        let code = style_code(&[
+            ("Button", "radius", StyleVal::Float(4.0)),
+            ("Button", "text_color", Color::RED.into()),
+        ]);
+        assert_eq!(
+            code,
+            "style!(\n    \
+             Button.radius = 4.0;\n    \
+             Button.text_color = Color::rgba(255.0, 0.0, 0.0, 1.0);\n)"
+        );
+    }
+
+    #[test]
+    fn test_contains() {
+        let outer = AABB::new(Pos::new(0.0, 0.0, 0.0), mctk_core::Scale::new(100.0, 100.0));
+        let inner = AABB::new(Pos::new(10.0, 10.0, 0.0), mctk_core::Scale::new(20.0, 20.0));
+        assert!(contains(outer, inner));
+        assert!(!contains(inner, outer));
+    }
+}
@@ -1,7 +1,7 @@
 extern crate proc_macro;
 
 use global_counter::primitive::exact::CounterU64;
-use proc_macro::{Group, TokenStream, TokenTree};
+use proc_macro::{Delimiter, Group, TokenStream, TokenTree};
 use quote::quote;
 use std::iter::FromIterator;
 use syn::{self, parse_macro_input, Lit, Meta, MetaNameValue, NestedMeta};
@@ -14,6 +14,14 @@ static ID_COUNTER: CounterU64 = CounterU64::new(0);
 ///
 /// e.g. `#[component(State = "ButtonState", Styled)]`
 /// e.g. `#[component(State = "StateType", Styled = "ComponentNameOverride")]`
+///
+/// A `#[styled]` or `#[styled(name = "ComponentNameOverride")]` attribute on the struct is equivalent to the `Styled` argument.
+///
+/// Fields marked with `#[prop]` get a builder method of the same name, e.g. `pub fn label(mut self, v: String) -> Self`.
+///
+/// With the `New` argument, a `new` constructor is generated that takes every field that is not an `Option`, in order. Optional fields start as `None`, and the `State`, if any, as its `Default`, which it must implement. When every field is optional, `Default` is also implemented.
+///
+/// e.g. `#[component(Styled, New)]`
 #[proc_macro_attribute]
 pub fn component(attr: TokenStream, input: TokenStream) -> TokenStream {
     let attr = parse_macro_input!(attr as syn::AttributeArgs);
@@ -32,6 +40,13 @@ pub fn component(attr: TokenStream, input: TokenStream) -> TokenStream {
             false
         }
     });
+    let is_new = attr.iter().any(|v| {
+        if let NestedMeta::Meta(m) = v {
+            m.path().segments.last().unwrap().ident == "New"
+        } else {
+            false
+        }
+    });
     let state_type = attr
         .iter()
         .find_map(|v| {
@@ -78,10 +93,27 @@ pub fn component(attr: TokenStream, input: TokenStream) -> TokenStream {
         quote! { style::Styled }
     };
 
+    let item = input.clone();
+    let item = parse_macro_input!(item as syn::ItemStruct);
+    let styled_attr = item.attrs.iter().find(|a| a.path.is_ident("styled"));
+    let is_styled = is_styled || styled_attr.is_some();
+    let component_name_override =
+        component_name_override.or_else(|| match styled_attr.and_then(|a| a.parse_meta().ok()) {
+            Some(Meta::List(l)) => l.nested.iter().find_map(|n| match n {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(s),
+                    ..
+                })) if path.is_ident("name") => Some(s.value()),
+                _ => None,
+            }),
+            _ => None,
+        });
+
     // Add in fields
-    let mut i: Vec<_> = input.clone().into_iter().collect();
+    let mut i = strip_attributes(input.clone(), "styled");
     if let Some(TokenTree::Group(g)) = i.last() {
-        let mut s = g.stream();
+        let mut s = TokenStream::from_iter(strip_attributes(g.stream(), "prop"));
         let len = i.len();
         if let Some(state) = &state_type {
             let state_field = quote! {
@@ -156,9 +188,108 @@ pub fn component(attr: TokenStream, input: TokenStream) -> TokenStream {
         struct_def.extend(TokenStream::from(expanded));
     }
 
+    let fields: Vec<&syn::Field> = input.fields.iter().collect();
+
+    // Prop builders
+    let (prop_names, prop_types): (Vec<_>, Vec<_>) = fields
+        .iter()
+        .filter(|f| f.attrs.iter().any(|a| a.path.is_ident("prop")))
+        .map(|f| (&f.ident, &f.ty))
+        .unzip();
+    if !prop_names.is_empty() {
+        let expanded = quote!(
+            impl #impl_generics #struct_name #ty_generics #where_clause {
+                #(
+                    pub fn #prop_names(mut self, v: #prop_types) -> Self {
+                        self.#prop_names = v;
+                        self
+                    }
+                )*
+            }
+        );
+        struct_def.extend(TokenStream::from(expanded));
+    }
+
+    // Constructor
+    if is_new {
+        let is_optional = |f: &&&syn::Field| match &f.ty {
+            syn::Type::Path(p) => p.path.segments.last().unwrap().ident == "Option",
+            _ => false,
+        };
+        let (required_names, required_types): (Vec<_>, Vec<_>) = fields
+            .iter()
+            .filter(|f| !is_optional(f))
+            .map(|f| (&f.ident, &f.ty))
+            .unzip();
+        let optional_names: Vec<_> = fields
+            .iter()
+            .filter(is_optional)
+            .map(|f| &f.ident)
+            .collect();
+        let state_init = if state_type.is_some() {
+            quote! { state: Some(Default::default()), dirty: false, }
+        } else {
+            quote! {}
+        };
+        let styled_init = if is_styled {
+            quote! { class: None, style_overrides: Default::default(), }
+        } else {
+            quote! {}
+        };
+        let expanded = quote!(
+            impl #impl_generics #struct_name #ty_generics #where_clause {
+                pub fn new(#(#required_names: #required_types),*) -> Self {
+                    Self {
+                        #(#required_names,)*
+                        #(#optional_names: None,)*
+                        #state_init
+                        #styled_init
+                    }
+                }
+            }
+        );
+        struct_def.extend(TokenStream::from(expanded));
+
+        if required_names.is_empty() {
+            let expanded = quote!(
+                impl #impl_generics Default for #struct_name #ty_generics #where_clause {
+                    fn default() -> Self {
+                        Self::new()
+                    }
+                }
+            );
+            struct_def.extend(TokenStream::from(expanded));
+        }
+    }
+
     struct_def
 }
 
+/// Remove the `#[name]` and `#[name(...)]` attributes from the top level of `tokens`.
+fn strip_attributes(tokens: TokenStream, name: &str) -> Vec<TokenTree> {
+    let mut out = vec![];
+    let mut iter = tokens.into_iter().peekable();
+    while let Some(t) = iter.next() {
+        if let TokenTree::Punct(p) = &t {
+            if p.as_char() == '#' {
+                if let Some(TokenTree::Group(g)) = iter.peek() {
+                    let is_match = g.delimiter() == Delimiter::Bracket
+                        && matches!(
+                            g.stream().into_iter().next(),
+                            Some(TokenTree::Ident(i)) if i.to_string() == name
+                        );
+                    if is_match {
+                        iter.next();
+                        continue;
+                    }
+                }
+            }
+        }
+        out.push(t);
+    }
+    out
+}
+
 /// TODO
 #[proc_macro_attribute]
 pub fn state_component_impl(attr: TokenStream, input: TokenStream) -> TokenStream {
@@ -205,6 +336,81 @@ pub fn static_id(_item: TokenStream) -> TokenStream {
     quote! { #id }.into()
 }
 
+/// Implements `Styled` for a struct with the fields `class: Option<&'static str>` and `style_overrides: StyleOverride`.
+///
+/// The name used to look up style keys defaults to the name of the struct, and can be set with the `styled` attribute.
+///
+/// e.g. `#[derive(Styled)] #[styled(name = "ComponentNameOverride")]`
+#[proc_macro_derive(Styled, attributes(styled))]
+pub fn styled_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let fields = if let syn::Data::Struct(s) = &input.data {
+        if let syn::Fields::Named(f) = &s.fields {
+            f
+        } else {
+            panic!("Styled derive only works on structs with named fields");
+        }
+    } else {
+        panic!("Styled derive only works on structs");
+    };
+    for required in ["class", "style_overrides"] {
+        if !fields
+            .named
+            .iter()
+            .any(|f| f.ident.as_ref().unwrap() == required)
+        {
+            let msg = format!("Styled derive requires a `{}` field", required);
+            return quote! { compile_error!(#msg); }.into();
+        }
+    }
+
+    let component_name_override = input
+        .attrs
+        .iter()
+        .filter(|a| a.path.is_ident("styled"))
+        .filter_map(|a| a.parse_meta().ok())
+        .find_map(|m| {
+            if let Meta::List(l) = m {
+                l.nested.iter().find_map(|n| match n {
+                    NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                        path,
+                        lit: Lit::Str(s),
+                        ..
+                    })) if path.is_ident("name") => Some(s.value()),
+                    _ => None,
+                })
+            } else {
+                None
+            }
+        });
+    let component_name = component_name_override.unwrap_or_else(|| struct_name.to_string());
+    let component_name = component_name.as_str();
+
+    let expanded = quote! {
+        impl #impl_generics mctk_core::style::Styled for #struct_name #ty_generics #where_clause {
+            fn name() -> &'static str {
+                #component_name
+            }
+            fn class(&self) -> Option<&'static str> {
+                self.class
+            }
+            fn class_mut(&mut self) -> &mut Option<&'static str> {
+                &mut self.class
+            }
+            fn style_overrides(&self) -> &mctk_core::style::StyleOverride {
+                &self.style_overrides
+            }
+            fn style_overrides_mut(&mut self) -> &mut mctk_core::style::StyleOverride {
+                &mut self.style_overrides
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
 #[proc_macro_derive(Model)]
 pub fn model_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as syn::DeriveInput);
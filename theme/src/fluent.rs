@@ -0,0 +1,249 @@
+//! [Fluent Design](https://fluent2.microsoft.design) tokens, as used by Windows 11.
+
+use mctk_core::style;
+use mctk_core::style::{FontWeight, Style};
+use mctk_core::types::Color;
+
+const FONT: &str = "Segoe UI";
+
+// Type ramp, as (font size, weight)
+const CAPTION: (f32, FontWeight) = (12.0, FontWeight::Normal);
+const BODY: (f32, FontWeight) = (14.0, FontWeight::Normal);
+const BODY_STRONG: (f32, FontWeight) = (14.0, FontWeight::Semibold);
+
+/// The corner radius of controls such as buttons and text boxes.
+const CONTROL_RADIUS: f32 = 4.0;
+/// The corner radius of overlays such as menus and tooltips.
+const OVERLAY_RADIUS: f32 = 8.0;
+
+/// The default Windows accent color.
+const DEFAULT_ACCENT: Color = Color {
+    r: 0.0,
+    g: 120.0,
+    b: 212.0,
+    a: 1.0,
+};
+
+/// The Fluent style, in its light or dark color scheme.
+///
+/// ```ignore
+/// set_current_style(FluentTheme::accent_color(Color::rgb(232.0, 17.0, 35.0)));
+/// ```
+pub struct FluentTheme;
+
+impl FluentTheme {
+    pub fn light() -> Style {
+        Palette::light(DEFAULT_ACCENT).style()
+    }
+
+    pub fn dark() -> Style {
+        Palette::dark(Color::rgb(96.0, 205.0, 255.0)).style()
+    }
+
+    /// The light style, with `accent` in place of the default blue accent.
+    pub fn accent_color(accent: Color) -> Style {
+        Palette::light(accent).style()
+    }
+}
+
+/// The light Fluent style.
+pub fn fluent_style() -> Style {
+    FluentTheme::light()
+}
+
+/// The dark Fluent style.
+pub fn fluent_dark_style() -> Style {
+    FluentTheme::dark()
+}
+
+/// The Fluent color tokens of a color scheme.
+struct Palette {
+    accent: Color,
+    text_on_accent: Color,
+    /// The window background, where Mica or Acrylic would be.
+    background: Color,
+    control_fill: Color,
+    control_fill_hover: Color,
+    control_fill_pressed: Color,
+    control_stroke: Color,
+    control_strong_stroke: Color,
+    text_primary: Color,
+    text_secondary: Color,
+    critical: Color,
+}
+
+impl Palette {
+    fn light(accent: Color) -> Self {
+        Self {
+            accent,
+            text_on_accent: Color::WHITE,
+            background: Color::rgb(243.0, 243.0, 243.0),
+            control_fill: Color::rgba(255.0, 255.0, 255.0, 0.7),
+            control_fill_hover: Color::rgba(249.0, 249.0, 249.0, 0.5),
+            control_fill_pressed: Color::rgba(249.0, 249.0, 249.0, 0.3),
+            control_stroke: Color::rgba(0.0, 0.0, 0.0, 0.06),
+            control_strong_stroke: Color::rgba(0.0, 0.0, 0.0, 0.45),
+            text_primary: Color::rgba(0.0, 0.0, 0.0, 0.9),
+            text_secondary: Color::rgba(0.0, 0.0, 0.0, 0.6),
+            critical: Color::rgb(196.0, 43.0, 28.0),
+        }
+    }
+
+    fn dark(accent: Color) -> Self {
+        Self {
+            accent,
+            text_on_accent: Color::BLACK,
+            background: Color::rgb(32.0, 32.0, 32.0),
+            control_fill: Color::rgba(255.0, 255.0, 255.0, 0.06),
+            control_fill_hover: Color::rgba(255.0, 255.0, 255.0, 0.08),
+            control_fill_pressed: Color::rgba(255.0, 255.0, 255.0, 0.03),
+            control_stroke: Color::rgba(255.0, 255.0, 255.0, 0.07),
+            control_strong_stroke: Color::rgba(255.0, 255.0, 255.0, 0.54),
+            text_primary: Color::WHITE,
+            text_secondary: Color::rgba(255.0, 255.0, 255.0, 0.79),
+            critical: Color::rgb(255.0, 153.0, 164.0),
+        }
+    }
+
+    fn style(&self) -> Style {
+        // Accent fills fade slightly when hovered and pressed
+        let accent_hover = Color {
+            a: self.accent.a * 0.9,
+            ..self.accent
+        };
+        let accent_pressed = Color {
+            a: self.accent.a * 0.8,
+            ..self.accent
+        };
+
+        style!(
+            // Standard button
+            Button.background_color = self.control_fill;
+            Button.highlight_color = self.control_fill_hover;
+            Button.active_color = self.control_fill_pressed;
+            Button.text_color = self.text_primary;
+            Button.border_color = self.control_stroke;
+            Button.border_width = 1.0;
+            Button.radius = CONTROL_RADIUS;
+            Button.font = FONT;
+            Button.font_size = BODY.0;
+            Button.font_weight = BODY.1;
+            // Accent button
+            accent.Button.background_color = self.accent;
+            accent.Button.highlight_color = accent_hover;
+            accent.Button.active_color = accent_pressed;
+            accent.Button.text_color = self.text_on_accent;
+            accent.Button.border_color = self.accent;
+
+            IconButton.background_color = Color::TRANSPARENT;
+            IconButton.highlight_color = self.control_fill_hover;
+            IconButton.active_color = self.control_fill_pressed;
+            IconButton.text_color = self.text_primary;
+            IconButton.border_color = Color::TRANSPARENT;
+            IconButton.radius = CONTROL_RADIUS;
+
+            Text.color = self.text_primary;
+            Text.font = FONT;
+            Text.size = BODY.0;
+            Text.font_weight = BODY.1;
+            critical.Text.color = self.critical;
+
+            TextBox.background_color = self.control_fill;
+            TextBox.border_color = self.control_strong_stroke;
+            TextBox.text_color = self.text_primary;
+            TextBox.placeholder_color = self.text_secondary;
+            TextBox.cursor_color = self.text_primary;
+            TextBox.selection_color = self.accent;
+            TextBox.border_width = 1.0;
+            TextBox.font_size = BODY.0;
+            TextBox.font_weight = BODY.1;
+            critical.TextBox.border_color = self.critical;
+
+            Select.background_color = self.background;
+            Select.highlight_color = self.control_fill_hover;
+            Select.text_color = self.text_primary;
+            Select.border_color = self.control_stroke;
+            Select.caret_color = self.text_secondary;
+            Select.radius = OVERLAY_RADIUS;
+            Select.font = FONT;
+            Select.font_size = BODY.0;
+            Select.group_label_color = self.text_secondary;
+            Select.group_label_background = self.background;
+            Select.group_label_font_size = CAPTION.0;
+            Select.group_separator_color = self.control_stroke;
+
+            Toggle.background_color = self.control_fill;
+            Toggle.highlight_color = self.control_fill_hover;
+            Toggle.active_color = self.accent;
+            Toggle.border_color = self.control_strong_stroke;
+            Toggle.border_width = 1.0;
+
+            RadioButton.background_color = self.control_fill;
+            RadioButton.highlight_color = self.control_fill_hover;
+            RadioButton.active_color = self.accent;
+            RadioButton.border_color = self.control_strong_stroke;
+            RadioButton.text_color = self.text_primary;
+            RadioButton.indeterminate_color = self.text_secondary;
+            RadioButton.font = FONT;
+            RadioButton.font_size = BODY.0;
+
+            ToolTip.background_color = self.background;
+            ToolTip.text_color = self.text_primary;
+            ToolTip.border_color = self.control_stroke;
+            ToolTip.border_width = 1.0;
+            ToolTip.font = FONT;
+            ToolTip.font_size = CAPTION.0;
+
+            Scroll.bar_background_color = Color::TRANSPARENT;
+            Scroll.bar_color = self.text_secondary;
+            Scroll.bar_highlight_color = self.text_primary;
+            Scroll.bar_active_color = self.text_primary;
+
+            strong.Text.font_weight = BODY_STRONG.1;
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fluent_style() {
+        for style in [fluent_style(), fluent_dark_style()] {
+            for component in [
+                "Button",
+                "IconButton",
+                "TextBox",
+                "Select",
+                "RadioButton",
+                "ToolTip",
+            ] {
+                assert!(style.style(component, "background_color").is_some());
+                assert!(style.style(component, "text_color").is_some());
+            }
+            assert!(style.style("Toggle", "background_color").is_some());
+            assert!(style.style("Text", "color").is_some());
+        }
+
+        let radius: f32 = fluent_style().style("Button", "radius").unwrap().f32();
+        assert_eq!(radius, CONTROL_RADIUS);
+    }
+
+    #[test]
+    fn test_accent_color() {
+        let red = Color::rgb(232.0, 17.0, 35.0);
+        let style = FluentTheme::accent_color(red);
+        let background: Color = style
+            .style_for_class("Button", "background_color", "accent")
+            .into();
+        assert_eq!(background, red);
+        let active: Color = style.style("Toggle", "active_color").into();
+        assert_eq!(active, red);
+
+        let background: Color = fluent_style()
+            .style_for_class("Button", "background_color", "accent")
+            .into();
+        assert_eq!(background, DEFAULT_ACCENT);
+    }
+}
@@ -0,0 +1,7 @@
+//! Ready-made [`Style`][mctk_core::style::Style]s, to pass to
+//! [`set_current_style`][mctk_core::style::set_current_style].
+
+mod fluent;
+mod material3;
+pub use fluent::{fluent_dark_style, fluent_style, FluentTheme};
+pub use material3::{material3_style, Material3Theme};
@@ -0,0 +1,249 @@
+//! [Material Design 3](https://m3.material.io) color, typography and shape tokens, using the
+//! baseline color scheme.
+
+use mctk_core::style;
+use mctk_core::style::{FontWeight, Style};
+use mctk_core::types::Color;
+
+/// The opacity of the hover state layer.
+const HOVER_OPACITY: f32 = 0.08;
+/// The opacity of the pressed state layer.
+const PRESSED_OPACITY: f32 = 0.12;
+
+// Typography roles, as (font size, weight)
+const LABEL_SMALL: (f32, FontWeight) = (11.0, FontWeight::Medium);
+const LABEL_LARGE: (f32, FontWeight) = (14.0, FontWeight::Medium);
+const BODY_SMALL: (f32, FontWeight) = (12.0, FontWeight::Normal);
+const BODY_MEDIUM: (f32, FontWeight) = (14.0, FontWeight::Normal);
+const BODY_LARGE: (f32, FontWeight) = (16.0, FontWeight::Normal);
+
+// Shape scale, as corner radii
+const SHAPE_EXTRA_SMALL: f32 = 4.0;
+const SHAPE_SMALL: f32 = 8.0;
+const SHAPE_MEDIUM: f32 = 12.0;
+const SHAPE_LARGE: f32 = 16.0;
+const SHAPE_EXTRA_LARGE: f32 = 28.0;
+/// Fully rounded, for the 40px tall buttons.
+const SHAPE_FULL: f32 = 20.0;
+
+/// The Material Design 3 style, in its light or dark color scheme.
+///
+/// ```ignore
+/// set_current_style(Material3Theme::dark());
+/// ```
+pub struct Material3Theme;
+
+impl Material3Theme {
+    pub fn light() -> Style {
+        ColorScheme::light().style()
+    }
+
+    pub fn dark() -> Style {
+        ColorScheme::dark().style()
+    }
+}
+
+/// The light Material Design 3 style.
+pub fn material3_style() -> Style {
+    Material3Theme::light()
+}
+
+/// The color roles of a Material Design 3 color scheme.
+struct ColorScheme {
+    primary: Color,
+    on_primary: Color,
+    primary_container: Color,
+    on_primary_container: Color,
+    secondary: Color,
+    on_secondary: Color,
+    secondary_container: Color,
+    surface: Color,
+    on_surface: Color,
+    surface_variant: Color,
+    on_surface_variant: Color,
+    outline: Color,
+    error: Color,
+}
+
+impl ColorScheme {
+    fn light() -> Self {
+        Self {
+            primary: Color::rgb(103.0, 80.0, 164.0),
+            on_primary: Color::rgb(255.0, 255.0, 255.0),
+            primary_container: Color::rgb(234.0, 221.0, 255.0),
+            on_primary_container: Color::rgb(33.0, 0.0, 93.0),
+            secondary: Color::rgb(98.0, 91.0, 113.0),
+            on_secondary: Color::rgb(255.0, 255.0, 255.0),
+            secondary_container: Color::rgb(232.0, 222.0, 248.0),
+            surface: Color::rgb(255.0, 251.0, 254.0),
+            on_surface: Color::rgb(28.0, 27.0, 31.0),
+            surface_variant: Color::rgb(231.0, 224.0, 236.0),
+            on_surface_variant: Color::rgb(73.0, 69.0, 79.0),
+            outline: Color::rgb(121.0, 116.0, 126.0),
+            error: Color::rgb(179.0, 38.0, 30.0),
+        }
+    }
+
+    fn dark() -> Self {
+        Self {
+            primary: Color::rgb(208.0, 188.0, 255.0),
+            on_primary: Color::rgb(56.0, 30.0, 114.0),
+            primary_container: Color::rgb(79.0, 55.0, 139.0),
+            on_primary_container: Color::rgb(234.0, 221.0, 255.0),
+            secondary: Color::rgb(204.0, 194.0, 220.0),
+            on_secondary: Color::rgb(51.0, 45.0, 65.0),
+            secondary_container: Color::rgb(74.0, 68.0, 88.0),
+            surface: Color::rgb(28.0, 27.0, 31.0),
+            on_surface: Color::rgb(230.0, 225.0, 229.0),
+            surface_variant: Color::rgb(73.0, 69.0, 79.0),
+            on_surface_variant: Color::rgb(202.0, 196.0, 208.0),
+            outline: Color::rgb(147.0, 143.0, 153.0),
+            error: Color::rgb(242.0, 184.0, 181.0),
+        }
+    }
+
+    fn style(&self) -> Style {
+        let hover = |base, on| state_layer(base, on, HOVER_OPACITY);
+        let pressed = |base, on| state_layer(base, on, PRESSED_OPACITY);
+
+        style!(
+            // Filled button
+            Button.background_color = self.primary;
+            Button.highlight_color = hover(self.primary, self.on_primary);
+            Button.active_color = pressed(self.primary, self.on_primary);
+            Button.text_color = self.on_primary;
+            Button.border_color = self.primary;
+            Button.border_width = 0.0;
+            Button.radius = SHAPE_FULL;
+            Button.font_size = LABEL_LARGE.0;
+            Button.font_weight = LABEL_LARGE.1;
+            // Button shape classes, on the MD3 shape scale
+            shape_xs.Button.radius = SHAPE_EXTRA_SMALL;
+            shape_sm.Button.radius = SHAPE_SMALL;
+            shape_md.Button.radius = SHAPE_MEDIUM;
+            shape_lg.Button.radius = SHAPE_LARGE;
+            shape_xl.Button.radius = SHAPE_EXTRA_LARGE;
+
+            // Standard icon button
+            IconButton.background_color = Color::TRANSPARENT;
+            IconButton.highlight_color = hover(self.surface, self.on_surface_variant);
+            IconButton.active_color = pressed(self.surface, self.on_surface_variant);
+            IconButton.text_color = self.on_surface_variant;
+            IconButton.border_color = Color::TRANSPARENT;
+            IconButton.radius = SHAPE_FULL;
+            IconButton.font_size = LABEL_LARGE.0;
+
+            Text.color = self.on_surface;
+            Text.size = BODY_LARGE.0;
+            Text.font_weight = BODY_LARGE.1;
+
+            // Filled text field
+            TextBox.background_color = self.surface_variant;
+            TextBox.border_color = self.on_surface_variant;
+            TextBox.text_color = self.on_surface;
+            TextBox.placeholder_color = self.on_surface_variant;
+            TextBox.cursor_color = self.primary;
+            TextBox.selection_color = self.primary_container;
+            TextBox.font_size = BODY_LARGE.0;
+            TextBox.font_weight = BODY_LARGE.1;
+
+            RadioButton.background_color = self.surface;
+            RadioButton.highlight_color = hover(self.surface, self.primary);
+            RadioButton.active_color = self.primary;
+            RadioButton.border_color = self.on_surface_variant;
+            RadioButton.text_color = self.on_surface;
+            RadioButton.indeterminate_color = self.on_surface_variant;
+            RadioButton.font_size = BODY_MEDIUM.0;
+
+            // Switch
+            Toggle.background_color = self.surface_variant;
+            Toggle.highlight_color = self.on_primary_container;
+            Toggle.active_color = self.primary;
+            Toggle.border_color = self.outline;
+
+            // Menu
+            Select.background_color = self.surface;
+            Select.highlight_color = self.secondary_container;
+            Select.text_color = self.on_surface;
+            Select.border_color = self.outline;
+            Select.caret_color = self.on_surface_variant;
+            Select.radius = SHAPE_EXTRA_SMALL;
+            Select.font_size = BODY_LARGE.0;
+            Select.group_label_color = self.on_surface_variant;
+            Select.group_label_background = self.surface;
+            Select.group_label_font_size = LABEL_SMALL.0;
+            Select.group_separator_color = self.outline;
+
+            // Plain tooltip
+            ToolTip.background_color = self.on_surface;
+            ToolTip.text_color = self.surface;
+            ToolTip.border_color = self.on_surface;
+            ToolTip.font_size = BODY_SMALL.0;
+
+            Scroll.bar_background_color = Color::TRANSPARENT;
+            Scroll.bar_color = self.outline;
+            Scroll.bar_highlight_color = self.on_surface_variant;
+            Scroll.bar_active_color = self.on_surface;
+
+            // Color role classes
+            primary.Button.background_color = self.primary;
+            primary.Button.text_color = self.on_primary;
+            secondary.Button.background_color = self.secondary;
+            secondary.Button.highlight_color = hover(self.secondary, self.on_secondary);
+            secondary.Button.active_color = pressed(self.secondary, self.on_secondary);
+            secondary.Button.text_color = self.on_secondary;
+            tonal.Button.background_color = self.secondary_container;
+            tonal.Button.text_color = self.on_primary_container;
+            error.Text.color = self.error;
+            error.TextBox.border_color = self.error;
+        )
+    }
+}
+
+/// `base` with a layer of `on` at `opacity` over it, as used for the hover and pressed states.
+fn state_layer(base: Color, on: Color, opacity: f32) -> Color {
+    let mix = |b: f32, o: f32| b + (o - b) * opacity;
+    Color::rgba(
+        mix(base.r, on.r),
+        mix(base.g, on.g),
+        mix(base.b, on.b),
+        base.a,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_material3_style() {
+        let style = material3_style();
+        let background: Color = style.style("Button", "background_color").into();
+        assert_eq!(background, Color::rgb(103.0, 80.0, 164.0));
+        assert_ne!(background, Color::WHITE);
+        let radius: f32 = style.style("Button", "radius").unwrap().f32();
+        assert_eq!(radius, SHAPE_FULL);
+
+        // Keys the theme doesn't cover keep their defaults
+        assert_eq!(
+            style.style("Scroll", "bar_width"),
+            Style::new().style("Scroll", "bar_width")
+        );
+
+        let dark = Material3Theme::dark();
+        let background: Color = dark.style("Button", "background_color").into();
+        assert_eq!(background, Color::rgb(208.0, 188.0, 255.0));
+        let surface: Color = dark.style("RadioButton", "background_color").into();
+        assert_eq!(surface, Color::rgb(28.0, 27.0, 31.0));
+    }
+
+    #[test]
+    fn test_state_layer() {
+        let hover = state_layer(
+            Color::rgb(0.0, 0.0, 0.0),
+            Color::rgb(100.0, 200.0, 0.0),
+            0.5,
+        );
+        assert_eq!(hover, Color::rgb(50.0, 100.0, 0.0));
+    }
+}